@@ -1,11 +1,39 @@
 use {
-    crate::prelude::*,
-    nexus_sdk::{sui, types::SecretValue, walrus::StorageConf},
+    crate::{
+        prelude::*,
+        secrets::{self, SecretStorage},
+    },
+    nexus_sdk::{
+        sui,
+        types::SecretValue,
+        walrus::{
+            data_store::{DataStoreRegistry, LocalFsStore, WalrusStore, LOCAL_FS_STORAGE_TAG},
+            encryption::{WalrusKey, WalrusKeyring},
+            BlobCache,
+            SharedBlobVerifier,
+            StorageConf,
+            WalrusCliBlobVerifier,
+            DEFAULT_BLOB_CACHE_MAX_BYTES,
+            WALRUS_AGGREGATOR_URL,
+            WALRUS_PUBLISHER_URL,
+        },
+    },
+    std::collections::BTreeMap,
 };
 
+/// Name of the profile held by configurations that never named one.
+pub(crate) const DEFAULT_PROFILE: &str = "default";
+
 /// Struct holding the config structure.
+///
+/// The `sui`, `nexus`, `tools`, `agents` and `data_storage` fields belong to
+/// the active profile. All other profiles are kept in `profiles` and swapped
+/// in by [`CliConf::use_profile`].
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct CliConf {
+    /// Name of the active profile, [`DEFAULT_PROFILE`] if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) profile: Option<String>,
     pub(crate) sui: SuiConf,
     pub(crate) nexus: Option<NexusObjects>,
     #[serde(default)]
@@ -15,19 +43,76 @@ pub(crate) struct CliConf {
     pub(crate) secrets: SecretsConf,
     #[serde(default)]
     pub(crate) data_storage: DataStorageConf,
+    /// Inactive profiles by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) profiles: BTreeMap<String, ProfileConf>,
+    /// Profile that stays active on disk while another one is selected for a
+    /// single invocation with `--profile`.
+    #[serde(skip)]
+    pub(crate) saved_profile: Option<String>,
+    /// Secrets of inactive profiles that could not be read. They are written
+    /// back unchanged when the configuration is saved.
+    #[serde(skip)]
+    pub(crate) unread_secrets: Vec<secrets::UnreadSecret>,
+    /// Secrets as they were stored when the configuration was loaded.
+    #[serde(skip)]
+    pub(crate) loaded_secrets: Vec<secrets::LoadedSecret>,
 }
 
 impl CliConf {
+    /// Load the configuration with the profile selected by `--profile`.
     pub(crate) async fn load() -> AnyResult<Self> {
         let conf_path = expand_tilde(CLI_CONF_PATH)?;
+        let mut conf = Self::load_from_path(&conf_path).await?;
+
+        conf.select_profile()?;
+
+        Ok(conf)
+    }
+
+    /// Like [`CliConf::load`] but a missing configuration file yields the
+    /// default configuration.
+    pub(crate) async fn load_or_default() -> AnyResult<Self> {
+        let conf_path = expand_tilde(CLI_CONF_PATH)?;
+        let mut conf = Self::load_from_path_or_default(&conf_path).await?;
+
+        conf.select_profile()?;
 
-        Self::load_from_path(&conf_path).await
+        Ok(conf)
     }
 
+    /// Load the configuration, resolving secrets from the OS keyring or
+    /// decrypting them as needed.
     pub(crate) async fn load_from_path(path: &PathBuf) -> AnyResult<Self> {
         let conf = tokio::fs::read_to_string(path).await?;
 
-        Ok(toml::from_str(&conf)?)
+        Self::parse(&conf)
+    }
+
+    /// Like [`CliConf::load_from_path`] but a missing file yields the default
+    /// configuration. Every other failure, such as a secret that cannot be
+    /// read, is returned so that the file is never replaced with defaults.
+    pub(crate) async fn load_from_path_or_default(path: &PathBuf) -> AnyResult<Self> {
+        match tokio::fs::read_to_string(path).await {
+            Ok(conf) => Self::parse(&conf),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn parse(conf: &str) -> AnyResult<Self> {
+        let mut conf = toml::from_str::<toml::Table>(conf)?;
+        let (loaded_secrets, unread_secrets) = secrets::open_table(
+            &mut conf,
+            PROFILE.get().map(String::as_str),
+            secrets::backend(),
+        )?;
+
+        Ok(Self {
+            unread_secrets,
+            loaded_secrets,
+            ..toml::Value::Table(conf).try_into()?
+        })
     }
 
     pub(crate) async fn save(&self) -> AnyResult<()> {
@@ -37,14 +122,180 @@ impl CliConf {
     }
 
     pub(crate) async fn save_to_path(&self, path: &PathBuf) -> AnyResult<()> {
+        self.save_sealed_to_path(path).await.map(|_| ())
+    }
+
+    /// Save the configuration, storing secrets according to
+    /// [`SecretsConf::mode`] and removing keyring entries that are no longer
+    /// used. Returns where each secret was stored.
+    pub(crate) async fn save_sealed_to_path(
+        &self,
+        path: &PathBuf,
+    ) -> AnyResult<Vec<(String, SecretStorage)>> {
         let parent_folder = path.parent().expect("Parent folder must exist.");
-        let conf = toml::to_string_pretty(&self)?;
+
+        // Profiles selected with `--profile` are not persisted as active.
+        let mut persisted = self.clone();
+        if let Some(saved_profile) = persisted.saved_profile.take() {
+            persisted.use_profile(&saved_profile)?;
+        }
+
+        let toml::Value::Table(mut conf) = toml::Value::try_from(&persisted)? else {
+            bail!("Configuration must serialize to a TOML table");
+        };
+        let mut sealed = secrets::seal_table(
+            &mut conf,
+            self.secrets.mode,
+            path,
+            &self.loaded_secrets,
+            secrets::backend(),
+        )?;
+        sealed.extend(secrets::restore_unread(&mut conf, &self.unread_secrets)?);
 
         tokio::fs::create_dir_all(parent_folder).await?;
-        tokio::fs::write(path, conf).await?;
+        tokio::fs::write(path, toml::to_string_pretty(&conf)?).await?;
+
+        secrets::remove_stale_keyring_entries(
+            &mut conf,
+            path,
+            &self.loaded_secrets,
+            secrets::backend(),
+        );
+
+        Ok(sealed)
+    }
+
+    /// Name of the active profile.
+    pub(crate) fn active_profile(&self) -> &str {
+        self.profile.as_deref().unwrap_or(DEFAULT_PROFILE)
+    }
+
+    /// Names of all profiles, including the active one, sorted.
+    pub(crate) fn profile_names(&self) -> Vec<&str> {
+        let mut names = self
+            .profiles
+            .keys()
+            .map(String::as_str)
+            .chain(std::iter::once(self.active_profile()))
+            .collect::<Vec<_>>();
+
+        names.sort_unstable();
+        names
+    }
+
+    pub(crate) fn has_profile(&self, name: &str) -> bool {
+        self.active_profile() == name || self.profiles.contains_key(name)
+    }
+
+    /// Store a new inactive profile.
+    pub(crate) fn add_profile(&mut self, name: &str, profile: ProfileConf) -> AnyResult<()> {
+        let is_valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+        if !is_valid {
+            bail!(
+                "Profile names may only contain ASCII letters, digits, '-' and '_', got '{name}'"
+            );
+        }
+
+        if self.has_profile(name) {
+            bail!("Profile '{name}' already exists");
+        }
+
+        self.profiles.insert(name.to_string(), profile);
 
         Ok(())
     }
+
+    /// Make `name` the active profile, storing the current one under its name.
+    pub(crate) fn use_profile(&mut self, name: &str) -> AnyResult<()> {
+        if self.active_profile() == name {
+            return Ok(());
+        }
+
+        if let Some(unread) = self.unread_secrets.iter().find(|s| s.profile == name) {
+            bail!("Failed to read '{}': {}", unread.name(), unread.error);
+        }
+
+        let Some(profile) = self.profiles.remove(name) else {
+            bail!(
+                "Profile '{name}' does not exist, available profiles: {}",
+                self.profile_names().join(", ")
+            );
+        };
+
+        let active = self.active_profile().to_string();
+        let current = self.replace_profile(profile);
+
+        self.profiles.insert(active, current);
+        self.profile = Some(name.to_string());
+
+        Ok(())
+    }
+
+    /// Remove an inactive profile.
+    pub(crate) fn remove_profile(&mut self, name: &str) -> AnyResult<ProfileConf> {
+        if self.active_profile() == name {
+            bail!("Profile '{name}' is active, switch to another profile before removing it");
+        }
+
+        let profile = self
+            .profiles
+            .remove(name)
+            .ok_or_else(|| anyhow!("Profile '{name}' does not exist"))?;
+
+        self.unread_secrets.retain(|secret| secret.profile != name);
+
+        Ok(profile)
+    }
+
+    /// Switch to the profile selected with `--profile` for this invocation
+    /// only. Saving the configuration keeps the previously active profile.
+    pub(crate) fn select_profile(&mut self) -> AnyResult<()> {
+        match PROFILE.get() {
+            Some(name) => self.select_profile_named(name),
+            None => Ok(()),
+        }
+    }
+
+    fn select_profile_named(&mut self, name: &str) -> AnyResult<()> {
+        let active = self.active_profile().to_string();
+
+        self.use_profile(name)?;
+
+        if active != name {
+            self.saved_profile.get_or_insert(active);
+        }
+
+        Ok(())
+    }
+
+    /// Swap the fields of the active profile with `profile`.
+    fn replace_profile(&mut self, profile: ProfileConf) -> ProfileConf {
+        ProfileConf {
+            sui: std::mem::replace(&mut self.sui, profile.sui),
+            nexus: std::mem::replace(&mut self.nexus, profile.nexus),
+            tools: std::mem::replace(&mut self.tools, profile.tools),
+            agents: std::mem::replace(&mut self.agents, profile.agents),
+            data_storage: std::mem::replace(&mut self.data_storage, profile.data_storage),
+        }
+    }
+}
+
+/// Settings that belong to a single deployment, see [`CliConf::use_profile`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ProfileConf {
+    #[serde(default)]
+    pub(crate) sui: SuiConf,
+    pub(crate) nexus: Option<NexusObjects>,
+    #[serde(default)]
+    pub(crate) tools: HashMap<ToolFqn, ToolOwnerCaps>,
+    #[serde(default)]
+    pub(crate) agents: HashMap<String, sui::types::Address>,
+    #[serde(default)]
+    pub(crate) data_storage: DataStorageConf,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -56,7 +307,8 @@ pub(crate) struct SuiConf {
     pub(crate) rpc_url: Option<reqwest::Url>,
 }
 
-/// Local secrets configuration.
+/// Local secrets configuration. See [`crate::secrets`] for how each mode
+/// stores secrets.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct SecretsConf {
     #[serde(default)]
@@ -95,6 +347,19 @@ impl std::fmt::Display for SecretsMode {
 pub(crate) enum StorageKind {
     Inline,
     Walrus,
+    /// Local directory set by `data_storage.local_path`, mostly for testing.
+    Local,
+}
+
+impl StorageKind {
+    /// `NexusData` storage tag of this backend.
+    pub(crate) fn tag(&self) -> &'static [u8] {
+        match self {
+            StorageKind::Inline => b"inline",
+            StorageKind::Walrus => WalrusStore::TAG,
+            StorageKind::Local => LOCAL_FS_STORAGE_TAG,
+        }
+    }
 }
 
 /// Remote data storage configuration.
@@ -104,18 +369,174 @@ pub(crate) struct DataStorageConf {
     pub(crate) walrus_aggregator_url: Option<reqwest::Url>,
     /// The preferred Walrus publisher URL.
     pub(crate) walrus_publisher_url: Option<reqwest::Url>,
+    /// Aggregators tried in order when the preferred one fails.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) walrus_fallback_aggregator_urls: Vec<reqwest::Url>,
+    /// Publishers tried in order when the preferred one fails.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) walrus_fallback_publisher_urls: Vec<reqwest::Url>,
     /// How many epochs to save remote data for?
     pub(crate) walrus_save_for_epochs: Option<u8>,
     /// What is the preferred remote storage backend?
     pub(crate) preferred_remote_storage: Option<StorageKind>,
+    /// Id of the key remotely stored payloads are encrypted with.
+    pub(crate) encryption_key_id: Option<String>,
+    /// Base64 encoded AES-256 key remotely stored payloads are encrypted with.
+    pub(crate) encryption_key: Option<SecretValue>,
+    /// Directory backing the `local` storage.
+    pub(crate) local_path: Option<PathBuf>,
+    /// Directory Walrus blobs are cached in. Nothing is cached when unset.
+    /// Cached blobs are only verified before caching with `verify_blobs`.
+    pub(crate) cache_dir: Option<PathBuf>,
+    /// How many bytes of Walrus blobs are cached at most.
+    pub(crate) cache_max_bytes: Option<u64>,
+    /// Serve Walrus blobs only from the cache.
+    #[serde(default)]
+    pub(crate) offline: bool,
+    /// Check downloaded Walrus blobs against their blob ID with the `walrus`
+    /// CLI.
+    #[serde(default)]
+    pub(crate) verify_blobs: bool,
 }
 
-impl From<DataStorageConf> for StorageConf {
-    fn from(val: DataStorageConf) -> StorageConf {
-        StorageConf {
+impl DataStorageConf {
+    /// Store remote data on the public Walrus testnet, keeping all settings
+    /// that are not Walrus endpoints.
+    pub(crate) fn with_walrus_testnet(self) -> Self {
+        Self {
+            walrus_aggregator_url: Some(WALRUS_AGGREGATOR_URL.parse().expect("valid URL")),
+            walrus_publisher_url: Some(WALRUS_PUBLISHER_URL.parse().expect("valid URL")),
+            walrus_save_for_epochs: Some(2),
+            preferred_remote_storage: Some(StorageKind::Walrus),
+            ..self
+        }
+    }
+}
+
+impl TryFrom<DataStorageConf> for StorageConf {
+    type Error = AnyError;
+
+    fn try_from(val: DataStorageConf) -> AnyResult<StorageConf> {
+        let encryption = match (val.encryption_key_id, val.encryption_key) {
+            (Some(id), Some(key)) => {
+                Some(WalrusKeyring::new().with_active_key(WalrusKey::from_base64(id, key.peek())?))
+            }
+            (None, None) => None,
+            _ => bail!("Both data_storage.encryption_key_id and data_storage.encryption_key must be set to encrypt Walrus data"),
+        };
+
+        let walrus_cache = match (val.cache_dir, val.offline) {
+            (Some(cache_dir), offline) => Some(
+                BlobCache::new(cache_dir)
+                    .with_max_bytes(val.cache_max_bytes.unwrap_or(DEFAULT_BLOB_CACHE_MAX_BYTES))
+                    .with_offline(offline),
+            ),
+            (None, true) => bail!("data_storage.offline requires data_storage.cache_dir to be set"),
+            (None, false) => None,
+        };
+
+        let mut stores = DataStoreRegistry::new();
+
+        if let Some(local_path) = val.local_path {
+            stores = stores.with_store(LocalFsStore::new(local_path));
+        }
+
+        Ok(StorageConf {
             walrus_aggregator_url: val.walrus_aggregator_url.map(|url| url.to_string()),
             walrus_publisher_url: val.walrus_publisher_url.map(|url| url.to_string()),
+            walrus_fallback_aggregator_urls: val
+                .walrus_fallback_aggregator_urls
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            walrus_fallback_publisher_urls: val
+                .walrus_fallback_publisher_urls
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
             walrus_save_for_epochs: val.walrus_save_for_epochs,
+            walrus_cache,
+            walrus_blob_verifier: val
+                .verify_blobs
+                .then(|| SharedBlobVerifier::new(WalrusCliBlobVerifier::new())),
+            encryption,
+            stores,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpc_profile(rpc_url: &str) -> ProfileConf {
+        ProfileConf {
+            sui: SuiConf {
+                pk: None,
+                rpc_url: Some(rpc_url.parse().unwrap()),
+            },
+            ..Default::default()
         }
     }
+
+    #[test]
+    fn test_use_profile_swaps_profile_fields() {
+        let mut conf = CliConf {
+            sui: rpc_profile("https://fullnode.default.sui.io/").sui,
+            ..Default::default()
+        };
+
+        conf.add_profile("testnet", rpc_profile(TESTNET_NEXUS_RPC_URL))
+            .unwrap();
+        assert!(conf.add_profile("default", ProfileConf::default()).is_err());
+        assert!(conf
+            .add_profile("not/valid", ProfileConf::default())
+            .is_err());
+
+        conf.use_profile("testnet").unwrap();
+
+        assert_eq!(conf.active_profile(), "testnet");
+        assert_eq!(
+            conf.sui.rpc_url.as_ref().map(reqwest::Url::as_str),
+            Some(TESTNET_NEXUS_RPC_URL)
+        );
+        assert_eq!(
+            conf.profiles["default"],
+            rpc_profile("https://fullnode.default.sui.io/")
+        );
+        assert!(conf.use_profile("missing").is_err());
+        assert!(conf.remove_profile("testnet").is_err());
+        assert!(conf.remove_profile("default").is_ok());
+        assert_eq!(conf.profile_names(), vec!["testnet"]);
+    }
+
+    #[tokio::test]
+    async fn test_selected_profile_is_not_persisted_as_active() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let path = tempdir.join("conf.toml");
+
+        let mut conf = CliConf::default();
+        conf.add_profile("testnet", rpc_profile(TESTNET_NEXUS_RPC_URL))
+            .unwrap();
+
+        // Unknown profiles are an error rather than a silent fallback.
+        assert!(conf.select_profile_named("testent").is_err());
+
+        conf.select_profile_named("testnet").unwrap();
+        assert_eq!(conf.active_profile(), "testnet");
+
+        // Changes made while a profile is selected end up in that profile.
+        conf.agents
+            .insert("agent".to_string(), sui::types::Address::ZERO);
+        conf.save_to_path(&path).await.unwrap();
+
+        let conf = CliConf::load_from_path(&path).await.unwrap();
+        assert_eq!(conf.active_profile(), DEFAULT_PROFILE);
+        assert!(conf.agents.is_empty());
+        assert_eq!(
+            conf.profiles["testnet"].agents["agent"],
+            sui::types::Address::ZERO
+        );
+    }
 }
@@ -2,13 +2,15 @@ use crate::{command_title, prelude::*};
 
 /// Print the current Nexus CLI configuration.
 pub(crate) async fn get_nexus_conf(conf_path: PathBuf) -> AnyResult<CliConf, NexusCliError> {
-    let conf = CliConf::load_from_path(&conf_path).await.map_err(|e| {
+    let mut conf = CliConf::load_from_path(&conf_path).await.map_err(|e| {
         NexusCliError::Any(anyhow!(
             "Failed to load Nexus CLI configuration from {}: {e}",
             conf_path.display(),
         ))
     })?;
 
+    conf.select_profile().map_err(NexusCliError::Any)?;
+
     command_title!("Current Nexus CLI Configuration");
 
     Ok(conf)
@@ -37,8 +39,17 @@ mod tests {
         let data_storage_conf = DataStorageConf {
             walrus_aggregator_url: None,
             walrus_publisher_url: None,
+            walrus_fallback_aggregator_urls: vec![],
+            walrus_fallback_publisher_urls: vec![],
             walrus_save_for_epochs: None,
             preferred_remote_storage: None,
+            encryption_key_id: None,
+            encryption_key: None,
+            local_path: None,
+            cache_dir: None,
+            cache_max_bytes: None,
+            offline: false,
+            verify_blobs: false,
         };
 
         let conf = CliConf {
@@ -48,6 +59,7 @@ mod tests {
             agents: HashMap::new(),
             secrets: SecretsConf::default(),
             data_storage: data_storage_conf.clone(),
+            ..Default::default()
         };
 
         // Write the configuration to the file.
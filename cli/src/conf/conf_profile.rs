@@ -0,0 +1,270 @@
+use crate::{
+    command_title,
+    display::json_output,
+    item,
+    loading,
+    notify_success,
+    notify_warning,
+    prelude::*,
+    sui::fetch_objects_from_url,
+};
+
+/// Add a new profile, optionally preset for a known network.
+pub(crate) async fn add_profile(
+    name: String,
+    network: Option<SuiNet>,
+    sui_rpc_url: Option<reqwest::Url>,
+    use_profile: bool,
+    conf_path: PathBuf,
+) -> AnyResult<(), NexusCliError> {
+    command_title!("Adding Nexus CLI profile '{name}'");
+
+    let mut conf = load_conf_or_default(&conf_path).await?;
+
+    let mut profile = ProfileConf::default();
+
+    if let Some(network) = network {
+        profile.sui.rpc_url = Some(network.rpc_url().parse().expect("valid URL"));
+
+        if network == SuiNet::Testnet {
+            profile.data_storage = DataStorageConf::default().with_walrus_testnet();
+        }
+
+        if let Some(objects_toml) = network.objects_toml() {
+            let objects_handle = loading!("Fetching Nexus objects for {network}...");
+
+            match fetch_objects_from_url(objects_toml).await {
+                Ok(objects) => {
+                    objects_handle.success();
+
+                    profile.nexus = Some(objects);
+                }
+                Err(e) => {
+                    objects_handle.error();
+
+                    // Objects are fetched again the first time they are needed.
+                    notify_warning!("Failed to fetch Nexus objects for {network}: {e}");
+                }
+            }
+        }
+    }
+
+    profile.sui.rpc_url = sui_rpc_url.or(profile.sui.rpc_url);
+
+    conf.add_profile(&name, profile)
+        .map_err(NexusCliError::Any)?;
+
+    if use_profile {
+        conf.use_profile(&name).map_err(NexusCliError::Any)?;
+    }
+
+    conf.save_to_path(&conf_path)
+        .await
+        .map_err(NexusCliError::Any)?;
+
+    json_output(&json!({ "profile": name, "active": conf.active_profile() }))?;
+
+    notify_success!(
+        "Profile '{name}' added, active profile is '{active}'",
+        active = conf.active_profile().truecolor(100, 100, 100)
+    );
+
+    Ok(())
+}
+
+/// Make a profile the active one.
+pub(crate) async fn use_profile(name: String, conf_path: PathBuf) -> AnyResult<(), NexusCliError> {
+    command_title!("Switching to Nexus CLI profile '{name}'");
+
+    let mut conf = load_conf(&conf_path).await?;
+
+    conf.use_profile(&name).map_err(NexusCliError::Any)?;
+
+    conf.save_to_path(&conf_path)
+        .await
+        .map_err(NexusCliError::Any)?;
+
+    json_output(&json!({ "active": conf.active_profile() }))?;
+
+    notify_success!(
+        "Active profile is '{name}'",
+        name = name.truecolor(100, 100, 100)
+    );
+
+    Ok(())
+}
+
+/// List all profiles and their Sui RPC URLs.
+pub(crate) async fn list_profiles(conf_path: PathBuf) -> AnyResult<(), NexusCliError> {
+    command_title!("Nexus CLI profiles");
+
+    let conf = load_conf(&conf_path).await?;
+
+    let profiles = conf
+        .profile_names()
+        .into_iter()
+        .map(|name| {
+            let active = name == conf.active_profile();
+            let rpc_url = if active {
+                conf.sui.rpc_url.as_ref()
+            } else {
+                conf.profiles[name].sui.rpc_url.as_ref()
+            };
+            let marker = if active { " (active)" } else { "" };
+
+            item!(
+                "{name}{marker}: {rpc_url}",
+                marker = marker.bold(),
+                rpc_url = rpc_url
+                    .map(ToString::to_string)
+                    .unwrap_or_else(|| "no RPC URL".to_string())
+                    .truecolor(100, 100, 100)
+            );
+
+            json!({ "name": name, "active": active, "rpc_url": rpc_url })
+        })
+        .collect::<Vec<_>>();
+
+    json_output(&json!({ "active": conf.active_profile(), "profiles": profiles }))?;
+
+    Ok(())
+}
+
+/// Remove an inactive profile.
+pub(crate) async fn remove_profile(
+    name: String,
+    conf_path: PathBuf,
+) -> AnyResult<(), NexusCliError> {
+    command_title!("Removing Nexus CLI profile '{name}'");
+
+    let mut conf = load_conf(&conf_path).await?;
+
+    conf.remove_profile(&name).map_err(NexusCliError::Any)?;
+
+    conf.save_to_path(&conf_path)
+        .await
+        .map_err(NexusCliError::Any)?;
+
+    json_output(&json!({ "removed": name }))?;
+
+    notify_success!(
+        "Profile '{name}' removed",
+        name = name.truecolor(100, 100, 100)
+    );
+
+    Ok(())
+}
+
+async fn load_conf(conf_path: &PathBuf) -> AnyResult<CliConf, NexusCliError> {
+    CliConf::load_from_path(conf_path)
+        .await
+        .map_err(|e| load_error(conf_path, e))
+}
+
+/// Like [`load_conf`] but a missing configuration file yields the default
+/// configuration.
+async fn load_conf_or_default(conf_path: &PathBuf) -> AnyResult<CliConf, NexusCliError> {
+    CliConf::load_from_path_or_default(conf_path)
+        .await
+        .map_err(|e| load_error(conf_path, e))
+}
+
+fn load_error(conf_path: &PathBuf, e: AnyError) -> NexusCliError {
+    NexusCliError::Any(anyhow!(
+        "Failed to load Nexus CLI configuration from {}: {e}",
+        conf_path.display(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_profile_lifecycle() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let path = tempdir.join("conf.toml");
+
+        let localnet: reqwest::Url = LOCALNET_NEXUS_RPC_URL.parse().unwrap();
+        let custom: reqwest::Url = "https://fullnode.custom.sui.io/".parse().unwrap();
+
+        add_profile(
+            "local".to_string(),
+            Some(SuiNet::Localnet),
+            None,
+            false,
+            path.clone(),
+        )
+        .await
+        .unwrap();
+
+        let conf = CliConf::load_from_path(&path).await.unwrap();
+        assert_eq!(conf.active_profile(), DEFAULT_PROFILE);
+        assert_eq!(conf.profile_names(), vec!["default", "local"]);
+        assert_eq!(conf.profiles["local"].sui.rpc_url, Some(localnet.clone()));
+        assert_eq!(conf.profiles["local"].nexus, None);
+
+        // Names are unique.
+        assert!(
+            add_profile("local".to_string(), None, None, false, path.clone())
+                .await
+                .is_err()
+        );
+
+        add_profile(
+            "custom".to_string(),
+            None,
+            Some(custom.clone()),
+            true,
+            path.clone(),
+        )
+        .await
+        .unwrap();
+
+        let conf = CliConf::load_from_path(&path).await.unwrap();
+        assert_eq!(conf.active_profile(), "custom");
+        assert_eq!(conf.sui.rpc_url, Some(custom));
+        assert_eq!(conf.profile_names(), vec!["custom", "default", "local"]);
+
+        use_profile("local".to_string(), path.clone())
+            .await
+            .unwrap();
+
+        let conf = CliConf::load_from_path(&path).await.unwrap();
+        assert_eq!(conf.active_profile(), "local");
+        assert_eq!(conf.sui.rpc_url, Some(localnet));
+        assert!(conf.profiles.contains_key("custom"));
+
+        // The active profile cannot be removed.
+        assert!(remove_profile("local".to_string(), path.clone())
+            .await
+            .is_err());
+        assert!(use_profile("missing".to_string(), path.clone())
+            .await
+            .is_err());
+
+        remove_profile("custom".to_string(), path.clone())
+            .await
+            .unwrap();
+
+        let conf = CliConf::load_from_path(&path).await.unwrap();
+        assert_eq!(conf.profile_names(), vec!["default", "local"]);
+    }
+
+    #[tokio::test]
+    async fn test_add_profile_keeps_unreadable_conf() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let path = tempdir.join("conf.toml");
+        let original = "[sui]\nrpc_url = \"https://testnet.sui.io/\"\n\n[sui.pk]\nkeyring = \"sui.pk@conf.toml\"\n";
+
+        tokio::fs::write(&path, original).await.unwrap();
+
+        // The keyring is disabled in tests, so the active secret is unreadable.
+        assert!(
+            add_profile("local".to_string(), None, None, false, path.clone())
+                .await
+                .is_err()
+        );
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), original);
+    }
+}
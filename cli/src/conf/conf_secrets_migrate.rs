@@ -0,0 +1,112 @@
+use {
+    crate::{command_title, display::json_output, item, notify_success, prelude::*},
+    std::collections::BTreeMap,
+};
+
+/// Re-store all secrets in the configuration according to the configured (or
+/// provided) [`SecretsMode`]. This converts plaintext configs to keyring or
+/// passphrase-encrypted storage and vice versa.
+///
+/// Like every other save, `auto` keeps secrets in plaintext with a warning if
+/// neither the keyring nor a passphrase is available, `require` fails.
+pub(crate) async fn migrate_secrets(
+    mode: Option<SecretsMode>,
+    conf_path: PathBuf,
+) -> AnyResult<(), NexusCliError> {
+    command_title!("Migrating Nexus CLI secrets");
+
+    let mut conf = CliConf::load_from_path(&conf_path).await.map_err(|e| {
+        NexusCliError::Any(anyhow!(
+            "Failed to load Nexus CLI configuration from {}: {e}",
+            conf_path.display(),
+        ))
+    })?;
+
+    if let Some(mode) = mode {
+        conf.secrets.mode = mode;
+    }
+
+    // No spinner here as storing secrets might prompt for a passphrase.
+    let sealed = conf
+        .save_sealed_to_path(&conf_path)
+        .await
+        .map_err(NexusCliError::Any)?;
+
+    for (name, storage) in &sealed {
+        item!("{name}: {storage}", storage = storage.to_string().bold());
+    }
+
+    json_output(&json!({
+        "mode": conf.secrets.mode,
+        "secrets": sealed.iter().cloned().collect::<BTreeMap<_, _>>(),
+    }))?;
+
+    notify_success!(
+        "Secrets stored with mode '{mode}'",
+        mode = conf.secrets.mode.to_string().truecolor(100, 100, 100)
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, assert_matches::assert_matches};
+
+    async fn write_plaintext_conf(path: &PathBuf) {
+        let conf = CliConf {
+            sui: SuiConf {
+                pk: Some("c3VwZXJzZWNyZXQ=".to_string().into()),
+                rpc_url: None,
+            },
+            ..Default::default()
+        };
+
+        tokio::fs::write(path, toml::to_string(&conf).unwrap())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_migrate_secrets_without_keyring_or_passphrase_follows_mode() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let path = tempdir.join("conf.toml");
+
+        write_plaintext_conf(&path).await;
+        let original = tokio::fs::read_to_string(&path).await.unwrap();
+
+        // No secrets backend is installed in tests, so neither the keyring
+        // nor a passphrase is available.
+        let result = migrate_secrets(Some(SecretsMode::Require), path.clone()).await;
+
+        assert_matches!(result, Err(NexusCliError::Any(_)));
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), original);
+
+        // `auto` falls back to plaintext just like saving the configuration.
+        let result = migrate_secrets(Some(SecretsMode::Auto), path.clone()).await;
+
+        assert_matches!(result, Ok(()));
+
+        let conf = CliConf::load_from_path(&path).await.unwrap();
+
+        assert_eq!(conf.secrets.mode, SecretsMode::Auto);
+        assert_eq!(conf.sui.pk, Some("c3VwZXJzZWNyZXQ=".to_string().into()));
+    }
+
+    #[tokio::test]
+    async fn test_migrate_secrets_persists_mode() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let path = tempdir.join("conf.toml");
+
+        write_plaintext_conf(&path).await;
+
+        let result = migrate_secrets(Some(SecretsMode::Off), path.clone()).await;
+
+        assert_matches!(result, Ok(()));
+
+        let conf = CliConf::load_from_path(&path).await.unwrap();
+
+        assert_eq!(conf.secrets.mode, SecretsMode::Off);
+        assert_eq!(conf.sui.pk, Some("c3VwZXJzZWNyZXQ=".to_string().into()));
+    }
+}
@@ -2,10 +2,13 @@ use {
     crate::{cli_conf::StorageKind, command_title, display::json_output, loading, prelude::*},
     nexus_sdk::{
         types::SecretValue,
-        walrus::{WALRUS_AGGREGATOR_URL, WALRUS_PUBLISHER_URL},
+        walrus::{encryption::WalrusKey, StorageConf},
     },
 };
 
+/// Key id used when generating a Walrus encryption key without an explicit id.
+const DEFAULT_ENCRYPTION_KEY_ID: &str = "default";
+
 /// Set the Nexus CLI configuration from the provided arguments.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn set_nexus_conf(
@@ -17,11 +20,27 @@ pub(crate) async fn set_nexus_conf(
     data_storage_walrus_save_for_epochs: Option<u8>,
     data_storage_preferred_remote_storage: Option<StorageKind>,
     data_storage_testnet: bool,
+    data_storage_encryption_key_id: Option<String>,
+    data_storage_encryption_key: Option<String>,
+    data_storage_generate_encryption_key: bool,
+    data_storage_local_path: Option<PathBuf>,
+    data_storage_cache_dir: Option<PathBuf>,
+    data_storage_cache_max_bytes: Option<u64>,
+    data_storage_offline: Option<bool>,
+    data_storage_verify_blobs: Option<bool>,
+    secrets_mode: Option<SecretsMode>,
     conf_path: PathBuf,
 ) -> AnyResult<(), NexusCliError> {
-    let mut conf = CliConf::load_from_path(&conf_path)
+    let mut conf = CliConf::load_from_path_or_default(&conf_path)
         .await
-        .unwrap_or_default();
+        .map_err(|e| {
+            NexusCliError::Any(anyhow!(
+                "Failed to load Nexus CLI configuration from {}: {e}",
+                conf_path.display(),
+            ))
+        })?;
+
+    conf.select_profile().map_err(NexusCliError::Any)?;
 
     command_title!("Updating Nexus CLI Configuration");
     let conf_handle = loading!("Updating configuration...");
@@ -47,6 +66,7 @@ pub(crate) async fn set_nexus_conf(
 
     conf.sui.pk = sui_pk.map(SecretValue::from).or(conf.sui.pk);
     conf.sui.rpc_url = sui_rpc_url.or(conf.sui.rpc_url);
+    conf.secrets.mode = secrets_mode.unwrap_or(conf.secrets.mode);
 
     // Preferred remote storage cannot be inline.
     if matches!(
@@ -69,13 +89,45 @@ pub(crate) async fn set_nexus_conf(
     conf.data_storage.preferred_remote_storage =
         data_storage_preferred_remote_storage.or(conf.data_storage.preferred_remote_storage);
 
+    conf.data_storage.encryption_key_id =
+        data_storage_encryption_key_id.or(conf.data_storage.encryption_key_id);
+    conf.data_storage.encryption_key = data_storage_encryption_key
+        .map(SecretValue::from)
+        .or(conf.data_storage.encryption_key);
+
+    conf.data_storage.local_path = data_storage_local_path.or(conf.data_storage.local_path);
+    conf.data_storage.cache_dir = data_storage_cache_dir.or(conf.data_storage.cache_dir);
+    conf.data_storage.cache_max_bytes =
+        data_storage_cache_max_bytes.or(conf.data_storage.cache_max_bytes);
+    conf.data_storage.offline = data_storage_offline.unwrap_or(conf.data_storage.offline);
+    conf.data_storage.verify_blobs =
+        data_storage_verify_blobs.unwrap_or(conf.data_storage.verify_blobs);
+
+    if data_storage_generate_encryption_key {
+        let id = conf
+            .data_storage
+            .encryption_key_id
+            .get_or_insert_with(|| DEFAULT_ENCRYPTION_KEY_ID.to_string());
+
+        match WalrusKey::generate(id.as_str()) {
+            Ok(key) => conf.data_storage.encryption_key = Some(key.to_base64()),
+            Err(e) => {
+                conf_handle.error();
+
+                return Err(NexusCliError::Any(e));
+            }
+        }
+    }
+
     if data_storage_testnet {
-        conf.data_storage = DataStorageConf {
-            walrus_aggregator_url: Some(WALRUS_AGGREGATOR_URL.parse().expect("valid URL")),
-            walrus_publisher_url: Some(WALRUS_PUBLISHER_URL.parse().expect("valid URL")),
-            walrus_save_for_epochs: Some(2),
-            preferred_remote_storage: Some(StorageKind::Walrus),
-        };
+        conf.data_storage = std::mem::take(&mut conf.data_storage).with_walrus_testnet();
+    }
+
+    // Make sure the encryption settings are usable before saving them.
+    if let Err(e) = StorageConf::try_from(conf.data_storage.clone()) {
+        conf_handle.error();
+
+        return Err(NexusCliError::Any(e));
     }
 
     json_output(&serde_json::to_value(&conf).unwrap())?;
@@ -94,7 +146,14 @@ pub(crate) async fn set_nexus_conf(
 
 #[cfg(test)]
 mod tests {
-    use {super::*, assert_matches::assert_matches, nexus_sdk::test_utils::sui_mocks};
+    use {
+        super::*,
+        assert_matches::assert_matches,
+        nexus_sdk::{
+            test_utils::sui_mocks,
+            walrus::{WALRUS_AGGREGATOR_URL, WALRUS_PUBLISHER_URL},
+        },
+    };
 
     #[tokio::test]
     async fn test_conf_loads_and_saves() {
@@ -125,6 +184,15 @@ mod tests {
             Some(42),
             Some(StorageKind::Walrus),
             false,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
             path.clone(),
         )
         .await;
@@ -165,6 +233,15 @@ mod tests {
             None,
             None,
             false,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
             path.clone(),
         )
         .await;
@@ -210,6 +287,15 @@ mod tests {
             None,
             None,
             true,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
             path.clone(),
         )
         .await;
@@ -246,10 +332,187 @@ mod tests {
             None,
             Some(StorageKind::Inline),
             false,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            path.clone(),
+        )
+        .await;
+
+        assert_matches!(result, Err(NexusCliError::Any(_)));
+    }
+
+    #[tokio::test]
+    async fn test_generate_encryption_key() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let path = tempdir.join("conf_encryption.toml");
+
+        let result = set_nexus_conf(
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            Some("team-a".to_string()),
+            None,
+            true,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(SecretsMode::Off),
+            path.clone(),
+        )
+        .await;
+
+        assert_matches!(result, Ok(()));
+
+        let conf = CliConf::load_from_path(&path).await.unwrap();
+        assert_eq!(
+            conf.data_storage.encryption_key_id.as_deref(),
+            Some("team-a")
+        );
+
+        let storage_conf = StorageConf::try_from(conf.data_storage).unwrap();
+        let keyring = storage_conf.encryption.unwrap();
+        assert_eq!(keyring.active_key().unwrap().id(), "team-a");
+
+        // A key without an id cannot be used.
+        let result = set_nexus_conf(
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+            Some("not a key".to_string()),
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            tempdir.join("conf_invalid.toml"),
+        )
+        .await;
+
+        assert_matches!(result, Err(NexusCliError::Any(_)));
+    }
+
+    #[tokio::test]
+    async fn test_walrus_cache_and_offline_mode() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let path = tempdir.join("conf_cache.toml");
+
+        // Offline mode serves blobs from the cache, so it needs one.
+        let result = set_nexus_conf(
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            Some(true),
+            None,
+            None,
+            path.clone(),
+        )
+        .await;
+
+        assert_matches!(result, Err(NexusCliError::Any(_)));
+
+        let result = set_nexus_conf(
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+            None,
+            false,
+            None,
+            Some(tempdir.join("cache")),
+            Some(1024),
+            Some(true),
+            Some(true),
+            None,
+            path.clone(),
+        )
+        .await;
+
+        assert_matches!(result, Ok(()));
+
+        let conf = CliConf::load_from_path(&path).await.unwrap();
+        assert!(conf.data_storage.offline);
+
+        let storage_conf = StorageConf::try_from(conf.data_storage).unwrap();
+        assert!(storage_conf.walrus_blob_verifier.is_some());
+
+        let cache = storage_conf.walrus_cache.unwrap();
+        assert_eq!(cache.root(), tempdir.join("cache"));
+        assert_eq!(cache.max_bytes(), 1024);
+        assert!(cache.is_offline());
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_conf_is_left_untouched() {
+        let tempdir = tempfile::tempdir().unwrap().keep();
+        let path = tempdir.join("conf_encrypted.toml");
+        let original = "[sui]\nrpc_url = \"https://testnet.sui.io/\"\n\n[sui.pk]\nsalt = \"c2FsdHNhbHRzYWx0c2FsdA==\"\nnonce = \"bm9uY2Vub25jZW5v\"\nciphertext = \"Y2lwaGVydGV4dA==\"\n";
+
+        tokio::fs::write(&path, original).await.unwrap();
+
+        // No secrets backend is installed in tests, so the secret cannot be
+        // decrypted. The command must fail instead of replacing the
+        // configuration with defaults.
+        let result = set_nexus_conf(
+            None,
+            Some(reqwest::Url::parse("https://mainnet.sui.io").unwrap()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
             path.clone(),
         )
         .await;
 
         assert_matches!(result, Err(NexusCliError::Any(_)));
+        assert_eq!(tokio::fs::read_to_string(&path).await.unwrap(), original);
     }
 }
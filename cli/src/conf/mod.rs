@@ -1,9 +1,13 @@
 mod conf_get;
+mod conf_profile;
+mod conf_secrets_migrate;
 mod conf_set;
 
 use {
     crate::{cli_conf::StorageKind, display::json_output, prelude::*},
     conf_get::*,
+    conf_profile::*,
+    conf_secrets_migrate::*,
     conf_set::*,
     nexus_sdk::walrus::WALRUS_MAX_EPOCHS,
 };
@@ -76,6 +80,174 @@ pub(crate) enum ConfCommand {
             help = "Preset configuration for testnet (overrides other data storage settings)"
         )]
         data_storage_testnet: bool,
+        #[arg(
+            long = "data-storage.encryption-key-id",
+            help = "Set the id of the key Walrus data is encrypted with",
+            value_name = "ID"
+        )]
+        data_storage_encryption_key_id: Option<String>,
+        #[arg(
+            long = "data-storage.encryption-key",
+            help = "Set the base64 encoded AES-256 key Walrus data is encrypted with",
+            value_name = "BASE64"
+        )]
+        data_storage_encryption_key: Option<String>,
+        #[arg(
+            long = "data-storage.generate-encryption-key",
+            help = "Generate a new key to encrypt Walrus data with",
+            conflicts_with = "data_storage_encryption_key"
+        )]
+        data_storage_generate_encryption_key: bool,
+        #[arg(
+            long = "data-storage.local-path",
+            help = "Set the directory backing the 'local' remote storage",
+            value_name = "PATH",
+            value_parser = ValueParser::from(expand_tilde)
+        )]
+        data_storage_local_path: Option<PathBuf>,
+        #[arg(
+            long = "data-storage.cache-dir",
+            help = "Set the directory Walrus blobs are cached in, enabling the cache",
+            value_name = "PATH",
+            value_parser = ValueParser::from(expand_tilde)
+        )]
+        data_storage_cache_dir: Option<PathBuf>,
+        #[arg(
+            long = "data-storage.cache-max-bytes",
+            help = "Set how many bytes of Walrus blobs are cached before the least recently used are evicted",
+            value_name = "BYTES"
+        )]
+        data_storage_cache_max_bytes: Option<u64>,
+        #[arg(
+            long = "data-storage.offline",
+            help = "Serve Walrus blobs only from the cache and never contact Walrus",
+            value_name = "BOOL"
+        )]
+        data_storage_offline: Option<bool>,
+        #[arg(
+            long = "data-storage.verify-blobs",
+            help = "Check downloaded Walrus blobs against their blob ID with the `walrus` CLI",
+            value_name = "BOOL"
+        )]
+        data_storage_verify_blobs: Option<bool>,
+        #[arg(
+            long = "secrets.mode",
+            help = "Set how secrets are stored on disk",
+            value_enum,
+            value_name = "MODE"
+        )]
+        secrets_mode: Option<SecretsMode>,
+
+        /// Hidden argument used for testing to set the path of the configuration
+        /// file.
+        #[arg(
+            long = "conf-path",
+            hide = true,
+            default_value = CLI_CONF_PATH,
+            value_parser = ValueParser::from(expand_tilde)
+        )]
+        conf_path: PathBuf,
+    },
+
+    #[command(subcommand, about = "Manage how secrets are stored")]
+    Secrets(SecretsCommand),
+
+    #[command(
+        subcommand,
+        about = "Manage named profiles, each with its own key, RPC URL, Nexus objects, data storage and agents"
+    )]
+    Profile(ProfileCommand),
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum ProfileCommand {
+    #[command(about = "Add a new profile")]
+    Add {
+        /// Name of the profile.
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Preset the Sui RPC URL, Nexus objects and data storage for a known
+        /// network.
+        #[arg(long = "network", short = 'n', value_enum, value_name = "NETWORK")]
+        network: Option<SuiNet>,
+
+        /// Use this Sui node RPC URL, overriding the network preset.
+        #[arg(long = "sui.rpc-url", value_name = "URL")]
+        sui_rpc_url: Option<reqwest::Url>,
+
+        /// Make the new profile the active one.
+        #[arg(long = "use")]
+        use_profile: bool,
+
+        /// Hidden argument used for testing to set the path of the configuration
+        /// file.
+        #[arg(
+            long = "conf-path",
+            hide = true,
+            default_value = CLI_CONF_PATH,
+            value_parser = ValueParser::from(expand_tilde)
+        )]
+        conf_path: PathBuf,
+    },
+
+    #[command(about = "Make a profile the active one")]
+    Use {
+        /// Name of the profile.
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Hidden argument used for testing to set the path of the configuration
+        /// file.
+        #[arg(
+            long = "conf-path",
+            hide = true,
+            default_value = CLI_CONF_PATH,
+            value_parser = ValueParser::from(expand_tilde)
+        )]
+        conf_path: PathBuf,
+    },
+
+    #[command(about = "List all profiles")]
+    List {
+        /// Hidden argument used for testing to set the path of the configuration
+        /// file.
+        #[arg(
+            long = "conf-path",
+            hide = true,
+            default_value = CLI_CONF_PATH,
+            value_parser = ValueParser::from(expand_tilde)
+        )]
+        conf_path: PathBuf,
+    },
+
+    #[command(about = "Remove a profile that is not active")]
+    Remove {
+        /// Name of the profile.
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Hidden argument used for testing to set the path of the configuration
+        /// file.
+        #[arg(
+            long = "conf-path",
+            hide = true,
+            default_value = CLI_CONF_PATH,
+            value_parser = ValueParser::from(expand_tilde)
+        )]
+        conf_path: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum SecretsCommand {
+    #[command(
+        about = "Store all secrets in the configuration according to the secrets mode, converting plaintext secrets"
+    )]
+    Migrate {
+        /// Switch to this secrets mode before migrating.
+        #[arg(long = "mode", short = 'm', value_enum, value_name = "MODE")]
+        mode: Option<SecretsMode>,
 
         /// Hidden argument used for testing to set the path of the configuration
         /// file.
@@ -117,6 +289,15 @@ pub(crate) async fn handle(command: ConfCommand) -> AnyResult<(), NexusCliError>
             data_storage_walrus_save_for_epochs,
             data_storage_preferred_remote_storage,
             data_storage_testnet,
+            data_storage_encryption_key_id,
+            data_storage_encryption_key,
+            data_storage_generate_encryption_key,
+            data_storage_local_path,
+            data_storage_cache_dir,
+            data_storage_cache_max_bytes,
+            data_storage_offline,
+            data_storage_verify_blobs,
+            secrets_mode,
             conf_path,
         } => {
             set_nexus_conf(
@@ -128,9 +309,40 @@ pub(crate) async fn handle(command: ConfCommand) -> AnyResult<(), NexusCliError>
                 data_storage_walrus_save_for_epochs,
                 data_storage_preferred_remote_storage,
                 data_storage_testnet,
+                data_storage_encryption_key_id,
+                data_storage_encryption_key,
+                data_storage_generate_encryption_key,
+                data_storage_local_path,
+                data_storage_cache_dir,
+                data_storage_cache_max_bytes,
+                data_storage_offline,
+                data_storage_verify_blobs,
+                secrets_mode,
                 conf_path,
             )
             .await
         }
+        // == `$ nexus conf secrets migrate` ==
+        ConfCommand::Secrets(SecretsCommand::Migrate { mode, conf_path }) => {
+            migrate_secrets(mode, conf_path).await
+        }
+        // == `$ nexus conf profile add` ==
+        ConfCommand::Profile(ProfileCommand::Add {
+            name,
+            network,
+            sui_rpc_url,
+            use_profile,
+            conf_path,
+        }) => add_profile(name, network, sui_rpc_url, use_profile, conf_path).await,
+        // == `$ nexus conf profile use` ==
+        ConfCommand::Profile(ProfileCommand::Use { name, conf_path }) => {
+            use_profile(name, conf_path).await
+        }
+        // == `$ nexus conf profile list` ==
+        ConfCommand::Profile(ProfileCommand::List { conf_path }) => list_profiles(conf_path).await,
+        // == `$ nexus conf profile remove` ==
+        ConfCommand::Profile(ProfileCommand::Remove { name, conf_path }) => {
+            remove_profile(name, conf_path).await
+        }
     }
 }
@@ -5,6 +5,7 @@ use {
         display::json_output,
         loading,
         notify_success,
+        notify_warning,
         prelude::*,
         sui::*,
         workflow,
@@ -46,7 +47,9 @@ pub(crate) async fn execute_dag(
 ) -> AnyResult<(), NexusCliError> {
     command_title!("Executing Nexus DAG '{dag_id}'");
 
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
     let pk = get_signing_key(&conf).await?;
     let owner = pk.public_key().derive_address();
     if payment_coin.is_none() {
@@ -89,7 +92,11 @@ pub(crate) async fn execute_dag(
 
     // Build the remote storage conf.
     let preferred_remote_storage = conf.data_storage.preferred_remote_storage;
-    let storage_conf = conf.data_storage.clone().into();
+    let storage_conf = conf
+        .data_storage
+        .clone()
+        .try_into()
+        .map_err(NexusCliError::Any)?;
 
     // Store ports remote if they need to be stored remotely.
     let input_data =
@@ -101,9 +108,36 @@ pub(crate) async fn execute_dag(
         payment_max_budget_mist,
     )?;
 
+    let workflow = nexus_client.workflow();
+
+    // Nothing is uploaded to remote storage in a dry run.
+    if DRY_RUN.load(Ordering::Relaxed) {
+        let has_remote_inputs = input_data
+            .values()
+            .flat_map(|ports| &ports.contents)
+            .any(|entry| !entry.value.is_inline());
+        let simulated = workflow
+            .simulate_default_agent_dag(
+                dag_id,
+                input_data,
+                Some(priority_fee_percentage),
+                Some(&entry_group),
+                agent_dag_options,
+            )
+            .await
+            .map_err(NexusCliError::Nexus)?;
+
+        if has_remote_inputs {
+            notify_warning!(
+                "Dry run: remote inputs were not uploaded, placeholders were simulated"
+            );
+        }
+
+        return report_dry_run(&nexus_client, &simulated).await;
+    }
+
     let tx_handle = loading!("Crafting and executing transaction...");
 
-    let workflow = nexus_client.workflow();
     let result = match workflow
         .execute_default_agent_dag(
             dag_id,
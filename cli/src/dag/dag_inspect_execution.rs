@@ -59,8 +59,14 @@ pub(crate) async fn inspect_dag_execution(
         .map_err(NexusCliError::Nexus)?;
 
     // Remote storage conf.
-    let conf = CliConf::load().await.unwrap_or_default();
-    let storage_conf = conf.data_storage.clone().into();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
+    let storage_conf = conf
+        .data_storage
+        .clone()
+        .try_into()
+        .map_err(NexusCliError::Any)?;
 
     let mut json_trace = Vec::new();
 
@@ -87,7 +93,7 @@ pub(crate) async fn inspect_dag_execution(
                     )))?;
 
                 for (port, data) in fetched_data.into_map() {
-                    let data_value = nexus_data_to_json_value(&data);
+                    let data_value = nexus_data_to_json_value(&data).map_err(NexusCliError::Any)?;
                     let storage = String::from_utf8_lossy(data.storage_tag()).into_owned();
                     let (display_data, json_data_value) = (
                         format!("{data_value}"),
@@ -133,7 +139,7 @@ pub(crate) async fn inspect_dag_execution(
                     )))?;
 
                 for (port, data) in fetched_data.into_map() {
-                    let data_value = nexus_data_to_json_value(&data);
+                    let data_value = nexus_data_to_json_value(&data).map_err(NexusCliError::Any)?;
                     let storage = String::from_utf8_lossy(data.storage_tag()).into_owned();
                     let (display_data, json_data_value) = (
                         format!("{data_value}"),
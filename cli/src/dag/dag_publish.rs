@@ -21,6 +21,16 @@ pub(crate) async fn publish_dag(
 
     let nexus_client = get_nexus_client(sui_gas_coin, sui_gas_budget).await?;
 
+    if DRY_RUN.load(Ordering::Relaxed) {
+        let simulated = nexus_client
+            .workflow()
+            .simulate_publish(dag)
+            .await
+            .map_err(NexusCliError::Nexus)?;
+
+        return report_dry_run(&nexus_client, &simulated).await;
+    }
+
     let tx_handle = loading!("Crafting and executing transaction...");
 
     let response = match nexus_client.workflow().publish(dag).await {
@@ -1,7 +1,7 @@
 use {
-    crate::{command_title, loading, prelude::*},
+    crate::{command_title, display::json_output, loading, prelude::*},
     nexus_sdk::{
-        dag::{json::parse_dag_spec, validator::validate},
+        dag::diagnostic::{check_source, DagDiagnostic, Severity},
         types::DagSpec,
     },
 };
@@ -9,12 +9,71 @@ use {
 /// Validate if a JSON file at the provided location is a valid Nexus DAG. If so,
 /// return the parsed DAG.
 pub(crate) async fn validate_dag(path: PathBuf) -> AnyResult<DagSpec, NexusCliError> {
+    check_dag(path).await?.into_dag()
+}
+
+/// Validate the DAG for `$ nexus dag validate`. In JSON mode, all diagnostics
+/// are printed as machine-readable objects.
+pub(crate) async fn validate_dag_command(path: PathBuf) -> AnyResult<(), NexusCliError> {
+    let report = check_dag(path).await?;
+
+    json_output(&json!({
+        "valid": report.is_valid(),
+        "diagnostics": report.diagnostics,
+    }))?;
+
+    report.into_dag().map(|_| ())
+}
+
+/// A parsed and validated DAG file.
+struct DagReport {
+    path: PathBuf,
+    source: String,
+    dag: Option<DagSpec>,
+    diagnostics: Vec<DagDiagnostic>,
+}
+
+impl DagReport {
+    fn is_valid(&self) -> bool {
+        self.dag.is_some() && !self.diagnostics.iter().any(DagDiagnostic::is_error)
+    }
+
+    /// Return the DAG if it is valid, otherwise render all diagnostics into
+    /// the returned error.
+    fn into_dag(self) -> AnyResult<DagSpec, NexusCliError> {
+        if self.is_valid() {
+            if let Some(dag) = self.dag {
+                return Ok(dag);
+            }
+        }
+
+        let path = self.path.display().to_string();
+        let rendered = self
+            .diagnostics
+            .iter()
+            .map(|diagnostic| render_diagnostic(diagnostic, &self.source, &path))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let errors = self
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_error())
+            .count();
+
+        Err(NexusCliError::Any(anyhow!(
+            "{rendered}\n\nFound {errors} error(s) in '{path}'.\nSee more about DAG rules at <https://github.com/Talus-Network/nexus-next/wiki/Package:-Workflow#rules>",
+        )))
+    }
+}
+
+/// Read the file at `path`, parse it and collect every diagnostic.
+async fn check_dag(path: PathBuf) -> AnyResult<DagReport, NexusCliError> {
     command_title!("Validating Nexus DAG at '{path}'", path = path.display());
 
     let parsing_handle = loading!("Parsing JSON file...");
 
     // Read file.
-    let file = match tokio::fs::read_to_string(path).await {
+    let source = match tokio::fs::read_to_string(&path).await {
         Ok(file) => file,
         Err(e) => {
             parsing_handle.error();
@@ -23,32 +82,134 @@ pub(crate) async fn validate_dag(path: PathBuf) -> AnyResult<DagSpec, NexusCliEr
         }
     };
 
-    let dag = match parse_dag_spec(file.as_str()) {
-        Ok(dag) => dag,
-        Err(e) => {
-            parsing_handle.error();
+    let (dag, diagnostics) = check_source(source.as_str());
 
-            return Err(NexusCliError::Any(anyhow!(e)));
-        }
-    };
+    if dag.is_none() {
+        parsing_handle.error();
+
+        return Ok(DagReport {
+            path,
+            source,
+            dag,
+            diagnostics,
+        });
+    }
 
     parsing_handle.success();
 
     let validation_handle = loading!("Validating Nexus DAG...");
 
-    // Validate the dag.
-    match validate(&dag) {
-        Ok(()) => {
-            validation_handle.success();
+    let report = DagReport {
+        path,
+        source,
+        dag,
+        diagnostics,
+    };
 
-            Ok(dag)
-        }
-        Err(e) => {
-            validation_handle.error();
+    if report.is_valid() {
+        validation_handle.success();
+    } else {
+        validation_handle.error();
+    }
 
-            Err(NexusCliError::Any(anyhow!(
-                "{e}\n\nSee more about DAG rules at <https://github.com/Talus-Network/nexus-next/wiki/Package:-Workflow#rules>",
-            )))
-        }
+    Ok(report)
+}
+
+/// Render a diagnostic like a compiler does, with the offending line of the
+/// source and a caret under the reported span.
+fn render_diagnostic(diagnostic: &DagDiagnostic, source: &str, path: &str) -> String {
+    let severity = match diagnostic.severity {
+        Severity::Error => format!("error[{}]", diagnostic.rule.code()).red().bold(),
+        Severity::Warning => format!("warning[{}]", diagnostic.rule.code())
+            .yellow()
+            .bold(),
+    };
+
+    let mut rendered = format!("{severity}: {}", diagnostic.message.bold());
+
+    let Some(span) = diagnostic.span else {
+        rendered.push_str(&format!("\n  {} {path}", "-->".blue().bold()));
+
+        return rendered;
+    };
+
+    let line = source.lines().nth(span.line - 1).unwrap_or_default();
+    let gutter = " ".repeat(span.line.to_string().len());
+    let pipe = "|".blue().bold();
+
+    // Only underline the part of the span on the first line.
+    let underline = source[span.offset..]
+        .get(..span.len)
+        .and_then(|spanned| spanned.lines().next())
+        .map(|spanned| spanned.chars().count())
+        .unwrap_or_default()
+        .max(1);
+
+    rendered.push_str(&format!(
+        "\n{gutter}{arrow} {path}:{row}:{column}\n{gutter} {pipe}\n{line_number} {pipe} {line}\n{gutter} {pipe} {padding}{carets}",
+        arrow = "-->".blue().bold(),
+        row = span.line,
+        line_number = span.line.to_string().blue().bold(),
+        column = span.column,
+        padding = " ".repeat(span.column - 1),
+        carets = "^".repeat(underline).red().bold(),
+    ));
+
+    if let Some(pointer) = &diagnostic.pointer {
+        rendered.push_str(&format!(
+            "\n{gutter} {equals} at {pointer}",
+            equals = "=".blue().bold()
+        ));
+    }
+
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        nexus_sdk::dag::diagnostic::{DagRule, SourceSpan},
+    };
+
+    #[test]
+    fn test_render_diagnostic_points_at_span() {
+        let source = "{\n  \"vertices\": [\n    { \"name\": \"b\" }\n  ]\n}";
+        let offset = source.find("{ \"name\"").unwrap();
+        let diagnostic = DagDiagnostic::new(
+            DagRule::DuplicateVertex,
+            "'Vertex: b' is defined multiple times.",
+        )
+        .with_pointer("/vertices/0");
+        let diagnostic = DagDiagnostic {
+            span: Some(SourceSpan::at(source, offset, 15)),
+            ..diagnostic
+        };
+
+        let rendered = render_diagnostic(&diagnostic, source, "dag.json");
+
+        assert!(rendered.contains("DAG006"));
+        assert!(rendered.contains("dag.json:3:5"));
+        assert!(rendered.contains("    { \"name\": \"b\" }"));
+        assert!(rendered.contains(&"^".repeat(15)));
+        assert!(rendered.contains("/vertices/0"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_dag_reports_all_diagnostics() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dag.json");
+
+        std::fs::write(&path, "{\n  \"vertices\": [,]\n}").unwrap();
+
+        let report = check_dag(path).await.unwrap();
+
+        assert!(!report.is_valid());
+        assert_eq!(report.diagnostics[0].rule, DagRule::Parse);
+
+        let error = report.into_dag().unwrap_err().to_string();
+
+        assert!(error.contains("DAG001"));
+        assert!(error.contains("Found 1 error(s)"));
     }
 }
@@ -0,0 +1,236 @@
+use {
+    crate::{command_title, display::json_output, loading, notify_success, prelude::*, sui::*},
+    nexus_sdk::{
+        dag::{
+            json::parse_dag_spec,
+            render::{to_dot, to_mermaid},
+        },
+        types::DagSpec,
+    },
+    tokio::io::AsyncWriteExt,
+};
+
+/// Available output formats for `$ nexus dag visualize`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum DiagramFormat {
+    /// Graphviz DOT source.
+    #[default]
+    Dot,
+    /// Mermaid flowchart source.
+    Mermaid,
+    /// SVG rendered via the Graphviz `dot` binary.
+    Svg,
+}
+
+impl std::fmt::Display for DiagramFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiagramFormat::Dot => write!(f, "dot"),
+            DiagramFormat::Mermaid => write!(f, "mermaid"),
+            DiagramFormat::Svg => write!(f, "svg"),
+        }
+    }
+}
+
+/// Render a Nexus DAG either from a local JSON file or from an on-chain DAG
+/// object into the requested diagram format. The diagram is written to
+/// `output` if provided, otherwise it is printed.
+pub(crate) async fn visualize_dag(
+    path: Option<PathBuf>,
+    dag_id: Option<sui::types::Address>,
+    format: DiagramFormat,
+    output: Option<PathBuf>,
+) -> AnyResult<(), NexusCliError> {
+    let dag = match (path, dag_id) {
+        (Some(path), None) => read_dag(path).await?,
+        (None, Some(dag_id)) => fetch_dag(dag_id).await?,
+        _ => {
+            return Err(NexusCliError::Any(anyhow!(
+                "Exactly one of '--path' or '--dag-id' must be provided"
+            )))
+        }
+    };
+
+    let render_handle = loading!("Rendering DAG as {format}...");
+
+    let diagram = match render(&dag, format).await {
+        Ok(diagram) => diagram,
+        Err(e) => {
+            render_handle.error();
+
+            return Err(NexusCliError::Any(e));
+        }
+    };
+
+    render_handle.success();
+
+    if let Some(output) = &output {
+        let mut file = tokio::fs::File::create(output)
+            .await
+            .map_err(NexusCliError::Io)?;
+
+        file.write_all(diagram.as_bytes())
+            .await
+            .map_err(NexusCliError::Io)?;
+
+        notify_success!(
+            "Diagram written to '{path}'",
+            path = output.display().to_string().truecolor(100, 100, 100)
+        );
+    } else if !JSON_MODE.load(Ordering::Relaxed) {
+        println!("{diagram}");
+    }
+
+    json_output(&json!({
+        "format": format.to_string(),
+        "output": output,
+        "diagram": diagram,
+    }))?;
+
+    Ok(())
+}
+
+/// Read and parse a DAG JSON file.
+async fn read_dag(path: PathBuf) -> AnyResult<DagSpec, NexusCliError> {
+    command_title!("Visualizing Nexus DAG at '{path}'", path = path.display());
+
+    let parsing_handle = loading!("Parsing JSON file...");
+
+    let file = match tokio::fs::read_to_string(path).await {
+        Ok(file) => file,
+        Err(e) => {
+            parsing_handle.error();
+
+            return Err(NexusCliError::Io(e));
+        }
+    };
+
+    match parse_dag_spec(file.as_str()) {
+        Ok(dag) => {
+            parsing_handle.success();
+
+            Ok(dag)
+        }
+        Err(e) => {
+            parsing_handle.error();
+
+            Err(NexusCliError::Any(anyhow!(e)))
+        }
+    }
+}
+
+/// Fetch a published DAG from Sui.
+async fn fetch_dag(dag_id: sui::types::Address) -> AnyResult<DagSpec, NexusCliError> {
+    command_title!("Visualizing Nexus DAG '{dag_id}'");
+
+    let nexus_client = get_nexus_client(None, DEFAULT_GAS_BUDGET).await?;
+
+    let fetch_handle = loading!("Fetching DAG from Sui...");
+
+    match nexus_client.workflow().fetch_dag(dag_id).await {
+        Ok(dag) => {
+            fetch_handle.success();
+
+            Ok(dag)
+        }
+        Err(e) => {
+            fetch_handle.error();
+
+            Err(NexusCliError::Nexus(e))
+        }
+    }
+}
+
+/// Render the DAG into the given format. SVG output requires the Graphviz
+/// `dot` binary to be available on `PATH`.
+async fn render(dag: &DagSpec, format: DiagramFormat) -> AnyResult<String> {
+    match format {
+        DiagramFormat::Dot => to_dot(dag),
+        DiagramFormat::Mermaid => to_mermaid(dag),
+        DiagramFormat::Svg => dot_to_svg(to_dot(dag)?).await,
+    }
+}
+
+/// Pipe DOT source through `dot -Tsvg`.
+async fn dot_to_svg(dot: String) -> AnyResult<String> {
+    use std::process::Stdio;
+
+    let mut child = match tokio::process::Command::new("dot")
+        .arg("-Tsvg")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            bail!("Graphviz 'dot' binary not found on PATH. Install Graphviz or use '--format dot' and render the output yourself")
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let Some(mut stdin) = child.stdin.take() else {
+        bail!("Failed to open stdin of the Graphviz 'dot' process");
+    };
+
+    stdin.write_all(dot.as_bytes()).await?;
+    // Close stdin so that `dot` starts rendering.
+    drop(stdin);
+
+    let output = child.wait_with_output().await?;
+
+    if !output.status.success() {
+        bail!(
+            "Graphviz 'dot' failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAG: &str = include_str!("../../../sdk/src/dag/_dags/trip_planner.json");
+
+    #[tokio::test]
+    async fn test_render_dot_and_mermaid() {
+        let dag = parse_dag_spec(DAG).unwrap();
+
+        let dot = render(&dag, DiagramFormat::Dot).await.unwrap();
+        assert!(dot.starts_with("digraph dag {"));
+
+        let mermaid = render(&dag, DiagramFormat::Mermaid).await.unwrap();
+        assert!(mermaid.starts_with("flowchart TB"));
+    }
+
+    #[tokio::test]
+    async fn test_visualize_dag_writes_output_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dag.json");
+        let output = dir.path().join("dag.mmd");
+
+        std::fs::write(&path, DAG).unwrap();
+
+        visualize_dag(
+            Some(path),
+            None,
+            DiagramFormat::Mermaid,
+            Some(output.clone()),
+        )
+        .await
+        .unwrap();
+
+        let written = std::fs::read_to_string(output).unwrap();
+        assert!(written.starts_with("flowchart TB"));
+    }
+
+    #[tokio::test]
+    async fn test_visualize_dag_requires_single_source() {
+        let result = visualize_dag(None, None, DiagramFormat::Dot, None).await;
+
+        assert!(result.is_err());
+    }
+}
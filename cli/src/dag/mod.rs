@@ -4,6 +4,7 @@ mod dag_execution_cost;
 mod dag_inspect_execution;
 mod dag_publish;
 mod dag_validate;
+mod dag_visualize;
 
 use {
     crate::prelude::*,
@@ -13,6 +14,7 @@ use {
     dag_inspect_execution::*,
     dag_publish::*,
     dag_validate::*,
+    dag_visualize::*,
     nexus_sdk::types::DEFAULT_ENTRY_GROUP,
 };
 
@@ -30,6 +32,47 @@ pub(crate) enum DagCommand {
         path: PathBuf,
     },
 
+    #[command(
+        about = "Render a Nexus DAG from a JSON file or an on-chain DAG object as a Graphviz DOT, Mermaid or SVG diagram."
+    )]
+    Visualize {
+        /// The path to the JSON file to visualize.
+        #[arg(
+            long = "path",
+            short = 'p',
+            help = "The path to the JSON file to visualize",
+            value_parser = ValueParser::from(expand_tilde),
+            conflicts_with = "dag_id",
+            required_unless_present = "dag_id"
+        )]
+        path: Option<PathBuf>,
+        /// The object ID of a published Nexus DAG to visualize.
+        #[arg(
+            long = "dag-id",
+            short = 'd',
+            help = "The object ID of a published Nexus DAG to visualize",
+            value_name = "OBJECT_ID"
+        )]
+        dag_id: Option<sui::types::Address>,
+        /// The diagram format to produce.
+        #[arg(
+            long = "format",
+            short = 'f',
+            help = "The diagram format to produce. SVG requires Graphviz to be installed",
+            value_enum,
+            default_value_t = DiagramFormat::Dot
+        )]
+        format: DiagramFormat,
+        /// Where to write the diagram.
+        #[arg(
+            long = "output",
+            short = 'o',
+            help = "Where to write the diagram. Printed to stdout if not provided",
+            value_parser = ValueParser::from(expand_tilde)
+        )]
+        output: Option<PathBuf>,
+    },
+
     #[command(
         about = "Publish a Nexus DAG spec file to the currently active Sui net. This command also performs validation on the file before publishing."
     )]
@@ -161,12 +204,30 @@ pub(crate) enum DagCommand {
     },
 }
 
+impl DagCommand {
+    /// Whether the command can be simulated with `--dry-run`.
+    pub(crate) fn supports_dry_run(&self) -> bool {
+        matches!(
+            self,
+            DagCommand::Publish { .. } | DagCommand::Execute { .. }
+        )
+    }
+}
+
 /// Handle the provided dag command. The [DagCommand] instance is passed from
 /// [crate::main].
 pub(crate) async fn handle(command: DagCommand) -> AnyResult<(), NexusCliError> {
     match command {
         // == `$ nexus dag validate` ==
-        DagCommand::Validate { path } => validate_dag(path).await.map(|_| ()),
+        DagCommand::Validate { path } => validate_dag_command(path).await,
+
+        // == `$ nexus dag visualize` ==
+        DagCommand::Visualize {
+            path,
+            dag_id,
+            format,
+            output,
+        } => visualize_dag(path, dag_id, format, output).await,
 
         // == `$ nexus dag publish` ==
         DagCommand::Publish { path, gas } => {
@@ -18,6 +18,70 @@ pub(crate) enum NexusCliError {
     Http(reqwest::Error),
     #[error("{error}{separator}\n{0}", error = "Sui Error".red().bold(), separator = separator())]
     Rpc(anyhow::Error),
-    #[error("{error}{separator}\n{0}", error = "Nexus Client Error".red().bold(), separator = separator())]
+    #[error("{error}{separator}\n{0}{hint}", error = "Nexus Client Error".red().bold(), separator = separator(), hint = move_abort_hint(.0))]
     Nexus(NexusError),
 }
+
+/// Explain a Move abort raised by the Nexus packages in terms of what the
+/// user was trying to do. Empty for any other error.
+fn move_abort_hint(error: &NexusError) -> String {
+    let Some(abort) = error.move_abort() else {
+        return String::new();
+    };
+
+    let area = match abort.module.as_str() {
+        "dag" => "The DAG definition was rejected",
+        "gas" => "Gas settlement for the DAG execution failed",
+        "main" => "The workflow could not be executed",
+        m if m.starts_with("execution") => "The DAG execution could not be advanced",
+        "tool_registry" => "The tool registry rejected the request",
+        "agent_registry" => "The agent registry (TAP) rejected the request",
+        "leader" | "leader_registry" => "The leader registry rejected the request",
+        "network_auth" => "Network authentication rejected the request",
+        "scheduler" => "The scheduler rejected the request",
+        "payment" | "agent" => "The agent interface (TAP) rejected the request",
+        _ => {
+            return match abort.humanized_name() {
+                Some(name) => format!("\n\n{}", name.truecolor(100, 100, 100)),
+                None => String::new(),
+            }
+        }
+    };
+
+    let hint = match abort.humanized_name() {
+        Some(name) => format!("{area}: {name}."),
+        None => format!("{area}."),
+    };
+
+    format!("\n\n{}", hint.truecolor(100, 100, 100))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, nexus_sdk::sui};
+
+    #[test]
+    fn test_move_abort_hint_explains_nexus_modules() {
+        let abort = |module: &str, name: Option<&str>| NexusError::MoveAbort {
+            package: sui::types::Address::ZERO,
+            module: module.to_string(),
+            function: Some("register_tool_".to_string()),
+            code: 1,
+            name: name.map(str::to_string),
+            command_index: Some(0),
+        };
+
+        assert!(
+            move_abort_hint(&abort("tool_registry", Some("EToolAlreadyRegistered")))
+                .contains("The tool registry rejected the request: Tool already registered.")
+        );
+        assert!(move_abort_hint(&abort("execution_gas", None))
+            .contains("The DAG execution could not be advanced."));
+        assert!(move_abort_hint(&abort("my_module", Some("E_NOT_FOUND"))).contains("Not found"));
+        assert_eq!(move_abort_hint(&abort("my_module", None)), "");
+        assert_eq!(
+            move_abort_hint(&NexusError::Timeout(anyhow::anyhow!("slow"))),
+            ""
+        );
+    }
+}
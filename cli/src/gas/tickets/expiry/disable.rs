@@ -13,7 +13,9 @@ pub(crate) async fn disable_expiry_extension(
     command_title!("Disabling the expiry gas extension for tool '{tool_fqn}'");
 
     let nexus_client = get_nexus_client(sui_gas_coin, sui_gas_budget).await?;
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
 
     let Some(owner_cap) = owner_cap.or(conf.tools.get(&tool_fqn).and_then(|t| t.over_gas)) else {
         return Err(NexusCliError::Any(anyhow!(
@@ -0,0 +1,46 @@
+use crate::{command_title, display::json_output, loading, notify_success, prelude::*, sui::*};
+
+/// Register the active address as a leader, staking `stake` `$US` from the
+/// provided coin.
+pub(crate) async fn register_leader(
+    stake_coin: sui::types::Address,
+    stake: u64,
+    sui_gas_coin: Option<sui::types::Address>,
+    sui_gas_budget: u64,
+) -> AnyResult<(), NexusCliError> {
+    command_title!("Registering leader with a stake of {stake} `$US`");
+
+    let nexus_client = get_nexus_client(sui_gas_coin, sui_gas_budget).await?;
+
+    let tx_handle = loading!("Crafting and executing transaction...");
+
+    let response = match nexus_client.leader().register(stake_coin, stake).await {
+        Ok(response) => response,
+        Err(e) => {
+            tx_handle.error();
+
+            return Err(NexusCliError::Nexus(e));
+        }
+    };
+
+    tx_handle.success();
+
+    notify_success!(
+        "Transaction digest: {digest}",
+        digest = response.tx_digest.to_string().truecolor(100, 100, 100)
+    );
+
+    if let Some(leader_cap_id) = response.leader_cap_id {
+        notify_success!(
+            "Leader cap ID: {id}",
+            id = leader_cap_id.to_string().truecolor(100, 100, 100)
+        );
+    }
+
+    json_output(&json!({
+        "digest": response.tx_digest,
+        "leader_cap_id": response.leader_cap_id,
+    }))?;
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use {
+    super::resolve_leader_cap,
+    crate::{
+        command_title,
+        display::json_output,
+        loading,
+        notify_success,
+        prelude::*,
+        sui::*,
+        tool::read_signing_key_arg,
+    },
+    nexus_sdk::signed_http::keys::parse_ed25519_signing_key,
+};
+
+/// Register a new message-signing key for the leader. The previous key stays
+/// registered until revoked.
+pub(crate) async fn rotate_leader_key(
+    leader_cap: Option<sui::types::Address>,
+    signing_key: String,
+    description: Option<String>,
+    sui_gas_coin: Option<sui::types::Address>,
+    sui_gas_budget: u64,
+) -> AnyResult<(), NexusCliError> {
+    command_title!("Rotating leader message-signing key");
+
+    let key_handle = loading!("Loading signing key...");
+
+    let signing_key_raw = match read_signing_key_arg(&signing_key) {
+        Ok(raw) => raw,
+        Err(e) => {
+            key_handle.error();
+
+            return Err(e);
+        }
+    };
+
+    let signing_key = match parse_ed25519_signing_key(&signing_key_raw) {
+        Ok(signing_key) => signing_key,
+        Err(e) => {
+            key_handle.error();
+
+            return Err(NexusCliError::Any(anyhow!(
+                "invalid signing key (expected hex/base64/base64url or a file containing it): {e}"
+            )));
+        }
+    };
+
+    key_handle.success();
+
+    let nexus_client = get_nexus_client(sui_gas_coin, sui_gas_budget).await?;
+    let leader_cap = resolve_leader_cap(&nexus_client, leader_cap).await?;
+
+    let tx_handle = loading!("Submitting network_auth transaction...");
+
+    let result = match nexus_client
+        .leader()
+        .rotate_key(leader_cap, signing_key, description.map(String::into_bytes))
+        .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            tx_handle.error();
+
+            return Err(NexusCliError::Nexus(e));
+        }
+    };
+
+    tx_handle.success();
+
+    notify_success!(
+        "Registered leader key {kid} in transaction {digest}",
+        kid = result.leader_kid.to_string().truecolor(100, 100, 100),
+        digest = result.tx_digest.to_string().truecolor(100, 100, 100)
+    );
+
+    json_output(&json!({
+        "digest": result.tx_digest,
+        "leader_cap_id": result.leader_cap_id,
+        "binding_object_id": result.binding_object_id,
+        "leader_kid": result.leader_kid,
+        "public_key_hex": hex::encode(result.public_key),
+    }))?;
+
+    Ok(())
+}
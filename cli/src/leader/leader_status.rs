@@ -0,0 +1,102 @@
+use {
+    super::resolve_leader_cap,
+    crate::{command_title, display::json_output, item, loading, prelude::*, sui::*},
+};
+
+/// Show the leader's stake, activation state, registered message keys and the
+/// registry-wide limits that apply to it.
+pub(crate) async fn leader_status(
+    leader_cap: Option<sui::types::Address>,
+) -> AnyResult<(), NexusCliError> {
+    command_title!("Fetching leader status");
+
+    let nexus_client = get_nexus_client(None, DEFAULT_GAS_BUDGET).await?;
+    let leader_cap = resolve_leader_cap(&nexus_client, leader_cap).await?;
+
+    let handle = loading!("Fetching leader record...");
+
+    let info = match nexus_client.leader().status(leader_cap).await {
+        Ok(info) => info,
+        Err(e) => {
+            handle.error();
+
+            return Err(NexusCliError::Nexus(e));
+        }
+    };
+
+    handle.success();
+
+    let active_key_id = info.keys.as_ref().and_then(|keys| keys.active_key_id);
+
+    item!("Leader cap: {}", info.leader_cap_id.to_string().bold());
+    item!("State: {}", info.state.to_string().bold());
+    item!("Stake: {} `$US`", info.stake_us.to_string().bold());
+    item!("Total shares: {}", info.total_shares.to_string().bold());
+    item!(
+        "Claim token: {}",
+        if info.claim_token.is_empty() {
+            "none".truecolor(100, 100, 100)
+        } else {
+            hex::encode(&info.claim_token).truecolor(100, 100, 100)
+        }
+    );
+    item!(
+        "Minimum stake: {} `$US`",
+        info.min_stake_us.to_string().bold()
+    );
+    item!(
+        "Max transaction budget: {} MIST",
+        info.max_transaction_budget.to_string().bold()
+    );
+
+    for (key, value) in &info.metadata {
+        item!("Metadata {key}: {value}");
+    }
+
+    match &info.keys {
+        None => item!(
+            "Message keys: {}",
+            "none registered".truecolor(100, 100, 100)
+        ),
+        Some(list) => {
+            for key in &list.keys {
+                item!(
+                    "Message key {kid}: {public_key}{suffix}",
+                    kid = key.kid.to_string().bold(),
+                    public_key = key.public_key_hex.truecolor(100, 100, 100),
+                    suffix = if key.revoked {
+                        " (revoked)"
+                    } else if Some(key.kid) == active_key_id {
+                        " (active)"
+                    } else {
+                        ""
+                    }
+                );
+            }
+        }
+    }
+
+    json_output(&json!({
+        "leader_cap_id": info.leader_cap_id,
+        "state": info.state.to_string(),
+        "stake_us": info.stake_us,
+        "total_shares": info.total_shares,
+        "claim_token": hex::encode(&info.claim_token),
+        "metadata": info.metadata.iter().cloned().collect::<std::collections::BTreeMap<_, _>>(),
+        "min_stake_us": info.min_stake_us,
+        "max_transaction_budget": info.max_transaction_budget,
+        "keys": info.keys.as_ref().map(|list| json!({
+            "binding_object_id": list.binding_object_id,
+            "active_key_id": list.active_key_id,
+            "next_key_id": list.next_key_id,
+            "keys": list.keys.iter().map(|k| json!({
+                "kid": k.kid,
+                "public_key_hex": k.public_key_hex,
+                "added_at_ms": k.added_at_ms,
+                "revoked": k.revoked,
+            })).collect::<Vec<_>>(),
+        })),
+    }))?;
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use {
+    super::resolve_leader_cap,
+    crate::{command_title, display::json_output, loading, notify_success, prelude::*, sui::*},
+};
+
+/// Suspend the leader, conditioned on the provided (or current) claim token.
+pub(crate) async fn suspend_leader(
+    leader_cap: Option<sui::types::Address>,
+    token: Option<Vec<u8>>,
+    sui_gas_coin: Option<sui::types::Address>,
+    sui_gas_budget: u64,
+) -> AnyResult<(), NexusCliError> {
+    command_title!("Suspending leader");
+
+    let nexus_client = get_nexus_client(sui_gas_coin, sui_gas_budget).await?;
+    let leader_cap = resolve_leader_cap(&nexus_client, leader_cap).await?;
+
+    let tx_handle = loading!("Crafting and executing transaction...");
+
+    let response = match nexus_client.leader().suspend(leader_cap, token).await {
+        Ok(response) => response,
+        Err(e) => {
+            tx_handle.error();
+
+            return Err(NexusCliError::Nexus(e));
+        }
+    };
+
+    tx_handle.success();
+
+    notify_success!(
+        "Transaction digest: {digest}",
+        digest = response.tx_digest.to_string().truecolor(100, 100, 100)
+    );
+
+    json_output(&json!({
+        "digest": response.tx_digest,
+        "leader_cap_id": leader_cap,
+        "token": hex::encode(&response.token),
+    }))?;
+
+    Ok(())
+}
@@ -0,0 +1,230 @@
+mod leader_activate;
+mod leader_register;
+mod leader_rotate_key;
+mod leader_status;
+mod leader_suspend;
+
+use {
+    crate::{loading, prelude::*},
+    leader_activate::*,
+    leader_register::*,
+    leader_rotate_key::*,
+    leader_status::*,
+    leader_suspend::*,
+};
+
+#[derive(Subcommand)]
+pub(crate) enum LeaderCommand {
+    #[command(about = "Register the active address as a leader by staking `$US`")]
+    Register {
+        #[arg(
+            long = "stake-coin",
+            help = "Owned `$US` coin object ID to stake from",
+            value_name = "OBJECT_ID"
+        )]
+        stake_coin: sui::types::Address,
+        #[arg(
+            long = "stake",
+            help = "Amount of `$US` to stake; any remaining coin balance stays with the sender",
+            value_name = "AMOUNT"
+        )]
+        stake: u64,
+        #[command(flatten)]
+        gas: GasArgs,
+    },
+
+    #[command(about = "Activate the leader and claim it for this operator")]
+    Activate {
+        #[command(flatten)]
+        leader: LeaderCapArgs,
+        #[command(flatten)]
+        gas: GasArgs,
+    },
+
+    #[command(about = "Suspend the leader if its claim token still matches")]
+    Suspend {
+        #[command(flatten)]
+        leader: LeaderCapArgs,
+        #[arg(
+            long = "token",
+            help = "Hex-encoded claim token to suspend on; defaults to the current claim token",
+            value_name = "HEX",
+            value_parser = ValueParser::from(parse_hex_token)
+        )]
+        token: Option<Vec<u8>>,
+        #[command(flatten)]
+        gas: GasArgs,
+    },
+
+    #[command(
+        about = "Show the leader's stake, activation state, registered message keys and registry limits"
+    )]
+    Status {
+        #[command(flatten)]
+        leader: LeaderCapArgs,
+    },
+
+    #[command(about = "Register a new message-signing key for the leader")]
+    RotateKey {
+        #[command(flatten)]
+        leader: LeaderCapArgs,
+        #[arg(
+            long = "signing-key",
+            short = 'k',
+            help = "Leader Ed25519 private key (hex/base64/base64url) OR a path to a file containing it.",
+            value_name = "KEY_OR_PATH"
+        )]
+        signing_key: String,
+        #[arg(
+            long = "description",
+            help = "Optional description bytes stored on the key binding.",
+            value_name = "TEXT"
+        )]
+        description: Option<String>,
+        #[command(flatten)]
+        gas: GasArgs,
+    },
+}
+
+/// Reusable leader cap selection args.
+#[derive(Args, Clone, Debug)]
+pub(crate) struct LeaderCapArgs {
+    #[arg(
+        long = "leader-cap",
+        help = "Leader cap object ID; defaults to the leader cap owned by the active address",
+        value_name = "OBJECT_ID"
+    )]
+    pub(crate) leader_cap: Option<sui::types::Address>,
+}
+
+/// Handle the provided leader command. The [LeaderCommand] instance is passed
+/// from [crate::main].
+pub(crate) async fn handle(command: LeaderCommand) -> AnyResult<(), NexusCliError> {
+    match command {
+        // == `$ nexus leader register` ==
+        LeaderCommand::Register {
+            stake_coin,
+            stake,
+            gas,
+        } => register_leader(stake_coin, stake, gas.sui_gas_coin, gas.sui_gas_budget).await,
+
+        // == `$ nexus leader activate` ==
+        LeaderCommand::Activate { leader, gas } => {
+            activate_leader(leader.leader_cap, gas.sui_gas_coin, gas.sui_gas_budget).await
+        }
+
+        // == `$ nexus leader suspend` ==
+        LeaderCommand::Suspend { leader, token, gas } => {
+            suspend_leader(
+                leader.leader_cap,
+                token,
+                gas.sui_gas_coin,
+                gas.sui_gas_budget,
+            )
+            .await
+        }
+
+        // == `$ nexus leader status` ==
+        LeaderCommand::Status { leader } => leader_status(leader.leader_cap).await,
+
+        // == `$ nexus leader rotate-key` ==
+        LeaderCommand::RotateKey {
+            leader,
+            signing_key,
+            description,
+            gas,
+        } => {
+            rotate_leader_key(
+                leader.leader_cap,
+                signing_key,
+                description,
+                gas.sui_gas_coin,
+                gas.sui_gas_budget,
+            )
+            .await
+        }
+    }
+}
+
+/// Use the provided leader cap or look up the one owned by the active address.
+pub(crate) async fn resolve_leader_cap(
+    nexus_client: &NexusClient,
+    leader_cap: Option<sui::types::Address>,
+) -> AnyResult<sui::types::Address, NexusCliError> {
+    if let Some(leader_cap) = leader_cap {
+        return Ok(leader_cap);
+    }
+
+    let address = nexus_client.signer().get_active_address();
+    let handle = loading!("Looking up leader cap for {address}...");
+
+    match nexus_client.leader().find_leader_cap(address).await {
+        Ok(Some(leader_cap)) => {
+            handle.success();
+
+            Ok(leader_cap)
+        }
+        Ok(None) => {
+            handle.error();
+
+            Err(NexusCliError::Any(anyhow!(
+                "No leader cap found for address '{address}'. Register with '$ nexus leader register' or pass --leader-cap"
+            )))
+        }
+        Err(e) => {
+            handle.error();
+
+            Err(NexusCliError::Nexus(e))
+        }
+    }
+}
+
+fn parse_hex_token(token: &str) -> AnyResult<Vec<u8>> {
+    Ok(hex::decode(token.trim_start_matches("0x"))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, clap::Parser};
+
+    #[test]
+    fn parses_suspend_with_hex_token() {
+        let cli = crate::Cli::try_parse_from([
+            "nexus",
+            "leader",
+            "suspend",
+            "--leader-cap",
+            "0x1",
+            "--token",
+            "0x0a0b",
+        ])
+        .expect("leader suspend should parse");
+
+        let crate::Command::Leader(LeaderCommand::Suspend { leader, token, .. }) = cli.command
+        else {
+            panic!("expected leader suspend command");
+        };
+
+        assert_eq!(
+            leader.leader_cap,
+            Some(sui::types::Address::from_static("0x1"))
+        );
+        assert_eq!(token, Some(vec![10, 11]));
+    }
+
+    #[test]
+    fn parses_status_without_leader_cap() {
+        let cli = crate::Cli::try_parse_from(["nexus", "leader", "status"])
+            .expect("leader status should parse");
+
+        assert!(matches!(
+            cli.command,
+            crate::Command::Leader(LeaderCommand::Status {
+                leader: LeaderCapArgs { leader_cap: None }
+            })
+        ));
+        assert!(
+            crate::Cli::try_parse_from(["nexus", "leader", "status", "--token", "00"]).is_err()
+        );
+    }
+}
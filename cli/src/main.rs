@@ -5,9 +5,11 @@ mod dag;
 mod display;
 mod error;
 mod gas;
+mod leader;
 mod nexus_data_json;
 mod prelude;
 mod scheduler;
+mod secrets;
 mod sui;
 mod tap;
 mod tool;
@@ -26,6 +28,32 @@ struct Cli {
     )]
     json: bool,
 
+    /// Whether to only simulate the state-changing transaction.
+    #[arg(
+        global = true,
+        long = "dry-run",
+        help = "Simulate the transaction and report the estimated gas instead of executing it. Supported by dag publish/execute, tool register/unregister/claim-collateral/set-invocation-cost and tap create-agent/register-skill/update-skill/bind"
+    )]
+    dry_run: bool,
+
+    /// Safety margin for simulated gas budgets.
+    #[arg(
+        global = true,
+        long = "gas-margin",
+        help = "Simulate each transaction first and size its gas budget to the estimate plus this margin, capped at --sui-gas-budget",
+        value_name = "PERCENT"
+    )]
+    gas_margin: Option<u64>,
+
+    /// Profile to use instead of the active one.
+    #[arg(
+        global = true,
+        long = "profile",
+        help = "Use this configuration profile for this command without changing the active one",
+        value_name = "NAME"
+    )]
+    profile: Option<String>,
+
     #[command(flatten)]
     verbose: clap_verbosity::Verbosity<clap_verbosity::ErrorLevel>,
 
@@ -45,12 +73,26 @@ enum Command {
     Scheduler(scheduler::SchedulerCommand),
     #[command(subcommand, about = "Manage Nexus gas budgets and tickets")]
     Gas(gas::GasCommand),
+    #[command(subcommand, about = "Register and operate a Nexus leader")]
+    Leader(leader::LeaderCommand),
     #[command(subcommand, about = "Prepare and operate standard TAP skills")]
     Tap(tap::TapCommand),
     #[command(about = "Provide shell completions")]
     Completion(completion::CompletionCommand),
 }
 
+impl Command {
+    /// Whether the command can be simulated with `--dry-run`.
+    fn supports_dry_run(&self) -> bool {
+        match self {
+            Command::Tool(tool) => tool.supports_dry_run(),
+            Command::Dag(dag) => dag.supports_dry_run(),
+            Command::Tap(tap) => tap.supports_dry_run(),
+            _ => false,
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() {
     // Customize parsing error handling.
@@ -78,11 +120,38 @@ async fn main() {
         }
     };
 
+    // Refuse to run a command that would execute transactions in a dry run.
+    if cli.dry_run && !cli.command.supports_dry_run() {
+        let e = Cli::command().error(
+            clap::error::ErrorKind::ArgumentConflict,
+            "--dry-run is not supported by this command",
+        );
+
+        eprintln!(
+            "{ballot} {error}",
+            ballot = "✖".red().bold(),
+            error = NexusCliError::Syntax(e)
+        );
+
+        std::process::exit(1);
+    }
+
     env_logger::builder()
         .filter(None, cli.verbose.log_level_filter())
         .init();
 
+    secrets::install_backend(secrets::OsSecretsBackend);
+
     JSON_MODE.store(cli.json, Ordering::Relaxed);
+    DRY_RUN.store(cli.dry_run, Ordering::Relaxed);
+
+    if let Some(gas_margin) = cli.gas_margin {
+        GAS_MARGIN.get_or_init(|| gas_margin);
+    }
+
+    if let Some(profile) = cli.profile {
+        PROFILE.get_or_init(|| profile);
+    }
 
     // Send each sub-command to the respective handler.
     let result = match cli.command {
@@ -90,6 +159,7 @@ async fn main() {
         Command::Conf(conf) => conf::handle(conf).await,
         Command::Dag(dag) => dag::handle(dag).await,
         Command::Gas(gas) => gas::handle(gas).await,
+        Command::Leader(leader) => leader::handle(leader).await,
         Command::Scheduler(scheduler) => scheduler::handle(scheduler).await,
         Command::Tap(tap) => tap::handle(tap).await,
         Command::Completion(completion) => completion::handle(completion),
@@ -11,41 +11,35 @@ const ENTRY_PORTS_RESERVED_BYTES: usize = 64 * 1024;
 const WALRUS_BLOB_ID_LENGTH: usize = 44;
 
 pub(crate) fn nexus_data_from_json_value(storage_kind: StorageKind, data: Value) -> NexusData {
+    let encode = |value: Value| serde_json::to_vec(&value).expect("JSON value must encode");
+
     match (storage_kind, data) {
-        (StorageKind::Inline, Value::Array(values)) => NexusData::inline_many(
-            values
-                .into_iter()
-                .map(|value| serde_json::to_vec(&value).expect("JSON value must encode")),
-        ),
-        (StorageKind::Inline, value) => {
-            NexusData::inline_one(serde_json::to_vec(&value).expect("JSON value must encode"))
+        (StorageKind::Inline, Value::Array(values)) => {
+            NexusData::inline_many(values.into_iter().map(encode))
         }
-        (StorageKind::Walrus, Value::Array(values)) => NexusData::walrus_many(
-            values
-                .into_iter()
-                .map(|value| serde_json::to_vec(&value).expect("JSON value must encode")),
-        ),
-        (StorageKind::Walrus, value) => {
-            NexusData::walrus_one(serde_json::to_vec(&value).expect("JSON value must encode"))
+        (StorageKind::Inline, value) => NexusData::inline_one(encode(value)),
+        (remote, Value::Array(values)) => {
+            NexusData::remote_many(remote.tag(), values.into_iter().map(encode))
         }
+        (remote, value) => NexusData::remote_one(remote.tag(), encode(value)),
     }
 }
 
-pub(crate) fn nexus_data_to_json_value(data: &NexusData) -> Value {
+pub(crate) fn nexus_data_to_json_value(data: &NexusData) -> anyhow::Result<Value> {
     if data.one.is_empty() && data.many.is_empty() {
-        return Value::Array(vec![]);
+        return Ok(Value::Array(vec![]));
     }
 
     if data.many.is_empty() {
         return decode_nexus_data_json(&data.one);
     }
 
-    Value::Array(
+    Ok(Value::Array(
         data.many
             .iter()
             .map(|bytes| decode_nexus_data_json(bytes))
-            .collect(),
-    )
+            .collect::<anyhow::Result<_>>()?,
+    ))
 }
 
 pub(crate) fn json_to_nexus_data_map(
@@ -69,12 +63,10 @@ pub(crate) fn json_to_nexus_data_map(
         match remote {
             false => map.insert(key, nexus_data_from_json_value(StorageKind::Inline, value)),
             true => match preferred_remote_storage {
-                StorageKind::Walrus => {
-                    map.insert(key, nexus_data_from_json_value(StorageKind::Walrus, value))
-                }
                 StorageKind::Inline => {
                     anyhow::bail!("Cannot store data remotely using inline storage")
                 }
+                remote => map.insert(key, nexus_data_from_json_value(remote, value)),
             },
         };
     }
@@ -128,10 +120,12 @@ pub(crate) fn hint_remote_fields(json: &Value) -> anyhow::Result<Vec<String>> {
     Ok(remote_fields)
 }
 
-fn decode_nexus_data_json(bytes: &[u8]) -> Value {
-    let text = std::str::from_utf8(bytes).expect("NexusData JSON bytes must be UTF-8");
+fn decode_nexus_data_json(bytes: &[u8]) -> anyhow::Result<Value> {
+    let text = std::str::from_utf8(bytes)
+        .map_err(|e| anyhow::anyhow!("NexusData JSON bytes must be UTF-8: {e}"))?;
     let adjusted = wrap_large_numbers_as_string(text.trim());
-    serde_json::from_str(&adjusted).unwrap_or_else(|_| Value::String(text.to_string()))
+
+    Ok(serde_json::from_str(&adjusted).unwrap_or_else(|_| Value::String(text.to_string())))
 }
 
 fn is_large_number(s: &str) -> bool {
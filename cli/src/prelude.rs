@@ -12,6 +12,7 @@ pub(crate) use {
         sync::{
             atomic::{AtomicBool, Ordering},
             Arc,
+            OnceLock,
         },
     },
     tokio::sync::Mutex,
@@ -21,6 +22,7 @@ pub(crate) use {
 pub(crate) const CLI_CONF_PATH: &str = "~/.nexus/conf.toml";
 
 /// Various Nexus RPC URLs.
+pub(crate) const LOCALNET_NEXUS_RPC_URL: &str = "http://127.0.0.1:9000/";
 pub(crate) const DEVNET_NEXUS_RPC_URL: &str = "https://rpc.ssfn.devnet.production.taluslabs.dev/";
 pub(crate) const TESTNET_NEXUS_RPC_URL: &str = "https://fullnode.testnet.sui.io/";
 pub(crate) const MAINNET_NEXUS_RPC_URL: &str = "https://fullnode.mainnet.sui.io/";
@@ -67,6 +69,29 @@ impl std::fmt::Display for SuiNet {
     }
 }
 
+impl SuiNet {
+    /// RPC URL of the network.
+    pub(crate) fn rpc_url(&self) -> &'static str {
+        match self {
+            SuiNet::Localnet => LOCALNET_NEXUS_RPC_URL,
+            SuiNet::Devnet => DEVNET_NEXUS_RPC_URL,
+            SuiNet::Testnet => TESTNET_NEXUS_RPC_URL,
+            SuiNet::Mainnet => MAINNET_NEXUS_RPC_URL,
+        }
+    }
+
+    /// Where to fetch the Nexus objects deployed on the network. Localnet
+    /// deployments are local so there is nothing to fetch.
+    pub(crate) fn objects_toml(&self) -> Option<&'static str> {
+        match self {
+            SuiNet::Localnet => None,
+            SuiNet::Devnet => Some(DEVNET_OBJECTS_TOML),
+            SuiNet::Testnet => Some(TESTNET_OBJECTS_TOML),
+            SuiNet::Mainnet => Some(MAINNET_OBJECTS_TOML),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct ToolOwnerCaps {
     pub(crate) over_tool: sui::types::Address,
@@ -96,6 +121,15 @@ pub(crate) struct GasArgs {
 /// Whether to change the output format to JSON.
 pub(crate) static JSON_MODE: AtomicBool = AtomicBool::new(false);
 
+/// Whether state-changing transactions are only simulated.
+pub(crate) static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+/// Safety margin in percent used to size gas budgets from a simulation.
+pub(crate) static GAS_MARGIN: OnceLock<u64> = OnceLock::new();
+
+/// Profile selected with `--profile` for this invocation.
+pub(crate) static PROFILE: OnceLock<String> = OnceLock::new();
+
 // == Used by clap ==
 
 /// Expands `~/` to the user's home directory in path arguments.
@@ -152,4 +186,12 @@ mod tests {
         assert_eq!(SuiNet::Testnet.to_string(), "testnet");
         assert_eq!(SuiNet::Mainnet.to_string(), "mainnet");
     }
+
+    #[test]
+    fn test_sui_net_presets() {
+        assert_eq!(SuiNet::Localnet.rpc_url(), LOCALNET_NEXUS_RPC_URL);
+        assert_eq!(SuiNet::Testnet.rpc_url(), TESTNET_NEXUS_RPC_URL);
+        assert_eq!(SuiNet::Localnet.objects_toml(), None);
+        assert_eq!(SuiNet::Mainnet.objects_toml(), Some(MAINNET_OBJECTS_TOML));
+    }
 }
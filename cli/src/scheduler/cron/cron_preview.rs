@@ -0,0 +1,86 @@
+use {
+    crate::{command_title, display::json_output, item, prelude::*},
+    nexus_sdk::nexus::cron::CronSchedule,
+    serde_json::json,
+};
+
+/// Print the next `count` fire times of a cron expression without touching
+/// the chain.
+pub(crate) async fn preview_cron(
+    expression: String,
+    timezone: String,
+    count: usize,
+    after_ms: Option<u64>,
+) -> AnyResult<(), NexusCliError> {
+    let schedule = CronSchedule::parse(&expression, &timezone).map_err(NexusCliError::Any)?;
+
+    command_title!("Previewing cron schedule '{schedule}'");
+
+    let after_ms = after_ms.unwrap_or_else(super::now_ms);
+    let fire_times = preview(&schedule, after_ms, count);
+
+    if fire_times.is_empty() {
+        item!("This schedule never fires");
+    }
+
+    for (start_ms, local_time) in &fire_times {
+        item!(
+            "{local_time} {start_ms}",
+            start_ms = format!("({start_ms})").truecolor(100, 100, 100)
+        );
+    }
+
+    json_output(&json!({
+        "expression": schedule.expression(),
+        "timezone": schedule.timezone(),
+        "after_ms": after_ms,
+        "fire_times": fire_times
+            .iter()
+            .map(|(start_ms, local_time)| json!({
+                "start_ms": start_ms,
+                "local_time": local_time,
+            }))
+            .collect::<Vec<_>>(),
+    }))?;
+
+    Ok(())
+}
+
+/// The next `count` fire times after `after_ms` with their local time.
+fn preview(schedule: &CronSchedule, after_ms: u64, count: usize) -> Vec<(u64, String)> {
+    schedule
+        .occurrences_after(after_ms)
+        .take(count)
+        .map(|start_ms| {
+            let local_time = schedule
+                .local_time(start_ms)
+                .unwrap_or_else(|| start_ms.to_string());
+
+            (start_ms, local_time)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_lists_local_fire_times() {
+        let schedule = CronSchedule::parse("30 9 * * *", "+02:00").unwrap();
+
+        let fire_times = preview(&schedule, 0, 2);
+
+        assert_eq!(fire_times.len(), 2);
+        assert_eq!(fire_times[0].0, 27_000_000);
+        assert!(fire_times[0].1.starts_with("1970-01-01T09:30:00+02:00"));
+        assert_eq!(fire_times[1].0, 27_000_000 + 86_400_000);
+    }
+
+    #[tokio::test]
+    async fn test_preview_cron_rejects_invalid_expression() {
+        let result = preview_cron("0 9 * *".to_string(), "UTC".to_string(), 5, Some(0)).await;
+
+        assert!(result.is_err());
+    }
+}
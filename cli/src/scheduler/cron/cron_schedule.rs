@@ -0,0 +1,185 @@
+use {
+    crate::{
+        command_title,
+        display::json_output,
+        item,
+        notify_success,
+        notify_warning,
+        prelude::*,
+        scheduler::helpers,
+        sui::get_nexus_client,
+    },
+    nexus_sdk::nexus::{
+        cron::CronSchedule,
+        scheduler::{CronScheduleConfig, SchedulerActions},
+    },
+    serde_json::json,
+    std::time::Duration,
+};
+
+/// By default, enqueue fire times for the next 24 hours.
+pub(crate) const DEFAULT_HORIZON_MS: u64 = 24 * 60 * 60 * 1000;
+/// By default, top up the queue every minute in watch mode.
+pub(crate) const DEFAULT_INTERVAL_MS: u64 = 60 * 1000;
+
+/// Enqueue the fire times of a cron expression as occurrences of a queue
+/// based task. With `watch_interval_ms`, keep running and top up the queue
+/// every interval so that it always covers `horizon_ms` from now.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn schedule_cron_task(
+    task_id: sui::types::Address,
+    expression: String,
+    timezone: String,
+    deadline_offset_ms: Option<u64>,
+    priority_fee_percentage: Option<u64>,
+    horizon_ms: u64,
+    from_ms: Option<u64>,
+    watch_interval_ms: Option<u64>,
+    gas: GasArgs,
+) -> AnyResult<(), NexusCliError> {
+    let schedule = CronSchedule::parse(&expression, &timezone).map_err(NexusCliError::Any)?;
+
+    command_title!("Scheduling cron '{schedule}' for task '{task_id}'");
+
+    let config = CronScheduleConfig {
+        schedule,
+        deadline_offset_ms,
+        priority_fee_percentage: priority_fee_percentage
+            .and_then(helpers::optional_priority_fee_quote),
+    };
+
+    let nexus_client = get_nexus_client(gas.sui_gas_coin, gas.sui_gas_budget).await?;
+    let scheduler = nexus_client.scheduler();
+
+    // Resume after the occurrences that are already queued so that re-running
+    // the command or restarting `--watch` never enqueues a fire time twice.
+    let queued_ms = scheduler
+        .last_queued_ms(task_id)
+        .await
+        .map_err(NexusCliError::Nexus)?;
+    let mut cursor = initial_cursor(from_ms, super::now_ms(), queued_ms);
+
+    let Some(interval_ms) = watch_interval_ms else {
+        let scheduled = top_up(&scheduler, task_id, &config, &mut cursor, horizon_ms).await?;
+
+        notify_success!("Scheduled {count} occurrence(s)", count = scheduled.len());
+
+        json_output(&json!({
+            "scheduled_task_id": task_id,
+            "expression": config.schedule.expression(),
+            "timezone": config.schedule.timezone(),
+            "scheduled": scheduled,
+            "cursor_ms": cursor,
+        }))?;
+
+        return Ok(());
+    };
+
+    notify_success!(
+        "Topping up occurrences {horizon_ms}ms ahead every {interval_ms}ms (Ctrl-C to stop)",
+    );
+
+    tokio::select! {
+        result = watch(&scheduler, task_id, &config, &mut cursor, horizon_ms, interval_ms) => result,
+        _ = tokio::signal::ctrl_c() => Ok(()),
+    }
+}
+
+/// Start after `from_ms` (or now) and after the latest queued occurrence.
+fn initial_cursor(from_ms: Option<u64>, now_ms: u64, queued_ms: Option<u64>) -> u64 {
+    from_ms.unwrap_or(now_ms).max(queued_ms.unwrap_or_default())
+}
+
+/// Top up the queue every `interval_ms` until an output error occurs.
+async fn watch(
+    scheduler: &SchedulerActions,
+    task_id: sui::types::Address,
+    config: &CronScheduleConfig,
+    cursor: &mut u64,
+    horizon_ms: u64,
+    interval_ms: u64,
+) -> AnyResult<(), NexusCliError> {
+    let mut interval = tokio::time::interval(Duration::from_millis(interval_ms.max(1)));
+
+    loop {
+        interval.tick().await;
+
+        // Failed fire times are retried on the next tick as the cursor only
+        // advances past occurrences that were enqueued.
+        match top_up(scheduler, task_id, config, cursor, horizon_ms).await {
+            Ok(scheduled) => json_output(&json!({
+                "scheduled_task_id": task_id,
+                "scheduled": scheduled,
+                "cursor_ms": *cursor,
+            }))?,
+            Err(e) => notify_warning!("Failed to top up occurrences: {e}"),
+        }
+    }
+}
+
+/// Enqueue all fire times between the cursor and `horizon_ms` from now.
+/// Fire times that are already in the past are skipped.
+async fn top_up(
+    scheduler: &SchedulerActions,
+    task_id: sui::types::Address,
+    config: &CronScheduleConfig,
+    cursor: &mut u64,
+    horizon_ms: u64,
+) -> AnyResult<Vec<serde_json::Value>, NexusCliError> {
+    let now = super::now_ms();
+
+    *cursor = (*cursor).max(now);
+
+    let until_ms = now.saturating_add(horizon_ms);
+    let scheduled = scheduler
+        .schedule_cron(task_id, config, cursor, until_ms)
+        .await
+        .map_err(NexusCliError::Nexus)?;
+
+    Ok(scheduled
+        .into_iter()
+        .map(|(start_ms, result)| {
+            item!(
+                "Scheduled occurrence at {local_time} {digest}",
+                local_time = config
+                    .schedule
+                    .local_time(start_ms)
+                    .unwrap_or_else(|| start_ms.to_string()),
+                digest = format!("({})", result.tx_digest).truecolor(100, 100, 100)
+            );
+
+            json!({ "start_ms": start_ms, "digest": result.tx_digest })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initial_cursor_resumes_after_queued_occurrences() {
+        let schedule = CronSchedule::parse("@hourly", "UTC").unwrap();
+        let now_ms = 36_000_000;
+        let until_ms = now_ms + 3 * 60 * 60 * 1000;
+
+        // First run queues 11:00, 12:00 and 13:00.
+        let cursor = initial_cursor(None, now_ms, None);
+        let first = schedule.occurrences_between(cursor, until_ms);
+        assert_eq!(first, vec![39_600_000, 43_200_000, 46_800_000]);
+
+        // A restart a few minutes later only picks up what is not queued yet.
+        let now_ms = now_ms + 5 * 60 * 1000;
+        let cursor = initial_cursor(None, now_ms, first.last().copied());
+        assert_eq!(cursor, 46_800_000);
+        assert!(schedule
+            .occurrences_between(cursor, now_ms + 3 * 60 * 60 * 1000)
+            .is_empty());
+
+        // An explicit start later than the queue wins.
+        assert_eq!(
+            initial_cursor(Some(50_000_000), now_ms, Some(46_800_000)),
+            50_000_000
+        );
+    }
+}
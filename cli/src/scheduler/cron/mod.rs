@@ -0,0 +1,123 @@
+mod cron_preview;
+mod cron_schedule;
+
+use crate::prelude::*;
+
+#[derive(Args, Debug, Clone)]
+pub(crate) struct CronExpressionArgs {
+    /// Cron expression with five fields (minute hour day-of-month month
+    /// day-of-week) or a shortcut such as `@daily`.
+    #[arg(long = "expression", short = 'e', value_name = "CRON")]
+    expression: String,
+    /// Timezone the expression is evaluated in. Either `UTC`, a fixed offset
+    /// such as `+05:30` or an IANA name such as `Europe/Prague`.
+    #[arg(
+        long = "timezone",
+        short = 'z',
+        value_name = "TZ",
+        default_value = "UTC"
+    )]
+    timezone: String,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum CronCommand {
+    #[command(about = "Print the next fire times of a cron expression")]
+    Preview {
+        #[command(flatten)]
+        cron: CronExpressionArgs,
+        /// How many fire times to print.
+        #[arg(
+            long = "count",
+            short = 'n',
+            value_name = "COUNT",
+            default_value_t = 10
+        )]
+        count: usize,
+        /// Only print fire times after this time in milliseconds since epoch.
+        /// Defaults to now.
+        #[arg(long = "after-ms", value_name = "MILLIS")]
+        after_ms: Option<u64>,
+    },
+    #[command(about = "Enqueue the fire times of a cron expression as task occurrences")]
+    Schedule {
+        /// Task object ID receiving the occurrences.
+        #[arg(long = "task-id", short = 't', value_name = "OBJECT_ID")]
+        task_id: sui::types::Address,
+        #[command(flatten)]
+        cron: CronExpressionArgs,
+        /// Deadline offset from each fire time in milliseconds.
+        #[arg(long = "deadline-offset-ms", value_name = "MILLIS")]
+        deadline_offset_ms: Option<u64>,
+        /// Optional priority fee percentage applied to the occurrences.
+        #[arg(long = "priority-fee-percentage", value_name = "PERCENTAGE")]
+        priority_fee_percentage: Option<u64>,
+        /// How far ahead of now to enqueue fire times, in milliseconds.
+        #[arg(
+            long = "horizon-ms",
+            value_name = "MILLIS",
+            default_value_t = cron_schedule::DEFAULT_HORIZON_MS
+        )]
+        horizon_ms: u64,
+        /// Only enqueue fire times after this time in milliseconds since
+        /// epoch. Defaults to now.
+        #[arg(long = "from-ms", value_name = "MILLIS")]
+        from_ms: Option<u64>,
+        /// Keep running and top up the queue so that it always covers the
+        /// horizon.
+        #[arg(long = "watch", short = 'w')]
+        watch: bool,
+        /// How often to top up the queue in watch mode, in milliseconds.
+        #[arg(
+            long = "interval-ms",
+            value_name = "MILLIS",
+            default_value_t = cron_schedule::DEFAULT_INTERVAL_MS,
+            requires = "watch"
+        )]
+        interval_ms: u64,
+        #[command(flatten)]
+        gas: GasArgs,
+    },
+}
+
+pub(crate) async fn handle(command: CronCommand) -> AnyResult<(), NexusCliError> {
+    match command {
+        CronCommand::Preview {
+            cron,
+            count,
+            after_ms,
+        } => cron_preview::preview_cron(cron.expression, cron.timezone, count, after_ms).await,
+        CronCommand::Schedule {
+            task_id,
+            cron,
+            deadline_offset_ms,
+            priority_fee_percentage,
+            horizon_ms,
+            from_ms,
+            watch,
+            interval_ms,
+            gas,
+        } => {
+            cron_schedule::schedule_cron_task(
+                task_id,
+                cron.expression,
+                cron.timezone,
+                deadline_offset_ms,
+                priority_fee_percentage,
+                horizon_ms,
+                from_ms,
+                watch.then_some(interval_ms),
+                gas,
+            )
+            .await
+        }
+    }
+}
+
+/// Current time in milliseconds since epoch.
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
@@ -1,3 +1,4 @@
+mod cron;
 pub(crate) mod helpers;
 mod occurrence;
 mod periodic;
@@ -13,6 +14,8 @@ pub(crate) enum SchedulerCommand {
     Occurrence(occurrence::OccurrenceCommand),
     #[command(subcommand, about = "Manage periodic scheduling for a task")]
     Periodic(periodic::PeriodicCommand),
+    #[command(subcommand, about = "Schedule task occurrences from cron expressions")]
+    Cron(cron::CronCommand),
 }
 
 /// Handle scheduler commands dispatched from the CLI root.
@@ -24,5 +27,7 @@ pub(crate) async fn handle(command: SchedulerCommand) -> AnyResult<(), NexusCliE
         SchedulerCommand::Occurrence(cmd) => occurrence::handle(cmd).await,
         // == `$ nexus scheduler periodic ...` ==
         SchedulerCommand::Periodic(cmd) => periodic::handle(cmd).await,
+        // == `$ nexus scheduler cron ...` ==
+        SchedulerCommand::Cron(cmd) => cron::handle(cmd).await,
     }
 }
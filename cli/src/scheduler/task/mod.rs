@@ -1,12 +1,16 @@
 mod task_create;
 mod task_inspect;
+mod task_list;
 mod task_metadata;
 mod task_state;
 
 use {
     self::task_state::TaskStateRequest,
     crate::prelude::*,
-    nexus_sdk::{nexus::scheduler::GeneratorKind, types::DEFAULT_ENTRY_GROUP},
+    nexus_sdk::{
+        nexus::scheduler::{GeneratorKind, TaskStatus},
+        types::DEFAULT_ENTRY_GROUP,
+    },
 };
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -24,6 +28,27 @@ impl From<TaskGeneratorArg> for GeneratorKind {
     }
 }
 
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub(crate) enum TaskStatusArg {
+    Active,
+    Paused,
+    Canceled,
+    Completed,
+    Failed,
+}
+
+impl From<TaskStatusArg> for TaskStatus {
+    fn from(value: TaskStatusArg) -> Self {
+        match value {
+            TaskStatusArg::Active => TaskStatus::Active,
+            TaskStatusArg::Paused => TaskStatus::Paused,
+            TaskStatusArg::Canceled => TaskStatus::Canceled,
+            TaskStatusArg::Completed => TaskStatus::Completed,
+            TaskStatusArg::Failed => TaskStatus::Failed,
+        }
+    }
+}
+
 #[derive(Args, Debug, Clone)]
 #[group(id = "schedule-start", multiple = false)]
 pub(crate) struct ScheduleStartOptions {
@@ -111,6 +136,23 @@ pub(crate) enum TaskCommand {
         #[arg(long = "task-id", short = 't', value_name = "OBJECT_ID")]
         task_id: sui::types::Address,
     },
+    #[command(about = "List scheduled tasks owned by an address")]
+    List {
+        /// Task owner. Defaults to the active address.
+        #[arg(long = "owner", short = 'o', value_name = "ADDRESS")]
+        owner: Option<sui::types::Address>,
+        /// Only list tasks in this state.
+        #[arg(long = "state", short = 's', value_enum, value_name = "STATE")]
+        state: Option<TaskStatusArg>,
+        /// Only list tasks whose metadata contains these key=value pairs.
+        #[arg(long = "metadata", short = 'm', value_name = "KEY=VALUE")]
+        metadata: Vec<String>,
+        /// Checkpoint to start discovering tasks from. Defaults to where the
+        /// previous listing for this owner stopped, or to the most recent
+        /// checkpoints on the first run.
+        #[arg(long = "from-checkpoint", value_name = "CHECKPOINT")]
+        from_checkpoint: Option<u64>,
+    },
     #[command(about = "Update scheduled task metadata")]
     Metadata {
         /// Task object ID to update.
@@ -197,6 +239,12 @@ pub(crate) async fn handle(command: TaskCommand) -> AnyResult<(), NexusCliError>
             .await
         }
         TaskCommand::Inspect { task_id } => task_inspect::inspect_task(task_id).await,
+        TaskCommand::List {
+            owner,
+            state,
+            metadata,
+            from_checkpoint,
+        } => task_list::list_tasks(owner, state.map(Into::into), metadata, from_checkpoint).await,
         TaskCommand::Metadata {
             task_id,
             metadata,
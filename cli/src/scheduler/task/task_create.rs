@@ -47,7 +47,9 @@ pub(crate) async fn create_task(
     );
 
     // Load CLI configuration.
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
 
     let nexus_client = get_nexus_client(gas.sui_gas_coin, gas.sui_gas_budget).await?;
 
@@ -57,7 +59,11 @@ pub(crate) async fn create_task(
 
     // Build the remote storage configuration.
     let preferred_remote_storage = conf.data_storage.preferred_remote_storage;
-    let storage_conf: StorageConf = conf.data_storage.clone().into();
+    let storage_conf: StorageConf = conf
+        .data_storage
+        .clone()
+        .try_into()
+        .map_err(NexusCliError::Any)?;
 
     let ports_data =
         workflow::process_entry_ports(&input_json, preferred_remote_storage, &remote).await?;
@@ -0,0 +1,224 @@
+use {
+    crate::{
+        command_title,
+        display::json_output,
+        item,
+        loading,
+        notify_success,
+        notify_warning,
+        prelude::*,
+        scheduler::helpers,
+        sui::*,
+    },
+    nexus_sdk::nexus::scheduler::{
+        GeneratorKind,
+        SchedulerActions,
+        TaskFilter,
+        TaskIndex,
+        TaskStatus,
+        TaskSummary,
+    },
+    serde_json::json,
+    std::path::Path,
+};
+
+/// Where discovered task IDs are kept between runs, one file per network and
+/// owner.
+const TASK_INDEX_DIR: &str = "~/.nexus/scheduler";
+
+/// List scheduled tasks owned by an address, optionally filtered by state
+/// and metadata.
+pub(crate) async fn list_tasks(
+    owner: Option<sui::types::Address>,
+    status: Option<TaskStatus>,
+    metadata: Vec<String>,
+    from_checkpoint: Option<u64>,
+) -> AnyResult<(), NexusCliError> {
+    let filter = TaskFilter {
+        status,
+        metadata: helpers::parse_metadata(&metadata)?,
+        from_checkpoint,
+    };
+
+    let nexus_client = get_nexus_client(None, DEFAULT_GAS_BUDGET).await?;
+    let owner = owner.unwrap_or_else(|| nexus_client.signer().get_active_address());
+
+    command_title!("Listing scheduled tasks owned by '{owner}'");
+
+    let tasks_handle = loading!("Discovering tasks from creation events...");
+
+    let scheduler = nexus_client.scheduler();
+    let index_path = expand_tilde(TASK_INDEX_DIR)
+        .map_err(NexusCliError::Any)?
+        .join(format!(
+            "tasks-{network}-{owner}.json",
+            network = nexus_client.get_nexus_objects().network_id
+        ));
+
+    let mut index = match load_task_index(&scheduler, &index_path, owner, from_checkpoint).await {
+        Ok(index) => index,
+        Err(e) => {
+            tasks_handle.error();
+
+            return Err(e);
+        }
+    };
+
+    let tasks = match scheduler.list_indexed_tasks(&mut index, &filter).await {
+        Ok(tasks) => tasks,
+        Err(e) => {
+            tasks_handle.error();
+
+            return Err(NexusCliError::Nexus(e));
+        }
+    };
+
+    tasks_handle.success();
+
+    // A stale index only costs a longer replay next time.
+    if let Err(e) = save_task_index(&index_path, &index) {
+        notify_warning!("Failed to save the task index: {e}");
+    }
+
+    notify_success!("Found {count} task(s)", count = tasks.len());
+
+    for task in &tasks {
+        item!(
+            "{task_id} {status}",
+            task_id = task.task_id,
+            status = format!("[{}]", task.status).truecolor(100, 100, 100)
+        );
+        item!("  {}", describe_task(task));
+    }
+
+    json_output(&json!(tasks.iter().map(task_json).collect::<Vec<_>>()))?;
+
+    Ok(())
+}
+
+/// Load the task index saved for `owner`, or start a new one. An explicit
+/// `from_checkpoint` before the saved progress replays from there while
+/// keeping the tasks found so far.
+async fn load_task_index(
+    scheduler: &SchedulerActions,
+    path: &Path,
+    owner: sui::types::Address,
+    from_checkpoint: Option<u64>,
+) -> AnyResult<TaskIndex, NexusCliError> {
+    let saved = match std::fs::read(path) {
+        Ok(bytes) => serde_json::from_slice::<TaskIndex>(&bytes)
+            .ok()
+            .filter(|index| index.owner == owner),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(NexusCliError::Io(e)),
+    };
+
+    match (saved, from_checkpoint) {
+        (Some(saved), Some(from)) if from < saved.progress.checkpoint => Ok(TaskIndex {
+            task_ids: saved.task_ids,
+            ..TaskIndex::new(owner, from)
+        }),
+        (Some(saved), _) => Ok(saved),
+        (None, from_checkpoint) => scheduler
+            .new_task_index(owner, from_checkpoint)
+            .await
+            .map_err(NexusCliError::Nexus),
+    }
+}
+
+fn save_task_index(path: &Path, index: &TaskIndex) -> AnyResult<(), NexusCliError> {
+    let bytes = serde_json::to_vec(index).map_err(|e| NexusCliError::Any(e.into()))?;
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(NexusCliError::Io)?;
+    }
+
+    std::fs::write(&tmp, bytes).map_err(NexusCliError::Io)?;
+    std::fs::rename(&tmp, path).map_err(NexusCliError::Io)?;
+
+    Ok(())
+}
+
+/// One line overview of a task's generator, next occurrence and reserve.
+fn describe_task(task: &TaskSummary) -> String {
+    let mut parts = vec![];
+
+    match task.generator {
+        Some(GeneratorKind::Queue) => parts.push("queue".to_string()),
+        Some(GeneratorKind::Periodic) => parts.push("periodic".to_string()),
+        None => parts.push("no generator".to_string()),
+    }
+
+    if let Some(next) = task.next_occurrence_ms {
+        parts.push(format!("next at {next}ms"));
+    }
+
+    if let Some(remaining) = task.remaining_iterations {
+        parts.push(format!("{remaining} iteration(s) left"));
+    }
+
+    if let Some(balance) = task.reserve_balance_mist {
+        parts.push(format!("reserve {balance} MIST"));
+    }
+
+    if !task.metadata.is_empty() {
+        let metadata = task
+            .metadata
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        parts.push(format!("metadata {{{metadata}}}"));
+    }
+
+    parts.join(", ")
+}
+
+fn task_json(task: &TaskSummary) -> serde_json::Value {
+    json!({
+        "task_id": task.task_id,
+        "owner": task.owner,
+        "state": task.status.to_string(),
+        "generator": task.generator.map(|generator| match generator {
+            GeneratorKind::Queue => "queue",
+            GeneratorKind::Periodic => "periodic",
+        }),
+        "next_occurrence_ms": task.next_occurrence_ms,
+        "remaining_iterations": task.remaining_iterations,
+        "metadata": task.metadata.iter().cloned().collect::<HashMap<_, _>>(),
+        "reserve_balance_mist": task.reserve_balance_mist,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_task() {
+        let task = TaskSummary {
+            task_id: sui::types::Address::from_static("0x1"),
+            owner: sui::types::Address::from_static("0x2"),
+            status: TaskStatus::Active,
+            generator: Some(GeneratorKind::Periodic),
+            next_occurrence_ms: Some(1_000),
+            remaining_iterations: Some(3),
+            metadata: vec![("env".to_string(), "prod".to_string())],
+            reserve_balance_mist: Some(500),
+        };
+
+        assert_eq!(
+            describe_task(&task),
+            "periodic, next at 1000ms, 3 iteration(s) left, reserve 500 MIST, metadata {env=prod}"
+        );
+
+        let json = task_json(&task);
+
+        assert_eq!(json["state"], "active");
+        assert_eq!(json["generator"], "periodic");
+        assert_eq!(json["metadata"]["env"], "prod");
+    }
+}
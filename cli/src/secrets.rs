@@ -0,0 +1,1018 @@
+//! At-rest protection for the [`SecretValue`] fields of [`CliConf`].
+//!
+//! When the configuration is written to disk, each secret is replaced by a
+//! [`StoredSecret`] according to the configured [`SecretsMode`]:
+//!
+//! - [`SecretsMode::Auto`] stores secrets in the OS keyring, falls back to
+//!   passphrase encryption if [`PASSPHRASE_ENV`] is set and only writes
+//!   plaintext (with a warning) if neither is available.
+//! - [`SecretsMode::Require`] behaves like `Auto` but prompts for a passphrase
+//!   when needed and refuses to write plaintext.
+//! - [`SecretsMode::Off`] writes plaintext.
+//!
+//! Passphrase encryption derives a key with Argon2id and seals the secret with
+//! AES-256-GCM. Plaintext secrets are always accepted when loading so that
+//! existing configurations keep working until they are migrated.
+//!
+//! Secrets that did not change since the configuration was loaded keep their
+//! stored form, and keyring entries that are no longer referenced are removed
+//! once the configuration is saved. The keyring and the passphrase are
+//! reached through the [`SecretsBackend`] installed with [`install_backend`].
+
+use {
+    crate::{notify_warning, prelude::*},
+    aes_gcm::{
+        aead::{Aead, KeyInit},
+        Aes256Gcm,
+        Key,
+        Nonce,
+    },
+    base64::{prelude::BASE64_STANDARD, Engine},
+    nexus_sdk::types::SecretValue,
+    rand::{rngs::OsRng, RngCore},
+    std::{collections::BTreeSet, io::IsTerminal, path::Path, sync::OnceLock},
+};
+
+/// Environment variable holding the passphrase used to encrypt secrets.
+pub(crate) const PASSPHRASE_ENV: &str = "NEXUS_SECRETS_PASSPHRASE";
+
+/// Keyring service under which all CLI secrets are stored.
+const KEYRING_SERVICE: &str = "nexus-cli";
+
+/// `(table, key)` locations of all secrets in the serialized [`CliConf`] and
+/// in each of its profiles.
+const SECRET_FIELDS: &[(&str, &str)] = &[("sui", "pk"), ("data_storage", "encryption_key")];
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// On-disk representation of a secret.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum StoredSecret {
+    /// Legacy plaintext value.
+    Plain(SecretValue),
+    /// Reference to an entry in the OS keyring.
+    Keyring { keyring: String },
+    /// Secret encrypted with a passphrase-derived key, all fields base64.
+    Encrypted {
+        salt: String,
+        nonce: String,
+        ciphertext: String,
+    },
+}
+
+/// Where a secret ended up after sealing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SecretStorage {
+    Plaintext,
+    Keyring,
+    Encrypted,
+}
+
+impl std::fmt::Display for SecretStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecretStorage::Plaintext => write!(f, "plaintext"),
+            SecretStorage::Keyring => write!(f, "keyring"),
+            SecretStorage::Encrypted => write!(f, "encrypted"),
+        }
+    }
+}
+
+impl From<&StoredSecret> for SecretStorage {
+    fn from(stored: &StoredSecret) -> Self {
+        match stored {
+            StoredSecret::Plain(_) => SecretStorage::Plaintext,
+            StoredSecret::Keyring { .. } => SecretStorage::Keyring,
+            StoredSecret::Encrypted { .. } => SecretStorage::Encrypted,
+        }
+    }
+}
+
+/// Access to the OS keyring and to the passphrase secrets are encrypted with.
+pub(crate) trait SecretsBackend: Send + Sync {
+    fn store_in_keyring(&self, account: &str, secret: &SecretValue) -> AnyResult<()>;
+
+    fn load_from_keyring(&self, account: &str) -> AnyResult<SecretValue>;
+
+    fn delete_from_keyring(&self, account: &str) -> AnyResult<()>;
+
+    /// The passphrase, if one is available. With `prompt` it may be asked
+    /// for interactively, twice if it is `new`.
+    fn passphrase(&self, prompt: bool, new: bool) -> AnyResult<Option<SecretValue>>;
+}
+
+/// The OS keyring and the [`PASSPHRASE_ENV`] passphrase, prompting for it on
+/// a terminal.
+pub(crate) struct OsSecretsBackend;
+
+impl SecretsBackend for OsSecretsBackend {
+    fn store_in_keyring(&self, account: &str, secret: &SecretValue) -> AnyResult<()> {
+        keyring::Entry::new(KEYRING_SERVICE, account)?.set_password(secret.peek())?;
+
+        Ok(())
+    }
+
+    fn load_from_keyring(&self, account: &str) -> AnyResult<SecretValue> {
+        let secret = keyring::Entry::new(KEYRING_SERVICE, account)?
+            .get_password()
+            .map_err(|e| anyhow!("Failed to read keyring entry '{account}': {e}"))?;
+
+        Ok(SecretValue::from(secret))
+    }
+
+    fn delete_from_keyring(&self, account: &str) -> AnyResult<()> {
+        match keyring::Entry::new(KEYRING_SERVICE, account)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn passphrase(&self, prompt: bool, new: bool) -> AnyResult<Option<SecretValue>> {
+        if let Some(passphrase) = std::env::var(PASSPHRASE_ENV)
+            .ok()
+            .filter(|passphrase| !passphrase.is_empty())
+        {
+            return Ok(Some(SecretValue::from(passphrase)));
+        }
+
+        if !prompt || !std::io::stdin().is_terminal() {
+            return Ok(None);
+        }
+
+        let passphrase = SecretValue::from(rpassword::prompt_password("Secrets passphrase: ")?);
+
+        if passphrase.peek().is_empty() {
+            bail!("Passphrase must not be empty");
+        }
+
+        if new {
+            let confirmation =
+                SecretValue::from(rpassword::prompt_password("Confirm passphrase: ")?);
+
+            if passphrase != confirmation {
+                bail!("Passphrases do not match");
+            }
+        }
+
+        Ok(Some(passphrase))
+    }
+}
+
+/// Used until a backend is installed, e.g. in unit tests. There is neither a
+/// keyring nor a passphrase.
+struct NoSecretsBackend;
+
+impl SecretsBackend for NoSecretsBackend {
+    fn store_in_keyring(&self, _: &str, _: &SecretValue) -> AnyResult<()> {
+        bail!("no keyring is available")
+    }
+
+    fn load_from_keyring(&self, account: &str) -> AnyResult<SecretValue> {
+        bail!("Failed to read keyring entry '{account}': no keyring is available")
+    }
+
+    fn delete_from_keyring(&self, _: &str) -> AnyResult<()> {
+        bail!("no keyring is available")
+    }
+
+    fn passphrase(&self, _: bool, _: bool) -> AnyResult<Option<SecretValue>> {
+        Ok(None)
+    }
+}
+
+static BACKEND: OnceLock<Box<dyn SecretsBackend>> = OnceLock::new();
+
+/// Install the backend used by [`CliConf`] to store and read secrets.
+pub(crate) fn install_backend(backend: impl SecretsBackend + 'static) {
+    let _ = BACKEND.set(Box::new(backend));
+}
+
+/// The installed backend, see [`install_backend`].
+pub(crate) fn backend() -> &'static dyn SecretsBackend {
+    match BACKEND.get() {
+        Some(backend) => backend.as_ref(),
+        None => &NoSecretsBackend,
+    }
+}
+
+/// A secret as it was stored in the configuration file when it was loaded.
+/// `value` is `None` if it could not be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct LoadedSecret {
+    name: String,
+    value: Option<SecretValue>,
+    stored: StoredSecret,
+}
+
+/// A secret of an inactive profile that could not be read when loading the
+/// configuration. It is kept in its stored form so that saving the
+/// configuration does not lose it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct UnreadSecret {
+    pub(crate) profile: String,
+    section: &'static str,
+    key: &'static str,
+    stored: StoredSecret,
+    pub(crate) error: String,
+}
+
+impl UnreadSecret {
+    pub(crate) fn name(&self) -> String {
+        format!("profiles.{}.{}.{}", self.profile, self.section, self.key)
+    }
+}
+
+/// Replace all secrets in the serialized configuration with their
+/// [`StoredSecret`] form. Secrets that are unchanged since they were
+/// `loaded` keep their stored form if it suits `mode`. Returns where each
+/// present secret was stored.
+pub(crate) fn seal_table(
+    table: &mut toml::Table,
+    mode: SecretsMode,
+    conf_path: &Path,
+    loaded: &[LoadedSecret],
+    backend: &dyn SecretsBackend,
+) -> AnyResult<Vec<(String, SecretStorage)>> {
+    let mut passphrase = ask_once(|| backend.passphrase(mode == SecretsMode::Require, true));
+    let mut sealed = Vec::new();
+
+    for slot in secret_slots(table) {
+        let name = slot.name();
+        let secret = slot.value.clone().try_into::<SecretValue>()?;
+        let account = keyring_account(conf_path, &name);
+        let unchanged = loaded
+            .iter()
+            .find(|loaded| loaded.name == name && loaded.value.as_ref() == Some(&secret))
+            .map(|loaded| &loaded.stored)
+            .filter(|stored| is_kept(mode, stored, &account));
+
+        let stored = match unchanged {
+            Some(stored) => stored.clone(),
+            None => seal_with(mode, &account, &secret, &mut passphrase, backend)?,
+        };
+
+        sealed.push((name, SecretStorage::from(&stored)));
+        *slot.value = toml::Value::try_from(stored)?;
+    }
+
+    Ok(sealed)
+}
+
+/// Put secrets that could not be read back into the serialized
+/// configuration, unless their profile was removed. Returns where each of
+/// them is stored.
+pub(crate) fn restore_unread(
+    table: &mut toml::Table,
+    unread: &[UnreadSecret],
+) -> AnyResult<Vec<(String, SecretStorage)>> {
+    let mut restored = Vec::new();
+
+    for secret in unread {
+        let Some(profile) = table
+            .get_mut("profiles")
+            .and_then(|profiles| profiles.get_mut(&secret.profile))
+            .and_then(toml::Value::as_table_mut)
+        else {
+            continue;
+        };
+
+        let section = profile
+            .entry(secret.section)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+
+        let Some(section) = section.as_table_mut() else {
+            bail!(
+                "Profile section '{}.{}' must be a table",
+                secret.profile,
+                secret.section
+            );
+        };
+
+        if section.contains_key(secret.key) {
+            continue;
+        }
+
+        section.insert(
+            secret.key.to_string(),
+            toml::Value::try_from(&secret.stored)?,
+        );
+        restored.push((secret.name(), SecretStorage::from(&secret.stored)));
+    }
+
+    Ok(restored)
+}
+
+/// Remove the keyring entries of `loaded` secrets of the configuration at
+/// `conf_path` that the sealed configuration no longer refers to. Failures
+/// are only reported, the configuration is already saved at this point.
+pub(crate) fn remove_stale_keyring_entries(
+    table: &mut toml::Table,
+    conf_path: &Path,
+    loaded: &[LoadedSecret],
+    backend: &dyn SecretsBackend,
+) {
+    let scope = keyring_scope(conf_path);
+    let referenced = secret_slots(table)
+        .into_iter()
+        .filter_map(|slot| match slot.value.clone().try_into::<StoredSecret>() {
+            Ok(StoredSecret::Keyring { keyring }) => Some(keyring),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+
+    let stale = loaded
+        .iter()
+        .filter_map(|loaded| match &loaded.stored {
+            StoredSecret::Keyring { keyring }
+                if keyring.ends_with(&scope) && !referenced.contains(keyring) =>
+            {
+                Some(keyring)
+            }
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+
+    for account in stale {
+        if let Err(e) = backend.delete_from_keyring(account) {
+            notify_warning!("Failed to remove stale keyring entry '{account}': {e}");
+        }
+    }
+}
+
+/// Resolve all [`StoredSecret`]s in the serialized configuration back into
+/// plain values so that it can be deserialized into [`CliConf`]. Returns all
+/// secrets as they were stored and the ones that could not be read.
+///
+/// Secrets of the active profile and of the `selected` one must be readable.
+/// Secrets of other profiles that cannot be read are removed from the table
+/// and returned, so that one stale keyring entry does not break every
+/// command.
+pub(crate) fn open_table(
+    table: &mut toml::Table,
+    selected: Option<&str>,
+    backend: &dyn SecretsBackend,
+) -> AnyResult<(Vec<LoadedSecret>, Vec<UnreadSecret>)> {
+    let mut passphrase = ask_once(|| backend.passphrase(true, false));
+    let mut loaded = Vec::new();
+    let mut unread = Vec::new();
+
+    for slot in secret_slots(table) {
+        let name = slot.name();
+        let stored = slot.value.clone().try_into::<StoredSecret>()?;
+        let opened = open_with(stored.clone(), &mut passphrase, backend);
+
+        loaded.push(LoadedSecret {
+            name: name.clone(),
+            value: opened.as_ref().ok().cloned(),
+            stored: stored.clone(),
+        });
+
+        match opened {
+            Ok(secret) => *slot.value = toml::Value::String(secret.peek().to_string()),
+            Err(e) => match slot.profile {
+                Some(profile) if Some(profile.as_str()) != selected => unread.push(UnreadSecret {
+                    profile: profile.clone(),
+                    section: slot.section,
+                    key: slot.key,
+                    stored,
+                    error: e.to_string(),
+                }),
+                _ => bail!("Failed to read '{name}': {e}"),
+            },
+        }
+    }
+
+    for secret in &unread {
+        if let Some(section) = table
+            .get_mut("profiles")
+            .and_then(|profiles| profiles.get_mut(&secret.profile))
+            .and_then(|profile| profile.get_mut(secret.section))
+            .and_then(toml::Value::as_table_mut)
+        {
+            section.remove(secret.key);
+        }
+    }
+
+    Ok((loaded, unread))
+}
+
+/// Ask for the passphrase at most once, so that a configuration with several
+/// secrets prompts only once and seals all of them with the same passphrase.
+fn ask_once(
+    mut passphrase: impl FnMut() -> AnyResult<Option<SecretValue>>,
+) -> impl FnMut() -> AnyResult<Option<SecretValue>> {
+    let mut answer: Option<Option<SecretValue>> = None;
+
+    move || {
+        if let Some(answer) = &answer {
+            return Ok(answer.clone());
+        }
+
+        let passphrase = passphrase()?;
+        answer = Some(passphrase.clone());
+
+        Ok(passphrase)
+    }
+}
+
+/// A secret in the serialized configuration.
+struct SecretSlot<'a> {
+    /// Inactive profile the secret belongs to, `None` for the active one.
+    profile: Option<&'a String>,
+    section: &'static str,
+    key: &'static str,
+    value: &'a mut toml::Value,
+}
+
+impl SecretSlot<'_> {
+    /// Dotted name of the secret.
+    fn name(&self) -> String {
+        match self.profile {
+            Some(profile) => format!("profiles.{profile}.{}.{}", self.section, self.key),
+            None => format!("{}.{}", self.section, self.key),
+        }
+    }
+}
+
+/// All secrets present in the serialized configuration, including the ones
+/// of inactive profiles.
+fn secret_slots(table: &mut toml::Table) -> Vec<SecretSlot<'_>> {
+    let mut slots = Vec::new();
+
+    for (section_name, section) in table.iter_mut() {
+        if section_name == "profiles" {
+            let Some(profiles) = section.as_table_mut() else {
+                continue;
+            };
+
+            for (profile, profile_table) in profiles.iter_mut() {
+                let Some(profile_table) = profile_table.as_table_mut() else {
+                    continue;
+                };
+
+                for (section_name, section) in profile_table.iter_mut() {
+                    slots.extend(section_secret(Some(profile), section_name, section));
+                }
+            }
+
+            continue;
+        }
+
+        slots.extend(section_secret(None, section_name, section));
+    }
+
+    slots
+}
+
+/// The secret of a single configuration table, if it holds one.
+fn section_secret<'a>(
+    profile: Option<&'a String>,
+    section_name: &str,
+    section: &'a mut toml::Value,
+) -> Option<SecretSlot<'a>> {
+    let &(name, key) = SECRET_FIELDS
+        .iter()
+        .find(|(name, _)| *name == section_name)?;
+    let value = section.get_mut(key)?;
+
+    Some(SecretSlot {
+        profile,
+        section: name,
+        key,
+        value,
+    })
+}
+
+/// Whether an unchanged secret can stay as it is stored when saving with
+/// `mode` under the keyring `account`.
+fn is_kept(mode: SecretsMode, stored: &StoredSecret, account: &str) -> bool {
+    match stored {
+        StoredSecret::Plain(_) => mode == SecretsMode::Off,
+        StoredSecret::Keyring { keyring } => mode != SecretsMode::Off && keyring == account,
+        StoredSecret::Encrypted { .. } => mode != SecretsMode::Off,
+    }
+}
+
+/// Turn a secret into its on-disk representation according to `mode`. The
+/// passphrase is only requested if the keyring is unavailable.
+fn seal_with(
+    mode: SecretsMode,
+    account: &str,
+    secret: &SecretValue,
+    passphrase: impl FnOnce() -> AnyResult<Option<SecretValue>>,
+    backend: &dyn SecretsBackend,
+) -> AnyResult<StoredSecret> {
+    if mode == SecretsMode::Off {
+        return Ok(StoredSecret::Plain(secret.clone()));
+    }
+
+    let keyring_error = match backend.store_in_keyring(account, secret) {
+        Ok(()) => {
+            return Ok(StoredSecret::Keyring {
+                keyring: account.to_string(),
+            })
+        }
+        Err(e) => e,
+    };
+
+    if let Some(passphrase) = passphrase()? {
+        return encrypt(&passphrase, secret);
+    }
+
+    if mode == SecretsMode::Require {
+        bail!(
+            "Secrets mode is 'require' but the OS keyring is unavailable ({keyring_error}) and no passphrase was provided. Set {PASSPHRASE_ENV} to encrypt secrets with a passphrase."
+        );
+    }
+
+    notify_warning!(
+        "Storing secrets in plaintext: the OS keyring is unavailable ({keyring_error}) and {PASSPHRASE_ENV} is not set"
+    );
+
+    Ok(StoredSecret::Plain(secret.clone()))
+}
+
+/// Resolve a [`StoredSecret`] into the secret value.
+fn open_with(
+    stored: StoredSecret,
+    passphrase: impl FnOnce() -> AnyResult<Option<SecretValue>>,
+    backend: &dyn SecretsBackend,
+) -> AnyResult<SecretValue> {
+    match stored {
+        StoredSecret::Plain(secret) => Ok(secret),
+        StoredSecret::Keyring { keyring } => backend.load_from_keyring(&keyring),
+        StoredSecret::Encrypted {
+            salt,
+            nonce,
+            ciphertext,
+        } => {
+            let Some(passphrase) = passphrase()? else {
+                bail!("Secret is encrypted but no passphrase was provided. Set {PASSPHRASE_ENV}.");
+            };
+
+            decrypt(&passphrase, &salt, &nonce, &ciphertext)
+        }
+    }
+}
+
+/// Keyring entries are scoped to the configuration file so that multiple
+/// configurations on the same machine do not overwrite each other.
+fn keyring_account(conf_path: &Path, name: &str) -> String {
+    format!("{name}{}", keyring_scope(conf_path))
+}
+
+/// Suffix of the keyring accounts of the configuration at `conf_path`.
+fn keyring_scope(conf_path: &Path) -> String {
+    let conf_path = std::path::absolute(conf_path).unwrap_or_else(|_| conf_path.to_path_buf());
+
+    format!("@{}", conf_path.display())
+}
+
+fn derive_key(passphrase: &SecretValue, salt: &[u8]) -> AnyResult<Key<Aes256Gcm>> {
+    let mut key = Key::<Aes256Gcm>::default();
+
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.peek().as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Failed to derive key from passphrase: {e}"))?;
+
+    Ok(key)
+}
+
+fn encrypt(passphrase: &SecretValue, secret: &SecretValue) -> AnyResult<StoredSecret> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, &salt)?);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), secret.peek().as_bytes())
+        .map_err(|_| anyhow!("Failed to encrypt secret"))?;
+
+    Ok(StoredSecret::Encrypted {
+        salt: BASE64_STANDARD.encode(salt),
+        nonce: BASE64_STANDARD.encode(nonce),
+        ciphertext: BASE64_STANDARD.encode(ciphertext),
+    })
+}
+
+fn decrypt(
+    passphrase: &SecretValue,
+    salt: &str,
+    nonce: &str,
+    ciphertext: &str,
+) -> AnyResult<SecretValue> {
+    let salt = BASE64_STANDARD.decode(salt)?;
+    let nonce = BASE64_STANDARD.decode(nonce)?;
+    let ciphertext = BASE64_STANDARD.decode(ciphertext)?;
+
+    if nonce.len() != NONCE_LEN {
+        bail!("Invalid nonce length {}", nonce.len());
+    }
+
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, &salt)?);
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("Failed to decrypt secret, is the passphrase correct?"))?;
+
+    Ok(SecretValue::from(String::from_utf8(plaintext)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{
+            collections::BTreeMap,
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Mutex,
+            },
+        },
+    };
+
+    const PASSPHRASE: &str = "correct horse battery staple";
+
+    /// Backend with a fixed passphrase and, if `keyring` is set, an in-memory
+    /// keyring.
+    #[derive(Default)]
+    struct TestBackend {
+        keyring: Option<Mutex<BTreeMap<String, SecretValue>>>,
+        passphrase: Option<&'static str>,
+        passphrase_requests: AtomicUsize,
+    }
+
+    impl TestBackend {
+        fn with_keyring() -> Self {
+            Self {
+                keyring: Some(Mutex::default()),
+                ..Default::default()
+            }
+        }
+
+        fn with_passphrase(passphrase: &'static str) -> Self {
+            Self {
+                passphrase: Some(passphrase),
+                ..Default::default()
+            }
+        }
+
+        fn keyring_accounts(&self) -> Vec<String> {
+            let keyring = self.keyring.as_ref().unwrap().lock().unwrap();
+
+            keyring.keys().cloned().collect()
+        }
+    }
+
+    impl SecretsBackend for TestBackend {
+        fn store_in_keyring(&self, account: &str, secret: &SecretValue) -> AnyResult<()> {
+            let Some(keyring) = &self.keyring else {
+                bail!("no keyring is available");
+            };
+
+            keyring
+                .lock()
+                .unwrap()
+                .insert(account.to_string(), secret.clone());
+
+            Ok(())
+        }
+
+        fn load_from_keyring(&self, account: &str) -> AnyResult<SecretValue> {
+            self.keyring
+                .as_ref()
+                .and_then(|keyring| keyring.lock().unwrap().get(account).cloned())
+                .ok_or_else(|| anyhow!("Failed to read keyring entry '{account}'"))
+        }
+
+        fn delete_from_keyring(&self, account: &str) -> AnyResult<()> {
+            let Some(keyring) = &self.keyring else {
+                bail!("no keyring is available");
+            };
+
+            keyring.lock().unwrap().remove(account);
+
+            Ok(())
+        }
+
+        fn passphrase(&self, _: bool, _: bool) -> AnyResult<Option<SecretValue>> {
+            self.passphrase_requests.fetch_add(1, Ordering::Relaxed);
+
+            Ok(self.passphrase.map(SecretValue::from))
+        }
+    }
+
+    fn with_passphrase(passphrase: &str) -> impl Fn() -> AnyResult<Option<SecretValue>> + '_ {
+        move || Ok(Some(SecretValue::from(passphrase)))
+    }
+
+    fn without_passphrase() -> AnyResult<Option<SecretValue>> {
+        Ok(None)
+    }
+
+    fn profiles_table() -> toml::Table {
+        toml::from_str(
+            "[sui]\npk = \"YWN0aXZl\"\n\n[profiles.testnet.sui]\npk = \"dGVzdG5ldA==\"\n\n[profiles.testnet.data_storage]\nencryption_key = \"a2V5\"",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_seal_and_open_with_passphrase() {
+        let secret = SecretValue::from("c3VwZXJzZWNyZXQ=");
+        let stored = seal_with(
+            SecretsMode::Require,
+            "sui.pk@test",
+            &secret,
+            with_passphrase(PASSPHRASE),
+            &NoSecretsBackend,
+        )
+        .unwrap();
+
+        assert_eq!(SecretStorage::from(&stored), SecretStorage::Encrypted);
+        assert_eq!(
+            open_with(
+                stored.clone(),
+                with_passphrase(PASSPHRASE),
+                &NoSecretsBackend
+            )
+            .unwrap(),
+            secret
+        );
+        assert!(open_with(
+            stored.clone(),
+            with_passphrase("wrong passphrase"),
+            &NoSecretsBackend
+        )
+        .is_err());
+        assert!(open_with(stored, without_passphrase, &NoSecretsBackend).is_err());
+    }
+
+    #[test]
+    fn test_seal_falls_back_according_to_mode() {
+        let secret = SecretValue::from("c3VwZXJzZWNyZXQ=");
+
+        assert_eq!(
+            seal_with(
+                SecretsMode::Off,
+                "sui.pk@test",
+                &secret,
+                with_passphrase("unused"),
+                &TestBackend::with_keyring(),
+            )
+            .unwrap(),
+            StoredSecret::Plain(secret.clone())
+        );
+        assert_eq!(
+            seal_with(
+                SecretsMode::Auto,
+                "sui.pk@test",
+                &secret,
+                with_passphrase("unused"),
+                &TestBackend::with_keyring(),
+            )
+            .unwrap(),
+            StoredSecret::Keyring {
+                keyring: "sui.pk@test".to_string()
+            }
+        );
+        assert_eq!(
+            seal_with(
+                SecretsMode::Auto,
+                "sui.pk@test",
+                &secret,
+                without_passphrase,
+                &NoSecretsBackend,
+            )
+            .unwrap(),
+            StoredSecret::Plain(secret.clone())
+        );
+        assert!(seal_with(
+            SecretsMode::Require,
+            "sui.pk@test",
+            &secret,
+            without_passphrase,
+            &NoSecretsBackend,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_seal_table_never_writes_plaintext_when_encrypted() {
+        let backend = TestBackend::with_passphrase(PASSPHRASE);
+        let mut table: toml::Table =
+            toml::from_str("[sui]\npk = \"c3VwZXJzZWNyZXQ=\"\nrpc_url = \"https://sui.io/\"")
+                .unwrap();
+        let sealed = seal_table(
+            &mut table,
+            SecretsMode::Auto,
+            Path::new("/tmp/conf.toml"),
+            &[],
+            &backend,
+        )
+        .unwrap();
+
+        assert_eq!(
+            sealed,
+            vec![("sui.pk".to_string(), SecretStorage::Encrypted)]
+        );
+
+        let serialized = toml::to_string(&table).unwrap();
+        assert!(!serialized.contains("c3VwZXJzZWNyZXQ="));
+
+        let mut table: toml::Table = toml::from_str(&serialized).unwrap();
+        open_table(&mut table, None, &backend).unwrap();
+
+        assert_eq!(
+            table["sui"]["pk"],
+            toml::Value::String("c3VwZXJzZWNyZXQ=".to_string())
+        );
+    }
+
+    #[test]
+    fn test_seal_table_seals_profile_secrets() {
+        let backend = TestBackend::with_passphrase(PASSPHRASE);
+        let mut table = profiles_table();
+        let sealed = seal_table(
+            &mut table,
+            SecretsMode::Require,
+            Path::new("/tmp/conf.toml"),
+            &[],
+            &backend,
+        )
+        .unwrap();
+
+        assert_eq!(
+            sealed,
+            vec![
+                (
+                    "profiles.testnet.data_storage.encryption_key".to_string(),
+                    SecretStorage::Encrypted
+                ),
+                (
+                    "profiles.testnet.sui.pk".to_string(),
+                    SecretStorage::Encrypted
+                ),
+                ("sui.pk".to_string(), SecretStorage::Encrypted),
+            ]
+        );
+
+        let serialized = toml::to_string(&table).unwrap();
+        assert!(!serialized.contains("dGVzdG5ldA=="));
+        assert!(!serialized.contains("a2V5"));
+
+        let mut table: toml::Table = toml::from_str(&serialized).unwrap();
+        open_table(&mut table, None, &backend).unwrap();
+
+        assert_eq!(
+            table["profiles"]["testnet"]["sui"]["pk"],
+            toml::Value::String("dGVzdG5ldA==".to_string())
+        );
+        assert_eq!(
+            table["sui"]["pk"],
+            toml::Value::String("YWN0aXZl".to_string())
+        );
+    }
+
+    #[test]
+    fn test_passphrase_is_requested_once_per_table() {
+        let backend = TestBackend::with_passphrase(PASSPHRASE);
+        let mut table = profiles_table();
+
+        seal_table(
+            &mut table,
+            SecretsMode::Require,
+            Path::new("/tmp/conf.toml"),
+            &[],
+            &backend,
+        )
+        .unwrap();
+
+        assert_eq!(backend.passphrase_requests.load(Ordering::Relaxed), 1);
+
+        open_table(&mut table, None, &backend).unwrap();
+
+        assert_eq!(backend.passphrase_requests.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_seal_table_keeps_unchanged_secrets() {
+        let backend = TestBackend::with_passphrase(PASSPHRASE);
+        let path = Path::new("/tmp/conf.toml");
+        let mut sealed = profiles_table();
+
+        seal_table(&mut sealed, SecretsMode::Require, path, &[], &backend).unwrap();
+
+        let mut table = sealed.clone();
+        let (loaded, _) = open_table(&mut table, None, &backend).unwrap();
+
+        // Only the changed secret gets a fresh salt and nonce.
+        table["sui"]
+            .as_table_mut()
+            .unwrap()
+            .insert("pk".to_string(), toml::Value::String("Y2hhbmdlZA==".into()));
+
+        seal_table(&mut table, SecretsMode::Require, path, &loaded, &backend).unwrap();
+
+        assert_eq!(table["profiles"], sealed["profiles"]);
+        assert_ne!(table["sui"]["pk"], sealed["sui"]["pk"]);
+
+        // Secrets that do not suit the mode are sealed again.
+        let mut table = sealed.clone();
+        let (loaded, _) = open_table(&mut table, None, &backend).unwrap();
+        let stored = seal_table(&mut table, SecretsMode::Off, path, &loaded, &backend).unwrap();
+
+        assert!(stored
+            .iter()
+            .all(|(_, storage)| *storage == SecretStorage::Plaintext));
+    }
+
+    #[test]
+    fn test_stale_keyring_entries_are_removed() {
+        let backend = TestBackend::with_keyring();
+        let path = Path::new("/tmp/conf.toml");
+        let mut table = profiles_table();
+
+        seal_table(&mut table, SecretsMode::Require, path, &[], &backend).unwrap();
+
+        // Entries of other configurations are never touched.
+        backend
+            .store_in_keyring("sui.pk@/tmp/other.toml", &SecretValue::from("b3RoZXI="))
+            .unwrap();
+
+        let (loaded, _) = open_table(&mut table, None, &backend).unwrap();
+        let mut removed = loaded.clone();
+        removed.push(LoadedSecret {
+            name: "sui.pk".to_string(),
+            value: None,
+            stored: StoredSecret::Keyring {
+                keyring: "sui.pk@/tmp/other.toml".to_string(),
+            },
+        });
+
+        table.remove("profiles");
+        seal_table(&mut table, SecretsMode::Require, path, &loaded, &backend).unwrap();
+        remove_stale_keyring_entries(&mut table, path, &removed, &backend);
+
+        assert_eq!(
+            backend.keyring_accounts(),
+            vec!["sui.pk@/tmp/conf.toml", "sui.pk@/tmp/other.toml"]
+        );
+    }
+
+    #[test]
+    fn test_open_table_skips_unreadable_inactive_profile_secrets() {
+        let mut table: toml::Table = toml::from_str(
+            "[sui]\npk = \"YWN0aXZl\"\n\n[profiles.testnet.sui]\npk = \"dGVzdG5ldA==\"\nrpc_url = \"https://sui.io/\"",
+        )
+        .unwrap();
+        seal_table(
+            &mut table,
+            SecretsMode::Require,
+            Path::new("/tmp/conf.toml"),
+            &[],
+            &TestBackend::with_passphrase(PASSPHRASE),
+        )
+        .unwrap();
+
+        // Make the active secret readable without a passphrase.
+        table["sui"].as_table_mut().unwrap().insert(
+            "pk".to_string(),
+            toml::Value::String("YWN0aXZl".to_string()),
+        );
+        let sealed = table.clone();
+
+        // The secret of the selected profile must be readable.
+        let err = open_table(&mut table.clone(), Some("testnet"), &NoSecretsBackend).unwrap_err();
+        assert!(err.to_string().contains("profiles.testnet.sui.pk"));
+
+        let (_, unread) = open_table(&mut table, None, &NoSecretsBackend).unwrap();
+
+        assert_eq!(unread.len(), 1);
+        assert_eq!(unread[0].name(), "profiles.testnet.sui.pk");
+        assert!(table["profiles"]["testnet"]["sui"].get("pk").is_none());
+        assert_eq!(
+            table["sui"]["pk"],
+            toml::Value::String("YWN0aXZl".to_string())
+        );
+
+        // Saving puts the unread secret back as it was.
+        let restored = restore_unread(&mut table, &unread).unwrap();
+
+        assert_eq!(
+            restored,
+            vec![(
+                "profiles.testnet.sui.pk".to_string(),
+                SecretStorage::Encrypted
+            )]
+        );
+        assert_eq!(
+            table["profiles"]["testnet"]["sui"]["pk"],
+            sealed["profiles"]["testnet"]["sui"]["pk"]
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use {
-    crate::{loading, prelude::*},
+    crate::{display::json_output, item, loading, notify_success, notify_warning, prelude::*},
     base64::{prelude::BASE64_STANDARD, Engine},
     nexus_sdk::{
-        nexus::{client::NexusClient, crawler::Crawler},
+        nexus::{client::NexusClient, crawler::Crawler, simulation::SimulatedTransaction},
         sui,
     },
 };
@@ -170,17 +170,19 @@ pub(crate) async fn get_nexus_objects(
     }
 
     // For some networks, we attempt to load the objects from public endpoints.
-    let response = match conf.sui.rpc_url.as_ref() {
-        Some(url) if url.as_str() == DEVNET_NEXUS_RPC_URL => {
-            fetch_objects_from_url(DEVNET_OBJECTS_TOML).await
-        }
-        Some(url) if url.as_str() == TESTNET_NEXUS_RPC_URL => {
-            fetch_objects_from_url(TESTNET_OBJECTS_TOML).await
-        }
-        Some(url) if url.as_str() == MAINNET_NEXUS_RPC_URL => {
-            fetch_objects_from_url(MAINNET_OBJECTS_TOML).await
-        }
-        _ => Err(anyhow!(
+    let objects_toml = SuiNet::value_variants()
+        .iter()
+        .find(|net| {
+            conf.sui
+                .rpc_url
+                .as_ref()
+                .is_some_and(|url| url.as_str() == net.rpc_url())
+        })
+        .and_then(SuiNet::objects_toml);
+
+    let response = match objects_toml {
+        Some(url) => fetch_objects_from_url(url).await,
+        None => Err(anyhow!(
             "Nexus objects are not configured for this network."
         )),
     };
@@ -203,7 +205,7 @@ pub(crate) async fn get_nexus_objects(
     )))
 }
 
-async fn fetch_objects_from_url(url: &str) -> AnyResult<NexusObjects> {
+pub(crate) async fn fetch_objects_from_url(url: &str) -> AnyResult<NexusObjects> {
     let response = reqwest::Client::new().get(url).send().await?;
 
     if !response.status().is_success() {
@@ -326,7 +328,9 @@ pub(crate) async fn get_nexus_client(
     sui_gas_coin: Option<sui::types::Address>,
     sui_gas_budget: u64,
 ) -> Result<NexusClient, NexusCliError> {
-    let mut conf = CliConf::load().await.unwrap_or_default();
+    let mut conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
 
     let client = build_sui_grpc_client(&conf).await?;
     let pk = get_signing_key(&conf).await?;
@@ -345,16 +349,109 @@ pub(crate) async fn get_nexus_client(
     let rpc_url = client.lock().await.uri().to_string();
 
     // Create Nexus client.
-    let nexus_client = NexusClient::builder()
+    let mut builder = NexusClient::builder()
         .with_private_key(pk)
         .with_nexus_objects(nexus_objects.clone())
         .with_gas(vec![gas_coin], sui_gas_budget)
-        .with_rpc_url(&rpc_url)
-        .build()
+        .with_rpc_url(&rpc_url);
+
+    if let Some(gas_margin) = GAS_MARGIN.get() {
+        builder = builder.with_budget_estimation(*gas_margin);
+    }
+
+    let nexus_client = builder.build().await.map_err(NexusCliError::Nexus)?;
+
+    Ok(nexus_client)
+}
+
+/// Simulate `tx` instead of executing it and report the outcome, used by
+/// commands run with `--dry-run`.
+pub(crate) async fn simulate_and_report(
+    nexus_client: &NexusClient,
+    tx: sui::types::ProgrammableTransaction,
+    address: sui::types::Address,
+) -> AnyResult<(), NexusCliError> {
+    let simulated = nexus_client
+        .simulate_transaction(tx, address)
         .await
         .map_err(NexusCliError::Nexus)?;
 
-    Ok(nexus_client)
+    report_dry_run(nexus_client, &simulated).await
+}
+
+/// Print the outcome of a transaction simulated with `--dry-run`. A
+/// transaction that would fail is reported as an error.
+pub(crate) async fn report_dry_run(
+    nexus_client: &NexusClient,
+    simulated: &SimulatedTransaction,
+) -> AnyResult<(), NexusCliError> {
+    print_dry_run(simulated);
+
+    json_output(&dry_run_json(simulated))?;
+
+    if let Some(abort) = simulated.move_abort() {
+        let abort = nexus_client.signer().resolve_move_abort(abort).await;
+
+        return Err(NexusCliError::Nexus(abort.into()));
+    }
+
+    if simulated.is_success() {
+        Ok(())
+    } else {
+        Err(NexusCliError::Any(anyhow!(
+            "Dry run: the simulated transaction failed"
+        )))
+    }
+}
+
+/// Print the estimated gas of a simulated transaction and whether it would
+/// succeed.
+pub(crate) fn print_dry_run(simulated: &SimulatedTransaction) {
+    let gas = &simulated.gas;
+
+    match simulated.failure() {
+        Some(failure) => notify_warning!("Dry run: the transaction would fail: {failure}"),
+        None => notify_success!("Dry run: the transaction would succeed"),
+    }
+
+    item!(
+        "Estimated gas: {} MIST (computation {} + storage {})",
+        gas.required_budget(),
+        gas.computation_cost,
+        gas.storage_cost
+    );
+    item!("Storage rebate: {} MIST", gas.storage_rebate);
+    item!("Net cost: {} MIST", gas.net_cost());
+    item!("Simulated with budget: {} MIST", simulated.budget);
+}
+
+pub(crate) fn dry_run_json(simulated: &SimulatedTransaction) -> serde_json::Value {
+    let gas = &simulated.gas;
+    let move_abort = simulated.move_abort().map(|abort| {
+        json!({
+            "package": abort.package,
+            "module": abort.module,
+            "function": abort.function,
+            "code": abort.code,
+            "command_index": abort.command_index,
+        })
+    });
+
+    json!({
+        "dry_run": true,
+        "success": simulated.is_success(),
+        "failure": simulated.failure(),
+        "move_abort": move_abort,
+        "budget": simulated.budget,
+        "gas": {
+            "required_budget": gas.required_budget(),
+            "computation_cost": gas.computation_cost,
+            "storage_cost": gas.storage_cost,
+            "storage_rebate": gas.storage_rebate,
+            "non_refundable_storage_fee": gas.non_refundable_storage_fee,
+            "net_cost": gas.net_cost(),
+        },
+    })
 }
 
 #[cfg(test)]
@@ -611,4 +708,77 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn parses_global_dry_run_and_gas_margin() {
+        use clap::Parser;
+
+        let cli = crate::Cli::try_parse_from([
+            "nexus",
+            "dag",
+            "publish",
+            "--path",
+            "dag.json",
+            "--dry-run",
+            "--gas-margin",
+            "20",
+        ])
+        .expect("global flags should parse after the subcommand");
+
+        assert!(cli.dry_run);
+        assert_eq!(cli.gas_margin, Some(20));
+        assert!(cli.command.supports_dry_run());
+
+        // Commands that would still execute transactions are refused.
+        let cli = crate::Cli::try_parse_from(["nexus", "leader", "status", "--dry-run"])
+            .expect("global flags should parse after the subcommand");
+
+        assert!(!cli.command.supports_dry_run());
+    }
+
+    #[test]
+    fn dry_run_json_reports_move_abort_and_gas() {
+        let package = sui::types::Address::from_static("0x2");
+        let effects = sui::types::TransactionEffectsV2 {
+            status: sui::types::ExecutionStatus::Failure {
+                error: sui::types::ExecutionError::MoveAbort {
+                    location: sui::types::MoveLocation {
+                        package,
+                        module: sui::types::Identifier::from_static("dag"),
+                        function: 0,
+                        instruction: 4,
+                        function_name: Some(sui::types::Identifier::from_static("execute")),
+                    },
+                    code: 3,
+                },
+                command: Some(2),
+            },
+            epoch: 1,
+            gas_used: sui::types::GasCostSummary {
+                computation_cost: 1_000,
+                storage_cost: 500,
+                storage_rebate: 2_000,
+                non_refundable_storage_fee: 0,
+            },
+            transaction_digest: sui::types::Digest::generate(rand::thread_rng()),
+            gas_object_index: None,
+            events_digest: None,
+            dependencies: vec![],
+            lamport_version: 1,
+            changed_objects: vec![],
+            unchanged_consensus_objects: vec![],
+            auxiliary_data_digest: None,
+        };
+
+        let json = dry_run_json(&SimulatedTransaction::new(effects, vec![], 10_000));
+
+        assert_eq!(json["success"], false);
+        assert_eq!(json["budget"], 10_000);
+        assert_eq!(json["move_abort"]["module"], "dag");
+        assert_eq!(json["move_abort"]["function"], "execute");
+        assert_eq!(json["move_abort"]["code"], 3);
+        assert_eq!(json["move_abort"]["command_index"], 2);
+        assert_eq!(json["gas"]["required_budget"], 1_500);
+        assert_eq!(json["gas"]["net_cost"], -500);
+    }
 }
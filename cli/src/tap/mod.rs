@@ -34,7 +34,7 @@ use {
         loading,
         notify_success,
         prelude::*,
-        sui::get_nexus_client,
+        sui::{get_nexus_client, report_dry_run},
         workflow,
     },
     convert_case::{Case, Casing},
@@ -663,6 +663,19 @@ pub(crate) enum ExecutionCommand {
     },
 }
 
+impl TapCommand {
+    /// Whether the command can be simulated with `--dry-run`.
+    pub(crate) fn supports_dry_run(&self) -> bool {
+        matches!(
+            self,
+            TapCommand::CreateAgent { .. }
+                | TapCommand::RegisterSkill { .. }
+                | TapCommand::UpdateSkill { .. }
+                | TapCommand::Bind { .. }
+        )
+    }
+}
+
 pub(crate) async fn handle(command: TapCommand) -> AnyResult<(), NexusCliError> {
     match command {
         TapCommand::Scaffold { name, target } => scaffold_tap_skill(name, target).await,
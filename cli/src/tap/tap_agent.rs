@@ -3,20 +3,26 @@ use super::*;
 pub(crate) async fn handle_agent_command(command: AgentCommand) -> AnyResult<(), NexusCliError> {
     match command {
         AgentCommand::Save { name, agent_id } => {
-            let mut conf = CliConf::load().await.unwrap_or_default();
+            let mut conf = CliConf::load_or_default()
+                .await
+                .map_err(NexusCliError::Any)?;
             conf.agents.insert(name.clone(), agent_id);
             conf.save().await.map_err(NexusCliError::Any)?;
             notify_success!("Saved Talus agent alias {name}");
             json_output(&agent_save_result_json(&name, agent_id))
         }
         AgentCommand::List => {
-            let conf = CliConf::load().await.unwrap_or_default();
+            let conf = CliConf::load_or_default()
+                .await
+                .map_err(NexusCliError::Any)?;
             let mut agents = conf.agents.into_iter().collect::<Vec<_>>();
             agents.sort_by(|left, right| left.0.cmp(&right.0).then(left.1.cmp(&right.1)));
             json_output(&agent_list_result_json(&agents))
         }
         AgentCommand::Remove { name } => {
-            let mut conf = CliConf::load().await.unwrap_or_default();
+            let mut conf = CliConf::load_or_default()
+                .await
+                .map_err(NexusCliError::Any)?;
             let removed = conf.agents.remove(&name);
             conf.save().await.map_err(NexusCliError::Any)?;
             json_output(&agent_remove_result_json(&name, removed))
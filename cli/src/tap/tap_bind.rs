@@ -10,6 +10,16 @@ pub(crate) async fn bind_agent_skill(
     let artifact = read_artifact(artifact_path).await?;
     let nexus_client = get_nexus_client(sui_gas_coin, sui_gas_budget).await?;
 
+    if DRY_RUN.load(Ordering::Relaxed) {
+        let simulated = nexus_client
+            .tap()
+            .simulate_bind_agent_skill(BindAgentSkillParams { artifact })
+            .await
+            .map_err(NexusCliError::Nexus)?;
+
+        return report_dry_run(&nexus_client, &simulated).await;
+    }
+
     let result = nexus_client
         .tap()
         .bind_agent_skill(BindAgentSkillParams {
@@ -7,6 +7,17 @@ pub(crate) async fn create_agent(
     command_title!("Creating Talus agent");
 
     let nexus_client = get_nexus_client(sui_gas_coin, sui_gas_budget).await?;
+
+    if DRY_RUN.load(Ordering::Relaxed) {
+        let simulated = nexus_client
+            .tap()
+            .simulate_create_agent()
+            .await
+            .map_err(NexusCliError::Nexus)?;
+
+        return report_dry_run(&nexus_client, &simulated).await;
+    }
+
     let result = nexus_client
         .tap()
         .create_agent()
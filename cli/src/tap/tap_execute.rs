@@ -18,9 +18,15 @@ pub(crate) async fn execute_agent_dag_skill(
     let options = agent_execute_options_from_cli(payment_source_hex, payment_max_budget_mist)?;
     let nexus_client = get_nexus_client(sui_gas_coin, sui_gas_budget).await?;
     ensure_cli_mutable_agent(&nexus_client, agent_id).await?;
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
     let preferred_remote_storage = conf.data_storage.preferred_remote_storage;
-    let storage_conf = conf.data_storage.clone().into();
+    let storage_conf = conf
+        .data_storage
+        .clone()
+        .try_into()
+        .map_err(NexusCliError::Any)?;
     let input_data =
         workflow::process_entry_ports(&input_json, preferred_remote_storage, &remote).await?;
 
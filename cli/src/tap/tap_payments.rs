@@ -111,7 +111,9 @@ async fn list_payments(
     completed: bool,
     pending: bool,
 ) -> AnyResult<(), NexusCliError> {
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
     let agent_id = if alias.is_some() || agent_id.is_some() {
         Some(agent_id_from_alias_or_arg(&conf, alias, agent_id)?)
     } else {
@@ -171,7 +173,9 @@ async fn resolve_payment(
 ) -> AnyResult<(), NexusCliError> {
     command_title!("Resolving standard TAP execution payment for DAGExecution '{execution_id}'");
 
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
     let resolved_agent_id = if alias.is_some() || agent_id.is_some() {
         Some(agent_id_from_alias_or_arg(&conf, alias, agent_id)?)
     } else {
@@ -209,7 +213,9 @@ async fn refill_payment(
 ) -> AnyResult<(), NexusCliError> {
     command_title!("Refilling standard TAP execution payment for DAGExecution '{execution_id}'");
 
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
     let resolved_agent_id = if alias.is_some() || agent_id.is_some() {
         Some(agent_id_from_alias_or_arg(&conf, alias, agent_id)?)
     } else {
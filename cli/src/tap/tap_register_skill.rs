@@ -11,6 +11,17 @@ pub(crate) async fn register_skill(
 
     let nexus_client = get_nexus_client(sui_gas_coin, sui_gas_budget).await?;
     ensure_cli_mutable_agent(&nexus_client, agent_id).await?;
+
+    if DRY_RUN.load(Ordering::Relaxed) {
+        let simulated = nexus_client
+            .tap()
+            .simulate_register_skill(agent_id, &artifact)
+            .await
+            .map_err(NexusCliError::Nexus)?;
+
+        return report_dry_run(&nexus_client, &simulated).await;
+    }
+
     let result = nexus_client
         .tap()
         .register_skill(agent_id, &artifact)
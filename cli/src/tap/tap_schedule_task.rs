@@ -70,7 +70,9 @@ pub(crate) async fn schedule_tap_task(
 
     command_title!("Creating TAP scheduled task for agent '{agent_id}' skill '{skill_id}'");
 
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
     let nexus_client = get_nexus_client(sui_gas_coin, sui_gas_budget).await?;
     match payment_source {
         TapTaskPaymentSourceArg::AgentFunded => {
@@ -99,7 +101,11 @@ pub(crate) async fn schedule_tap_task(
     let metadata_pairs = helpers::parse_metadata(&metadata)?;
     let input_json = input_json.take().unwrap_or_else(|| serde_json::json!({}));
     let preferred_remote_storage = conf.data_storage.preferred_remote_storage;
-    let storage_conf: StorageConf = conf.data_storage.clone().into();
+    let storage_conf: StorageConf = conf
+        .data_storage
+        .clone()
+        .try_into()
+        .map_err(NexusCliError::Any)?;
 
     let ports_data =
         workflow::process_entry_ports(&input_json, preferred_remote_storage, &remote).await?;
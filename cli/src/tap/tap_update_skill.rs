@@ -12,6 +12,17 @@ pub(crate) async fn update_skill_from_artifact(
 
     let nexus_client = get_nexus_client(sui_gas_coin, sui_gas_budget).await?;
     ensure_cli_mutable_agent(&nexus_client, agent_id).await?;
+
+    if DRY_RUN.load(Ordering::Relaxed) {
+        let simulated = nexus_client
+            .tap()
+            .simulate_update_skill_from_artifact(agent_id, skill_id, &artifact)
+            .await
+            .map_err(NexusCliError::Nexus)?;
+
+        return report_dry_run(&nexus_client, &simulated).await;
+    }
+
     let result = nexus_client
         .tap()
         .update_skill_from_artifact(agent_id, skill_id, &artifact)
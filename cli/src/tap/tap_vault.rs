@@ -3,7 +3,9 @@ use super::*;
 pub(crate) async fn handle_vault_command(command: VaultCommand) -> AnyResult<(), NexusCliError> {
     match command {
         VaultCommand::Balance { alias, agent_id } => {
-            let conf = CliConf::load().await.unwrap_or_default();
+            let conf = CliConf::load_or_default()
+                .await
+                .map_err(NexusCliError::Any)?;
             let agent_id = agent_id_from_alias_or_arg(&conf, alias, agent_id)?;
             let nexus_client = get_nexus_client(None, DEFAULT_GAS_BUDGET).await?;
             ensure_cli_agent_owner(&nexus_client, agent_id).await?;
@@ -7,7 +7,9 @@ pub(crate) async fn deposit_agent_vault(
     sui_gas_coin: Option<sui::types::Address>,
     sui_gas_budget: u64,
 ) -> AnyResult<(), NexusCliError> {
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
     let agent_id = agent_id_from_alias_or_arg(&conf, alias, agent_id)?;
 
     command_title!("Depositing {amount} MIST into agent {agent_id} payment vault");
@@ -2,6 +2,7 @@ mod tool_auth;
 mod tool_claim_collateral;
 mod tool_configure_verifier;
 mod tool_inspect;
+mod tool_invoke;
 mod tool_list;
 mod tool_new;
 mod tool_register_offchain;
@@ -11,12 +12,14 @@ mod tool_unregister;
 mod tool_update_timeout;
 mod tool_validate;
 
+pub(crate) use tool_auth::read_signing_key_arg;
 use {
     crate::{prelude::*, tool::tool_update_timeout::update_tool_timeout},
     tool_auth::handle_tool_auth,
     tool_claim_collateral::*,
     tool_configure_verifier::configure_verifier,
     tool_inspect::inspect_tool,
+    tool_invoke::{invoke_tool, parse_tool_target, InvokeSigning, ToolTarget},
     tool_list::*,
     tool_new::*,
     tool_register_offchain::register_off_chain_tool,
@@ -474,6 +477,70 @@ pub(crate) enum ToolCommand {
         tool_fqn: ToolFqn,
     },
 
+    #[command(
+        about = "Invoke an offchain tool by URL or FQN and decode its output. Optionally signs the request as a leader and verifies the tool signature."
+    )]
+    Invoke {
+        #[arg(
+            help = "URL of the offchain tool or FQN of a registered offchain tool.",
+            value_name = "URL|FQN",
+            value_parser = ValueParser::from(parse_tool_target)
+        )]
+        tool: ToolTarget,
+
+        #[arg(
+            long = "input",
+            short = 'i',
+            help = "The tool input as a JSON object. It is validated against the input schema from the tool's /meta.",
+            value_parser = ValueParser::from(parse_json_string),
+            value_name = "JSON"
+        )]
+        input: serde_json::Value,
+
+        #[arg(
+            long = "signing-key",
+            short = 'k',
+            help = "Leader Ed25519 private key (hex/base64/base64url) OR a path to a file containing it. Signs the request and verifies the tool signature on the response.",
+            value_name = "KEY_OR_PATH"
+        )]
+        signing_key: Option<String>,
+
+        #[arg(
+            long = "leader-cap",
+            help = "Leader cap object ID to sign as; defaults to the leader cap owned by the active address",
+            value_name = "OBJECT_ID",
+            requires = "signing_key"
+        )]
+        leader_cap: Option<sui::types::Address>,
+
+        #[arg(
+            long = "leader-key-id",
+            help = "Registered key ID of the leader signing key.",
+            default_value = "0",
+            value_name = "KID",
+            requires = "signing_key"
+        )]
+        leader_key_id: u64,
+
+        #[arg(
+            long = "tool-public-key",
+            help = "Hex-encoded tool public key to verify the response with; defaults to the tool's active key registered on-chain",
+            value_name = "HEX",
+            requires = "signing_key"
+        )]
+        tool_public_key: Option<String>,
+
+        #[arg(
+            long = "repeat",
+            short = 'n',
+            help = "Invoke the tool N times and report latency statistics.",
+            default_value = "1",
+            value_name = "N",
+            value_parser = clap::value_parser!(u32).range(1..)
+        )]
+        repeat: u32,
+    },
+
     #[command(about = "Manage tool auth for signed HTTP.")]
     Auth {
         #[command(subcommand)]
@@ -509,6 +576,19 @@ pub(crate) enum ToolCommand {
     },
 }
 
+impl ToolCommand {
+    /// Whether the command can be simulated with `--dry-run`.
+    pub(crate) fn supports_dry_run(&self) -> bool {
+        matches!(
+            self,
+            ToolCommand::Register { .. }
+                | ToolCommand::Unregister { .. }
+                | ToolCommand::ClaimCollateral { .. }
+                | ToolCommand::SetInvocationCost { .. }
+        )
+    }
+}
+
 /// Handle the provided tool command. The [ToolCommand] instance is passed from
 /// [crate::main].
 pub(crate) async fn handle(command: ToolCommand) -> AnyResult<(), NexusCliError> {
@@ -628,6 +708,26 @@ pub(crate) async fn handle(command: ToolCommand) -> AnyResult<(), NexusCliError>
         // == `$ nexus tool inspect` ==
         ToolCommand::Inspect { tool_fqn } => inspect_tool(tool_fqn).await,
 
+        // == `$ nexus tool invoke` ==
+        ToolCommand::Invoke {
+            tool,
+            input,
+            signing_key,
+            leader_cap,
+            leader_key_id,
+            tool_public_key,
+            repeat,
+        } => {
+            let signing = signing_key.map(|signing_key| InvokeSigning {
+                signing_key,
+                leader_cap,
+                leader_key_id,
+                tool_public_key,
+            });
+
+            invoke_tool(tool, input, signing, repeat).await
+        }
+
         // == `$ nexus tool auth` ==
         ToolCommand::Auth { cmd } => handle_tool_auth(cmd).await,
 
@@ -88,7 +88,9 @@ async fn register_key(
 ) -> AnyResult<(), NexusCliError> {
     command_title!("Registering signed HTTP key for tool '{tool_fqn}'");
 
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
     let Some(owner_cap) = owner_cap.or(conf.tools.get(&tool_fqn).map(|t| t.over_tool)) else {
         return Err(NexusCliError::Any(anyhow!(
             "No OwnerCap<OverTool> object ID found for tool '{tool_fqn}'."
@@ -96,18 +98,7 @@ async fn register_key(
     };
 
     let key_handle = loading!("Parsing signing key...");
-    let signing_key_raw = signing_key.trim();
-    let path = PathBuf::from(signing_key_raw);
-    let signing_key_raw = if path.exists() {
-        std::fs::read_to_string(&path).map_err(|e| {
-            NexusCliError::Any(anyhow!(
-                "failed to read signing key file {}: {e}",
-                path.display()
-            ))
-        })?
-    } else {
-        signing_key_raw.to_string()
-    };
+    let signing_key_raw = read_signing_key_arg(&signing_key)?;
 
     let signing_key = parse_ed25519_signing_key(&signing_key_raw).map_err(|e| {
         NexusCliError::Any(anyhow!(
@@ -192,6 +183,24 @@ async fn register_key(
     Ok(())
 }
 
+/// Resolve a `--signing-key` argument that is either the key itself or a path
+/// to a file containing it.
+pub(crate) fn read_signing_key_arg(signing_key: &str) -> AnyResult<String, NexusCliError> {
+    let signing_key = signing_key.trim();
+    let path = PathBuf::from(signing_key);
+
+    if !path.exists() {
+        return Ok(signing_key.to_string());
+    }
+
+    std::fs::read_to_string(&path).map_err(|e| {
+        NexusCliError::Any(anyhow!(
+            "failed to read signing key file {}: {e}",
+            path.display()
+        ))
+    })
+}
+
 /// Query and display all registered message-signing keys for the given tool FQN.
 async fn list_keys(tool_fqn: ToolFqn) -> AnyResult<(), NexusCliError> {
     command_title!("Listing keys for tool '{tool_fqn}'");
@@ -380,7 +389,9 @@ async fn sync_allowed_leaders(
         )));
     }
 
-    let mut conf = CliConf::load().await.unwrap_or_default();
+    let mut conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
     let client = build_sui_grpc_client(&conf).await?;
     let rpc_url = client.lock().await.uri().to_string();
     let objects = get_nexus_objects(&mut conf).await?;
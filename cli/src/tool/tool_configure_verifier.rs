@@ -82,7 +82,9 @@ async fn resolve_owner_cap(
     tool_fqn: &ToolFqn,
     owner_cap: Option<sui::types::Address>,
 ) -> AnyResult<sui::types::Address, NexusCliError> {
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
     owner_cap
         .or_else(|| conf.tools.get(tool_fqn).map(|tool| tool.over_tool))
         .ok_or_else(|| {
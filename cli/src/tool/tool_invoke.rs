@@ -0,0 +1,654 @@
+use {
+    crate::{
+        command_title,
+        display::json_output,
+        item,
+        leader::resolve_leader_cap,
+        loading,
+        nexus_data_json::{json_to_nexus_data_map, nexus_data_to_json_value},
+        notify_success,
+        prelude::*,
+        sui::get_nexus_client,
+        tool::{
+            read_signing_key_arg,
+            tool_validate::{build_tool_http_client, validate_off_chain_tool_with_client},
+        },
+    },
+    nexus_sdk::{
+        move_bindings::primitives::{
+            data::{DataTypeHint, NexusData, TypedNexusData},
+            tagged_output::TaggedOutput,
+        },
+        nexus::{
+            client::NexusClient,
+            network_auth::NetworkAuthReader,
+            signed_tool_client::{SignedToolClient, ToolInvocation},
+        },
+        signed_http::keys::parse_ed25519_signing_key,
+        types::{Tool, ToolMeta},
+    },
+    serde_json::Value,
+    std::time::{Duration, Instant},
+};
+
+/// Vertex name used to derive invocation nonces for requests signed by the CLI.
+const CLI_INVOCATION_VERTEX: &str = "nexus-cli";
+
+/// Where the tool to invoke is served.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ToolTarget {
+    Url(reqwest::Url),
+    Fqn(ToolFqn),
+}
+
+pub(crate) fn parse_tool_target(target: &str) -> AnyResult<ToolTarget> {
+    if let Ok(url) = reqwest::Url::parse(target) {
+        if matches!(url.scheme(), "http" | "https") {
+            return Ok(ToolTarget::Url(url));
+        }
+    }
+
+    target
+        .parse::<ToolFqn>()
+        .map(ToolTarget::Fqn)
+        .map_err(|e| anyhow!("'{target}' is neither an HTTP(S) URL nor a tool FQN: {e}"))
+}
+
+/// Leader identity used to sign invoke requests.
+pub(crate) struct InvokeSigning {
+    pub(crate) signing_key: String,
+    pub(crate) leader_cap: Option<sui::types::Address>,
+    pub(crate) leader_key_id: u64,
+    pub(crate) tool_public_key: Option<String>,
+}
+
+/// Invoke an off-chain tool, optionally signing the request as a leader, and
+/// print the decoded output.
+pub(crate) async fn invoke_tool(
+    target: ToolTarget,
+    input: Value,
+    signing: Option<InvokeSigning>,
+    repeat: u32,
+) -> AnyResult<(), NexusCliError> {
+    let http = build_tool_http_client()?;
+
+    let mut nexus_client = None;
+
+    let url = match target {
+        ToolTarget::Url(url) => url,
+        ToolTarget::Fqn(fqn) => {
+            let client = get_nexus_client(None, DEFAULT_GAS_BUDGET).await?;
+            let url = resolve_tool_url(&client, &fqn).await?;
+
+            nexus_client = Some(client);
+
+            url
+        }
+    };
+
+    let meta = validate_off_chain_tool_with_client(url.clone(), &http).await?;
+
+    command_title!("Invoking tool '{fqn}' at '{url}'", fqn = meta.fqn);
+
+    let input_handle = loading!("Validating input against the tool input schema...");
+
+    if let Err(e) = validate_input(&meta, &input) {
+        input_handle.error();
+
+        return Err(NexusCliError::Any(e));
+    }
+
+    input_handle.success();
+
+    let signer = match signing {
+        Some(signing) => {
+            let nexus_client = match nexus_client {
+                Some(client) => client,
+                None => get_nexus_client(None, DEFAULT_GAS_BUDGET).await?,
+            };
+
+            Some(InvokeSigner::new(&nexus_client, &meta, signing, http.clone()).await?)
+        }
+        None => None,
+    };
+
+    let invoke_url = invoke_url(&url)?;
+    let inputs = json_to_nexus_data_map(&input, &[], None).map_err(NexusCliError::Any)?;
+    let mut latencies = Vec::with_capacity(repeat as usize);
+    let mut output = None;
+
+    for iteration in 0..repeat {
+        let current = iteration + 1;
+        let handle = loading!("Invoking tool ({current}/{repeat})...");
+        let started = Instant::now();
+
+        let result = match &signer {
+            Some(signer) => signer.invoke(&url, &inputs, iteration).await,
+            None => invoke_unsigned(&http, &invoke_url, &input).await,
+        };
+
+        match result {
+            Ok(result) => {
+                handle.success();
+
+                latencies.push(started.elapsed());
+                output = Some(result);
+            }
+            Err(e) => {
+                handle.error();
+
+                return Err(e);
+            }
+        }
+    }
+
+    let output = tagged_output_json(&output.expect("repeat is at least 1"))?;
+
+    notify_success!(
+        "Tool responded with variant '{variant}'{verified}",
+        variant = output["variant"]
+            .as_str()
+            .unwrap_or_default()
+            .truecolor(100, 100, 100),
+        verified = if signer.is_some() {
+            " and a valid signature"
+        } else {
+            ""
+        }
+    );
+
+    if let Some(ports) = output["ports"].as_object() {
+        for (port, value) in ports {
+            item!(
+                "{port}: {value}",
+                value = value.to_string().truecolor(100, 100, 100)
+            );
+        }
+    }
+
+    let latency = latency_summary(&latencies);
+
+    if latencies.len() > 1 {
+        item!(
+            "Latency over {count} invocations: min {min}ms, p50 {p50}ms, mean {mean}ms, max {max}ms",
+            count = latencies.len(),
+            min = latency["min_ms"],
+            p50 = latency["p50_ms"],
+            mean = latency["mean_ms"],
+            max = latency["max_ms"],
+        );
+    }
+
+    json_output(&json!({
+        "url": url,
+        "fqn": meta.fqn,
+        "signed": signer.is_some(),
+        "output": output,
+        "latency": latency,
+    }))
+}
+
+/// Look up the URL of a registered off-chain tool.
+async fn resolve_tool_url(
+    nexus_client: &NexusClient,
+    fqn: &ToolFqn,
+) -> AnyResult<reqwest::Url, NexusCliError> {
+    let handle = loading!("Resolving URL of tool '{fqn}'...");
+
+    let inspection = match nexus_client.tool().inspect_tool(fqn).await {
+        Ok(inspection) => inspection,
+        Err(e) => {
+            handle.error();
+
+            return Err(NexusCliError::Nexus(e));
+        }
+    };
+
+    let url = inspection
+        .tool
+        .as_ref()
+        .ok_or_else(|| anyhow!("Tool '{fqn}' is not registered"))
+        .and_then(|tool| tool.reference().http_url_string())
+        .and_then(|url| url.ok_or_else(|| anyhow!("Tool '{fqn}' is not an off-chain tool")))
+        .and_then(|url| Ok(reqwest::Url::parse(&url)?));
+
+    match url {
+        Ok(url) => {
+            handle.success();
+
+            Ok(url)
+        }
+        Err(e) => {
+            handle.error();
+
+            Err(NexusCliError::Any(e))
+        }
+    }
+}
+
+fn validate_input(meta: &ToolMeta, input: &Value) -> AnyResult<()> {
+    let schema = serde_json::from_slice::<Value>(&meta.input_schema)
+        .map_err(|e| anyhow!("Tool input schema is not valid JSON: {e}"))?;
+
+    jsonschema::draft202012::validate(&schema, input)
+        .map_err(|e| anyhow!("Input does not match the tool input schema: {e}"))
+}
+
+fn invoke_url(url: &reqwest::Url) -> AnyResult<reqwest::Url, NexusCliError> {
+    let mut invoke_url = url.clone();
+
+    invoke_url
+        .path_segments_mut()
+        .map_err(|_| NexusCliError::Any(anyhow!("Tool URL '{url}' cannot be used as a base URL")))?
+        .pop_if_empty()
+        .push("invoke");
+
+    Ok(invoke_url)
+}
+
+async fn invoke_unsigned(
+    http: &reqwest::Client,
+    invoke_url: &reqwest::Url,
+    input: &Value,
+) -> AnyResult<TaggedOutput, NexusCliError> {
+    let response = http
+        .post(invoke_url.clone())
+        .json(input)
+        .send()
+        .await
+        .map_err(NexusCliError::Http)?;
+
+    let status = response.status();
+    let body = response.bytes().await.map_err(NexusCliError::Http)?;
+
+    if !status.is_success() {
+        return Err(NexusCliError::Any(anyhow!(
+            "Tool responded with {status}: {}",
+            String::from_utf8_lossy(&body)
+        )));
+    }
+
+    bcs::from_bytes(&body)
+        .map_err(|e| NexusCliError::Any(anyhow!("Failed to decode tool result: {e}")))
+}
+
+/// Signs requests as a leader and verifies the tool signature on responses.
+struct InvokeSigner {
+    client: SignedToolClient,
+    tool_public_key: [u8; 32],
+    /// Random execution ID so that nonces never collide with real invocations.
+    execution_id: sui::types::Address,
+}
+
+impl InvokeSigner {
+    async fn new(
+        nexus_client: &NexusClient,
+        meta: &ToolMeta,
+        signing: InvokeSigning,
+        http: reqwest::Client,
+    ) -> AnyResult<Self, NexusCliError> {
+        let signing_key = parse_ed25519_signing_key(&read_signing_key_arg(&signing.signing_key)?)
+            .map_err(|e| {
+            NexusCliError::Any(anyhow!(
+                "invalid signing key (expected hex/base64/base64url or a file containing it): {e}"
+            ))
+        })?;
+
+        let leader_cap = resolve_leader_cap(nexus_client, signing.leader_cap).await?;
+        let objects = nexus_client.get_nexus_objects();
+        let reader = NetworkAuthReader::from_rpc_url(
+            nexus_client.rpc_url(),
+            objects.registry_pkg_id,
+            *objects.network_auth.object_id(),
+        )
+        .map_err(NexusCliError::Nexus)?;
+        let client = SignedToolClient::new(reader, leader_cap, signing.leader_key_id, signing_key)
+            .with_http_client(http);
+
+        let tool_public_key = match signing.tool_public_key {
+            Some(public_key) => parse_public_key(&public_key).map_err(NexusCliError::Any)?,
+            None => {
+                let handle = loading!("Fetching tool message-signing key...");
+
+                let tool_id = Tool::derive_id(*objects.tool_registry.object_id(), &meta.fqn)
+                    .map_err(NexusCliError::Any)?;
+
+                match client.tool_public_key(tool_id).await {
+                    Ok(public_key) => {
+                        handle.success();
+
+                        public_key
+                    }
+                    Err(e) => {
+                        handle.error();
+
+                        return Err(NexusCliError::Any(anyhow!(e)));
+                    }
+                }
+            }
+        };
+
+        Ok(Self {
+            client,
+            tool_public_key,
+            execution_id: sui::types::Address::generate(&mut rand::thread_rng()),
+        })
+    }
+
+    async fn invoke(
+        &self,
+        url: &reqwest::Url,
+        inputs: &HashMap<String, NexusData>,
+        iteration: u32,
+    ) -> AnyResult<TaggedOutput, NexusCliError> {
+        // Every repetition gets its own nonce so the tool does not answer from
+        // its replay cache.
+        let invocation = ToolInvocation {
+            execution_id: self.execution_id,
+            walk_index: 0,
+            vertex_name: CLI_INVOCATION_VERTEX.to_string(),
+            iteration: iteration as u64,
+        };
+
+        self.client
+            .invoke_with_tool_key(url, self.tool_public_key, &invocation, inputs)
+            .await
+            .map(|response| response.output)
+            .map_err(|e| NexusCliError::Any(anyhow!(e)))
+    }
+}
+
+fn parse_public_key(public_key: &str) -> AnyResult<[u8; 32]> {
+    hex::decode(public_key.trim().trim_start_matches("0x"))?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| {
+            anyhow!(
+                "tool public key must be 32 bytes, got {len}",
+                len = bytes.len()
+            )
+        })
+}
+
+/// Decode a [`TaggedOutput`] into `{"variant": ..., "ports": {...}}`.
+pub(crate) fn tagged_output_json(output: &TaggedOutput) -> AnyResult<Value, NexusCliError> {
+    let variant = String::from_utf8(output.tag.clone())
+        .map_err(|e| NexusCliError::Any(anyhow!("Tool output variant is not UTF-8: {e}")))?;
+
+    let ports = output
+        .named_payload
+        .contents
+        .iter()
+        .map(|entry| {
+            let port = String::from_utf8(entry.key.clone())
+                .map_err(|e| anyhow!("Tool output port name is not UTF-8: {e}"))?;
+
+            Ok((port, typed_nexus_data_json(&entry.value)?))
+        })
+        .collect::<AnyResult<serde_json::Map<_, _>>>()
+        .map_err(NexusCliError::Any)?;
+
+    Ok(json!({ "variant": variant, "ports": ports }))
+}
+
+/// Strings are stored as raw UTF-8, everything else as JSON. Walrus payloads
+/// are shown as storage references.
+fn typed_nexus_data_json(value: &TypedNexusData) -> AnyResult<Value> {
+    let data = &value.data;
+
+    let json = if value.type_hint == DataTypeHint::String && data.is_inline() {
+        let string = |bytes: &[u8]| Value::String(String::from_utf8_lossy(bytes).into_owned());
+
+        if data.many.is_empty() {
+            string(&data.one)
+        } else {
+            Value::Array(data.many.iter().map(|bytes| string(bytes)).collect())
+        }
+    } else {
+        nexus_data_to_json_value(data)?
+    };
+
+    if data.is_walrus() {
+        return Ok(json!({ "storage": "walrus", "data": json }));
+    }
+
+    Ok(json)
+}
+
+fn latency_summary(latencies: &[Duration]) -> Value {
+    let mut millis = latencies
+        .iter()
+        .map(|latency| latency.as_secs_f64() * 1000.0)
+        .collect::<Vec<_>>();
+
+    millis.sort_by(f64::total_cmp);
+
+    let round = |value: f64| (value * 100.0).round() / 100.0;
+    let count = millis.len();
+
+    if count == 0 {
+        return json!({ "samples_ms": [] });
+    }
+
+    json!({
+        "samples_ms": latencies.iter().map(|latency| round(latency.as_secs_f64() * 1000.0)).collect::<Vec<_>>(),
+        "min_ms": round(millis[0]),
+        "p50_ms": round(millis[(count - 1) / 2]),
+        "mean_ms": round(millis.iter().sum::<f64>() / count as f64),
+        "max_ms": round(millis[count - 1]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        nexus_sdk::move_bindings::sui_framework::vec_map::{Entry as VecMapEntry, VecMap},
+        nexus_toolkit::*,
+        schemars::JsonSchema,
+        warp::http::StatusCode,
+    };
+
+    #[derive(Debug, Deserialize, JsonSchema)]
+    struct Input {
+        a: i64,
+        b: i64,
+    }
+
+    #[derive(Debug, Serialize, JsonSchema)]
+    enum Output {
+        Ok { sum: i64 },
+    }
+
+    struct AddTool;
+
+    impl NexusTool for AddTool {
+        type Input = Input;
+        type Output = Output;
+
+        async fn new() -> Self {
+            Self
+        }
+
+        fn fqn() -> ToolFqn {
+            fqn!("xyz.dummy.add@1")
+        }
+
+        async fn health(&self) -> AnyResult<StatusCode> {
+            Ok(StatusCode::OK)
+        }
+
+        async fn invoke(&self, Self::Input { a, b }: Self::Input) -> Self::Output {
+            Self::Output::Ok { sum: a + b }
+        }
+    }
+
+    #[test]
+    fn parses_invoke_target_and_flags() {
+        let cli = crate::Cli::try_parse_from([
+            "nexus",
+            "tool",
+            "invoke",
+            "xyz.dummy.add@1",
+            "--input",
+            r#"{"a":1,"b":2}"#,
+            "--signing-key",
+            "key.hex",
+            "--repeat",
+            "3",
+        ])
+        .expect("tool invoke should parse");
+
+        let crate::Command::Tool(crate::tool::ToolCommand::Invoke {
+            tool,
+            input,
+            signing_key,
+            repeat,
+            ..
+        }) = cli.command
+        else {
+            panic!("expected tool invoke command");
+        };
+
+        assert_eq!(tool, ToolTarget::Fqn(fqn!("xyz.dummy.add@1")));
+        assert_eq!(input, json!({"a": 1, "b": 2}));
+        assert_eq!(signing_key.as_deref(), Some("key.hex"));
+        assert_eq!(repeat, 3);
+
+        assert_eq!(
+            parse_tool_target("http://localhost:8080/add").unwrap(),
+            ToolTarget::Url(reqwest::Url::parse("http://localhost:8080/add").unwrap())
+        );
+        assert!(parse_tool_target("not a tool").is_err());
+        assert!(crate::Cli::try_parse_from([
+            "nexus",
+            "tool",
+            "invoke",
+            "xyz.dummy.add@1",
+            "--input",
+            "{}",
+            "--repeat",
+            "0",
+        ])
+        .is_err());
+        assert!(crate::Cli::try_parse_from([
+            "nexus",
+            "tool",
+            "invoke",
+            "xyz.dummy.add@1",
+            "--input",
+            "{}",
+            "--leader-key-id",
+            "1",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn tagged_output_decodes_string_json_and_walrus_ports() {
+        let entry = |key: &str, type_hint, data| VecMapEntry {
+            key: key.as_bytes().to_vec(),
+            value: TypedNexusData { type_hint, data },
+        };
+        let output = TaggedOutput {
+            tag: b"Ok".to_vec(),
+            named_payload: VecMap {
+                contents: vec![
+                    entry(
+                        "message",
+                        DataTypeHint::String,
+                        NexusData::inline_one(b"42".to_vec()),
+                    ),
+                    entry(
+                        "numbers",
+                        DataTypeHint::Number,
+                        NexusData::inline_many([b"1".to_vec(), b"2".to_vec()]),
+                    ),
+                    entry(
+                        "report",
+                        DataTypeHint::Raw,
+                        NexusData::walrus_one(b"blob-id".to_vec()),
+                    ),
+                ],
+            },
+        };
+
+        assert_eq!(
+            tagged_output_json(&output).unwrap(),
+            json!({
+                "variant": "Ok",
+                "ports": {
+                    "message": "42",
+                    "numbers": [1, 2],
+                    "report": { "storage": "walrus", "data": "blob-id" },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn tagged_output_rejects_non_utf8_json_ports() {
+        let output = TaggedOutput {
+            tag: b"Ok".to_vec(),
+            named_payload: VecMap {
+                contents: vec![VecMapEntry {
+                    key: b"bytes".to_vec(),
+                    value: TypedNexusData {
+                        type_hint: DataTypeHint::Raw,
+                        data: NexusData::inline_one(vec![0xff, 0xfe]),
+                    },
+                }],
+            },
+        };
+
+        let err = tagged_output_json(&output).unwrap_err();
+
+        assert!(err.to_string().contains("must be UTF-8"));
+    }
+
+    #[test]
+    fn latency_summary_reports_order_statistics() {
+        let summary = latency_summary(&[
+            Duration::from_millis(30),
+            Duration::from_millis(10),
+            Duration::from_millis(20),
+        ]);
+
+        assert_eq!(summary["samples_ms"], json!([30.0, 10.0, 20.0]));
+        assert_eq!(summary["min_ms"], json!(10.0));
+        assert_eq!(summary["p50_ms"], json!(20.0));
+        assert_eq!(summary["mean_ms"], json!(20.0));
+        assert_eq!(summary["max_ms"], json!(30.0));
+    }
+
+    #[tokio::test]
+    async fn test_invoke_unsigned_tool_validates_input_and_decodes_output() {
+        tokio::spawn(async move { bootstrap!(([127, 0, 0, 1], 8049), [AddTool]) });
+
+        // Give the webserver some time to start.
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let url = reqwest::Url::parse("http://localhost:8049").unwrap();
+
+        let result = invoke_tool(
+            ToolTarget::Url(url.clone()),
+            json!({"a": 1, "b": 2}),
+            None,
+            2,
+        )
+        .await;
+        assert!(result.is_ok());
+
+        let http = build_tool_http_client().unwrap();
+        let output = invoke_unsigned(&http, &invoke_url(&url).unwrap(), &json!({"a": 1, "b": 2}))
+            .await
+            .unwrap();
+        assert_eq!(
+            tagged_output_json(&output).unwrap(),
+            json!({ "variant": "Ok", "ports": { "sum": 3 } })
+        );
+
+        // Input that does not match the schema is rejected before invoking.
+        let result = invoke_tool(ToolTarget::Url(url), json!({"a": "one"}), None, 1).await;
+        assert!(matches!(result, Err(NexusCliError::Any(_))));
+    }
+}
@@ -77,9 +77,10 @@ fn load_meta_from_source(
 
 /// Register a single tool from its already-validated `ToolMeta`.
 ///
-/// Handles the "already registered" and "registration error" cases as non-fatal
-/// results so that batch mode can continue to the next tool. Fatal errors
-/// (e.g. missing OwnerCap in the response) are returned as `Err`.
+/// Handles the "already registered" abort and non-abort submission errors as
+/// non-fatal results so that batch mode can continue to the next tool. Any
+/// other Move abort and fatal errors (e.g. missing OwnerCap in the response)
+/// are returned as `Err`.
 ///
 /// On success, returns the JSON result and optionally the `(ToolFqn, ToolOwnerCaps)`
 /// pair for the caller to persist in `CliConf`.
@@ -122,12 +123,33 @@ async fn register_one_tool(
 
     tx_handle.success();
 
+    // Each tool is registered in its own transaction, so every one of them
+    // is simulated on its own in a dry run.
+    if DRY_RUN.load(Ordering::Relaxed) {
+        let simulated = nexus_client
+            .simulate_transaction(tx, address)
+            .await
+            .map_err(NexusCliError::Nexus)?;
+
+        print_dry_run(&simulated);
+
+        let mut result = dry_run_json(&simulated);
+        result["tool_fqn"] = json!(meta.fqn);
+
+        return Ok((result, None));
+    }
+
     // Sign and submit the TX.
     let response = match nexus_client.submit_transaction(tx, address).await {
         Ok(response) => response,
         // If the tool is already registered, treat as a non-fatal result so
         // batch mode can continue to the next tool.
-        Err(NexusError::Wallet(e)) if e.to_string().contains("register_off_chain_tool_") => {
+        Err(e @ NexusError::MoveAbort { .. })
+            if e.move_abort().is_some_and(|abort| {
+                abort.module == "tool_registry"
+                    && abort.name.as_deref() == Some("EToolAlreadyRegistered")
+            }) =>
+        {
             notify_error!(
                 "Tool '{fqn}' is already registered.",
                 fqn = meta.fqn.to_string().truecolor(100, 100, 100)
@@ -141,7 +163,10 @@ async fn register_one_tool(
                 None,
             ));
         }
-        // Any other error is also non-fatal for batch mode.
+        // Any other Move abort is a real failure and is surfaced with its
+        // hint.
+        Err(e @ NexusError::MoveAbort { .. }) => return Err(NexusCliError::Nexus(e)),
+        // Any other error is non-fatal for batch mode.
         Err(e) => {
             notify_error!(
                 "Failed to register tool '{fqn}': {error}",
@@ -275,7 +300,9 @@ pub(crate) async fn register_off_chain_tool(
     sui_gas_coin: Option<sui::types::Address>,
     sui_gas_budget: u64,
 ) -> AnyResult<(), NexusCliError> {
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
     let client = build_sui_grpc_client(&conf).await?;
     let pk = get_signing_key(&conf).await?;
     let owner = pk.public_key().derive_address();
@@ -283,6 +310,7 @@ pub(crate) async fn register_off_chain_tool(
 
     let mut registration_results = Vec::new();
     let mut caps_to_save: Vec<(ToolFqn, ToolOwnerCaps)> = Vec::new();
+    let mut failure = None;
 
     if let Some(meta_source) = from_meta {
         // Load metadata from file/stdin without hitting a live HTTP endpoint.
@@ -341,7 +369,7 @@ pub(crate) async fn register_off_chain_tool(
                 url = meta.url
             );
 
-            let (result, caps) = register_one_tool(
+            match register_one_tool(
                 meta,
                 &nexus_client,
                 client.clone(),
@@ -349,10 +377,20 @@ pub(crate) async fn register_off_chain_tool(
                 collateral_coin,
                 invocation_cost,
             )
-            .await?;
-
-            registration_results.push(result);
-            caps_to_save.extend(caps);
+            .await
+            {
+                Ok((result, caps)) => {
+                    registration_results.push(result);
+                    caps_to_save.extend(caps);
+                }
+                // Stop the batch but still persist the caps of the tools
+                // registered so far.
+                Err(e) => {
+                    failure = Some(e);
+
+                    break;
+                }
+            }
         }
     }
 
@@ -360,7 +398,9 @@ pub(crate) async fn register_off_chain_tool(
     if !no_save && !caps_to_save.is_empty() {
         let save_handle = loading!("Saving the owner caps to the CLI configuration...");
 
-        let mut conf = CliConf::load().await.unwrap_or_default();
+        let mut conf = CliConf::load_or_default()
+            .await
+            .map_err(NexusCliError::Any)?;
         for (fqn, caps) in caps_to_save {
             conf.tools.insert(fqn, caps);
         }
@@ -373,6 +413,10 @@ pub(crate) async fn register_off_chain_tool(
         save_handle.success();
     }
 
+    if let Some(e) = failure {
+        return Err(e);
+    }
+
     json_output(&registration_results)?;
 
     Ok(())
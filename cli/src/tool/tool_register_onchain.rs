@@ -62,7 +62,9 @@ pub(crate) async fn register_onchain_tool(
     let signer = nexus_client.signer();
     let address = signer.get_active_address();
     let nexus_objects = &*nexus_client.get_nexus_objects();
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
     let client = build_sui_grpc_client(&conf).await?;
 
     let collateral_coin = fetch_coin_by_type(
@@ -103,12 +105,21 @@ pub(crate) async fn register_onchain_tool(
 
     tx_handle.success();
 
+    if DRY_RUN.load(Ordering::Relaxed) {
+        return simulate_and_report(&nexus_client, tx, address).await;
+    }
+
     // Sign and submit the TX.
     let response = match nexus_client.submit_transaction(tx, address).await {
         Ok(response) => response,
         // If the tool is already registered, we don't want to fail the
         // command.
-        Err(NexusError::Wallet(e)) if e.to_string().contains("register_tool_") => {
+        Err(e @ NexusError::MoveAbort { .. })
+            if e.move_abort().is_some_and(|abort| {
+                abort.module == "tool_registry"
+                    && abort.name.as_deref() == Some("EToolAlreadyRegistered")
+            }) =>
+        {
             notify_error!(
                 "Tool '{fqn}' is already registered.",
                 fqn = fqn.to_string().truecolor(100, 100, 100)
@@ -119,10 +130,9 @@ pub(crate) async fn register_onchain_tool(
                 "already_registered": true,
             }))?;
 
-            return Err(NexusCliError::Any(e));
+            return Err(NexusCliError::Nexus(e));
         }
-        // Any other error fails the tool registration but continues the
-        // loop.
+        // Any other error fails the tool registration.
         Err(e) => {
             notify_error!(
                 "Failed to register tool '{fqn}': {error}",
@@ -295,7 +305,9 @@ async fn save_tool_owner_caps(
 ) -> AnyResult<(), NexusCliError> {
     let save_handle = loading!("Saving the owner caps to the CLI configuration...");
 
-    let mut conf = CliConf::load().await.unwrap_or_default();
+    let mut conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
 
     conf.tools.insert(
         fqn,
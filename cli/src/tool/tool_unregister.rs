@@ -33,7 +33,9 @@ pub(crate) async fn unregister_tool(
     let nexus_objects = &*nexus_client.get_nexus_objects();
     let crawler = nexus_client.crawler();
 
-    let conf = CliConf::load().await.unwrap_or_default();
+    let conf = CliConf::load_or_default()
+        .await
+        .map_err(NexusCliError::Any)?;
 
     // Use the provided or saved `owner_cap` object ID and fetch the object.
     let Some(owner_cap) = owner_cap.or(conf.tools.get(&tool_fqn).map(|t| t.over_tool)) else {
@@ -84,6 +86,12 @@ pub(crate) async fn unregister_tool(
         }
     };
 
+    if DRY_RUN.load(Ordering::Relaxed) {
+        tx_handle.success();
+
+        return simulate_and_report(&nexus_client, tx, address).await;
+    }
+
     let response = match nexus_client.submit_transaction(tx, address).await {
         Ok(response) => response,
         Err(e) => {
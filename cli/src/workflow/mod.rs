@@ -103,6 +103,7 @@ mod tests {
             walrus_publisher_url: Some(server_url.clone()),
             walrus_aggregator_url: Some(server_url),
             walrus_save_for_epochs: Some(2),
+            ..Default::default()
         };
 
         Ok((server, storage_conf))
@@ -137,10 +138,10 @@ mod tests {
         let port2 = vertex.get("port2").expect("port2 missing");
 
         assert!(port1.is_inline());
-        assert_eq!(nexus_data_to_json_value(port1), json!("value1"));
+        assert_eq!(nexus_data_to_json_value(port1).unwrap(), json!("value1"));
 
         assert!(port2.is_inline());
-        assert_eq!(nexus_data_to_json_value(port2), json!("value2"));
+        assert_eq!(nexus_data_to_json_value(port2).unwrap(), json!("value2"));
     }
 
     #[tokio::test]
@@ -196,6 +197,51 @@ mod tests {
         mock_put.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn process_entry_ports_remote_local_storage() {
+        let input = json!({
+            "vertex1": {
+                "port1": ["a", "b"],
+            }
+        });
+        let dir = tempfile::tempdir().expect("tempdir must be created");
+        let storage_conf = StorageConf::try_from(DataStorageConf {
+            local_path: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        })
+        .expect("storage conf must be valid");
+        let remote = vec!["vertex1.port1".to_string()];
+
+        let result = process_entry_ports(&input, Some(StorageKind::Local), &remote)
+            .await
+            .expect("Should succeed");
+
+        let committed = result
+            .get("vertex1")
+            .expect("vertex1 missing")
+            .clone()
+            .commit_all(&storage_conf)
+            .await
+            .expect("commit_all failed");
+        let port1 = committed
+            .clone()
+            .into_map()
+            .remove("port1")
+            .expect("port1 missing");
+        assert_eq!(port1.storage_tag(), b"local");
+        assert_eq!(port1.many.len(), 2);
+
+        let fetched = committed
+            .fetch_all(&storage_conf)
+            .await
+            .expect("fetch_all failed")
+            .into_map();
+        assert_eq!(
+            nexus_data_to_json_value(&fetched["port1"]).unwrap(),
+            json!(["a", "b"])
+        );
+    }
+
     #[tokio::test]
     async fn process_entry_ports_missing_remote_hint() {
         let input = json!({
@@ -0,0 +1,515 @@
+//! Fluent builder for authoring a [`DagSpec`] directly in Rust.
+//!
+//! Ports are referenced with the same dotted paths used in DAG docs and error
+//! messages: input ports as `vertex.port` and output ports as
+//! `vertex.variant.port`. Mistakes such as malformed paths, duplicate vertices
+//! or references to vertices that were never declared are collected while
+//! chaining and reported by [`DagBuilder::build`], which also runs
+//! [`crate::dag::validator::validate`] on the result.
+//!
+//! # Examples
+//!
+//! ```
+//! use nexus_sdk::{dag::builder::DagBuilder, fqn};
+//!
+//! let dag = DagBuilder::new()
+//!     .vertex("a", fqn!("xyz.taluslabs.math.i64.add@1"))
+//!     .vertex("b", fqn!("xyz.taluslabs.math.i64.mul@1"))
+//!     .entry_port("a.a")
+//!     .default_value("a.b", 1)
+//!     .edge("a.ok.result", "b.a")
+//!     .default_value("b.b", 7)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(dag.vertices.len(), 2);
+//! assert_eq!(dag.edges.len(), 1);
+//! ```
+
+use {
+    crate::{
+        dag::validator::validate,
+        move_bindings::{
+            interface::{
+                graph::{EdgeKind, PostFailureAction},
+                verifier::ToolVerifierMode,
+            },
+            primitives::data::NexusData,
+        },
+        types::{
+            DagDefaultValue,
+            DagEdge,
+            DagEntryGroup,
+            DagEntryPort,
+            DagInput,
+            DagOutput,
+            DagSpec,
+            DagVertex,
+            DagVertexKind,
+        },
+        ToolFqn,
+    },
+    anyhow::{anyhow, bail, Result as AnyResult},
+    serde::Serialize,
+    std::collections::HashSet,
+};
+
+/// Typed builder for [`DagSpec`]. See the [module docs](self) for an example.
+#[derive(Clone, Debug, Default)]
+pub struct DagBuilder {
+    dag: DagSpec,
+    errors: Vec<String>,
+    /// Per-vertex settings, resolved against the declared vertices in
+    /// [`DagBuilder::build`] so that vertices can be declared in any order.
+    entry_ports: Vec<DagInput>,
+    post_failure_actions: Vec<(String, PostFailureAction)>,
+    verifiers: Vec<(String, ToolVerifierMode)>,
+}
+
+impl DagBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an off-chain vertex invoking the tool with the given FQN.
+    pub fn vertex(self, name: impl Into<String>, tool_fqn: ToolFqn) -> Self {
+        self.push_vertex(name.into(), DagVertexKind::OffChain { tool_fqn })
+    }
+
+    /// Add an on-chain vertex invoking the tool with the given FQN.
+    pub fn onchain_vertex(self, name: impl Into<String>, tool_fqn: ToolFqn) -> Self {
+        self.push_vertex(name.into(), DagVertexKind::OnChain { tool_fqn })
+    }
+
+    /// Mark `vertex.port` as an entry port provided when the DAG is executed.
+    pub fn entry_port(mut self, input: &str) -> Self {
+        if let Some(input) = self.parse_input(input) {
+            self.entry_ports.push(input);
+        }
+
+        self
+    }
+
+    /// Connect `vertex.variant.port` to `vertex.port` with a normal edge.
+    pub fn edge(self, from: &str, to: &str) -> Self {
+        self.push_edge(from, to, EdgeKind::Normal)
+    }
+
+    /// Connect an output port to an input port with a for-each edge.
+    pub fn for_each(self, from: &str, to: &str) -> Self {
+        self.push_edge(from, to, EdgeKind::ForEach)
+    }
+
+    /// Connect an output port to an input port with a collect edge.
+    pub fn collect(self, from: &str, to: &str) -> Self {
+        self.push_edge(from, to, EdgeKind::Collect)
+    }
+
+    /// Connect an output port to an input port with a do-while edge.
+    pub fn do_while(self, from: &str, to: &str) -> Self {
+        self.push_edge(from, to, EdgeKind::DoWhile)
+    }
+
+    /// Connect an output port to an input port with a break edge.
+    pub fn break_loop(self, from: &str, to: &str) -> Self {
+        self.push_edge(from, to, EdgeKind::Break)
+    }
+
+    /// Connect an output port to an input port with a static edge.
+    pub fn static_edge(self, from: &str, to: &str) -> Self {
+        self.push_edge(from, to, EdgeKind::Static)
+    }
+
+    /// Set an inline default value for `vertex.port`. Arrays are stored as
+    /// `many` payloads, the same way the JSON parser treats them.
+    pub fn default_value(mut self, input: &str, value: impl Serialize) -> Self {
+        let value = serde_json::to_value(value).and_then(|value| match value {
+            serde_json::Value::Array(values) => values
+                .iter()
+                .map(serde_json::to_vec)
+                .collect::<Result<Vec<_>, _>>()
+                .map(NexusData::inline_many),
+            value => serde_json::to_vec(&value).map(NexusData::inline_one),
+        });
+
+        match value {
+            Ok(value) => self.default_data(input, value),
+            Err(e) => {
+                self.errors.push(format!(
+                    "Default value for '{input}' is not valid JSON: {e}"
+                ));
+
+                self
+            }
+        }
+    }
+
+    /// Set a default value for `vertex.port` from raw [`NexusData`], for
+    /// example to reference data that is already stored remotely.
+    pub fn default_data(mut self, input: &str, value: NexusData) -> Self {
+        if let Some(input) = self.parse_input(input) {
+            self.dag.default_values.push(DagDefaultValue {
+                vertex: input.vertex,
+                input_port: input.input_port,
+                value,
+            });
+        }
+
+        self
+    }
+
+    /// Add a named entry group containing the given vertices.
+    pub fn entry_group<I, S>(mut self, name: impl Into<String>, vertices: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.dag.entry_groups.push(DagEntryGroup {
+            name: name.into(),
+            vertices: vertices.into_iter().map(Into::into).collect(),
+        });
+
+        self
+    }
+
+    /// Mark `vertex.variant.port` as a DAG output.
+    pub fn output(mut self, output: &str) -> Self {
+        if let Some(output) = self.parse_output(output) {
+            self.dag.outputs.push(output);
+        }
+
+        self
+    }
+
+    /// Set the DAG-wide post failure action.
+    pub fn post_failure_action(mut self, action: PostFailureAction) -> Self {
+        self.dag.post_failure_action = Some(action);
+
+        self
+    }
+
+    /// Override the post failure action of a single vertex.
+    pub fn vertex_post_failure_action(mut self, vertex: &str, action: PostFailureAction) -> Self {
+        self.post_failure_actions.push((vertex.to_string(), action));
+
+        self
+    }
+
+    /// Configure the verifier mode of an off-chain vertex.
+    pub fn verifier(mut self, vertex: &str, mode: ToolVerifierMode) -> Self {
+        self.verifiers.push((vertex.to_string(), mode));
+
+        self
+    }
+
+    /// Check that every reference points to a declared vertex and validate
+    /// the resulting DAG with [`validate`].
+    pub fn build(mut self) -> AnyResult<DagSpec> {
+        if let Some(error) = self.errors.first() {
+            bail!("{error}");
+        }
+
+        let mut names = HashSet::new();
+
+        for vertex in &self.dag.vertices {
+            if !names.insert(vertex.name.as_str()) {
+                bail!("Vertex '{}' is declared multiple times.", vertex.name);
+            }
+        }
+
+        for input in std::mem::take(&mut self.entry_ports) {
+            let Some(vertex) = self.vertex_mut(&input.vertex) else {
+                bail!(
+                    "Entry port '{}.{}' references an undeclared vertex '{}'.",
+                    input.vertex,
+                    input.input_port,
+                    input.vertex
+                );
+            };
+
+            vertex.entry_ports.push(DagEntryPort {
+                name: input.input_port,
+            });
+        }
+
+        for (name, action) in std::mem::take(&mut self.post_failure_actions) {
+            let Some(vertex) = self.vertex_mut(&name) else {
+                bail!("Post failure action references an undeclared vertex '{name}'.");
+            };
+
+            vertex.post_failure_action = Some(action);
+        }
+
+        for (name, mode) in std::mem::take(&mut self.verifiers) {
+            match self.vertex_mut(&name) {
+                Some(DagVertex {
+                    kind: DagVertexKind::OnChain { .. },
+                    ..
+                }) => {
+                    bail!("Vertex '{name}' is on-chain and cannot configure an off-chain verifier.")
+                }
+                Some(vertex) => vertex.verifier = Some(mode),
+                None => bail!("Verifier references an undeclared vertex '{name}'."),
+            }
+        }
+
+        let dag = self.dag;
+        let vertices = dag
+            .vertices
+            .iter()
+            .map(|vertex| vertex.name.as_str())
+            .collect::<HashSet<_>>();
+
+        let ensure_declared = |vertex: &str, context: String| {
+            if vertices.contains(vertex) {
+                Ok(())
+            } else {
+                Err(anyhow!(
+                    "{context} references an undeclared vertex '{vertex}'."
+                ))
+            }
+        };
+
+        for edge in &dag.edges {
+            let context = format!(
+                "Edge '{}.{}.{}' -> '{}.{}'",
+                edge.from.vertex,
+                edge.from.output_variant,
+                edge.from.output_port,
+                edge.to.vertex,
+                edge.to.input_port
+            );
+
+            ensure_declared(&edge.from.vertex, context.clone())?;
+            ensure_declared(&edge.to.vertex, context)?;
+        }
+
+        for default_value in &dag.default_values {
+            ensure_declared(
+                &default_value.vertex,
+                format!(
+                    "Default value for '{}.{}'",
+                    default_value.vertex, default_value.input_port
+                ),
+            )?;
+        }
+
+        for entry_group in &dag.entry_groups {
+            for vertex in &entry_group.vertices {
+                ensure_declared(vertex, format!("Entry group '{}'", entry_group.name))?;
+            }
+        }
+
+        for output in &dag.outputs {
+            ensure_declared(
+                &output.vertex,
+                format!(
+                    "Output '{}.{}.{}'",
+                    output.vertex, output.output_variant, output.output_port
+                ),
+            )?;
+        }
+
+        validate(&dag)?;
+
+        Ok(dag)
+    }
+
+    fn push_vertex(mut self, name: String, kind: DagVertexKind) -> Self {
+        self.dag.vertices.push(DagVertex {
+            kind,
+            name,
+            entry_ports: vec![],
+            post_failure_action: None,
+            verifier: None,
+        });
+
+        self
+    }
+
+    fn push_edge(mut self, from: &str, to: &str, kind: EdgeKind) -> Self {
+        let from = self.parse_output(from);
+        let to = self.parse_input(to);
+
+        if let (Some(from), Some(to)) = (from, to) {
+            self.dag.edges.push(DagEdge { from, to, kind });
+        }
+
+        self
+    }
+
+    fn vertex_mut(&mut self, name: &str) -> Option<&mut DagVertex> {
+        self.dag
+            .vertices
+            .iter_mut()
+            .find(|vertex| vertex.name == name)
+    }
+
+    /// Parse a `vertex.port` path, recording an error if it is malformed.
+    fn parse_input(&mut self, path: &str) -> Option<DagInput> {
+        match path.split_once('.') {
+            Some((vertex, input_port)) if !vertex.is_empty() && !input_port.is_empty() => {
+                Some(DagInput {
+                    vertex: vertex.to_string(),
+                    input_port: input_port.to_string(),
+                })
+            }
+            _ => {
+                self.errors.push(format!(
+                    "Input port '{path}' must be in the form 'vertex.port'."
+                ));
+
+                None
+            }
+        }
+    }
+
+    /// Parse a `vertex.variant.port` path, recording an error if it is
+    /// malformed.
+    fn parse_output(&mut self, path: &str) -> Option<DagOutput> {
+        let mut parts = path.splitn(3, '.');
+
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(vertex), Some(output_variant), Some(output_port))
+                if !vertex.is_empty() && !output_variant.is_empty() && !output_port.is_empty() =>
+            {
+                Some(DagOutput {
+                    vertex: vertex.to_string(),
+                    output_variant: output_variant.to_string(),
+                    output_port: output_port.to_string(),
+                })
+            }
+            _ => {
+                self.errors.push(format!(
+                    "Output port '{path}' must be in the form 'vertex.variant.port'."
+                ));
+
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::fqn, assert_matches::assert_matches};
+
+    fn math_branching() -> DagBuilder {
+        DagBuilder::new()
+            .vertex(
+                "add_input_and_default",
+                fqn!("xyz.taluslabs.math.i64.add@1"),
+            )
+            .vertex("is_negative", fqn!("xyz.taluslabs.math.i64.cmp@1"))
+            .vertex("mul_by_neg_3", fqn!("xyz.taluslabs.math.i64.mul@1"))
+            .vertex("mul_by_7", fqn!("xyz.taluslabs.math.i64.mul@1"))
+            .vertex("add_1", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .entry_port("add_input_and_default.a")
+            .default_value("add_input_and_default.b", -3)
+            .default_value("is_negative.b", 0)
+            .default_value("mul_by_neg_3.b", -3)
+            .default_value("mul_by_7.b", 7)
+            .default_value("add_1.b", 1)
+            .edge("add_input_and_default.ok.result", "is_negative.a")
+            .edge("is_negative.lt.a", "mul_by_neg_3.a")
+            .edge("is_negative.gt.a", "mul_by_7.a")
+            .edge("is_negative.eq.a", "add_1.a")
+    }
+
+    #[test]
+    fn test_build_matches_parsed_document() {
+        let built = math_branching().build().unwrap();
+        let parsed =
+            crate::dag::json::parse_dag_spec(include_str!("_dags/math_branching.json")).unwrap();
+
+        assert_eq!(
+            crate::dag::json::to_json(&built).unwrap(),
+            crate::dag::json::to_json(&parsed).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_default_value_arrays_are_many() {
+        let dag = DagBuilder::new()
+            .vertex("a", fqn!("xyz.tool.test@1"))
+            .entry_port("a.input")
+            .default_value("a.items", ["x", "y"])
+            .build()
+            .unwrap();
+
+        let value = &dag.default_values[0].value;
+        assert!(value.is_inline());
+        assert!(value.one.is_empty());
+        assert_eq!(value.many, vec![b"\"x\"".to_vec(), b"\"y\"".to_vec()]);
+    }
+
+    #[test]
+    fn test_build_rejects_dangling_vertex() {
+        let res = math_branching()
+            .edge("add_1.ok.result", "missing.a")
+            .build();
+
+        assert_matches!(res, Err(e) if e.to_string().contains("Edge 'add_1.ok.result' -> 'missing.a' references an undeclared vertex 'missing'."));
+    }
+
+    #[test]
+    fn test_vertex_settings_do_not_depend_on_declaration_order() {
+        let dag = DagBuilder::new()
+            .entry_port("a.input")
+            .vertex_post_failure_action("a", PostFailureAction::TransientContinue)
+            .verifier("a", ToolVerifierMode::RegisteredKey)
+            .vertex("a", fqn!("xyz.tool.test@1"))
+            .build()
+            .unwrap();
+
+        assert_eq!(dag.vertices[0].entry_ports[0].name, "input");
+        assert!(dag.vertices[0].post_failure_action.is_some());
+        assert!(dag.vertices[0].verifier.is_some());
+
+        let res = DagBuilder::new()
+            .vertex("a", fqn!("xyz.tool.test@1"))
+            .entry_port("a.input")
+            .verifier("missing", ToolVerifierMode::RegisteredKey)
+            .build();
+
+        assert_matches!(res, Err(e) if e.to_string().contains("Verifier references an undeclared vertex 'missing'."));
+    }
+
+    #[test]
+    fn test_build_rejects_malformed_paths() {
+        let res = math_branching().edge("add_1.result", "b.a").build();
+
+        assert_matches!(res, Err(e) if e.to_string().contains("Output port 'add_1.result' must be in the form 'vertex.variant.port'."));
+
+        let res = math_branching().entry_port("add_1").build();
+
+        assert_matches!(res, Err(e) if e.to_string().contains("Input port 'add_1' must be in the form 'vertex.port'."));
+    }
+
+    #[test]
+    fn test_build_rejects_duplicate_vertex() {
+        let res = math_branching()
+            .vertex("add_1", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .build();
+
+        assert_matches!(res, Err(e) if e.to_string().contains("Vertex 'add_1' is declared multiple times."));
+    }
+
+    #[test]
+    fn test_build_rejects_verifier_on_onchain_vertex() {
+        let res = DagBuilder::new()
+            .onchain_vertex("a", fqn!("xyz.tool.test@1"))
+            .entry_port("a.input")
+            .verifier("a", ToolVerifierMode::RegisteredKey)
+            .build();
+
+        assert_matches!(res, Err(e) if e.to_string().contains("Vertex 'a' is on-chain"));
+    }
+
+    #[test]
+    fn test_build_runs_validator() {
+        let res = math_branching().default_value("is_negative.a", 1).build();
+
+        assert_matches!(res, Err(e) if e.to_string().contains("'Input port: is_negative.a' is an entry port or has an edge leading into it and therefore cannot have a default value."));
+    }
+}
@@ -0,0 +1,651 @@
+//! Structured diagnostics for DAG parsing and validation.
+//!
+//! [`crate::dag::validator::diagnose`] reports every violated DAG rule as a
+//! [`DagDiagnostic`] instead of stopping at the first one. Each diagnostic
+//! carries a stable rule code, a severity, the offending vertex, port or edge
+//! and a JSON pointer into the DAG document. [`check_source`] parses a DAG
+//! JSON document, validates it and resolves those pointers to line and column
+//! locations in the source text.
+
+use {
+    crate::{
+        dag::{json::parse_dag_spec, validator},
+        types::DagSpec,
+    },
+    serde::{ser::SerializeStruct, Serialize, Serializer},
+};
+
+/// How serious a [`DagDiagnostic`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The DAG cannot be published or executed.
+    Error,
+    /// The DAG is valid but likely not what the author intended.
+    Warning,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// The DAG rule a [`DagDiagnostic`] is reported for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DagRule {
+    /// The document is not valid DAG JSON.
+    Parse,
+    /// No vertex has entry ports and no entry groups are defined.
+    NoEntryPoint,
+    /// An entry group references a vertex that does not exist.
+    UnknownEntryVertex,
+    /// Two edges leave the same output port or connect the same ports.
+    DuplicateEdge,
+    /// A vertex has no edges leading to or from it.
+    DisconnectedVertex,
+    /// A vertex name is used more than once.
+    DuplicateVertex,
+    /// An entry port also has an edge leading into it.
+    EntryPortWithEdge,
+    /// An entry port is declared more than once.
+    DuplicateEntryPort,
+    /// A default value is set on an entry port or a connected input port.
+    DefaultValueOnConnectedPort,
+    /// A vertex has both outgoing edges and ports marked as output.
+    OutputWithEdges,
+    /// A vertex has a do-while edge without a break edge or vice versa.
+    UnpairedLoop,
+    /// A do-while and a break edge leave the same output variant.
+    LoopMustBranch,
+    /// The graph contains a cycle.
+    Cycle,
+    /// A node has the wrong number of outgoing edges.
+    EdgeCount,
+    /// An edge connects two nodes in the wrong order.
+    EdgeOrder,
+    /// An input port cannot be reached from an entry group.
+    Unreachable,
+    /// An input port can be reached by more than one concurrent walk.
+    RaceCondition,
+    /// A collect edge is not preceded by a for-each edge.
+    CollectWithoutForEach,
+    /// A for-each edge is never closed by a collect edge.
+    ForEachWithoutCollect,
+    /// A for-each edge is nested in another for-each.
+    NestedForEach,
+    /// A do-while, break or static edge is inside a for-each.
+    LoopInForEach,
+    /// A do-while loop contains another loop edge.
+    NestedLoop,
+}
+
+impl DagRule {
+    /// Stable code of the rule, used by editor integrations to link and
+    /// suppress diagnostics.
+    pub fn code(&self) -> &'static str {
+        match self {
+            DagRule::Parse => "DAG001",
+            DagRule::NoEntryPoint => "DAG002",
+            DagRule::UnknownEntryVertex => "DAG003",
+            DagRule::DuplicateEdge => "DAG004",
+            DagRule::DisconnectedVertex => "DAG005",
+            DagRule::DuplicateVertex => "DAG006",
+            DagRule::EntryPortWithEdge => "DAG007",
+            DagRule::DuplicateEntryPort => "DAG008",
+            DagRule::DefaultValueOnConnectedPort => "DAG009",
+            DagRule::OutputWithEdges => "DAG010",
+            DagRule::UnpairedLoop => "DAG011",
+            DagRule::LoopMustBranch => "DAG012",
+            DagRule::Cycle => "DAG013",
+            DagRule::EdgeCount => "DAG014",
+            DagRule::EdgeOrder => "DAG015",
+            DagRule::Unreachable => "DAG016",
+            DagRule::RaceCondition => "DAG017",
+            DagRule::CollectWithoutForEach => "DAG018",
+            DagRule::ForEachWithoutCollect => "DAG019",
+            DagRule::NestedForEach => "DAG020",
+            DagRule::LoopInForEach => "DAG021",
+            DagRule::NestedLoop => "DAG022",
+        }
+    }
+
+    /// Severity diagnostics for this rule are reported with.
+    pub fn severity(&self) -> Severity {
+        Severity::Error
+    }
+}
+
+/// The part of the DAG a [`DagDiagnostic`] points at.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DiagnosticSubject {
+    Vertex {
+        vertex: String,
+    },
+    InputPort {
+        vertex: String,
+        port: String,
+    },
+    OutputVariant {
+        vertex: String,
+        variant: String,
+    },
+    OutputPort {
+        vertex: String,
+        variant: String,
+        port: String,
+    },
+    Edge {
+        index: usize,
+    },
+    EntryGroup {
+        group: String,
+    },
+}
+
+impl DiagnosticSubject {
+    /// Find the JSON pointer of the first place in the DAG document that
+    /// defines this subject.
+    pub fn pointer(&self, dag: &DagSpec) -> Option<String> {
+        let vertex_index = |name: &str| dag.vertices.iter().position(|v| v.name == name);
+
+        match self {
+            DiagnosticSubject::Vertex { vertex } => {
+                vertex_index(vertex).map(|index| format!("/vertices/{index}"))
+            }
+            DiagnosticSubject::InputPort { vertex, port } => {
+                let entry_port = vertex_index(vertex).and_then(|index| {
+                    dag.vertices[index]
+                        .entry_ports
+                        .iter()
+                        .position(|entry_port| &entry_port.name == port)
+                        .map(|port| format!("/vertices/{index}/entry_ports/{port}"))
+                });
+                let edge = || {
+                    dag.edges
+                        .iter()
+                        .position(|edge| &edge.to.vertex == vertex && &edge.to.input_port == port)
+                        .map(|index| format!("/edges/{index}/to"))
+                };
+                let default_value = || {
+                    dag.default_values
+                        .iter()
+                        .position(|default| {
+                            &default.vertex == vertex && &default.input_port == port
+                        })
+                        .map(|index| format!("/default_values/{index}"))
+                };
+
+                entry_port.or_else(edge).or_else(default_value)
+            }
+            DiagnosticSubject::OutputVariant { vertex, variant } => dag
+                .edges
+                .iter()
+                .position(|edge| {
+                    &edge.from.vertex == vertex && &edge.from.output_variant == variant
+                })
+                .map(|index| format!("/edges/{index}/from"))
+                .or_else(|| {
+                    dag.outputs
+                        .iter()
+                        .position(|output| {
+                            &output.vertex == vertex && &output.output_variant == variant
+                        })
+                        .map(|index| format!("/outputs/{index}"))
+                }),
+            DiagnosticSubject::OutputPort {
+                vertex,
+                variant,
+                port,
+            } => dag
+                .edges
+                .iter()
+                .position(|edge| {
+                    &edge.from.vertex == vertex
+                        && &edge.from.output_variant == variant
+                        && &edge.from.output_port == port
+                })
+                .map(|index| format!("/edges/{index}/from"))
+                .or_else(|| {
+                    dag.outputs
+                        .iter()
+                        .position(|output| {
+                            &output.vertex == vertex
+                                && &output.output_variant == variant
+                                && &output.output_port == port
+                        })
+                        .map(|index| format!("/outputs/{index}"))
+                }),
+            DiagnosticSubject::Edge { index } => Some(format!("/edges/{index}")),
+            DiagnosticSubject::EntryGroup { group } => dag
+                .entry_groups
+                .iter()
+                .position(|entry_group| &entry_group.name == group)
+                .map(|index| format!("/entry_groups/{index}")),
+        }
+    }
+}
+
+/// Location of a diagnostic in the DAG source text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct SourceSpan {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// Byte offset of the start of the span.
+    pub offset: usize,
+    /// Length of the span in bytes.
+    pub len: usize,
+}
+
+impl SourceSpan {
+    /// Build a span starting at the byte `offset` of `source`.
+    pub fn at(source: &str, offset: usize, len: usize) -> Self {
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            offset,
+            len,
+        }
+    }
+}
+
+/// A single violated DAG rule.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DagDiagnostic {
+    pub rule: DagRule,
+    pub severity: Severity,
+    pub message: String,
+    pub subject: Option<DiagnosticSubject>,
+    /// JSON pointer (RFC 6901) into the DAG document.
+    pub pointer: Option<String>,
+    /// Location in the source text, only known when the diagnostic was
+    /// produced from a JSON document via [`check_source`].
+    pub span: Option<SourceSpan>,
+}
+
+impl DagDiagnostic {
+    pub fn new(rule: DagRule, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            severity: rule.severity(),
+            message: message.into(),
+            subject: None,
+            pointer: None,
+            span: None,
+        }
+    }
+
+    pub fn with_subject(mut self, subject: DiagnosticSubject) -> Self {
+        self.subject = Some(subject);
+        self
+    }
+
+    pub fn with_pointer(mut self, pointer: impl Into<String>) -> Self {
+        self.pointer = Some(pointer.into());
+        self
+    }
+
+    /// Build a diagnostic from an error returned by [`parse_dag_spec`].
+    pub fn from_parse_error(error: &serde_json::Error, source: &str) -> Self {
+        let message = error.to_string();
+        let location = format!(" at line {} column {}", error.line(), error.column());
+        let message = message
+            .strip_suffix(&location)
+            .unwrap_or(&message)
+            .to_string();
+
+        let mut diagnostic = Self::new(DagRule::Parse, message);
+
+        // Errors raised after deserialization carry no position.
+        if error.line() > 0 {
+            let offset = source
+                .split_inclusive('\n')
+                .take(error.line() - 1)
+                .map(str::len)
+                .sum::<usize>();
+            let column = source[offset..]
+                .char_indices()
+                .nth(error.column().saturating_sub(1))
+                .map(|(index, _)| index)
+                .unwrap_or(source.len() - offset);
+
+            diagnostic.span = Some(SourceSpan::at(source, offset + column, 1));
+        }
+
+        diagnostic
+    }
+
+    /// Resolve [`DagDiagnostic::pointer`] to a [`SourceSpan`] in `source`.
+    pub fn locate(mut self, source: &str) -> Self {
+        if self.span.is_none() {
+            self.span = self
+                .pointer
+                .as_deref()
+                .and_then(|pointer| locate_pointer(source, pointer));
+        }
+
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl std::fmt::Display for DagDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for DagDiagnostic {}
+
+impl Serialize for DagDiagnostic {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("DagDiagnostic", 7)?;
+
+        state.serialize_field("code", self.rule.code())?;
+        state.serialize_field("rule", &self.rule)?;
+        state.serialize_field("severity", &self.severity)?;
+        state.serialize_field("message", &self.message)?;
+        state.serialize_field("subject", &self.subject)?;
+        state.serialize_field("pointer", &self.pointer)?;
+        state.serialize_field("span", &self.span)?;
+
+        state.end()
+    }
+}
+
+/// Parse and validate a DAG JSON document, returning the parsed DAG if the
+/// document is well-formed and every diagnostic located in `source`.
+pub fn check_source(source: &str) -> (Option<DagSpec>, Vec<DagDiagnostic>) {
+    let dag = match parse_dag_spec(source) {
+        Ok(dag) => dag,
+        Err(e) => return (None, vec![DagDiagnostic::from_parse_error(&e, source)]),
+    };
+
+    let diagnostics = validator::diagnose(&dag)
+        .into_iter()
+        .map(|diagnostic| diagnostic.locate(source))
+        .collect();
+
+    (Some(dag), diagnostics)
+}
+
+/// Find the span of the JSON value a JSON pointer refers to.
+pub fn locate_pointer(source: &str, pointer: &str) -> Option<SourceSpan> {
+    let mut cursor = Cursor {
+        source,
+        bytes: source.as_bytes(),
+        pos: 0,
+    };
+
+    cursor.skip_whitespace();
+
+    if !pointer.is_empty() {
+        for token in pointer.strip_prefix('/')?.split('/') {
+            let token = token.replace("~1", "/").replace("~0", "~");
+
+            cursor.enter(&token)?;
+        }
+    }
+
+    let start = cursor.pos;
+
+    cursor.skip_value()?;
+
+    Some(SourceSpan::at(source, start, cursor.pos - start))
+}
+
+/// Minimal JSON scanner that only needs to skip over values and find object
+/// keys and array elements. The document is known to be valid JSON.
+struct Cursor<'a> {
+    source: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.skip_whitespace();
+
+        (self.peek()? == byte).then(|| self.pos += 1)
+    }
+
+    /// Move the cursor to the start of the member or element named `token`
+    /// of the current value.
+    fn enter(&mut self, token: &str) -> Option<()> {
+        match self.peek()? {
+            b'{' => {
+                self.pos += 1;
+
+                loop {
+                    self.skip_whitespace();
+
+                    let key = self.read_string()?;
+
+                    self.expect(b':')?;
+                    self.skip_whitespace();
+
+                    if key == token {
+                        return Some(());
+                    }
+
+                    self.skip_value()?;
+                    self.expect(b',')?;
+                }
+            }
+            b'[' => {
+                let index = token.parse::<usize>().ok()?;
+
+                self.pos += 1;
+
+                for _ in 0..index {
+                    self.skip_whitespace();
+                    self.skip_value()?;
+                    self.expect(b',')?;
+                }
+
+                self.skip_whitespace();
+
+                (self.peek()? != b']').then_some(())
+            }
+            _ => None,
+        }
+    }
+
+    fn read_string(&mut self) -> Option<String> {
+        let start = self.pos;
+
+        self.skip_string()?;
+
+        serde_json::from_str(&self.source[start..self.pos]).ok()
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        if self.peek()? != b'"' {
+            return None;
+        }
+
+        self.pos += 1;
+
+        loop {
+            match self.peek()? {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+
+                    return Some(());
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn skip_value(&mut self) -> Option<()> {
+        match self.peek()? {
+            b'"' => self.skip_string(),
+            open @ (b'{' | b'[') => {
+                let close = if open == b'{' { b'}' } else { b']' };
+
+                self.pos += 1;
+                self.skip_whitespace();
+
+                if self.peek()? == close {
+                    self.pos += 1;
+
+                    return Some(());
+                }
+
+                loop {
+                    self.skip_whitespace();
+
+                    if open == b'{' {
+                        self.skip_string()?;
+                        self.expect(b':')?;
+                        self.skip_whitespace();
+                    }
+
+                    self.skip_value()?;
+                    self.skip_whitespace();
+
+                    match self.peek()? {
+                        b',' => self.pos += 1,
+                        byte if byte == close => {
+                            self.pos += 1;
+
+                            return Some(());
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            _ => {
+                while !matches!(
+                    self.peek(),
+                    None | Some(b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r')
+                ) {
+                    self.pos += 1;
+                }
+
+                Some(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"{
+  "vertices": [
+    { "name": "a", "entry_ports": [{ "name": "in" }] },
+    { "name": "b\"c" }
+  ],
+  "edges": []
+}"#;
+
+    #[test]
+    fn test_locate_pointer() {
+        let span = locate_pointer(SOURCE, "/vertices/1").unwrap();
+        assert_eq!((span.line, span.column), (4, 5));
+        assert_eq!(
+            &SOURCE[span.offset..span.offset + span.len],
+            r#"{ "name": "b\"c" }"#
+        );
+
+        let span = locate_pointer(SOURCE, "/vertices/0/entry_ports/0/name").unwrap();
+        assert_eq!(&SOURCE[span.offset..span.offset + span.len], r#""in""#);
+
+        let span = locate_pointer(SOURCE, "/edges").unwrap();
+        assert_eq!((span.line, span.column, span.len), (6, 12, 2));
+
+        assert!(locate_pointer(SOURCE, "/vertices/2").is_none());
+        assert!(locate_pointer(SOURCE, "/missing").is_none());
+        assert_eq!(locate_pointer(SOURCE, "").unwrap().len, SOURCE.len());
+    }
+
+    #[test]
+    fn test_check_source_parse_error() {
+        let source = "{\n  \"vertices\": [,]\n}";
+        let (dag, diagnostics) = check_source(source);
+
+        assert!(dag.is_none());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, DagRule::Parse);
+        assert!(!diagnostics[0].message.contains(" at line "));
+
+        let span = diagnostics[0].span.unwrap();
+        assert_eq!(span.line, 2);
+        assert_eq!(&source[span.offset..span.offset + span.len], ",");
+    }
+
+    #[test]
+    fn test_check_source_reports_every_violation() {
+        let source = include_str!("_dags/both_vertex_and_entry_vertex_invalid.json");
+        let (dag, diagnostics) = check_source(source);
+
+        assert!(dag.is_some());
+        assert!(!diagnostics.is_empty());
+
+        for diagnostic in &diagnostics {
+            let span = diagnostic.span.expect("every diagnostic is located");
+            let pointer = diagnostic.pointer.as_deref().unwrap();
+
+            assert_eq!(locate_pointer(source, pointer), Some(span));
+        }
+
+        let duplicate = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.rule == DagRule::DuplicateVertex)
+            .unwrap();
+
+        assert_eq!(
+            duplicate.subject,
+            Some(DiagnosticSubject::Vertex {
+                vertex: "b".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_json() {
+        let diagnostic = DagDiagnostic::new(DagRule::Cycle, "cycle")
+            .with_subject(DiagnosticSubject::Edge { index: 2 })
+            .with_pointer("/edges/2");
+
+        assert_eq!(
+            serde_json::to_value(&diagnostic).unwrap(),
+            serde_json::json!({
+                "code": "DAG013",
+                "rule": "cycle",
+                "severity": "error",
+                "message": "cycle",
+                "subject": { "kind": "edge", "index": 2 },
+                "pointer": "/edges/2",
+                "span": null,
+            })
+        );
+    }
+}
@@ -1,4 +1,4 @@
-//! JSON document parser for Nexus DAG specification files.
+//! JSON document parser and canonical writer for Nexus DAG specification files.
 
 use {
     crate::{
@@ -22,7 +22,7 @@ use {
         },
         ToolFqn,
     },
-    serde::{Deserialize, Deserializer},
+    serde::{Deserialize, Deserializer, Serialize, Serializer},
 };
 
 pub fn parse_dag_spec(input: &str) -> Result<DagSpec, serde_json::Error> {
@@ -37,14 +37,47 @@ pub fn parse_dag_spec(input: &str) -> Result<DagSpec, serde_json::Error> {
     Ok(document.into())
 }
 
-#[derive(Clone, Debug, Deserialize)]
+/// Serialize a [`DagSpec`] into the JSON document format accepted by
+/// [`parse_dag_spec`].
+///
+/// The document is written in a canonical form: vertices, edges, default
+/// values, entry groups and outputs are sorted and optional fields are only
+/// emitted when set, so exporting a parsed DAG again yields the same bytes.
+pub fn to_json(dag: &DagSpec) -> Result<String, serde_json::Error> {
+    let mut json = serde_json::to_string_pretty(&DagDocument::from(dag))?;
+    json.push('\n');
+
+    Ok(json)
+}
+
+/// Write a [`DagSpec`] to `writer` in the same canonical form as [`to_json`].
+pub fn write_dag_spec<W: std::io::Write>(
+    mut writer: W,
+    dag: &DagSpec,
+) -> Result<(), serde_json::Error> {
+    let json = to_json(dag)?;
+
+    writer
+        .write_all(json.as_bytes())
+        .map_err(serde_json::Error::io)
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 struct DagDocument {
     vertices: Vec<VertexDocument>,
     edges: Vec<EdgeDocument>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     default_values: Option<Vec<DefaultValueDocument>>,
-    #[serde(default, deserialize_with = "deserialize_post_failure_action_option")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_post_failure_action_option",
+        serialize_with = "serialize_post_failure_action_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     post_failure_action: Option<PostFailureAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     entry_groups: Option<Vec<EntryGroupDocument>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     outputs: Option<Vec<OutputPortDocument>>,
 }
 
@@ -76,7 +109,61 @@ impl From<DagDocument> for DagSpec {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl From<&DagSpec> for DagDocument {
+    fn from(dag: &DagSpec) -> Self {
+        let mut vertices = dag
+            .vertices
+            .iter()
+            .map(Into::into)
+            .collect::<Vec<VertexDocument>>();
+        vertices.sort_by(|left, right| left.name.cmp(&right.name));
+
+        let mut edges = dag
+            .edges
+            .iter()
+            .map(Into::into)
+            .collect::<Vec<EdgeDocument>>();
+        edges.sort_by(|left, right| left.sort_key().cmp(&right.sort_key()));
+
+        let mut default_values = dag
+            .default_values
+            .iter()
+            .map(Into::into)
+            .collect::<Vec<DefaultValueDocument>>();
+        default_values.sort_by(|left, right| {
+            (&left.vertex, &left.input_port).cmp(&(&right.vertex, &right.input_port))
+        });
+
+        let mut entry_groups = dag
+            .entry_groups
+            .iter()
+            .map(Into::into)
+            .collect::<Vec<EntryGroupDocument>>();
+        entry_groups.sort_by(|left, right| left.name.cmp(&right.name));
+
+        let mut outputs = dag
+            .outputs
+            .iter()
+            .map(Into::into)
+            .collect::<Vec<OutputPortDocument>>();
+        outputs.sort_by(|left, right| left.sort_key().cmp(&right.sort_key()));
+
+        Self {
+            vertices,
+            edges,
+            default_values: non_empty(default_values),
+            post_failure_action: dag.post_failure_action.as_ref().cloned(),
+            entry_groups: non_empty(entry_groups),
+            outputs: non_empty(outputs),
+        }
+    }
+}
+
+fn non_empty<T>(values: Vec<T>) -> Option<Vec<T>> {
+    (!values.is_empty()).then_some(values)
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(tag = "variant", rename_all = "snake_case")]
 enum VertexKindDocument {
     OffChain { tool_fqn: ToolFqn },
@@ -92,7 +179,20 @@ impl From<VertexKindDocument> for DagVertexKind {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl From<&DagVertexKind> for VertexKindDocument {
+    fn from(kind: &DagVertexKind) -> Self {
+        match kind {
+            DagVertexKind::OffChain { tool_fqn } => Self::OffChain {
+                tool_fqn: tool_fqn.clone(),
+            },
+            DagVertexKind::OnChain { tool_fqn } => Self::OnChain {
+                tool_fqn: tool_fqn.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 struct EntryPortDocument {
     name: String,
 }
@@ -105,14 +205,33 @@ impl From<EntryPortDocument> for DagEntryPort {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl From<&DagEntryPort> for EntryPortDocument {
+    fn from(entry_port: &DagEntryPort) -> Self {
+        Self {
+            name: entry_port.name.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 struct VertexDocument {
     kind: VertexKindDocument,
     name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     entry_ports: Option<Vec<EntryPortDocument>>,
-    #[serde(default, deserialize_with = "deserialize_post_failure_action_option")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_post_failure_action_option",
+        serialize_with = "serialize_post_failure_action_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     post_failure_action: Option<PostFailureAction>,
-    #[serde(default, deserialize_with = "deserialize_verifier_mode_option")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_verifier_mode_option",
+        serialize_with = "serialize_verifier_mode_option",
+        skip_serializing_if = "Option::is_none"
+    )]
     verifier: Option<ToolVerifierMode>,
 }
 
@@ -133,6 +252,30 @@ impl From<VertexDocument> for DagVertex {
     }
 }
 
+impl From<&DagVertex> for VertexDocument {
+    fn from(vertex: &DagVertex) -> Self {
+        let mut entry_ports = vertex
+            .entry_ports
+            .iter()
+            .map(Into::into)
+            .collect::<Vec<EntryPortDocument>>();
+        entry_ports.sort_by(|left, right| left.name.cmp(&right.name));
+
+        Self {
+            kind: (&vertex.kind).into(),
+            name: vertex.name.clone(),
+            entry_ports: non_empty(entry_ports),
+            post_failure_action: vertex.post_failure_action.as_ref().cloned(),
+            // `none` is the parser default, keep the canonical form minimal.
+            verifier: vertex
+                .verifier
+                .as_ref()
+                .filter(|mode| **mode != ToolVerifierMode::None)
+                .cloned(),
+        }
+    }
+}
+
 fn deserialize_post_failure_action_option<'de, D>(
     deserializer: D,
 ) -> Result<Option<PostFailureAction>, D::Error>
@@ -167,6 +310,26 @@ fn post_failure_action_from_name(name: &str) -> Result<PostFailureAction, String
     }
 }
 
+fn post_failure_action_name(action: &PostFailureAction) -> &'static str {
+    match action {
+        PostFailureAction::TransientContinue => "TransientContinue",
+        PostFailureAction::Terminate => "Terminate",
+    }
+}
+
+fn serialize_post_failure_action_option<S>(
+    action: &Option<PostFailureAction>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    action
+        .as_ref()
+        .map(post_failure_action_name)
+        .serialize(serializer)
+}
+
 fn deserialize_verifier_mode_option<'de, D>(
     deserializer: D,
 ) -> Result<Option<ToolVerifierMode>, D::Error>
@@ -200,7 +363,25 @@ fn verifier_mode_from_name(name: &str) -> Result<ToolVerifierMode, String> {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+fn verifier_mode_name(mode: &ToolVerifierMode) -> &'static str {
+    match mode {
+        ToolVerifierMode::None => "none",
+        ToolVerifierMode::RegisteredKey => "registered_key",
+        ToolVerifierMode::External => "external",
+    }
+}
+
+fn serialize_verifier_mode_option<S>(
+    mode: &Option<ToolVerifierMode>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    mode.as_ref().map(verifier_mode_name).serialize(serializer)
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 struct EntryGroupDocument {
     name: String,
     vertices: Vec<String>,
@@ -215,11 +396,26 @@ impl From<EntryGroupDocument> for DagEntryGroup {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl From<&DagEntryGroup> for EntryGroupDocument {
+    fn from(entry_group: &DagEntryGroup) -> Self {
+        let mut vertices = entry_group.vertices.clone();
+        vertices.sort();
+
+        Self {
+            name: entry_group.name.clone(),
+            vertices,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 struct DefaultValueDocument {
     vertex: String,
     input_port: String,
-    #[serde(deserialize_with = "deserialize_nexus_data")]
+    #[serde(
+        deserialize_with = "deserialize_nexus_data",
+        serialize_with = "serialize_nexus_data"
+    )]
     value: NexusData,
 }
 
@@ -233,6 +429,16 @@ impl From<DefaultValueDocument> for DagDefaultValue {
     }
 }
 
+impl From<&DagDefaultValue> for DefaultValueDocument {
+    fn from(default_value: &DagDefaultValue) -> Self {
+        Self {
+            vertex: default_value.vertex.clone(),
+            input_port: default_value.input_port.clone(),
+            value: default_value.value.clone(),
+        }
+    }
+}
+
 fn deserialize_nexus_data<'de, D>(deserializer: D) -> Result<NexusData, D::Error>
 where
     D: Deserializer<'de>,
@@ -273,25 +479,97 @@ fn nexus_data_from_json(value: serde_json::Value) -> Result<NexusData, String> {
     }
 }
 
+fn serialize_nexus_data<S>(data: &NexusData, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    nexus_data_to_json(data)
+        .map_err(serde::ser::Error::custom)?
+        .serialize(serializer)
+}
+
+/// Inverse of [`nexus_data_from_json`]. Payloads are written back as JSON
+/// values under `data` so they stay readable and diffable.
+fn nexus_data_to_json(data: &NexusData) -> Result<serde_json::Value, String> {
+    let storage = storage_tag_name(data.storage_tag())?;
+
+    if !data.one.is_empty() && !data.many.is_empty() {
+        return Err("nexus data cannot contain both one and many payloads".to_string());
+    }
+
+    let payload_to_json = |bytes: &[u8]| {
+        serde_json::from_slice::<serde_json::Value>(bytes)
+            .map_err(|error| format!("nexus data payload is not valid JSON: {error}"))
+    };
+
+    // An empty `one` payload is only produced by an array, so both empty
+    // payloads are written as an empty array.
+    let data = if data.one.is_empty() {
+        serde_json::Value::Array(
+            data.many
+                .iter()
+                .map(|bytes| payload_to_json(bytes))
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+    } else {
+        payload_to_json(&data.one)?
+    };
+
+    Ok(serde_json::json!({ "storage": storage, "data": data }))
+}
+
+/// Storage tags are `inline`, `walrus` or the tag of a custom remote
+/// `DataStore`, which must be lowercase ASCII letters, digits, `-` or `_`.
 fn storage_tag_bytes(name: &str) -> Result<Vec<u8>, String> {
-    match name {
-        "inline" => Ok(b"inline".to_vec()),
-        "walrus" => Ok(b"walrus".to_vec()),
-        _ => Err(format!("unknown nexus data storage `{name}`")),
+    if !is_storage_tag(name) {
+        return Err(format!("unknown nexus data storage `{name}`"));
     }
+
+    Ok(name.as_bytes().to_vec())
+}
+
+fn storage_tag_name(tag: &[u8]) -> Result<&str, String> {
+    std::str::from_utf8(tag)
+        .ok()
+        .filter(|name| is_storage_tag(name))
+        .ok_or_else(|| {
+            format!(
+                "unknown nexus data storage `{}`",
+                String::from_utf8_lossy(tag)
+            )
+        })
+}
+
+fn is_storage_tag(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_'))
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 struct EdgeDocument {
     from: OutputPortDocument,
     to: InputPortDocument,
     #[serde(
         default = "default_edge_kind",
-        deserialize_with = "deserialize_edge_kind"
+        deserialize_with = "deserialize_edge_kind",
+        serialize_with = "serialize_edge_kind",
+        skip_serializing_if = "is_default_edge_kind"
     )]
     kind: EdgeKind,
 }
 
+impl EdgeDocument {
+    fn sort_key(&self) -> (OutputPortSortKey<'_>, (&str, &str), &'static str) {
+        (
+            self.from.sort_key(),
+            (self.to.vertex.as_str(), self.to.input_port.as_str()),
+            edge_kind_name(&self.kind),
+        )
+    }
+}
+
 impl From<EdgeDocument> for DagEdge {
     fn from(document: EdgeDocument) -> Self {
         Self {
@@ -302,10 +580,31 @@ impl From<EdgeDocument> for DagEdge {
     }
 }
 
+impl From<&DagEdge> for EdgeDocument {
+    fn from(edge: &DagEdge) -> Self {
+        Self {
+            from: (&edge.from).into(),
+            to: (&edge.to).into(),
+            kind: edge.kind,
+        }
+    }
+}
+
 fn default_edge_kind() -> EdgeKind {
     EdgeKind::Normal
 }
 
+fn is_default_edge_kind(kind: &EdgeKind) -> bool {
+    *kind == EdgeKind::Normal
+}
+
+fn serialize_edge_kind<S>(kind: &EdgeKind, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(edge_kind_name(kind))
+}
+
 fn deserialize_edge_kind<'de, D>(deserializer: D) -> Result<EdgeKind, D::Error>
 where
     D: Deserializer<'de>,
@@ -344,13 +643,36 @@ fn edge_kind_from_name(name: &str) -> Result<EdgeKind, String> {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+fn edge_kind_name(kind: &EdgeKind) -> &'static str {
+    match kind {
+        EdgeKind::Normal => "normal",
+        EdgeKind::ForEach => "for_each",
+        EdgeKind::Collect => "collect",
+        EdgeKind::DoWhile => "do_while",
+        EdgeKind::Break => "break",
+        EdgeKind::Static => "static",
+    }
+}
+
+type OutputPortSortKey<'a> = (&'a str, &'a str, &'a str);
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 struct OutputPortDocument {
     vertex: String,
     output_variant: String,
     output_port: String,
 }
 
+impl OutputPortDocument {
+    fn sort_key(&self) -> OutputPortSortKey<'_> {
+        (
+            self.vertex.as_str(),
+            self.output_variant.as_str(),
+            self.output_port.as_str(),
+        )
+    }
+}
+
 impl From<OutputPortDocument> for DagOutput {
     fn from(document: OutputPortDocument) -> Self {
         Self {
@@ -361,7 +683,17 @@ impl From<OutputPortDocument> for DagOutput {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+impl From<&DagOutput> for OutputPortDocument {
+    fn from(output: &DagOutput) -> Self {
+        Self {
+            vertex: output.vertex.clone(),
+            output_variant: output.output_variant.clone(),
+            output_port: output.output_port.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 struct InputPortDocument {
     vertex: String,
     input_port: String,
@@ -376,6 +708,15 @@ impl From<InputPortDocument> for DagInput {
     }
 }
 
+impl From<&DagInput> for InputPortDocument {
+    fn from(input: &DagInput) -> Self {
+        Self {
+            vertex: input.vertex.clone(),
+            input_port: input.input_port.clone(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -628,4 +969,133 @@ mod tests {
         .unwrap();
         assert_eq!(default_edge.kind, EdgeKind::Normal);
     }
+
+    #[test]
+    fn to_json_round_trips_byte_stable() {
+        for input in [
+            include_str!("_dags/math_branching.json"),
+            include_str!("_dags/math_branching_groups.json"),
+            include_str!("_dags/both_loops_valid.json"),
+            include_str!("_dags/trip_planner.json"),
+            include_str!("_dags/encrypted_port_output_valid.json"),
+            include_str!("_dags/double_do_while_static_valid.json"),
+        ] {
+            let exported = to_json(&parse_dag_spec(input).unwrap()).unwrap();
+            let reexported = to_json(&parse_dag_spec(&exported).unwrap()).unwrap();
+
+            assert_eq!(exported, reexported);
+        }
+    }
+
+    #[test]
+    fn to_json_writes_canonical_document() {
+        let dag = parse_dag_spec(
+            r#"{
+                "post_failure_action": "continue",
+                "vertices": [
+                    {
+                        "kind": { "variant": "on_chain", "tool_fqn": "xyz.tool.second@1" },
+                        "name": "b"
+                    },
+                    {
+                        "kind": { "variant": "off_chain", "tool_fqn": "xyz.tool.first@1" },
+                        "name": "a",
+                        "entry_ports": [{ "name": "y" }, { "name": "x" }],
+                        "verifier": "none"
+                    }
+                ],
+                "edges": [
+                    {
+                        "from": { "vertex": "a", "output_variant": "ok", "output_port": "items" },
+                        "to": { "vertex": "b", "input_port": "item" },
+                        "kind": "ForEach"
+                    }
+                ],
+                "default_values": [
+                    {
+                        "vertex": "b",
+                        "input_port": "limit",
+                        "value": { "storage": "walrus", "data": ["a", 1] }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let json = serde_json::from_str::<serde_json::Value>(&to_json(&dag).unwrap()).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "vertices": [
+                    {
+                        "kind": { "variant": "off_chain", "tool_fqn": "xyz.tool.first@1" },
+                        "name": "a",
+                        "entry_ports": [{ "name": "x" }, { "name": "y" }]
+                    },
+                    {
+                        "kind": { "variant": "on_chain", "tool_fqn": "xyz.tool.second@1" },
+                        "name": "b"
+                    }
+                ],
+                "edges": [
+                    {
+                        "from": { "vertex": "a", "output_variant": "ok", "output_port": "items" },
+                        "to": { "vertex": "b", "input_port": "item" },
+                        "kind": "for_each"
+                    }
+                ],
+                "default_values": [
+                    {
+                        "vertex": "b",
+                        "input_port": "limit",
+                        "value": { "storage": "walrus", "data": ["a", 1] }
+                    }
+                ],
+                "post_failure_action": "TransientContinue"
+            })
+        );
+    }
+
+    #[test]
+    fn nexus_data_accepts_custom_storage_tags() {
+        let data =
+            nexus_data_from_json(serde_json::json!({ "storage": "s3", "data": "key" })).unwrap();
+        assert_eq!(data.storage_tag(), b"s3");
+        assert_eq!(
+            nexus_data_to_json(&data).unwrap(),
+            serde_json::json!({ "storage": "s3", "data": "key" })
+        );
+
+        assert!(
+            nexus_data_from_json(serde_json::json!({ "storage": "S3 bucket", "data": 1 }))
+                .unwrap_err()
+                .contains("unknown nexus data storage `S3 bucket`")
+        );
+    }
+
+    #[test]
+    fn to_json_rejects_non_json_payloads() {
+        let dag = DagSpec {
+            vertices: vec![DagVertex {
+                kind: DagVertexKind::OffChain {
+                    tool_fqn: "xyz.tool.test@1".parse().unwrap(),
+                },
+                name: "root".to_string(),
+                entry_ports: vec![],
+                post_failure_action: None,
+                verifier: None,
+            }],
+            default_values: vec![DagDefaultValue {
+                vertex: "root".to_string(),
+                input_port: "input".to_string(),
+                value: NexusData::inline_one(b"not json".to_vec()),
+            }],
+            ..Default::default()
+        };
+
+        let error = to_json(&dag).unwrap_err();
+
+        assert!(error.to_string().contains("not valid JSON"));
+    }
 }
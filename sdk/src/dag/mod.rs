@@ -1,2 +1,8 @@
+pub mod builder;
+pub mod diagnostic;
 pub mod json;
+pub mod render;
+pub mod schema;
+#[cfg(feature = "dag_simulator")]
+pub mod simulator;
 pub mod validator;
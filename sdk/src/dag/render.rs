@@ -0,0 +1,475 @@
+//! Render a [`DagSpec`] as a Graphviz DOT or Mermaid diagram.
+//!
+//! Both renderers draw the graph built by
+//! [`crate::dag::validator::try_into_graph`]: vertices, their output variants
+//! and ports and the input ports they lead into. Entry ports and default
+//! values are added as extra input ports, and each entry group is drawn as a
+//! cluster holding the entry ports it invokes. Edge kinds are styled
+//! differently and labelled, except for normal edges.
+//!
+//! Do-while edges point back at the real loop vertex instead of the phantom
+//! vertex the validator uses to keep the graph acyclic.
+
+use {
+    crate::{
+        dag::{
+            schema::vertex_tool_fqn,
+            validator::{try_into_graph, GraphNode},
+        },
+        move_bindings::interface::graph::EdgeKind,
+        types::{DagSpec, DEFAULT_ENTRY_GROUP},
+    },
+    anyhow::Result as AnyResult,
+    petgraph::visit::EdgeRef,
+    std::{collections::HashMap, fmt::Write as _},
+};
+
+/// Render `dag` as a Graphviz DOT digraph.
+pub fn to_dot(dag: &DagSpec) -> AnyResult<String> {
+    let diagram = Diagram::new(dag)?;
+    let mut out = String::new();
+
+    writeln!(out, "digraph dag {{")?;
+    writeln!(out, "    rankdir=TB;")?;
+    writeln!(out, "    node [fontname=\"Helvetica\", fontsize=11];")?;
+    writeln!(out, "    edge [fontname=\"Helvetica\", fontsize=9];")?;
+
+    for (index, group) in diagram.groups.iter().enumerate() {
+        writeln!(out)?;
+        writeln!(out, "    subgraph cluster_{index} {{")?;
+        writeln!(
+            out,
+            "        label={};",
+            dot_string(&format!("Entry group: {}", group.name))
+        )?;
+        writeln!(out, "        style=dashed;")?;
+
+        for node in &group.nodes {
+            writeln!(out, "        {};", dot_node(&diagram.nodes[*node]))?;
+        }
+
+        writeln!(out, "    }}")?;
+    }
+
+    writeln!(out)?;
+
+    for node in diagram.ungrouped_nodes() {
+        writeln!(out, "    {};", dot_node(node))?;
+    }
+
+    writeln!(out)?;
+
+    for edge in &diagram.edges {
+        let attributes = match edge.kind {
+            Some(EdgeKind::Normal) | None => String::new(),
+            Some(EdgeKind::ForEach) => {
+                " [label=\"for each\", style=bold, color=\"#1f77b4\"]".to_string()
+            }
+            Some(EdgeKind::Collect) => {
+                " [label=\"collect\", style=bold, color=\"#2ca02c\"]".to_string()
+            }
+            Some(EdgeKind::DoWhile) => {
+                " [label=\"do while\", style=dashed, color=\"#ff7f0e\", constraint=false]"
+                    .to_string()
+            }
+            Some(EdgeKind::Break) => {
+                " [label=\"break\", style=dashed, color=\"#d62728\"]".to_string()
+            }
+            Some(EdgeKind::Static) => {
+                " [label=\"static\", style=dotted, color=\"#7f7f7f\"]".to_string()
+            }
+        };
+
+        writeln!(
+            out,
+            "    {} -> {}{attributes};",
+            dot_string(&diagram.nodes[edge.from].id),
+            dot_string(&diagram.nodes[edge.to].id)
+        )?;
+    }
+
+    writeln!(out, "}}")?;
+
+    Ok(out)
+}
+
+/// Render `dag` as a Mermaid flowchart.
+pub fn to_mermaid(dag: &DagSpec) -> AnyResult<String> {
+    let diagram = Diagram::new(dag)?;
+    let mut out = String::new();
+
+    writeln!(out, "flowchart TB")?;
+
+    for (index, group) in diagram.groups.iter().enumerate() {
+        writeln!(
+            out,
+            "    subgraph group{index}[{}]",
+            mermaid_string(&format!("Entry group: {}", group.name))
+        )?;
+
+        for node in &group.nodes {
+            writeln!(
+                out,
+                "        {}",
+                mermaid_node(*node, &diagram.nodes[*node])
+            )?;
+        }
+
+        writeln!(out, "    end")?;
+    }
+
+    for (index, node) in diagram.nodes.iter().enumerate() {
+        if !diagram.is_grouped(index) {
+            writeln!(out, "    {}", mermaid_node(index, node))?;
+        }
+    }
+
+    // Mermaid styles links by their position so keep track of them.
+    let mut link_styles = vec![];
+
+    for (index, edge) in diagram.edges.iter().enumerate() {
+        let arrow = match edge.kind {
+            Some(EdgeKind::Normal) | None => "-->".to_string(),
+            Some(EdgeKind::ForEach) => "== for each ==>".to_string(),
+            Some(EdgeKind::Collect) => "== collect ==>".to_string(),
+            Some(EdgeKind::DoWhile) => "-. do while .->".to_string(),
+            Some(EdgeKind::Break) => "-. break .->".to_string(),
+            Some(EdgeKind::Static) => "-. static .->".to_string(),
+        };
+
+        let color = match edge.kind {
+            Some(EdgeKind::ForEach) => Some("#1f77b4"),
+            Some(EdgeKind::Collect) => Some("#2ca02c"),
+            Some(EdgeKind::DoWhile) => Some("#ff7f0e"),
+            Some(EdgeKind::Break) => Some("#d62728"),
+            Some(EdgeKind::Static) => Some("#7f7f7f"),
+            Some(EdgeKind::Normal) | None => None,
+        };
+
+        if let Some(color) = color {
+            link_styles.push(format!("    linkStyle {index} stroke:{color}"));
+        }
+
+        writeln!(out, "    n{} {arrow} n{}", edge.from, edge.to)?;
+    }
+
+    for link_style in link_styles {
+        writeln!(out, "{link_style}")?;
+    }
+
+    Ok(out)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NodeKind {
+    Vertex,
+    OutputVariant,
+    OutputPort,
+    InputPort,
+    EntryPort,
+    DefaultValue,
+}
+
+#[derive(Debug)]
+struct Node {
+    id: String,
+    kind: NodeKind,
+    label: String,
+}
+
+#[derive(Debug)]
+struct Edge {
+    from: usize,
+    to: usize,
+    /// Only edges between an output port and an input port have a kind.
+    kind: Option<EdgeKind>,
+}
+
+#[derive(Debug)]
+struct Group {
+    name: String,
+    nodes: Vec<usize>,
+}
+
+/// Renderer independent view of the DAG graph.
+#[derive(Debug, Default)]
+struct Diagram {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    groups: Vec<Group>,
+    index: HashMap<String, usize>,
+}
+
+impl Diagram {
+    fn new(dag: &DagSpec) -> AnyResult<Self> {
+        let (graph, _) = try_into_graph(dag)?;
+        let mut diagram = Self::default();
+
+        let tool_fqns = dag
+            .vertices
+            .iter()
+            .map(|vertex| (vertex.name.as_str(), vertex_tool_fqn(&vertex.kind)))
+            .collect::<HashMap<_, _>>();
+
+        for node in graph.node_indices() {
+            let (kind, label) = match &graph[node] {
+                GraphNode::Vertex { name } => {
+                    let name = loop_vertex(name, dag);
+                    let label = match tool_fqns.get(name) {
+                        Some(tool_fqn) => format!("{name}\n{tool_fqn}"),
+                        None => name.to_string(),
+                    };
+
+                    (NodeKind::Vertex, label)
+                }
+                GraphNode::OutputVariant { name, .. } => (NodeKind::OutputVariant, name.clone()),
+                GraphNode::OutputPort { name, .. } => (NodeKind::OutputPort, name.clone()),
+                GraphNode::InputPort { name, .. } => (NodeKind::InputPort, name.clone()),
+            };
+
+            diagram.add_node(node_id(&graph[node], dag), kind, label);
+        }
+
+        for edge in graph.edge_references() {
+            let from = diagram.index[&node_id(&graph[edge.source()], dag)];
+            let to = diagram.index[&node_id(&graph[edge.target()], dag)];
+            let kind = match graph[edge.source()] {
+                GraphNode::OutputPort { .. } => Some(*edge.weight()),
+                _ => None,
+            };
+
+            // Do-while edges share the input port of the loop vertex.
+            let exists = diagram
+                .edges
+                .iter()
+                .any(|edge| edge.from == from && edge.to == to && edge.kind == kind);
+
+            if !exists {
+                diagram.edges.push(Edge { from, to, kind });
+            }
+        }
+
+        for default_value in &dag.default_values {
+            let port = diagram.add_node(
+                format!(
+                    "default:{}.{}",
+                    default_value.vertex, default_value.input_port
+                ),
+                NodeKind::DefaultValue,
+                format!("{} (default)", default_value.input_port),
+            );
+            let vertex = diagram.index[&format!("vertex:{}", default_value.vertex)];
+
+            diagram.edges.push(Edge {
+                from: port,
+                to: vertex,
+                kind: None,
+            });
+        }
+
+        // Entry ports are drawn once per entry group that invokes them.
+        let groups = if dag.entry_groups.is_empty() {
+            vec![(
+                DEFAULT_ENTRY_GROUP.to_string(),
+                dag.vertices
+                    .iter()
+                    .filter(|vertex| !vertex.entry_ports.is_empty())
+                    .map(|vertex| vertex.name.clone())
+                    .collect::<Vec<_>>(),
+            )]
+        } else {
+            dag.entry_groups
+                .iter()
+                .map(|group| (group.name.clone(), group.vertices.clone()))
+                .collect()
+        };
+
+        for (group, vertices) in groups {
+            let mut nodes = vec![];
+
+            for vertex in dag
+                .vertices
+                .iter()
+                .filter(|vertex| vertices.contains(&vertex.name))
+            {
+                let target = diagram.index[&format!("vertex:{}", vertex.name)];
+
+                for entry_port in &vertex.entry_ports {
+                    let port = diagram.add_node(
+                        format!("entry:{group}:{}.{}", vertex.name, entry_port.name),
+                        NodeKind::EntryPort,
+                        format!("{}.{}", vertex.name, entry_port.name),
+                    );
+
+                    diagram.edges.push(Edge {
+                        from: port,
+                        to: target,
+                        kind: None,
+                    });
+
+                    nodes.push(port);
+                }
+            }
+
+            diagram.groups.push(Group { name: group, nodes });
+        }
+
+        Ok(diagram)
+    }
+
+    fn add_node(&mut self, id: String, kind: NodeKind, label: String) -> usize {
+        if let Some(index) = self.index.get(&id) {
+            return *index;
+        }
+
+        let index = self.nodes.len();
+
+        self.index.insert(id.clone(), index);
+        self.nodes.push(Node { id, kind, label });
+
+        index
+    }
+
+    fn is_grouped(&self, node: usize) -> bool {
+        self.groups.iter().any(|group| group.nodes.contains(&node))
+    }
+
+    fn ungrouped_nodes(&self) -> impl Iterator<Item = &Node> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.is_grouped(*index))
+            .map(|(_, node)| node)
+    }
+}
+
+/// Stable identifier of a graph node. Phantom do-while vertices and their
+/// input ports are mapped back to the loop vertex.
+fn node_id(node: &GraphNode, dag: &DagSpec) -> String {
+    match node {
+        GraphNode::Vertex { name } => format!("vertex:{}", loop_vertex(name, dag)),
+        GraphNode::OutputVariant { vertex, name } => format!("variant:{vertex}.{name}"),
+        GraphNode::OutputPort {
+            vertex,
+            variant,
+            name,
+        } => format!("output:{vertex}.{variant}.{name}"),
+        GraphNode::InputPort { vertex, name } => {
+            format!("input:{}.{name}", loop_vertex(vertex, dag))
+        }
+    }
+}
+
+fn loop_vertex<'a>(vertex: &'a str, dag: &DagSpec) -> &'a str {
+    match vertex.strip_suffix("-do-while") {
+        Some(name) if dag.vertices.iter().any(|vertex| vertex.name == name) => name,
+        _ => vertex,
+    }
+}
+
+fn dot_node(node: &Node) -> String {
+    let shape = match node.kind {
+        NodeKind::Vertex => "shape=box, style=\"rounded,filled\", fillcolor=\"#f2f2f2\"",
+        NodeKind::OutputVariant => "shape=ellipse",
+        NodeKind::OutputPort => "shape=house, fontsize=9",
+        NodeKind::InputPort => "shape=invhouse, fontsize=9",
+        NodeKind::EntryPort => "shape=cds, fontsize=9",
+        NodeKind::DefaultValue => "shape=note, fontsize=9",
+    };
+
+    format!(
+        "{} [label={}, {shape}]",
+        dot_string(&node.id),
+        dot_string(&node.label)
+    )
+}
+
+fn dot_string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+fn mermaid_node(index: usize, node: &Node) -> String {
+    let label = mermaid_string(&node.label);
+
+    match node.kind {
+        NodeKind::Vertex => format!("n{index}[{label}]"),
+        NodeKind::OutputVariant => format!("n{index}([{label}])"),
+        NodeKind::OutputPort => format!("n{index}[/{label}/]"),
+        NodeKind::InputPort => format!("n{index}[\\{label}\\]"),
+        NodeKind::EntryPort => format!("n{index}>{label}]"),
+        NodeKind::DefaultValue => format!("n{index}[({label})]"),
+    }
+}
+
+fn mermaid_string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value.replace('"', "#quot;").replace('\n', "<br/>")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> DagSpec {
+        crate::dag::json::parse_dag_spec(input).unwrap()
+    }
+
+    #[test]
+    fn test_dot_styles_edges_and_clusters_entry_groups() {
+        let dag = parse(include_str!("_dags/math_branching_groups.json"));
+
+        let dot = to_dot(&dag).unwrap();
+
+        assert!(dot.starts_with("digraph dag {"));
+        assert!(dot.contains("subgraph cluster_0 {"));
+        assert!(dot.contains("label=\"Entry group: "));
+        assert!(dot.contains("shape=cds"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_do_while_points_back_at_loop_vertex() {
+        let dag = parse(include_str!("_dags/double_do_while_static_valid.json"));
+
+        let dot = to_dot(&dag).unwrap();
+
+        assert!(!dot.contains("-do-while"));
+        assert!(dot.contains(
+            "\"output:decide.lt.a\" -> \"input:loop_1.a\" [label=\"do while\", style=dashed, color=\"#ff7f0e\", constraint=false];"
+        ));
+        assert!(dot.contains(
+            "\"output:start.lt.comp\" -> \"input:decide.comp\" [label=\"static\", style=dotted, color=\"#7f7f7f\"];"
+        ));
+        assert!(dot.contains("\"output:decide.gte.a\" -> \"input:output.a\" [label=\"break\""));
+    }
+
+    #[test]
+    fn test_mermaid_flowchart() {
+        let dag = parse(include_str!("_dags/double_do_while_static_valid.json"));
+
+        let mermaid = to_mermaid(&dag).unwrap();
+
+        assert!(mermaid.starts_with("flowchart TB\n"));
+        assert!(mermaid.contains("subgraph group0[\"Entry group: _default_group\"]"));
+        assert!(mermaid.contains("-. do while .->"));
+        assert!(mermaid.contains("-. static .->"));
+        assert!(mermaid.contains("linkStyle"));
+        assert!(!mermaid.contains("-do-while"));
+    }
+
+    #[test]
+    fn test_rendering_is_deterministic() {
+        let dag = parse(include_str!("_dags/trip_planner.json"));
+
+        assert_eq!(to_dot(&dag).unwrap(), to_dot(&dag).unwrap());
+        assert_eq!(to_mermaid(&dag).unwrap(), to_mermaid(&dag).unwrap());
+    }
+}
@@ -0,0 +1,877 @@
+//! Schema-aware DAG validation against registered tool input and output
+//! schemas.
+//!
+//! [`crate::dag::validator::validate`] only checks the shape of the graph.
+//! [`validate_against_registry`] additionally fetches the tool behind every
+//! vertex and checks that the DAG actually fits those tools:
+//!
+//! - every edge leaves from an output port that exists on the source tool's
+//!   output variant,
+//! - every input port referenced by an edge, entry port or default value is
+//!   declared by the tool and every required input port is satisfied exactly
+//!   once,
+//! - inline default values conform to the tool's input schema,
+//! - connected ports have compatible types, taking for-each and collect edges
+//!   into account.
+//!
+//! Off-chain tools publish JSON schemas generated by `schemars`. On-chain
+//! tools publish the Move-derived schemas from [`crate::onchain_schema_gen`],
+//! which are normalized into JSON schemas before comparison.
+
+use {
+    crate::{
+        move_bindings::{interface::graph::EdgeKind, primitives::data::NexusData},
+        types::{DagSpec, DagVertexKind, Tool, ToolRef},
+        ToolFqn,
+    },
+    anyhow::{anyhow, bail, Result as AnyResult},
+    serde_json::{json, Map, Value},
+    std::collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+};
+
+/// How many `$ref`s may be resolved within one another.
+const MAX_REF_DEPTH: usize = 16;
+
+/// Fetch the tool behind every vertex of `dag` from the tool registry and
+/// check the DAG against the tools' input and output schemas.
+///
+/// All violations are reported together in the returned error. The crawler
+/// must have a tool registry set, see
+/// [`crate::nexus::crawler::Crawler::with_tool_registry`].
+#[cfg(feature = "nexus")]
+pub async fn validate_against_registry(
+    dag: &DagSpec,
+    crawler: &crate::nexus::crawler::Crawler,
+) -> AnyResult<()> {
+    let mut tools = HashMap::new();
+
+    for vertex in &dag.vertices {
+        let tool_fqn = vertex_tool_fqn(&vertex.kind);
+
+        if tools.contains_key(tool_fqn) {
+            continue;
+        }
+
+        let tool = crawler
+            .get_tool(tool_fqn)
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "Tool '{tool_fqn}' used by 'Vertex: {}' could not be fetched from the registry: {e}",
+                    vertex.name
+                )
+            })?
+            .data;
+        let schema = ToolSchema::from_tool(&tool)
+            .map_err(|e| anyhow!("Tool '{tool_fqn}' has invalid schemas: {e}"))?;
+
+        tools.insert(tool_fqn.clone(), schema);
+    }
+
+    validate_against_schemas(dag, &tools)
+}
+
+/// Check `dag` against already resolved tool schemas keyed by tool FQN. This
+/// is the offline half of [`validate_against_registry`].
+pub fn validate_against_schemas(
+    dag: &DagSpec,
+    tools: &HashMap<ToolFqn, ToolSchema>,
+) -> AnyResult<()> {
+    let mut violations = vec![];
+    let mut vertex_tools = HashMap::new();
+
+    for vertex in &dag.vertices {
+        let tool_fqn = vertex_tool_fqn(&vertex.kind);
+
+        match tools.get(tool_fqn) {
+            Some(tool) => {
+                vertex_tools.insert(vertex.name.as_str(), tool);
+            }
+            None => violations.push(format!(
+                "'Vertex: {}' uses tool '{tool_fqn}' which has no known schema.",
+                vertex.name
+            )),
+        }
+    }
+
+    // Check that edges and outputs leave from declared output ports.
+    for edge in &dag.edges {
+        let Some(tool) = vertex_tools.get(edge.from.vertex.as_str()) else {
+            continue;
+        };
+
+        if let Err(e) = tool.output_port(&edge.from.output_variant, &edge.from.output_port) {
+            violations.push(format!(
+                "'Output port: {}.{}.{}' {e}",
+                edge.from.vertex, edge.from.output_variant, edge.from.output_port
+            ));
+        }
+    }
+
+    for output in &dag.outputs {
+        let Some(tool) = vertex_tools.get(output.vertex.as_str()) else {
+            continue;
+        };
+
+        if let Err(e) = tool.output_port(&output.output_variant, &output.output_port) {
+            violations.push(format!(
+                "'Output port: {}.{}.{}' {e}",
+                output.vertex, output.output_variant, output.output_port
+            ));
+        }
+    }
+
+    // Count how each input port is satisfied. Do-while edges only feed
+    // subsequent iterations so they don't satisfy a port on their own.
+    let mut edge_inputs = HashSet::new();
+    let mut loop_inputs = BTreeSet::new();
+    let mut sources = BTreeMap::<(&str, &str), usize>::new();
+
+    for edge in &dag.edges {
+        let input = (edge.to.vertex.as_str(), edge.to.input_port.as_str());
+
+        if edge.kind == EdgeKind::DoWhile {
+            loop_inputs.insert(input);
+        } else if edge_inputs.insert(input) {
+            *sources.entry(input).or_default() += 1;
+        }
+    }
+
+    for vertex in &dag.vertices {
+        for entry_port in &vertex.entry_ports {
+            *sources
+                .entry((vertex.name.as_str(), entry_port.name.as_str()))
+                .or_default() += 1;
+        }
+    }
+
+    for default_value in &dag.default_values {
+        *sources
+            .entry((
+                default_value.vertex.as_str(),
+                default_value.input_port.as_str(),
+            ))
+            .or_default() += 1;
+    }
+
+    let referenced = sources
+        .keys()
+        .chain(loop_inputs.iter())
+        .collect::<BTreeSet<_>>();
+
+    for &(vertex, port) in referenced {
+        let Some(tool) = vertex_tools.get(vertex) else {
+            continue;
+        };
+
+        if !tool.inputs.contains_key(port) {
+            violations.push(format!(
+                "'Input port: {vertex}.{port}' is not an input of the tool."
+            ));
+        }
+    }
+
+    for vertex in &dag.vertices {
+        let Some(tool) = vertex_tools.get(vertex.name.as_str()) else {
+            continue;
+        };
+
+        for (port, input) in &tool.inputs {
+            let count = sources
+                .get(&(vertex.name.as_str(), port.as_str()))
+                .copied()
+                .unwrap_or_default();
+
+            if count == 0 && input.required {
+                violations.push(format!(
+                    "'Input port: {}.{port}' is required but has no edge, entry port or default value.",
+                    vertex.name
+                ));
+            }
+
+            if count > 1 {
+                violations.push(format!(
+                    "'Input port: {}.{port}' is satisfied more than once by edges, entry ports or default values.",
+                    vertex.name
+                ));
+            }
+        }
+    }
+
+    // Check that inline default values match the input schema.
+    for default_value in &dag.default_values {
+        let Some(input) = vertex_tools
+            .get(default_value.vertex.as_str())
+            .and_then(|tool| tool.inputs.get(&default_value.input_port))
+        else {
+            continue;
+        };
+
+        // Remote data cannot be checked without fetching it.
+        if !default_value.value.is_inline() {
+            continue;
+        }
+
+        let value = match inline_json(&default_value.value) {
+            Ok(value) => value,
+            Err(e) => {
+                violations.push(format!(
+                    "'Input port: {}.{}' has a default value that {e}",
+                    default_value.vertex, default_value.input_port
+                ));
+
+                continue;
+            }
+        };
+
+        if let Err(e) = jsonschema::draft202012::validate(&input.schema, &value) {
+            violations.push(format!(
+                "'Input port: {}.{}' has a default value that does not match the input schema: {e}",
+                default_value.vertex, default_value.input_port
+            ));
+        }
+    }
+
+    // Check that connected ports have compatible types.
+    for edge in &dag.edges {
+        let (Some(from_tool), Some(to_tool)) = (
+            vertex_tools.get(edge.from.vertex.as_str()),
+            vertex_tools.get(edge.to.vertex.as_str()),
+        ) else {
+            continue;
+        };
+
+        let (Ok(from), Some(to)) = (
+            from_tool.output_port(&edge.from.output_variant, &edge.from.output_port),
+            to_tool.inputs.get(&edge.to.input_port),
+        ) else {
+            continue;
+        };
+
+        let edge_ident = format!(
+            "Edge from 'Output port: {}.{}.{}' to 'Input port: {}.{}'",
+            edge.from.vertex,
+            edge.from.output_variant,
+            edge.from.output_port,
+            edge.to.vertex,
+            edge.to.input_port
+        );
+
+        let pair = match edge.kind {
+            EdgeKind::ForEach => match array_items(from) {
+                Ok(items) => Some((items, &to.schema)),
+                Err(()) => {
+                    violations.push(format!(
+                        "{edge_ident} is a for-each edge but the output port is not an array."
+                    ));
+
+                    None
+                }
+            },
+            EdgeKind::Collect => match array_items(&to.schema) {
+                Ok(items) => Some((from, items)),
+                Err(()) => {
+                    violations.push(format!(
+                        "{edge_ident} is a collect edge but the input port is not an array."
+                    ));
+
+                    None
+                }
+            },
+            _ => Some((from, &to.schema)),
+        };
+
+        if let Some((from, to)) = pair {
+            if !types_compatible(from, to) {
+                violations.push(format!(
+                    "{edge_ident} connects incompatible types {} and {}.",
+                    type_label(from),
+                    type_label(to)
+                ));
+            }
+        }
+    }
+
+    if !violations.is_empty() {
+        bail!(
+            "The DAG does not match the registered tool schemas:\n{}",
+            violations
+                .iter()
+                .map(|violation| format!("- {violation}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    Ok(())
+}
+
+/// Input and output ports declared by a tool, normalized into self-contained
+/// JSON schemas.
+#[derive(Clone, Debug, Default)]
+pub struct ToolSchema {
+    inputs: BTreeMap<String, InputPortSchema>,
+    outputs: BTreeMap<String, BTreeMap<String, Value>>,
+}
+
+#[derive(Clone, Debug)]
+struct InputPortSchema {
+    schema: Value,
+    required: bool,
+}
+
+impl ToolSchema {
+    /// Parse the schemas stored on a registered [`Tool`].
+    pub fn from_tool(tool: &Tool) -> AnyResult<Self> {
+        let input = tool.input_schema_json()?;
+        let output = tool.output_schema_json()?;
+
+        match tool.reference() {
+            ToolRef::Http { .. } => Self::from_json_schemas(&input, &output),
+            ToolRef::Sui { .. } => Self::from_onchain_schemas(&input, &output),
+        }
+    }
+
+    /// Parse the `schemars` generated schemas of an off-chain tool. The input
+    /// schema is an object whose properties are the input ports and the
+    /// output schema is a `oneOf` over the output variants.
+    pub fn from_json_schemas(input: &Value, output: &Value) -> AnyResult<Self> {
+        let required = input["required"]
+            .as_array()
+            .map(|required| required.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_else(HashSet::<&str>::new);
+
+        let inputs = input["properties"]
+            .as_object()
+            .ok_or_else(|| anyhow!("Tool input schema has no properties"))?
+            .iter()
+            .map(|(port, schema)| {
+                let input = InputPortSchema {
+                    schema: inline_refs(schema, input, &mut vec![])
+                        .map_err(|e| anyhow!("Input port '{port}': {e}"))?,
+                    required: required.contains(port.as_str()),
+                };
+
+                Ok((port.clone(), input))
+            })
+            .collect::<AnyResult<_>>()?;
+
+        let mut outputs = BTreeMap::new();
+
+        let variants = output["oneOf"]
+            .as_array()
+            .ok_or_else(|| anyhow!("Tool output schema has no 'oneOf' variants"))?;
+
+        for variant in variants {
+            let variant = inline_refs(variant, output, &mut vec![])
+                .map_err(|e| anyhow!("Output variant: {e}"))?;
+
+            // Unit variants are serialized as plain strings.
+            if let Some(names) = variant["enum"].as_array() {
+                for name in names.iter().filter_map(Value::as_str) {
+                    outputs.insert(name.to_string(), BTreeMap::new());
+                }
+
+                continue;
+            }
+
+            let Some((name, ports)) = variant["properties"]
+                .as_object()
+                .filter(|properties| properties.len() == 1)
+                .and_then(|properties| properties.iter().next())
+            else {
+                bail!("Tool output variant schema must have exactly one property");
+            };
+
+            let ports = ports["properties"]
+                .as_object()
+                .map(|ports| {
+                    ports
+                        .iter()
+                        .map(|(port, schema)| (port.clone(), schema.clone()))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            outputs.insert(name.clone(), ports);
+        }
+
+        Ok(Self { inputs, outputs })
+    }
+
+    /// Parse the Move-derived schemas of an on-chain tool as generated by
+    /// [`crate::onchain_schema_gen`].
+    pub fn from_onchain_schemas(input: &Value, output: &Value) -> AnyResult<Self> {
+        let inputs = input
+            .as_object()
+            .ok_or_else(|| anyhow!("On-chain tool input schema must be an object"))?
+            .iter()
+            .map(|(port, schema)| {
+                // The current execution is provided by the workflow itself.
+                let implicit = schema["nexus_current_execution"].as_bool() == Some(true);
+                let input = InputPortSchema {
+                    schema: move_schema_to_json_schema(schema),
+                    required: !implicit,
+                };
+
+                (port.clone(), input)
+            })
+            .collect();
+
+        let outputs = output
+            .as_object()
+            .ok_or_else(|| anyhow!("On-chain tool output schema must be an object"))?
+            .iter()
+            .map(|(variant, schema)| {
+                let ports = schema["fields"]
+                    .as_object()
+                    .map(|fields| {
+                        fields
+                            .iter()
+                            .map(|(port, schema)| {
+                                (port.clone(), move_schema_to_json_schema(schema))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                (variant.clone(), ports)
+            })
+            .collect();
+
+        Ok(Self { inputs, outputs })
+    }
+
+    fn output_port(&self, variant: &str, port: &str) -> Result<&Value, String> {
+        let ports = self.outputs.get(variant).ok_or_else(|| {
+            format!("references output variant '{variant}' which the tool does not declare.")
+        })?;
+
+        ports
+            .get(port)
+            .ok_or_else(|| format!("is not declared on output variant '{variant}' of the tool."))
+    }
+}
+
+pub(super) fn vertex_tool_fqn(kind: &DagVertexKind) -> &ToolFqn {
+    match kind {
+        DagVertexKind::OffChain { tool_fqn } | DagVertexKind::OnChain { tool_fqn } => tool_fqn,
+    }
+}
+
+/// Decode inline [`NexusData`] into the JSON value the tool receives. `many`
+/// payloads become an array.
+pub(super) fn inline_json(data: &NexusData) -> Result<Value, String> {
+    let decode = |bytes: &[u8]| {
+        serde_json::from_slice::<Value>(bytes).map_err(|e| format!("is not valid JSON: {e}"))
+    };
+
+    if data.one.is_empty() {
+        return data
+            .many
+            .iter()
+            .map(|bytes| decode(bytes))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array);
+    }
+
+    decode(&data.one)
+}
+
+/// Replace local `$ref`s in `schema` with the definitions they point to in
+/// `root` so that port schemas can be compared and validated on their own.
+/// `refs` holds the references currently being resolved.
+///
+/// Recursive references and chains of more than [`MAX_REF_DEPTH`] references
+/// are rejected, as they cannot be inlined.
+fn inline_refs(schema: &Value, root: &Value, refs: &mut Vec<String>) -> AnyResult<Value> {
+    match schema {
+        Value::Object(object) => {
+            if let Some((reference, target)) =
+                object
+                    .get("$ref")
+                    .and_then(Value::as_str)
+                    .and_then(|reference| {
+                        let pointer = reference.strip_prefix('#')?;
+                        Some((reference, root.pointer(pointer)?))
+                    })
+            {
+                if refs.iter().any(|resolving| resolving == reference) {
+                    bail!("Schema reference '{reference}' is recursive");
+                }
+
+                if refs.len() == MAX_REF_DEPTH {
+                    bail!("Schema references are nested more than {MAX_REF_DEPTH} levels deep");
+                }
+
+                refs.push(reference.to_string());
+                let inlined = inline_refs(target, root, refs);
+                refs.pop();
+
+                return inlined;
+            }
+
+            object
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), inline_refs(value, root, refs)?)))
+                .collect::<AnyResult<Map<_, _>>>()
+                .map(Value::Object)
+        }
+        Value::Array(values) => values
+            .iter()
+            .map(|value| inline_refs(value, root, refs))
+            .collect::<AnyResult<Vec<_>>>()
+            .map(Value::Array),
+        value => Ok(value.clone()),
+    }
+}
+
+/// Convert a single Move-derived port schema into a JSON schema describing
+/// how the value is represented in JSON. Unknown types accept anything.
+fn move_schema_to_json_schema(schema: &Value) -> Value {
+    match schema["type"].as_str() {
+        Some("bool") => json!({ "type": "boolean" }),
+        Some("u8" | "u16" | "u32") => json!({ "type": "integer", "minimum": 0 }),
+        // Large integers may be passed as strings to avoid precision loss.
+        Some("u64" | "u128" | "u256") => json!({ "type": ["integer", "string"] }),
+        Some("address" | "object_id" | "string") => json!({ "type": "string" }),
+        Some("vector") => json!({
+            "type": "array",
+            "items": move_schema_to_json_schema(&schema["element_type"]),
+        }),
+        _ => json!({}),
+    }
+}
+
+/// The declared JSON types of a schema, or `None` if the schema doesn't
+/// constrain the type.
+fn json_types(schema: &Value) -> Option<HashSet<&str>> {
+    match &schema["type"] {
+        Value::String(kind) => Some(HashSet::from([kind.as_str()])),
+        Value::Array(kinds) => Some(kinds.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
+
+/// Return the item schema of an array schema. Schemas without a declared
+/// type are treated as arrays of anything.
+fn array_items(schema: &Value) -> Result<&Value, ()> {
+    static ANY: Value = Value::Null;
+
+    match json_types(schema) {
+        Some(kinds) if !kinds.contains("array") => Err(()),
+        _ => Ok(schema.get("items").unwrap_or(&ANY)),
+    }
+}
+
+/// Two port schemas are compatible unless their declared types can never
+/// overlap. Integers are considered numbers.
+fn types_compatible(from: &Value, to: &Value) -> bool {
+    let (Some(from), Some(to)) = (json_types(from), json_types(to)) else {
+        return true;
+    };
+
+    let normalize = |kind: &str| match kind {
+        "integer" => "number",
+        kind => kind,
+    };
+
+    from.iter()
+        .any(|from| to.iter().any(|to| normalize(from) == normalize(to)))
+}
+
+fn type_label(schema: &Value) -> String {
+    match json_types(schema) {
+        Some(kinds) => {
+            let mut kinds = kinds.into_iter().collect::<Vec<_>>();
+            kinds.sort();
+
+            format!("'{}'", kinds.join(" | "))
+        }
+        None => "'any'".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{dag::builder::DagBuilder, fqn},
+        assert_matches::assert_matches,
+    };
+
+    fn add_tool() -> ToolSchema {
+        ToolSchema::from_json_schemas(
+            &json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "a": { "type": "integer" },
+                    "b": { "$ref": "#/$defs/Operand" },
+                    "label": { "type": ["string", "null"] }
+                },
+                "required": ["a", "b"],
+                "$defs": { "Operand": { "type": "integer" } }
+            }),
+            &json!({
+                "oneOf": [
+                    {
+                        "type": "object",
+                        "properties": {
+                            "ok": {
+                                "type": "object",
+                                "properties": { "result": { "type": "integer" } }
+                            }
+                        },
+                        "required": ["ok"]
+                    },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "err": {
+                                "type": "object",
+                                "properties": { "reason": { "type": "string" } }
+                            }
+                        },
+                        "required": ["err"]
+                    }
+                ]
+            }),
+        )
+        .unwrap()
+    }
+
+    fn split_tool() -> ToolSchema {
+        ToolSchema::from_json_schemas(
+            &json!({
+                "type": "object",
+                "properties": { "text": { "type": "string" } },
+                "required": ["text"]
+            }),
+            &json!({
+                "oneOf": [
+                    {
+                        "type": "object",
+                        "properties": {
+                            "ok": {
+                                "type": "object",
+                                "properties": {
+                                    "words": { "type": "array", "items": { "type": "string" } }
+                                }
+                            }
+                        }
+                    },
+                    { "type": "string", "enum": ["empty"] }
+                ]
+            }),
+        )
+        .unwrap()
+    }
+
+    fn tools() -> HashMap<ToolFqn, ToolSchema> {
+        HashMap::from([
+            (fqn!("xyz.taluslabs.math.i64.add@1"), add_tool()),
+            (fqn!("xyz.taluslabs.text.split@1"), split_tool()),
+        ])
+    }
+
+    fn chain() -> DagBuilder {
+        DagBuilder::new()
+            .vertex("a", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .vertex("b", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .entry_port("a.a")
+            .default_value("a.b", 1)
+            .edge("a.ok.result", "b.a")
+            .default_value("b.b", 2)
+    }
+
+    #[test]
+    fn test_matching_dag_is_valid() {
+        let dag = chain().build().unwrap();
+
+        assert!(validate_against_schemas(&dag, &tools()).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_output_port_and_variant() {
+        let mut dag = chain().build().unwrap();
+        dag.edges[0].from.output_port = "missing".to_string();
+
+        let res = validate_against_schemas(&dag, &tools());
+
+        assert_matches!(res, Err(e) if e.to_string().contains("'Output port: a.ok.missing' is not declared on output variant 'ok' of the tool."));
+
+        dag.edges[0].from.output_variant = "nope".to_string();
+
+        let res = validate_against_schemas(&dag, &tools());
+
+        assert_matches!(res, Err(e) if e.to_string().contains("'Output port: a.nope.missing' references output variant 'nope' which the tool does not declare."));
+    }
+
+    #[test]
+    fn test_unsatisfied_and_unknown_input_ports() {
+        let mut dag = chain().build().unwrap();
+        dag.default_values[1].input_port = "c".to_string();
+
+        let res = validate_against_schemas(&dag, &tools());
+
+        assert_matches!(res, Err(e) if e.to_string().contains("'Input port: b.c' is not an input of the tool.")
+            && e.to_string().contains("'Input port: b.b' is required but has no edge, entry port or default value."));
+    }
+
+    #[test]
+    fn test_input_port_satisfied_twice() {
+        let mut dag = chain().build().unwrap();
+        dag.default_values.push(dag.default_values[1].clone());
+
+        let res = validate_against_schemas(&dag, &tools());
+
+        assert_matches!(res, Err(e) if e.to_string().contains("'Input port: b.b' is satisfied more than once"));
+    }
+
+    #[test]
+    fn test_default_value_must_match_schema() {
+        let dag = DagBuilder::new()
+            .vertex("a", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .entry_port("a.a")
+            .default_value("a.b", "one")
+            .build()
+            .unwrap();
+
+        let res = validate_against_schemas(&dag, &tools());
+
+        assert_matches!(res, Err(e) if e.to_string().contains("'Input port: a.b' has a default value that does not match the input schema"));
+    }
+
+    #[test]
+    fn test_incompatible_port_types() {
+        let mut dag = chain().build().unwrap();
+        dag.vertices[1].kind = DagVertexKind::OffChain {
+            tool_fqn: fqn!("xyz.taluslabs.text.split@1"),
+        };
+        dag.edges[0].to.input_port = "text".to_string();
+        dag.default_values.pop();
+
+        let res = validate_against_schemas(&dag, &tools());
+
+        assert_matches!(res, Err(e) if e.to_string().contains("Edge from 'Output port: a.ok.result' to 'Input port: b.text' connects incompatible types 'integer' and 'string'."));
+    }
+
+    #[test]
+    fn test_for_each_compares_items() {
+        let dag = DagBuilder::new()
+            .vertex("s", fqn!("xyz.taluslabs.text.split@1"))
+            .vertex("t", fqn!("xyz.taluslabs.text.split@1"))
+            .vertex("a", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .entry_port("s.text")
+            .for_each("s.ok.words", "t.text")
+            .collect("t.ok.words", "a.a")
+            .default_value("a.b", 1)
+            .build()
+            .unwrap();
+
+        let res = validate_against_schemas(&dag, &tools());
+
+        assert_matches!(res, Err(e) if !e.to_string().contains("'Input port: t.text'")
+            && e.to_string().contains("is a collect edge but the input port is not an array."));
+    }
+
+    #[test]
+    fn test_onchain_schemas_are_normalized() {
+        let tool = ToolSchema::from_onchain_schemas(
+            &json!({
+                "0": { "type": "u64", "description": "64-bit unsigned integer" },
+                "1": { "type": "object", "nexus_current_execution": true },
+                "2": { "type": "vector", "element_type": { "type": "bool" } }
+            }),
+            &json!({
+                "ok": { "type": "variant", "fields": { "value": { "type": "u64" } } }
+            }),
+        )
+        .unwrap();
+
+        assert!(tool.inputs["0"].required);
+        assert!(!tool.inputs["1"].required);
+        assert_eq!(
+            tool.inputs["2"].schema,
+            json!({ "type": "array", "items": { "type": "boolean" } })
+        );
+        assert_eq!(
+            tool.output_port("ok", "value").unwrap(),
+            &json!({ "type": ["integer", "string"] })
+        );
+    }
+
+    fn output_schema() -> Value {
+        json!({
+            "oneOf": [{
+                "type": "object",
+                "properties": { "ok": { "type": "object", "properties": {} } },
+                "required": ["ok"]
+            }]
+        })
+    }
+
+    #[test]
+    fn test_deeply_nested_schemas_are_kept() {
+        let mut nested = json!({ "type": "integer" });
+        for _ in 0..2 * MAX_REF_DEPTH {
+            nested = json!({ "type": "object", "properties": { "inner": nested } });
+        }
+
+        let tool = ToolSchema::from_json_schemas(
+            &json!({ "type": "object", "properties": { "a": nested } }),
+            &output_schema(),
+        )
+        .unwrap();
+
+        assert_eq!(tool.inputs["a"].schema, nested);
+    }
+
+    #[test]
+    fn test_recursive_refs_are_rejected() {
+        let res = ToolSchema::from_json_schemas(
+            &json!({
+                "type": "object",
+                "properties": { "a": { "$ref": "#/$defs/Node" } },
+                "$defs": {
+                    "Node": {
+                        "type": "object",
+                        "properties": { "next": { "$ref": "#/$defs/Node" } }
+                    }
+                }
+            }),
+            &output_schema(),
+        );
+
+        assert_matches!(res, Err(e) if e.to_string().contains("Input port 'a'")
+            && e.to_string().contains("'#/$defs/Node' is recursive"));
+    }
+
+    #[test]
+    fn test_ref_chains_over_the_limit_are_rejected() {
+        let defs = (0..=MAX_REF_DEPTH)
+            .map(|i| {
+                let def = match i {
+                    MAX_REF_DEPTH => json!({ "type": "integer" }),
+                    i => json!({ "$ref": format!("#/$defs/D{}", i + 1) }),
+                };
+
+                (format!("D{i}"), def)
+            })
+            .collect::<Map<_, _>>();
+        let input = |root: &str| {
+            json!({
+                "type": "object",
+                "properties": { "a": { "$ref": format!("#/$defs/{root}") } },
+                "$defs": defs
+            })
+        };
+
+        let tool = ToolSchema::from_json_schemas(&input("D1"), &output_schema()).unwrap();
+        assert_eq!(tool.inputs["a"].schema, json!({ "type": "integer" }));
+
+        let res = ToolSchema::from_json_schemas(&input("D0"), &output_schema());
+        assert_matches!(res, Err(e) if e.to_string().contains("nested more than 16 levels deep"));
+    }
+}
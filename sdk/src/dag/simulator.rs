@@ -0,0 +1,1351 @@
+//! Offline simulator that executes a [`DagSpec`] with mock tool
+//! implementations.
+//!
+//! The simulator walks the DAG following the same rules as the on-chain
+//! workflow and records the [`NexusEventKind`]s a real execution would emit,
+//! so workflows can be unit-tested without a Sui node:
+//!
+//! - the chosen entry group decides which vertices receive the entry input,
+//! - a vertex is invoked once all of its input ports hold data,
+//! - normal edges pass data along the walk, static edges keep their data for
+//!   every later invocation of the target vertex,
+//! - do-while edges start the next iteration of a loop and break edges leave
+//!   it, both discarding data the loop body holds from the iteration that
+//!   just ended,
+//! - for-each edges invoke the target once per array element and collect
+//!   edges gather the results back into an array. A for-each over an empty
+//!   array fails the walk as there is nothing to collect,
+//! - an output variant without outgoing edges is an end state,
+//! - a failed tool applies the vertex or DAG [`PostFailureAction`] and
+//!   defaults to terminating the execution.
+//!
+//! Tools are either Rust closures or local tools served by `nexus-toolkit`.
+//!
+//! ```no_run
+//! use {
+//!     nexus_sdk::{
+//!         dag::{
+//!             builder::DagBuilder,
+//!             simulator::{DagSimulator, ToolOutput},
+//!         },
+//!         fqn,
+//!     },
+//!     serde_json::json,
+//!     std::collections::HashMap,
+//! };
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let dag = DagBuilder::new()
+//!     .vertex("add", fqn!("xyz.taluslabs.math.i64.add@1"))
+//!     .entry_port("add.a")
+//!     .default_value("add.b", 2)
+//!     .build()?;
+//!
+//! let input = HashMap::from([(
+//!     "add".to_string(),
+//!     HashMap::from([("a".to_string(), json!(40))]),
+//! )]);
+//!
+//! let trace = DagSimulator::new(dag)
+//!     .tool(fqn!("xyz.taluslabs.math.i64.add@1"), |input| {
+//!         let a = input["a"].as_i64().unwrap_or_default();
+//!         let b = input["b"].as_i64().unwrap_or_default();
+//!
+//!         Ok(ToolOutput::new("ok").port("result", a + b))
+//!     })
+//!     .run(None, input)
+//!     .await?;
+//!
+//! assert_eq!(trace.end_states().count(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use {
+    crate::{
+        dag::schema::{inline_json, vertex_tool_fqn},
+        events::NexusEventKind,
+        move_bindings::{
+            interface::graph::{
+                EdgeKind,
+                OutputPort,
+                OutputVariant,
+                PostFailureAction,
+                RuntimeVertex,
+            },
+            move_std::ascii::String as MoveString,
+            primitives::{
+                data::{DataTypeHint, NexusData, TypedNexusData},
+                tagged_output::TaggedOutput,
+            },
+            sui_framework::{
+                object::ID,
+                vec_map::{Entry as VecMapEntry, VecMap},
+            },
+            workflow::execution_events::{
+                EndStateReachedEvent,
+                ExecutionFinishedEvent,
+                WalkAdvancedEvent,
+                WalkCancelledEvent,
+                WalkFailedEvent,
+            },
+        },
+        sui,
+        types::{DagSpec, DagVertex, DEFAULT_ENTRY_GROUP},
+        ToolFqn,
+    },
+    anyhow::{anyhow, bail, Result as AnyResult},
+    serde_json::Value,
+    std::{
+        collections::{BTreeMap, HashMap, HashSet, VecDeque},
+        sync::Arc,
+    },
+};
+
+/// How many tool invocations a simulation may perform before it is considered
+/// stuck, for example in a do-while loop that never breaks.
+pub const DEFAULT_MAX_INVOCATIONS: usize = 10_000;
+
+/// Result of a single tool invocation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ToolOutput {
+    /// Name of the output variant the tool chose.
+    pub variant: String,
+    /// Data for each output port of the variant.
+    pub ports: HashMap<String, Value>,
+}
+
+impl ToolOutput {
+    pub fn new(variant: impl Into<String>) -> Self {
+        Self {
+            variant: variant.into(),
+            ports: HashMap::new(),
+        }
+    }
+
+    /// Set the data of an output port.
+    pub fn port(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.ports.insert(name.into(), value.into());
+
+        self
+    }
+}
+
+type ToolFn = dyn Fn(HashMap<String, Value>) -> Result<ToolOutput, String> + Send + Sync;
+
+/// Implementation standing in for a registered tool.
+#[derive(Clone)]
+pub enum MockTool {
+    /// In-process closure receiving the input ports. Returning an error fails
+    /// the walk with the given reason.
+    Fn(Arc<ToolFn>),
+    /// Base URL of a tool served by `nexus-toolkit`. Input is sent unsigned to
+    /// its `/invoke` endpoint.
+    Http(reqwest::Url),
+}
+
+impl std::fmt::Debug for MockTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fn(_) => write!(f, "Fn(..)"),
+            Self::Http(url) => write!(f, "Http({url})"),
+        }
+    }
+}
+
+/// Events emitted by a simulated execution, in order.
+#[derive(Clone, Debug, Default)]
+pub struct SimulationTrace {
+    pub events: Vec<NexusEventKind>,
+}
+
+impl SimulationTrace {
+    /// All end states reached by the execution.
+    pub fn end_states(&self) -> impl Iterator<Item = &EndStateReachedEvent> {
+        self.events.iter().filter_map(|event| match event {
+            NexusEventKind::EndStateReached(event) => Some(event),
+            _ => None,
+        })
+    }
+
+    /// The final event of a simulation that ran to completion.
+    pub fn finished(&self) -> Option<&ExecutionFinishedEvent> {
+        self.events.iter().rev().find_map(|event| match event {
+            NexusEventKind::ExecutionFinished(event) => Some(event),
+            _ => None,
+        })
+    }
+}
+
+/// Executes a [`DagSpec`] in-process against mock tools.
+#[derive(Clone, Debug)]
+pub struct DagSimulator {
+    dag: DagSpec,
+    tools: HashMap<ToolFqn, MockTool>,
+    max_invocations: usize,
+}
+
+impl DagSimulator {
+    pub fn new(dag: DagSpec) -> Self {
+        Self {
+            dag,
+            tools: HashMap::new(),
+            max_invocations: DEFAULT_MAX_INVOCATIONS,
+        }
+    }
+
+    /// Implement the tool `fqn` with a closure.
+    pub fn tool<F>(mut self, fqn: ToolFqn, tool: F) -> Self
+    where
+        F: Fn(HashMap<String, Value>) -> Result<ToolOutput, String> + Send + Sync + 'static,
+    {
+        self.tools.insert(fqn, MockTool::Fn(Arc::new(tool)));
+
+        self
+    }
+
+    /// Implement the tool `fqn` with a local `nexus-toolkit` server.
+    pub fn http_tool(mut self, fqn: ToolFqn, url: reqwest::Url) -> Self {
+        self.tools.insert(fqn, MockTool::Http(url));
+
+        self
+    }
+
+    /// Override [`DEFAULT_MAX_INVOCATIONS`].
+    pub fn max_invocations(mut self, max_invocations: usize) -> Self {
+        self.max_invocations = max_invocations;
+
+        self
+    }
+
+    /// Execute the DAG starting from `entry_group`, or the default entry group
+    /// if `None`. `input` maps entry vertices to the data of their entry
+    /// ports.
+    ///
+    /// Tool failures are part of the returned trace. An error is only
+    /// returned if the simulation itself cannot proceed, e.g. when a tool has
+    /// no mock or the input doesn't match the entry group.
+    pub async fn run(
+        &self,
+        entry_group: Option<&str>,
+        input: HashMap<String, HashMap<String, Value>>,
+    ) -> AnyResult<SimulationTrace> {
+        let entry_group = entry_group.unwrap_or(DEFAULT_ENTRY_GROUP);
+        let entry_vertices = self.entry_vertices(entry_group)?;
+        let mut execution = Execution::new(&self.dag)?;
+
+        // Check and deliver the entry input.
+        for vertex in &entry_vertices {
+            for entry_port in &vertex.entry_ports {
+                let provided = input
+                    .get(&vertex.name)
+                    .is_some_and(|ports| ports.contains_key(&entry_port.name));
+
+                if !provided {
+                    bail!(
+                        "'Input port: {}.{}' is an entry port of entry group '{entry_group}' but no input was provided.",
+                        vertex.name,
+                        entry_port.name
+                    );
+                }
+            }
+        }
+
+        for (vertex, ports) in input {
+            for (port, value) in ports {
+                let is_entry_port = entry_vertices.iter().any(|entry_vertex| {
+                    entry_vertex.name == vertex
+                        && entry_vertex
+                            .entry_ports
+                            .iter()
+                            .any(|entry_port| entry_port.name == port)
+                });
+
+                if !is_entry_port {
+                    bail!(
+                        "'Input port: {vertex}.{port}' is not an entry port of entry group '{entry_group}'."
+                    );
+                }
+
+                execution.deliver(&vertex, None, port, value);
+            }
+        }
+
+        for vertex in &entry_vertices {
+            execution.schedule(&vertex.name, None, None);
+        }
+
+        // Invoke tools until every walk has ended.
+        let mut invocations = 0;
+        let mut has_any_walk_failed = false;
+        let mut has_any_walk_succeeded = false;
+
+        while let Some(invocation) = execution.queue.pop_front() {
+            invocations += 1;
+
+            if invocations > self.max_invocations {
+                bail!(
+                    "The simulation exceeded {} tool invocations. Is there a do-while loop that never breaks?",
+                    self.max_invocations
+                );
+            }
+
+            let vertex = self
+                .dag
+                .vertices
+                .iter()
+                .find(|vertex| vertex.name == invocation.vertex)
+                .ok_or_else(|| anyhow!("'Vertex: {}' does not exist.", invocation.vertex))?;
+
+            let runtime_vertex = match invocation.iteration {
+                Some((iteration, out_of)) => {
+                    RuntimeVertex::with_iterator(&vertex.name, iteration, out_of)
+                }
+                None => RuntimeVertex::plain(&vertex.name),
+            };
+
+            let output = match self.invoke(vertex, invocation.inputs).await? {
+                Ok(output) => output,
+                Err(reason) => {
+                    has_any_walk_failed = true;
+
+                    if self.fail_walk(
+                        &mut execution,
+                        vertex,
+                        invocation.walk_index,
+                        runtime_vertex,
+                        reason,
+                    ) {
+                        break;
+                    }
+
+                    continue;
+                }
+            };
+
+            let edges = self
+                .dag
+                .edges
+                .iter()
+                .filter(|edge| {
+                    edge.from.vertex == vertex.name && edge.from.output_variant == output.variant
+                })
+                .collect::<Vec<_>>();
+
+            // A for-each over an empty array would end the walk without any
+            // outcome, so it is reported as a failure instead.
+            let empty_for_each = edges.iter().find(|edge| {
+                edge.kind == EdgeKind::ForEach
+                    && output
+                        .ports
+                        .get(&edge.from.output_port)
+                        .and_then(Value::as_array)
+                        .is_some_and(Vec::is_empty)
+            });
+
+            if let Some(edge) = empty_for_each {
+                has_any_walk_failed = true;
+
+                let reason = format!(
+                    "'Output port: {}.{}.{}' has a for-each edge but produced an empty array.",
+                    edge.from.vertex, edge.from.output_variant, edge.from.output_port
+                );
+
+                if self.fail_walk(
+                    &mut execution,
+                    vertex,
+                    invocation.walk_index,
+                    runtime_vertex,
+                    reason,
+                ) {
+                    break;
+                }
+
+                continue;
+            }
+
+            let variant = OutputVariant {
+                name: MoveString::from(output.variant.as_str()),
+            };
+            let variant_ports_to_data = ports_to_data(&output.ports)?;
+
+            // A variant without outgoing edges ends the walk.
+            if edges.is_empty() {
+                has_any_walk_succeeded = true;
+
+                execution.emit(NexusEventKind::EndStateReached(EndStateReachedEvent {
+                    dag: zero_id(),
+                    execution: zero_id(),
+                    walk_index: invocation.walk_index,
+                    vertex: runtime_vertex,
+                    variant,
+                    variant_ports_to_data,
+                }));
+
+                continue;
+            }
+
+            execution.emit(NexusEventKind::WalkAdvanced(WalkAdvancedEvent {
+                dag: zero_id(),
+                execution: zero_id(),
+                walk_index: invocation.walk_index,
+                vertex: runtime_vertex,
+                variant,
+                variant_ports_to_data,
+            }));
+
+            // Taking a do-while or break edge ends the current loop iteration.
+            if edges
+                .iter()
+                .any(|edge| matches!(edge.kind, EdgeKind::DoWhile | EdgeKind::Break))
+            {
+                execution.end_loop_iteration(&vertex.name);
+            }
+
+            let mut touched = vec![];
+
+            for edge in edges {
+                let value = output.ports.get(&edge.from.output_port).ok_or_else(|| {
+                    anyhow!(
+                        "'Output port: {}.{}.{}' was not produced by the tool.",
+                        edge.from.vertex,
+                        edge.from.output_variant,
+                        edge.from.output_port
+                    )
+                })?;
+
+                let target = edge.to.vertex.as_str();
+                let port = edge.to.input_port.clone();
+
+                match edge.kind {
+                    EdgeKind::Static => {
+                        execution
+                            .persistent
+                            .entry(target)
+                            .or_default()
+                            .insert(port, value.clone());
+
+                        touched.push((target, None));
+                    }
+                    EdgeKind::ForEach => {
+                        let Value::Array(items) = value else {
+                            bail!(
+                                "'Output port: {}.{}.{}' has a for-each edge but did not produce an array.",
+                                edge.from.vertex,
+                                edge.from.output_variant,
+                                edge.from.output_port
+                            );
+                        };
+
+                        let out_of = items.len() as u64;
+
+                        for (iteration, item) in (0..out_of).zip(items) {
+                            let iteration = Some((iteration, out_of));
+
+                            execution.deliver(target, iteration, port.clone(), item.clone());
+
+                            touched.push((target, iteration));
+                        }
+                    }
+                    EdgeKind::Collect => {
+                        let Some((iteration, out_of)) = invocation.iteration else {
+                            bail!(
+                                "'Output port: {}.{}.{}' has a collect edge but is not inside a for-each.",
+                                edge.from.vertex,
+                                edge.from.output_variant,
+                                edge.from.output_port
+                            );
+                        };
+
+                        let key = (target.to_string(), port.clone());
+                        let collected = execution.collected.entry(key.clone()).or_default();
+
+                        collected.insert(iteration, value.clone());
+
+                        // Only continue once every iteration has arrived.
+                        if collected.len() as u64 == out_of {
+                            let items = execution
+                                .collected
+                                .remove(&key)
+                                .unwrap_or_default()
+                                .into_values()
+                                .collect();
+
+                            execution.deliver(target, None, port, Value::Array(items));
+
+                            touched.push((target, None));
+                        }
+                    }
+                    // Loops cannot be inside a for-each, so loop edges never
+                    // carry an iteration.
+                    EdgeKind::DoWhile | EdgeKind::Break => {
+                        execution.deliver(target, None, port, value.clone());
+
+                        touched.push((target, None));
+                    }
+                    EdgeKind::Normal => {
+                        execution.deliver(target, invocation.iteration, port, value.clone());
+
+                        touched.push((target, invocation.iteration));
+                    }
+                }
+            }
+
+            // The first vertex that becomes ready continues this walk, others
+            // start new walks.
+            let mut walk_index = Some(invocation.walk_index);
+            let mut seen = HashSet::new();
+
+            for (target, iteration) in touched {
+                if seen.insert((target, iteration)) {
+                    execution.schedule(target, iteration, walk_index.take());
+                }
+            }
+        }
+
+        execution.emit(NexusEventKind::ExecutionFinished(ExecutionFinishedEvent {
+            dag: zero_id(),
+            execution: zero_id(),
+            has_any_walk_failed,
+            has_any_walk_succeeded,
+            was_aborted: false,
+        }));
+
+        Ok(SimulationTrace {
+            events: execution.events,
+        })
+    }
+
+    /// Record a failed walk and apply the vertex or DAG [`PostFailureAction`].
+    /// Returns whether the execution was terminated.
+    fn fail_walk(
+        &self,
+        execution: &mut Execution,
+        vertex: &DagVertex,
+        walk_index: u64,
+        runtime_vertex: RuntimeVertex,
+        reason: String,
+    ) -> bool {
+        execution.emit(NexusEventKind::WalkFailed(WalkFailedEvent {
+            dag: zero_id(),
+            execution: zero_id(),
+            walk_index,
+            vertex: runtime_vertex,
+            reason: MoveString::from(reason),
+        }));
+
+        let action = vertex
+            .post_failure_action
+            .as_ref()
+            .or(self.dag.post_failure_action.as_ref())
+            .cloned()
+            .unwrap_or(PostFailureAction::Terminate);
+
+        if let PostFailureAction::Terminate = action {
+            execution.cancel_all();
+
+            return true;
+        }
+
+        false
+    }
+
+    fn entry_vertices(&self, entry_group: &str) -> AnyResult<Vec<&DagVertex>> {
+        // Without explicit entry groups, all vertices with entry ports form
+        // the default entry group.
+        if self.dag.entry_groups.is_empty() {
+            if entry_group != DEFAULT_ENTRY_GROUP {
+                bail!("Entry group '{entry_group}' does not exist.");
+            }
+
+            return Ok(self
+                .dag
+                .vertices
+                .iter()
+                .filter(|vertex| !vertex.entry_ports.is_empty())
+                .collect());
+        }
+
+        let group = self
+            .dag
+            .entry_groups
+            .iter()
+            .find(|group| group.name == entry_group)
+            .ok_or_else(|| anyhow!("Entry group '{entry_group}' does not exist."))?;
+
+        Ok(self
+            .dag
+            .vertices
+            .iter()
+            .filter(|vertex| group.vertices.contains(&vertex.name))
+            .collect())
+    }
+
+    /// Invoke the mock for the vertex's tool. The inner result is the outcome
+    /// of the tool itself, the outer one fails the whole simulation.
+    async fn invoke(
+        &self,
+        vertex: &DagVertex,
+        inputs: HashMap<String, Value>,
+    ) -> AnyResult<Result<ToolOutput, String>> {
+        let tool_fqn = vertex_tool_fqn(&vertex.kind);
+
+        match self.tools.get(tool_fqn) {
+            Some(MockTool::Fn(tool)) => Ok(tool(inputs)),
+            Some(MockTool::Http(url)) => invoke_http(url, &inputs).await,
+            None => bail!(
+                "No mock registered for tool '{tool_fqn}' used by 'Vertex: {}'.",
+                vertex.name
+            ),
+        }
+    }
+}
+
+/// A vertex inside a for-each is invoked once per element and carries the
+/// `(iteration, out_of)` pair it belongs to.
+type Iteration = Option<(u64, u64)>;
+
+struct Invocation {
+    walk_index: u64,
+    vertex: String,
+    iteration: Iteration,
+    inputs: HashMap<String, Value>,
+}
+
+/// Mutable state of a running simulation.
+struct Execution<'a> {
+    /// Input ports that must hold data before a vertex is invoked.
+    required: HashMap<&'a str, HashSet<&'a str>>,
+    /// Default values and static edge data that survive invocations.
+    persistent: HashMap<&'a str, HashMap<String, Value>>,
+    /// Data waiting on input ports, consumed by the next invocation.
+    pending: HashMap<(String, Iteration), HashMap<String, Value>>,
+    /// Partial results of collect edges keyed by target vertex and port.
+    collected: HashMap<(String, String), BTreeMap<u64, Value>>,
+    /// Vertices of each do-while loop keyed by the vertex the loop's
+    /// do-while and break edges leave from.
+    loops: HashMap<&'a str, HashSet<&'a str>>,
+    queue: VecDeque<Invocation>,
+    next_walk_index: u64,
+    events: Vec<NexusEventKind>,
+}
+
+impl<'a> Execution<'a> {
+    fn new(dag: &'a DagSpec) -> AnyResult<Self> {
+        let mut required = HashMap::<&str, HashSet<&str>>::new();
+        let mut persistent = HashMap::<&str, HashMap<String, Value>>::new();
+
+        for vertex in &dag.vertices {
+            let ports = required.entry(vertex.name.as_str()).or_default();
+
+            ports.extend(vertex.entry_ports.iter().map(|port| port.name.as_str()));
+        }
+
+        for edge in &dag.edges {
+            required
+                .entry(edge.to.vertex.as_str())
+                .or_default()
+                .insert(edge.to.input_port.as_str());
+        }
+
+        for default_value in &dag.default_values {
+            if !default_value.value.is_inline() {
+                bail!(
+                    "'Input port: {}.{}' has a remote default value which cannot be simulated.",
+                    default_value.vertex,
+                    default_value.input_port
+                );
+            }
+
+            let value = inline_json(&default_value.value).map_err(|e| {
+                anyhow!(
+                    "'Input port: {}.{}' has a default value that {e}",
+                    default_value.vertex,
+                    default_value.input_port
+                )
+            })?;
+
+            required
+                .entry(default_value.vertex.as_str())
+                .or_default()
+                .insert(default_value.input_port.as_str());
+
+            persistent
+                .entry(default_value.vertex.as_str())
+                .or_default()
+                .insert(default_value.input_port.clone(), value);
+        }
+
+        Ok(Self {
+            required,
+            persistent,
+            pending: HashMap::new(),
+            collected: HashMap::new(),
+            loops: loop_bodies(dag),
+            queue: VecDeque::new(),
+            next_walk_index: 0,
+            events: vec![],
+        })
+    }
+
+    fn emit(&mut self, event: NexusEventKind) {
+        self.events.push(event);
+    }
+
+    fn deliver(&mut self, vertex: &str, iteration: Iteration, port: String, value: Value) {
+        self.pending
+            .entry((vertex.to_string(), iteration))
+            .or_default()
+            .insert(port, value);
+    }
+
+    /// Discard the data waiting in the body of the loop that `vertex` closes
+    /// so that it cannot mix with data of the next iteration or linger after
+    /// the loop.
+    fn end_loop_iteration(&mut self, vertex: &str) {
+        let Some(body) = self.loops.get(vertex) else {
+            return;
+        };
+
+        for vertex in body {
+            self.pending.remove(&(vertex.to_string(), None));
+        }
+    }
+
+    /// Queue an invocation of `vertex` if all of its input ports hold data.
+    /// The invocation continues `walk_index` or starts a new walk.
+    fn schedule(&mut self, vertex: &str, iteration: Iteration, walk_index: Option<u64>) {
+        let key = (vertex.to_string(), iteration);
+        let persistent = self.persistent.get(vertex);
+        let pending = self.pending.get(&key);
+
+        let is_ready = self.required.get(vertex).is_some_and(|ports| {
+            ports.iter().all(|port| {
+                pending.is_some_and(|data| data.contains_key(*port))
+                    || persistent.is_some_and(|data| data.contains_key(*port))
+            })
+        });
+
+        if !is_ready {
+            return;
+        }
+
+        let mut inputs = persistent.cloned().unwrap_or_default();
+
+        inputs.extend(self.pending.remove(&key).unwrap_or_default());
+
+        let walk_index = walk_index.unwrap_or_else(|| {
+            let walk_index = self.next_walk_index;
+
+            self.next_walk_index += 1;
+
+            walk_index
+        });
+
+        // New walks must not reuse an index that is still in use.
+        self.next_walk_index = self.next_walk_index.max(walk_index + 1);
+
+        self.queue.push_back(Invocation {
+            walk_index,
+            vertex: vertex.to_string(),
+            iteration,
+            inputs,
+        });
+    }
+
+    /// Cancel every queued walk after the execution was terminated.
+    fn cancel_all(&mut self) {
+        while let Some(invocation) = self.queue.pop_front() {
+            let vertex = match invocation.iteration {
+                Some((iteration, out_of)) => {
+                    RuntimeVertex::with_iterator(&invocation.vertex, iteration, out_of)
+                }
+                None => RuntimeVertex::plain(&invocation.vertex),
+            };
+
+            self.emit(NexusEventKind::WalkCancelled(WalkCancelledEvent {
+                dag: zero_id(),
+                execution: zero_id(),
+                walk_index: invocation.walk_index,
+                vertex,
+            }));
+        }
+    }
+}
+
+/// Vertices on a path from the target of a do-while edge back to the vertex
+/// it leaves from, keyed by the latter. Loop edges themselves are not
+/// followed.
+fn loop_bodies(dag: &DagSpec) -> HashMap<&str, HashSet<&str>> {
+    let mut successors = HashMap::<&str, Vec<&str>>::new();
+    let mut predecessors = HashMap::<&str, Vec<&str>>::new();
+
+    for edge in &dag.edges {
+        if matches!(edge.kind, EdgeKind::DoWhile | EdgeKind::Break) {
+            continue;
+        }
+
+        successors
+            .entry(edge.from.vertex.as_str())
+            .or_default()
+            .push(edge.to.vertex.as_str());
+        predecessors
+            .entry(edge.to.vertex.as_str())
+            .or_default()
+            .push(edge.from.vertex.as_str());
+    }
+
+    let mut loops = HashMap::<&str, HashSet<&str>>::new();
+
+    for edge in dag
+        .edges
+        .iter()
+        .filter(|edge| edge.kind == EdgeKind::DoWhile)
+    {
+        let (head, tail) = (edge.to.vertex.as_str(), edge.from.vertex.as_str());
+        let from_head = reachable(head, &successors);
+        let to_tail = reachable(tail, &predecessors);
+
+        loops
+            .entry(tail)
+            .or_default()
+            .extend(from_head.intersection(&to_tail));
+    }
+
+    loops
+}
+
+/// Vertices reachable from `start` through `next`, including `start`.
+fn reachable<'a>(start: &'a str, next: &HashMap<&'a str, Vec<&'a str>>) -> HashSet<&'a str> {
+    let mut seen = HashSet::from([start]);
+    let mut stack = vec![start];
+
+    while let Some(vertex) = stack.pop() {
+        for &neighbor in next.get(vertex).into_iter().flatten() {
+            if seen.insert(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    seen
+}
+
+/// Simulated executions have no on-chain DAG or execution object.
+fn zero_id() -> ID {
+    ID {
+        bytes: sui::types::Address::ZERO,
+    }
+}
+
+/// Encode output ports as inline [`NexusData`], sorted by port name. Arrays are
+/// stored as `many` so that for-each edges can iterate over them.
+fn ports_to_data(ports: &HashMap<String, Value>) -> AnyResult<VecMap<OutputPort, NexusData>> {
+    let contents = ports
+        .iter()
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .map(|(name, value)| {
+            let data = match value {
+                Value::Array(items) => NexusData::inline_many(
+                    items
+                        .iter()
+                        .map(serde_json::to_vec)
+                        .collect::<Result<Vec<_>, _>>()?,
+                ),
+                value => NexusData::inline_one(serde_json::to_vec(value)?),
+            };
+
+            Ok(VecMapEntry {
+                key: OutputPort {
+                    name: MoveString::from(name.as_str()),
+                },
+                value: data,
+            })
+        })
+        .collect::<AnyResult<Vec<_>>>()?;
+
+    Ok(VecMap { contents })
+}
+
+/// Invoke a tool served by `nexus-toolkit` and decode its BCS
+/// [`TaggedOutput`].
+async fn invoke_http(
+    url: &reqwest::Url,
+    inputs: &HashMap<String, Value>,
+) -> AnyResult<Result<ToolOutput, String>> {
+    let mut invoke_url = url.clone();
+
+    invoke_url
+        .path_segments_mut()
+        .map_err(|_| anyhow!("Tool URL '{url}' cannot be used as a base URL"))?
+        .pop_if_empty()
+        .push("invoke");
+
+    let response = reqwest::Client::new()
+        .post(invoke_url)
+        .json(inputs)
+        .send()
+        .await
+        .map_err(|e| anyhow!("Could not reach tool at '{url}': {e}"))?;
+
+    let status = response.status();
+    let body = response.bytes().await?;
+
+    // Errors reported by the tool fail the walk like any other tool failure.
+    if !status.is_success() {
+        return Ok(Err(format!(
+            "Tool responded with {status}: {}",
+            String::from_utf8_lossy(&body)
+        )));
+    }
+
+    let output = bcs::from_bytes::<TaggedOutput>(&body)
+        .map_err(|e| anyhow!("Tool at '{url}' returned an invalid result: {e}"))?;
+
+    let ports = output
+        .named_payload
+        .contents
+        .iter()
+        .map(|entry| {
+            Ok((
+                String::from_utf8(entry.key.clone())?,
+                typed_json(&entry.value)?,
+            ))
+        })
+        .collect::<AnyResult<HashMap<_, _>>>()?;
+
+    Ok(Ok(ToolOutput {
+        variant: String::from_utf8(output.tag)?,
+        ports,
+    }))
+}
+
+/// Decode [`TypedNexusData`] produced by `nexus-toolkit` back into JSON.
+/// Strings are stored as raw UTF-8, everything else as JSON.
+fn typed_json(value: &TypedNexusData) -> AnyResult<Value> {
+    let decode = |bytes: &[u8]| -> AnyResult<Value> {
+        match value.type_hint {
+            DataTypeHint::String => Ok(Value::String(String::from_utf8(bytes.to_vec())?)),
+            _ => Ok(serde_json::from_slice(bytes)?),
+        }
+    };
+
+    let data = &value.data;
+
+    if !data.many.is_empty() || (data.one.is_empty() && value.type_hint != DataTypeHint::String) {
+        return data
+            .many
+            .iter()
+            .map(|bytes| decode(bytes))
+            .collect::<AnyResult<Vec<_>>>()
+            .map(Value::Array);
+    }
+
+    decode(&data.one)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{dag::builder::DagBuilder, fqn},
+        assert_matches::assert_matches,
+        serde_json::json,
+    };
+
+    fn add(input: HashMap<String, Value>) -> Result<ToolOutput, String> {
+        let a = input["a"].as_i64().ok_or("a is not a number")?;
+        let b = input["b"].as_i64().ok_or("b is not a number")?;
+
+        Ok(ToolOutput::new("ok").port("result", a + b))
+    }
+
+    fn input(vertex: &str, port: &str, value: Value) -> HashMap<String, HashMap<String, Value>> {
+        HashMap::from([(
+            vertex.to_string(),
+            HashMap::from([(port.to_string(), value)]),
+        )])
+    }
+
+    fn event_names(trace: &SimulationTrace) -> Vec<String> {
+        trace.events.iter().map(NexusEventKind::name).collect()
+    }
+
+    fn end_state_value(event: &EndStateReachedEvent, port: &str) -> Value {
+        let data = event
+            .variant_ports_to_data
+            .contents
+            .iter()
+            .find(|entry| String::from(entry.key.name.clone()) == port)
+            .map(|entry| &entry.value)
+            .unwrap();
+
+        inline_json(data).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_linear_walk() {
+        let dag = DagBuilder::new()
+            .vertex("a", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .vertex("b", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .entry_port("a.a")
+            .default_value("a.b", 1)
+            .edge("a.ok.result", "b.a")
+            .default_value("b.b", 2)
+            .build()
+            .unwrap();
+
+        let trace = DagSimulator::new(dag)
+            .tool(fqn!("xyz.taluslabs.math.i64.add@1"), add)
+            .run(None, input("a", "a", json!(10)))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            event_names(&trace),
+            vec![
+                "WalkAdvancedEvent",
+                "EndStateReachedEvent",
+                "ExecutionFinishedEvent"
+            ]
+        );
+
+        let end_state = trace.end_states().next().unwrap();
+
+        assert_eq!(end_state.walk_index, 0);
+        assert_eq!(end_state.vertex, RuntimeVertex::plain("b"));
+        assert_eq!(end_state_value(end_state, "result"), json!(13));
+
+        let finished = trace.finished().unwrap();
+
+        assert!(finished.has_any_walk_succeeded);
+        assert!(!finished.has_any_walk_failed);
+    }
+
+    #[tokio::test]
+    async fn test_for_each_and_collect() {
+        let dag = DagBuilder::new()
+            .vertex("split", fqn!("xyz.taluslabs.split@1"))
+            .vertex("double", fqn!("xyz.taluslabs.double@1"))
+            .vertex("sum", fqn!("xyz.taluslabs.sum@1"))
+            .entry_port("split.values")
+            .for_each("split.ok.values", "double.value")
+            .collect("double.ok.value", "sum.values")
+            .build()
+            .unwrap();
+
+        let trace = DagSimulator::new(dag)
+            .tool(fqn!("xyz.taluslabs.split@1"), |input| {
+                Ok(ToolOutput::new("ok").port("values", input["values"].clone()))
+            })
+            .tool(fqn!("xyz.taluslabs.double@1"), |input| {
+                Ok(ToolOutput::new("ok").port("value", input["value"].as_i64().unwrap() * 2))
+            })
+            .tool(fqn!("xyz.taluslabs.sum@1"), |input| {
+                let values = input["values"].as_array().unwrap();
+
+                Ok(ToolOutput::new("ok").port("values", values.clone()))
+            })
+            .run(None, input("split", "values", json!([1, 2, 3])))
+            .await
+            .unwrap();
+
+        let iterations = trace
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                NexusEventKind::WalkAdvanced(event) => Some(event.vertex.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            iterations,
+            vec![
+                RuntimeVertex::plain("split"),
+                RuntimeVertex::with_iterator("double", 0, 3),
+                RuntimeVertex::with_iterator("double", 1, 3),
+                RuntimeVertex::with_iterator("double", 2, 3),
+            ]
+        );
+
+        let end_state = trace.end_states().next().unwrap();
+
+        assert_eq!(end_state.vertex, RuntimeVertex::plain("sum"));
+        assert_eq!(end_state_value(end_state, "values"), json!([2, 4, 6]));
+    }
+
+    /// `start` feeds `step` and passes a static `limit` to `check`, which
+    /// loops back into `step` until the value reaches the limit.
+    fn loop_dag() -> DagSpec {
+        DagBuilder::new()
+            .vertex("start", fqn!("xyz.taluslabs.start@1"))
+            .vertex("step", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .vertex("check", fqn!("xyz.taluslabs.cmp@1"))
+            .vertex("done", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .entry_port("start.a")
+            .edge("start.ok.result", "step.a")
+            .default_value("step.b", 1)
+            .static_edge("start.ok.limit", "check.limit")
+            .edge("step.ok.result", "check.value")
+            .do_while("check.lt.value", "step.a")
+            .break_loop("check.gte.value", "done.a")
+            .default_value("done.b", 0)
+            .build()
+            .unwrap()
+    }
+
+    fn start(input: HashMap<String, Value>) -> Result<ToolOutput, String> {
+        Ok(ToolOutput::new("ok")
+            .port("result", input["a"].clone())
+            .port("limit", input["a"].as_i64().unwrap() * 2))
+    }
+
+    #[tokio::test]
+    async fn test_do_while_with_static_edge() {
+        let trace = DagSimulator::new(loop_dag())
+            .tool(fqn!("xyz.taluslabs.start@1"), start)
+            .tool(fqn!("xyz.taluslabs.math.i64.add@1"), add)
+            .tool(fqn!("xyz.taluslabs.cmp@1"), |input| {
+                let value = input["value"].as_i64().unwrap();
+                let variant = if value < input["limit"].as_i64().unwrap() {
+                    "lt"
+                } else {
+                    "gte"
+                };
+
+                Ok(ToolOutput::new(variant).port("value", value))
+            })
+            .run(None, input("start", "a", json!(3)))
+            .await
+            .unwrap();
+
+        let checks = trace
+            .events
+            .iter()
+            .filter(|event| {
+                matches!(event, NexusEventKind::WalkAdvanced(event) if event.vertex == RuntimeVertex::plain("check"))
+            })
+            .count();
+
+        assert_eq!(checks, 3);
+        assert_eq!(
+            end_state_value(trace.end_states().next().unwrap(), "result"),
+            json!(6)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_for_each_fails_walk() {
+        let dag = DagBuilder::new()
+            .vertex("split", fqn!("xyz.taluslabs.split@1"))
+            .vertex("double", fqn!("xyz.taluslabs.double@1"))
+            .vertex("sum", fqn!("xyz.taluslabs.sum@1"))
+            .entry_port("split.values")
+            .for_each("split.ok.values", "double.value")
+            .collect("double.ok.value", "sum.values")
+            .build()
+            .unwrap();
+
+        let trace = DagSimulator::new(dag)
+            .tool(fqn!("xyz.taluslabs.split@1"), |input| {
+                Ok(ToolOutput::new("ok").port("values", input["values"].clone()))
+            })
+            .run(None, input("split", "values", json!([])))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            event_names(&trace),
+            vec!["WalkFailedEvent", "ExecutionFinishedEvent"]
+        );
+        assert_matches!(
+            &trace.events[0],
+            NexusEventKind::WalkFailed(event) if event.vertex == RuntimeVertex::plain("split")
+                && String::from(event.reason.clone()).contains("'Output port: split.ok.values' has a for-each edge but produced an empty array.")
+        );
+        assert!(trace.finished().unwrap().has_any_walk_failed);
+        assert!(!trace.finished().unwrap().has_any_walk_succeeded);
+    }
+
+    /// `check` loops back into `head` until `v` reaches 2. `parity` feeds
+    /// `sum.left` for odd and `sum.right` for even values, so `sum` only runs
+    /// if data of two iterations is mixed.
+    fn parity_loop_dag() -> DagSpec {
+        DagBuilder::new()
+            .vertex("head", fqn!("xyz.taluslabs.pass@1"))
+            .vertex("parity", fqn!("xyz.taluslabs.parity@1"))
+            .vertex("sum", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .vertex("check", fqn!("xyz.taluslabs.cmp@1"))
+            .vertex("exit", fqn!("xyz.taluslabs.pass@1"))
+            .entry_port("head.n")
+            .edge("head.ok.n", "parity.n")
+            .edge("parity.odd.n", "sum.a")
+            .edge("parity.odd.n", "check.v")
+            .edge("parity.even.n", "sum.b")
+            .edge("parity.even.n", "check.v")
+            .edge("sum.ok.result", "check.v")
+            .do_while("check.again.n", "head.n")
+            .break_loop("check.done.n", "exit.n")
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_do_while_and_break_discard_unfinished_iteration() {
+        let trace = DagSimulator::new(parity_loop_dag())
+            .tool(fqn!("xyz.taluslabs.pass@1"), |input| {
+                Ok(ToolOutput::new("ok").port("n", input["n"].clone()))
+            })
+            .tool(fqn!("xyz.taluslabs.parity@1"), |input| {
+                let n = input["n"].as_i64().unwrap();
+                let variant = if n % 2 == 0 { "even" } else { "odd" };
+
+                Ok(ToolOutput::new(variant).port("n", n))
+            })
+            .tool(fqn!("xyz.taluslabs.math.i64.add@1"), add)
+            .tool(fqn!("xyz.taluslabs.cmp@1"), |input| {
+                let v = input["v"].as_i64().unwrap();
+
+                if v < 2 {
+                    Ok(ToolOutput::new("again").port("n", v + 1))
+                } else {
+                    Ok(ToolOutput::new("done").port("n", v))
+                }
+            })
+            .run(None, input("head", "n", json!(1)))
+            .await
+            .unwrap();
+
+        let advanced = trace
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                NexusEventKind::WalkAdvanced(event) => {
+                    Some((event.walk_index, event.vertex.vertex_name().to_string()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // The do-while edge drops `sum.a` of the first iteration and the
+        // break edge drops `sum.b` of the second one.
+        assert_eq!(
+            advanced,
+            ["head", "parity", "check", "head", "parity", "check"]
+                .map(|vertex| (0, vertex.to_string()))
+                .to_vec()
+        );
+
+        let end_state = trace.end_states().next().unwrap();
+
+        assert_eq!(trace.end_states().count(), 1);
+        assert_eq!(end_state.walk_index, 0);
+        assert_eq!(end_state.vertex, RuntimeVertex::plain("exit"));
+        assert_eq!(end_state_value(end_state, "n"), json!(2));
+        assert!(!trace.finished().unwrap().has_any_walk_failed);
+    }
+
+    #[tokio::test]
+    async fn test_failure_terminates_or_continues() {
+        let dag = DagBuilder::new()
+            .vertex("fan", fqn!("xyz.taluslabs.fan@1"))
+            .vertex("fails", fqn!("xyz.taluslabs.fail@1"))
+            .vertex("b", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .entry_port("fan.a")
+            .edge("fan.ok.left", "fails.value")
+            .edge("fan.ok.right", "b.a")
+            .default_value("b.b", 1)
+            .build()
+            .unwrap();
+
+        let simulator = DagSimulator::new(dag.clone())
+            .tool(fqn!("xyz.taluslabs.fan@1"), |input| {
+                Ok(ToolOutput::new("ok")
+                    .port("left", input["a"].clone())
+                    .port("right", input["a"].clone()))
+            })
+            .tool(fqn!("xyz.taluslabs.math.i64.add@1"), add)
+            .tool(fqn!("xyz.taluslabs.fail@1"), |_| Err("boom".to_string()));
+
+        let trace = simulator
+            .run(None, input("fan", "a", json!(1)))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            event_names(&trace),
+            vec![
+                "WalkAdvancedEvent",
+                "WalkFailedEvent",
+                "WalkCancelledEvent",
+                "ExecutionFinishedEvent"
+            ]
+        );
+        assert!(trace.finished().unwrap().has_any_walk_failed);
+        assert!(!trace.finished().unwrap().has_any_walk_succeeded);
+
+        let mut dag = dag;
+        dag.post_failure_action = Some(PostFailureAction::TransientContinue);
+
+        let trace = DagSimulator { dag, ..simulator }
+            .run(None, input("fan", "a", json!(1)))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            event_names(&trace),
+            vec![
+                "WalkAdvancedEvent",
+                "WalkFailedEvent",
+                "EndStateReachedEvent",
+                "ExecutionFinishedEvent"
+            ]
+        );
+        assert!(trace.finished().unwrap().has_any_walk_succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_entry_groups_and_input_errors() {
+        let dag = DagBuilder::new()
+            .vertex("a", fqn!("xyz.taluslabs.math.i64.add@1"))
+            .entry_port("a.a")
+            .default_value("a.b", 1)
+            .build()
+            .unwrap();
+
+        let simulator = DagSimulator::new(dag);
+
+        let res = simulator.run(Some("other"), HashMap::new()).await;
+
+        assert_matches!(res, Err(e) if e.to_string() == "Entry group 'other' does not exist.");
+
+        let res = simulator.run(None, HashMap::new()).await;
+
+        assert_matches!(res, Err(e) if e.to_string().contains("'Input port: a.a' is an entry port of entry group '_default_group' but no input was provided."));
+
+        let res = simulator.run(None, input("a", "b", json!(1))).await;
+
+        assert_matches!(res, Err(e) if e.to_string().contains("'Input port: a.b' is not an entry port"));
+
+        let res = simulator.run(None, input("a", "a", json!(1))).await;
+
+        assert_matches!(res, Err(e) if e.to_string().contains("No mock registered for tool 'xyz.taluslabs.math.i64.add@1'"));
+    }
+
+    #[tokio::test]
+    async fn test_endless_loop_is_bounded() {
+        let res = DagSimulator::new(loop_dag())
+            .tool(fqn!("xyz.taluslabs.start@1"), start)
+            .tool(fqn!("xyz.taluslabs.math.i64.add@1"), add)
+            .tool(fqn!("xyz.taluslabs.cmp@1"), |input| {
+                Ok(ToolOutput::new("lt").port("value", input["value"].clone()))
+            })
+            .max_invocations(10)
+            .run(None, input("start", "a", json!(0)))
+            .await;
+
+        assert_matches!(res, Err(e) if e.to_string().contains("exceeded 10 tool invocations"));
+    }
+}
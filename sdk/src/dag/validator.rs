@@ -1,51 +1,156 @@
 use {
     crate::{
+        dag::diagnostic::{DagDiagnostic, DagRule, DiagnosticSubject},
         move_bindings::interface::graph::EdgeKind,
         types::{DagSpec, DEFAULT_ENTRY_GROUP},
     },
-    anyhow::{bail, Result as AnyResult},
+    anyhow::{anyhow, Result as AnyResult},
     petgraph::{
         graph::{DiGraph, NodeIndex},
         visit::EdgeRef,
     },
-    std::collections::{HashMap, HashSet},
+    std::collections::{BTreeSet, HashMap, HashSet},
 };
 
-type GraphAndVertexEntryGroups = (
+/// The DAG as a [`DiGraph`] together with the entry groups each entry vertex
+/// belongs to.
+pub type GraphAndVertexEntryGroups = (
     DiGraph<GraphNode, EdgeKind>,
     HashMap<GraphNode, Vec<String>>,
 );
 
 /// Validate function takes a DAG and validates it based on nexus execution
-/// rules.
+/// rules. Returns the first violated rule, use [`diagnose`] to get all of
+/// them.
 ///
 /// See our wiki for more information on the rules:
 /// <https://docs.talus.network/talus-documentation/devs/index/workflow#rules>
 /// <https://docs.talus.network/talus-documentation/devs/index-1/cli#nexus-dag>
 pub fn validate(dag: &DagSpec) -> AnyResult<()> {
+    match diagnose(dag).into_iter().find(DagDiagnostic::is_error) {
+        Some(diagnostic) => Err(diagnostic.into()),
+        None => Ok(()),
+    }
+}
+
+/// Check the DAG against all nexus execution rules and report every
+/// violation as a [`DagDiagnostic`].
+///
+/// Structural problems found while building the graph do not stop the
+/// validation. Only a DAG without entry points or a cyclic graph skips the
+/// checks that walk the graph.
+pub fn diagnose(dag: &DagSpec) -> Vec<DagDiagnostic> {
+    let mut diagnostics = Diagnostics::new(dag);
+
     // Parse the dag into a petgraph DiGraph.
-    let (graph, vertex_entry_groups) = try_into_graph(dag)?;
+    let Some((graph, vertex_entry_groups)) = build_graph(dag, &mut diagnostics) else {
+        return diagnostics.list;
+    };
 
     if !graph.is_directed() || petgraph::algo::is_cyclic_directed(&graph) {
-        bail!("The provided graph contains one or more cycles.");
+        diagnostics.report(DagDiagnostic::new(
+            DagRule::Cycle,
+            "The provided graph contains one or more cycles.",
+        ));
+
+        return diagnostics.list;
     }
 
     // Check that the shape of the graph is correct.
-    has_correct_order_of_actions(&graph)?;
+    has_correct_order_of_actions(&graph, &mut diagnostics);
 
     // Check that no walks in the graph violate the concurrency rules.
-    follows_concurrency_rules(&graph, &vertex_entry_groups)?;
+    follows_concurrency_rules(&graph, &vertex_entry_groups, &mut diagnostics);
 
     // Check that for-each and collect edges are correctly paired and not nesting.
-    validate_for_each_pairs(&graph, &vertex_entry_groups)?;
+    validate_for_each_pairs(&graph, &vertex_entry_groups, &mut diagnostics);
 
     // Check that do-whiles don't nest.
-    validate_do_while_nesting(&graph)?;
+    validate_do_while_nesting(&graph, &mut diagnostics);
+
+    diagnostics.list
+}
 
-    Ok(())
+/// Collects the diagnostics reported while validating a DAG and points them
+/// back into the DAG document.
+struct Diagnostics<'a> {
+    dag: &'a DagSpec,
+    list: Vec<DagDiagnostic>,
+}
+
+impl<'a> Diagnostics<'a> {
+    fn new(dag: &'a DagSpec) -> Self {
+        Self { dag, list: vec![] }
+    }
+
+    /// Record a diagnostic, resolving its pointer from the subject if it has
+    /// none yet. The same violation found through different walks is only
+    /// recorded once.
+    fn report(&mut self, mut diagnostic: DagDiagnostic) {
+        if diagnostic.pointer.is_none() {
+            diagnostic.pointer = diagnostic
+                .subject
+                .as_ref()
+                .and_then(|subject| subject.pointer(self.dag));
+        }
+
+        if !self.list.contains(&diagnostic) {
+            self.list.push(diagnostic);
+        }
+    }
+
+    /// Record a diagnostic about a graph node.
+    fn report_node(&mut self, rule: DagRule, node: &GraphNode, message: String) {
+        let subject = self.subject(node);
+
+        self.report(DagDiagnostic::new(rule, message).with_subject(subject));
+    }
+
+    /// Map a graph node to the part of the DAG it was built from. Phantom
+    /// do-while vertices map back to the real vertex.
+    fn subject(&self, node: &GraphNode) -> DiagnosticSubject {
+        let vertex = |name: &str| match name.strip_suffix("-do-while") {
+            Some(real) if !self.dag.vertices.iter().any(|v| v.name == name) => real.to_string(),
+            _ => name.to_string(),
+        };
+
+        match node {
+            GraphNode::InputPort { vertex: v, name } => DiagnosticSubject::InputPort {
+                vertex: vertex(v),
+                port: name.clone(),
+            },
+            GraphNode::Vertex { name } => DiagnosticSubject::Vertex {
+                vertex: vertex(name),
+            },
+            GraphNode::OutputVariant { vertex: v, name } => DiagnosticSubject::OutputVariant {
+                vertex: v.clone(),
+                variant: name.clone(),
+            },
+            GraphNode::OutputPort {
+                vertex: v,
+                variant,
+                name,
+            } => DiagnosticSubject::OutputPort {
+                vertex: v.clone(),
+                variant: variant.clone(),
+                port: name.clone(),
+            },
+        }
+    }
+
+    /// Turn the first reported error into an [`anyhow::Error`].
+    fn into_result(self) -> AnyResult<()> {
+        match self.list.into_iter().find(DagDiagnostic::is_error) {
+            Some(diagnostic) => Err(diagnostic.into()),
+            None => Ok(()),
+        }
+    }
 }
 
-fn has_correct_order_of_actions(graph: &DiGraph<GraphNode, EdgeKind>) -> AnyResult<()> {
+fn has_correct_order_of_actions(
+    graph: &DiGraph<GraphNode, EdgeKind>,
+    diagnostics: &mut Diagnostics,
+) {
     for node in graph.node_indices() {
         let vertex = &graph[node];
         let neighbors = graph
@@ -53,24 +158,28 @@ fn has_correct_order_of_actions(graph: &DiGraph<GraphNode, EdgeKind>) -> AnyResu
             .collect::<Vec<NodeIndex>>();
 
         // Check if the vertex has the correct number of edges.
-        match vertex {
+        let message = match vertex {
             // Input ports must have exactly 1 outgoing edge.
             GraphNode::InputPort { .. } if neighbors.len() != 1 => {
-                bail!("'{vertex}' must have exactly 1 outgoing edge")
+                Some(format!("'{vertex}' must have exactly 1 outgoing edge"))
             }
             // Tools can be the last vertex and can have any number of edges.
-            GraphNode::Vertex { .. } => (),
+            GraphNode::Vertex { .. } => None,
             // Output variants must have at least 1 outgoing edge.
             GraphNode::OutputVariant { .. } if neighbors.is_empty() => {
-                bail!("'{vertex}' must have at least 1 outgoing edge")
+                Some(format!("'{vertex}' must have at least 1 outgoing edge"))
             }
             // Output ports must have exactly 1 outgoing edge.
             GraphNode::OutputPort { .. } if neighbors.len() != 1 => {
-                bail!("'{vertex}' must have exactly 1 outgoing edge")
+                Some(format!("'{vertex}' must have exactly 1 outgoing edge"))
             }
-            _ => (),
+            _ => None,
         };
 
+        if let Some(message) = message {
+            diagnostics.report_node(DagRule::EdgeCount, vertex, message);
+        }
+
         // Check if the edges are connected in the correct order.
         for node in neighbors {
             let neighbor = graph[node].clone();
@@ -83,12 +192,14 @@ fn has_correct_order_of_actions(graph: &DiGraph<GraphNode, EdgeKind>) -> AnyResu
             };
 
             if !is_ok {
-                bail!("The edge from '{vertex}' to '{neighbor}' is invalid.");
+                diagnostics.report_node(
+                    DagRule::EdgeOrder,
+                    vertex,
+                    format!("The edge from '{vertex}' to '{neighbor}' is invalid."),
+                );
             }
         }
     }
-
-    Ok(())
 }
 
 /// For each distinct group of entry vertices, check that the net concurrency
@@ -96,12 +207,13 @@ fn has_correct_order_of_actions(graph: &DiGraph<GraphNode, EdgeKind>) -> AnyResu
 fn follows_concurrency_rules(
     graph: &DiGraph<GraphNode, EdgeKind>,
     vertex_entry_groups: &HashMap<GraphNode, Vec<String>>,
-) -> AnyResult<()> {
+    diagnostics: &mut Diagnostics,
+) {
     // Get all distinct groups of entry vertices.
     let groups = vertex_entry_groups
         .values()
         .flatten()
-        .collect::<HashSet<_>>();
+        .collect::<BTreeSet<_>>();
 
     // For each group...
     for group in groups {
@@ -141,35 +253,39 @@ fn follows_concurrency_rules(
                 })
                 .collect::<HashSet<_>>();
 
+            let node = &graph[input_port];
+
             // If there is no path to this input port then it is unreachable.
             if all_nodes_in_paths.is_empty() {
-                bail!(
-                    "'{}' is unreachable when invoking group '{group}'",
-                    graph[input_port]
+                diagnostics.report_node(
+                    DagRule::Unreachable,
+                    node,
+                    format!("'{node}' is unreachable when invoking group '{group}'"),
                 );
+
+                continue;
             }
 
             let concurrency = get_net_concurrency_in_subgraph(graph, &all_nodes_in_paths);
 
             if concurrency < 0 {
-                bail!(
-                    "'{}' is unreachable when invoking group '{group}'",
-                    graph[input_port]
-                )
+                diagnostics.report_node(
+                    DagRule::Unreachable,
+                    node,
+                    format!("'{node}' is unreachable when invoking group '{group}'"),
+                );
             }
 
             if concurrency > 0 {
-                bail!(
-                    "'{}' has a race condition on it when invoking group '{group}'",
-                    graph[input_port]
-                )
+                diagnostics.report_node(
+                    DagRule::RaceCondition,
+                    node,
+                    format!("'{node}' has a race condition on it when invoking group '{group}'"),
+                );
             }
         }
     }
-
-    Ok(())
 }
-
 fn get_net_concurrency_in_subgraph(
     graph: &DiGraph<GraphNode, EdgeKind>,
     nodes: &HashSet<NodeIndex>,
@@ -211,7 +327,8 @@ fn get_net_concurrency_in_subgraph(
 fn validate_for_each_pairs(
     graph: &DiGraph<GraphNode, EdgeKind>,
     vertex_entry_groups: &HashMap<GraphNode, Vec<String>>,
-) -> AnyResult<()> {
+    diagnostics: &mut Diagnostics,
+) {
     #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
     enum ForEachState {
         Idle,
@@ -224,18 +341,20 @@ fn validate_for_each_pairs(
     let mut stack = vec![];
 
     for vertex in vertex_entry_groups.keys() {
-        let node = graph
-            .node_indices()
-            .find(|&n| &graph[n] == vertex)
-            .ok_or_else(|| anyhow::anyhow!("'{vertex}' not found in graph nodes."))?;
-
-        stack.push((node, vertex, Idle));
+        // Entry vertices missing from the graph are reported when building it.
+        if let Some(node) = graph.node_indices().find(|&n| &graph[n] == vertex) {
+            stack.push((node, vertex, Idle));
+        }
     }
 
     while let Some((node, vertex, state)) = stack.pop() {
         if graph.edges(node).count() == 0 {
             if state == InForEach {
-                bail!("'{vertex}' has a for-each edge without a corresponding collect");
+                diagnostics.report_node(
+                    DagRule::ForEachWithoutCollect,
+                    vertex,
+                    format!("'{vertex}' has a for-each edge without a corresponding collect"),
+                );
             }
 
             continue;
@@ -243,36 +362,43 @@ fn validate_for_each_pairs(
 
         for edge in graph.edges(node) {
             let next_state = match (state, edge.weight()) {
-                (Idle, EdgeKind::Static) => Idle,
-                (Idle, EdgeKind::Normal) => Idle,
-                (Idle, EdgeKind::DoWhile) => Idle,
-                (Idle, EdgeKind::Break) => Idle,
-                (Idle, EdgeKind::ForEach) => InForEach,
-                (Idle, EdgeKind::Collect) => {
-                    bail!("'{vertex}' has a collect edge without for-each");
-                }
-
-                (InForEach, EdgeKind::Normal) => InForEach,
-                (InForEach, EdgeKind::ForEach) => {
-                    bail!("'{vertex}' has a nested for-each");
-                }
-                (InForEach, EdgeKind::Collect) => Idle,
-                (InForEach, EdgeKind::DoWhile) | (InForEach, EdgeKind::Break) => {
-                    bail!("'{vertex}' has a do-while or break edge inside a for-each");
-                }
-                (InForEach, EdgeKind::Static) => {
-                    bail!("'{vertex}' has a static edge inside a for-each");
-                }
+                (Idle, EdgeKind::Static) => Ok(Idle),
+                (Idle, EdgeKind::Normal) => Ok(Idle),
+                (Idle, EdgeKind::DoWhile) => Ok(Idle),
+                (Idle, EdgeKind::Break) => Ok(Idle),
+                (Idle, EdgeKind::ForEach) => Ok(InForEach),
+                (Idle, EdgeKind::Collect) => Err((
+                    DagRule::CollectWithoutForEach,
+                    format!("'{vertex}' has a collect edge without for-each"),
+                )),
+
+                (InForEach, EdgeKind::Normal) => Ok(InForEach),
+                (InForEach, EdgeKind::ForEach) => Err((
+                    DagRule::NestedForEach,
+                    format!("'{vertex}' has a nested for-each"),
+                )),
+                (InForEach, EdgeKind::Collect) => Ok(Idle),
+                (InForEach, EdgeKind::DoWhile) | (InForEach, EdgeKind::Break) => Err((
+                    DagRule::LoopInForEach,
+                    format!("'{vertex}' has a do-while or break edge inside a for-each"),
+                )),
+                (InForEach, EdgeKind::Static) => Err((
+                    DagRule::LoopInForEach,
+                    format!("'{vertex}' has a static edge inside a for-each"),
+                )),
             };
 
-            stack.push((edge.target(), &graph[edge.target()], next_state));
+            // Walks past a violation are not followed to avoid reporting the
+            // same problem again further down.
+            match next_state {
+                Ok(next_state) => stack.push((edge.target(), &graph[edge.target()], next_state)),
+                Err((rule, message)) => diagnostics.report_node(rule, vertex, message),
+            }
         }
     }
-
-    Ok(())
 }
 
-fn validate_do_while_nesting(graph: &DiGraph<GraphNode, EdgeKind>) -> AnyResult<()> {
+fn validate_do_while_nesting(graph: &DiGraph<GraphNode, EdgeKind>, diagnostics: &mut Diagnostics) {
     // Find original destination vertices for do-while edges.
     let vertices = graph
         .edge_references()
@@ -295,7 +421,7 @@ fn validate_do_while_nesting(graph: &DiGraph<GraphNode, EdgeKind>) -> AnyResult<
             }
             _ => None,
         })
-        .collect::<HashSet<_>>();
+        .collect::<BTreeSet<_>>();
 
     for (source, target) in &vertices {
         // Check that none of the paths in the do-while loop contain another
@@ -317,26 +443,36 @@ fn validate_do_while_nesting(graph: &DiGraph<GraphNode, EdgeKind>) -> AnyResult<
         })
         .collect::<HashSet<_>>();
 
-        for path_node in paths {
-            for edge in graph.edges(path_node) {
-                if matches!(
+        let has_nested_loop = paths.into_iter().any(|path_node| {
+            graph.edges(path_node).any(|edge| {
+                matches!(
                     edge.weight(),
                     EdgeKind::DoWhile | EdgeKind::Break | EdgeKind::ForEach | EdgeKind::Collect
-                ) {
-                    bail!(
-                        "Do-while edge at '{}' has a nested loop inside.",
-                        graph[*source]
-                    );
-                }
-            }
+                )
+            })
+        });
+
+        if has_nested_loop {
+            diagnostics.report_node(
+                DagRule::NestedLoop,
+                &graph[*source],
+                format!(
+                    "Do-while edge at '{}' has a nested loop inside.",
+                    graph[*source]
+                ),
+            );
         }
     }
-
-    Ok(())
 }
 
+/// Node of the graph built by [`try_into_graph`]. Edges always lead from a
+/// vertex to its output variants, to their output ports, to input ports and
+/// back into a vertex.
+///
+/// Do-while edges lead into a phantom vertex named `{vertex}-do-while` to keep
+/// the graph acyclic.
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
-enum GraphNode {
+pub enum GraphNode {
     InputPort {
         vertex: String,
         name: String,
@@ -376,7 +512,19 @@ impl std::fmt::Display for GraphNode {
 
 /// [`DagSpec`] to [petgraph::graph::DiGraph]. Also performs structure checks on the
 /// graph.
-fn try_into_graph(dag: &DagSpec) -> AnyResult<GraphAndVertexEntryGroups> {
+pub fn try_into_graph(dag: &DagSpec) -> AnyResult<GraphAndVertexEntryGroups> {
+    let mut diagnostics = Diagnostics::new(dag);
+    let graph = build_graph(dag, &mut diagnostics);
+
+    diagnostics.into_result()?;
+
+    // The graph is only missing if an error was reported.
+    graph.ok_or_else(|| anyhow!("The DAG has no entry vertices or ports."))
+}
+
+/// Build the graph, reporting structural problems to `diagnostics`. Returns
+/// [`None`] only if the DAG has no entry points.
+fn build_graph(dag: &DagSpec, diagnostics: &mut Diagnostics) -> Option<GraphAndVertexEntryGroups> {
     let mut graph = DiGraph::<GraphNode, EdgeKind>::new();
 
     // Build a hash map of graph nodes that are part of entry groups. If there
@@ -385,18 +533,31 @@ fn try_into_graph(dag: &DagSpec) -> AnyResult<GraphAndVertexEntryGroups> {
     let mut vertex_entry_groups: HashMap<GraphNode, Vec<String>> = HashMap::new();
 
     if !dag.entry_groups.is_empty() {
-        for entry_group in &dag.entry_groups {
-            for vertex in &entry_group.vertices {
+        for (group_index, entry_group) in dag.entry_groups.iter().enumerate() {
+            for (vertex_index, vertex) in entry_group.vertices.iter().enumerate() {
                 let vertex_ident = GraphNode::Vertex {
                     name: vertex.clone(),
                 };
 
                 // Check that the group references a vertex that exists.
                 if !dag.vertices.iter().any(|v| &v.name == vertex) {
-                    bail!(
-                        "Entry group '{}' references a non-existing vertex '{vertex}'.",
-                        entry_group.name
+                    diagnostics.report(
+                        DagDiagnostic::new(
+                            DagRule::UnknownEntryVertex,
+                            format!(
+                                "Entry group '{}' references a non-existing vertex '{vertex}'.",
+                                entry_group.name
+                            ),
+                        )
+                        .with_subject(DiagnosticSubject::EntryGroup {
+                            group: entry_group.name.clone(),
+                        })
+                        .with_pointer(format!(
+                            "/entry_groups/{group_index}/vertices/{vertex_index}"
+                        )),
                     );
+
+                    continue;
                 }
 
                 let mut groups = vertex_entry_groups
@@ -424,7 +585,12 @@ fn try_into_graph(dag: &DagSpec) -> AnyResult<GraphAndVertexEntryGroups> {
 
     // Check that there is at least one entry point.
     if vertex_entry_groups.is_empty() {
-        bail!("The DAG has no entry vertices or ports.");
+        diagnostics.report(DagDiagnostic::new(
+            DagRule::NoEntryPoint,
+            "The DAG has no entry vertices or ports.",
+        ));
+
+        return None;
     }
 
     // Edges are always between an output port and an input port. We also
@@ -432,7 +598,7 @@ fn try_into_graph(dag: &DagSpec) -> AnyResult<GraphAndVertexEntryGroups> {
     // output port if they don't exist yet.
     let mut graph_nodes: HashMap<GraphNode, NodeIndex> = HashMap::new();
 
-    for edge in &dag.edges {
+    for (edge_index, edge) in dag.edges.iter().enumerate() {
         let origin_vertex = GraphNode::Vertex {
             name: edge.from.vertex.clone(),
         };
@@ -512,12 +678,25 @@ fn try_into_graph(dag: &DagSpec) -> AnyResult<GraphAndVertexEntryGroups> {
         });
 
         // Check that these edges don't already exist.
-        if graph.contains_edge(output_variant_node, output_port_node) {
-            bail!("Edge from '{output_variant}' to '{output_port}' already exists.",);
-        }
+        let duplicate = if graph.contains_edge(output_variant_node, output_port_node) {
+            Some(format!(
+                "Edge from '{output_variant}' to '{output_port}' already exists."
+            ))
+        } else if graph.contains_edge(output_port_node, input_port_node) {
+            Some(format!(
+                "Edge from '{output_port}' to '{input_port}' already exists."
+            ))
+        } else {
+            None
+        };
 
-        if graph.contains_edge(output_port_node, input_port_node) {
-            bail!("Edge from '{output_port}' to '{input_port}' already exists.",);
+        if let Some(message) = duplicate {
+            diagnostics.report(
+                DagDiagnostic::new(DagRule::DuplicateEdge, message)
+                    .with_subject(DiagnosticSubject::Edge { index: edge_index }),
+            );
+
+            continue;
         }
 
         // These are allowed.
@@ -538,10 +717,11 @@ fn try_into_graph(dag: &DagSpec) -> AnyResult<GraphAndVertexEntryGroups> {
     let mut all_entry_ports = HashSet::new();
 
     // Check that all normal vertices are in the graph and are unique.
-    for vertex in &dag.vertices {
+    for (vertex_index, vertex) in dag.vertices.iter().enumerate() {
         let vertex_ident = GraphNode::Vertex {
             name: vertex.name.clone(),
         };
+        let pointer = format!("/vertices/{vertex_index}");
 
         // If the dag has no edges and only 1 vertex, we add this vertex as a
         // graph node.
@@ -552,47 +732,77 @@ fn try_into_graph(dag: &DagSpec) -> AnyResult<GraphAndVertexEntryGroups> {
         }
 
         if !graph_nodes.contains_key(&vertex_ident) {
-            bail!("'{vertex_ident}' is not connected to the DAG.",);
+            diagnostics.report(
+                DagDiagnostic::new(
+                    DagRule::DisconnectedVertex,
+                    format!("'{vertex_ident}' is not connected to the DAG."),
+                )
+                .with_subject(diagnostics.subject(&vertex_ident))
+                .with_pointer(pointer.clone()),
+            );
         }
 
         if !all_vertices.insert(vertex_ident.clone()) {
-            bail!("'{vertex_ident}' is defined multiple times.",);
+            diagnostics.report(
+                DagDiagnostic::new(
+                    DagRule::DuplicateVertex,
+                    format!("'{vertex_ident}' is defined multiple times."),
+                )
+                .with_subject(diagnostics.subject(&vertex_ident))
+                .with_pointer(pointer),
+            );
         }
     }
 
     // Check that entry ports are not defined twice and that they have no edges
     // leading into them.
-    for vertex in &dag.vertices {
-        if vertex.entry_ports.is_empty() {
-            continue;
-        }
-
-        for entry_port in &vertex.entry_ports {
+    for (vertex_index, vertex) in dag.vertices.iter().enumerate() {
+        for (port_index, entry_port) in vertex.entry_ports.iter().enumerate() {
             let entry_port_ident = GraphNode::InputPort {
                 vertex: vertex.name.clone(),
                 name: entry_port.name.clone(),
             };
+            let pointer = format!("/vertices/{vertex_index}/entry_ports/{port_index}");
 
             if graph_nodes.contains_key(&entry_port_ident) {
-                bail!("'{entry_port_ident}' has an edge leading to it and therefore cannot be an entry port.",);
+                diagnostics.report(
+                    DagDiagnostic::new(
+                        DagRule::EntryPortWithEdge,
+                        format!("'{entry_port_ident}' has an edge leading to it and therefore cannot be an entry port."),
+                    )
+                    .with_subject(diagnostics.subject(&entry_port_ident))
+                    .with_pointer(pointer.clone()),
+                );
             }
 
             if !all_entry_ports.insert(entry_port_ident.clone()) {
-                bail!("'{entry_port_ident}' is defined multiple times.",);
+                diagnostics.report(
+                    DagDiagnostic::new(
+                        DagRule::DuplicateEntryPort,
+                        format!("'{entry_port_ident}' is defined multiple times."),
+                    )
+                    .with_subject(diagnostics.subject(&entry_port_ident))
+                    .with_pointer(pointer),
+                );
             }
         }
     }
 
     // Check that none of the default value input ports are in the graph.
-    for default_value in &dag.default_values {
+    for (default_index, default_value) in dag.default_values.iter().enumerate() {
         let default_value = GraphNode::InputPort {
             vertex: default_value.vertex.clone(),
             name: default_value.input_port.clone(),
         };
 
         if graph_nodes.contains_key(&default_value) || all_entry_ports.contains(&default_value) {
-            bail!(
-                "'{default_value}' is an entry port or has an edge leading into it and therefore cannot have a default value.",
+            diagnostics.report(
+                DagDiagnostic::new(
+                    DagRule::DefaultValueOnConnectedPort,
+                    format!("'{default_value}' is an entry port or has an edge leading into it and therefore cannot have a default value."),
+                )
+                .with_subject(diagnostics.subject(&default_value))
+                .with_pointer(format!("/default_values/{default_index}")),
             );
         }
     }
@@ -610,7 +820,13 @@ fn try_into_graph(dag: &DagSpec) -> AnyResult<GraphAndVertexEntryGroups> {
         };
 
         if has_outputs && has_edges {
-            bail!("'{vertex_ident}' cannot have both outgoing edges and ports marked as output.");
+            diagnostics.report_node(
+                DagRule::OutputWithEdges,
+                &vertex_ident,
+                format!(
+                    "'{vertex_ident}' cannot have both outgoing edges and ports marked as output."
+                ),
+            );
         }
     }
 
@@ -620,25 +836,33 @@ fn try_into_graph(dag: &DagSpec) -> AnyResult<GraphAndVertexEntryGroups> {
         .iter()
         .filter(|edge| edge.kind == EdgeKind::DoWhile)
         .map(|edge| edge.from.vertex.clone())
-        .collect::<HashSet<_>>();
+        .collect::<BTreeSet<_>>();
 
     let break_edge_vertices = dag
         .edges
         .iter()
         .filter(|edge| edge.kind == EdgeKind::Break)
         .map(|edge| edge.from.vertex.clone())
-        .collect::<HashSet<_>>();
-
-    for vertex in &do_while_edge_vertices {
-        if !break_edge_vertices.contains(vertex) {
-            bail!("Vertex '{vertex}' has a do-while edge but no corresponding break edge.");
-        }
+        .collect::<BTreeSet<_>>();
+
+    for vertex in do_while_edge_vertices.difference(&break_edge_vertices) {
+        diagnostics.report_node(
+            DagRule::UnpairedLoop,
+            &GraphNode::Vertex {
+                name: vertex.clone(),
+            },
+            format!("Vertex '{vertex}' has a do-while edge but no corresponding break edge."),
+        );
     }
 
-    for vertex in &break_edge_vertices {
-        if !do_while_edge_vertices.contains(vertex) {
-            bail!("Vertex '{vertex}' has a break edge but no corresponding do-while edge.");
-        }
+    for vertex in break_edge_vertices.difference(&do_while_edge_vertices) {
+        diagnostics.report_node(
+            DagRule::UnpairedLoop,
+            &GraphNode::Vertex {
+                name: vertex.clone(),
+            },
+            format!("Vertex '{vertex}' has a break edge but no corresponding do-while edge."),
+        );
     }
 
     // Check that do-whiles and breaks always branch.
@@ -647,25 +871,29 @@ fn try_into_graph(dag: &DagSpec) -> AnyResult<GraphAndVertexEntryGroups> {
         .iter()
         .filter(|edge| edge.kind == EdgeKind::DoWhile)
         .map(|edge| (edge.from.vertex.clone(), edge.from.output_variant.clone()))
-        .collect::<HashSet<_>>();
+        .collect::<BTreeSet<_>>();
 
     let break_variants = dag
         .edges
         .iter()
         .filter(|edge| edge.kind == EdgeKind::Break)
         .map(|edge| (edge.from.vertex.clone(), edge.from.output_variant.clone()))
-        .collect::<HashSet<_>>();
+        .collect::<BTreeSet<_>>();
 
-    if let Some(variant) = do_while_variants.intersection(&break_variants).next() {
+    for (vertex, variant) in do_while_variants.intersection(&break_variants) {
         let node = GraphNode::OutputVariant {
-            vertex: variant.0.clone(),
-            name: variant.1.clone(),
+            vertex: vertex.clone(),
+            name: variant.clone(),
         };
 
-        bail!("'{node}' has both a do-while and a break edge, but they must branch.");
+        diagnostics.report_node(
+            DagRule::LoopMustBranch,
+            &node,
+            format!("'{node}' has both a do-while and a break edge, but they must branch."),
+        );
     }
 
-    Ok((graph, vertex_entry_groups))
+    Some((graph, vertex_entry_groups))
 }
 
 #[cfg(test)]
@@ -957,6 +1185,63 @@ mod tests {
         assert_matches!(res, Err(e) if e.to_string().contains("The DAG has no entry vertices or ports."));
     }
 
+    #[test]
+    fn test_diagnose_reports_every_violation() {
+        let dag = parse(
+            r#"{
+                "vertices": [
+                    { "kind": { "variant": "off_chain", "tool_fqn": "com.test.test@1" }, "name": "a", "entry_ports": [{ "name": "input" }] },
+                    { "kind": { "variant": "off_chain", "tool_fqn": "com.test.test@1" }, "name": "b" },
+                    { "kind": { "variant": "off_chain", "tool_fqn": "com.test.test@1" }, "name": "b" }
+                ],
+                "edges": [
+                    { "from": { "vertex": "a", "output_variant": "ok", "output_port": "out" }, "to": { "vertex": "b", "input_port": "in" } }
+                ],
+                "default_values": [
+                    { "vertex": "b", "input_port": "in", "value": { "storage": "inline", "data": 1 } }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let diagnostics = diagnose(&dag);
+
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.rule, diagnostic.pointer.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                (DagRule::DuplicateVertex, Some("/vertices/2")),
+                (
+                    DagRule::DefaultValueOnConnectedPort,
+                    Some("/default_values/0")
+                ),
+            ]
+        );
+
+        // `validate` still reports the first violation.
+        assert_matches!(validate(&dag), Err(e) if e.to_string().contains("'Vertex: b' is defined multiple times."));
+    }
+
+    #[test]
+    fn test_diagnose_points_at_loop_vertex() {
+        let dag = parse(include_str!("_dags/missing_break_invalid.json")).unwrap();
+
+        let diagnostic = diagnose(&dag)
+            .into_iter()
+            .find(|diagnostic| diagnostic.rule == DagRule::UnpairedLoop)
+            .unwrap();
+
+        assert_eq!(
+            diagnostic.subject,
+            Some(DiagnosticSubject::Vertex {
+                vertex: "until_11".to_string()
+            })
+        );
+        assert!(diagnostic.pointer.unwrap().starts_with("/vertices/"));
+    }
+
     #[test]
     fn test_both_vertex_and_entry_vertex_invalid() {
         let dag = parse(include_str!(
@@ -8,6 +8,11 @@ const NEXUS_DATA_INLINE_STORAGE_TAG: &[u8] = b"inline";
 const NEXUS_DATA_WALRUS_STORAGE_TAG: &[u8] = b"walrus";
 
 impl crate::move_bindings::primitives::data::NexusData {
+    /// Walrus payloads are passed to tools in the JSON invocation body as
+    /// `{"$nexus_walrus": <blob id or array of blob ids>}`. The `$` prefix
+    /// keeps the reference from being confused with a regular input object.
+    pub const WALRUS_INPUT_KEY: &'static str = "$nexus_walrus";
+
     pub fn inline_one(data: impl Into<Vec<u8>>) -> Self {
         Self::from_parts(NEXUS_DATA_INLINE_STORAGE_TAG, data.into(), Vec::new())
     }
@@ -25,16 +30,29 @@ impl crate::move_bindings::primitives::data::NexusData {
     }
 
     pub fn walrus_one(data: impl Into<Vec<u8>>) -> Self {
-        Self::from_parts(NEXUS_DATA_WALRUS_STORAGE_TAG, data.into(), Vec::new())
+        Self::remote_one(NEXUS_DATA_WALRUS_STORAGE_TAG, data)
     }
 
     pub fn walrus_many<I, B>(many: I) -> Self
+    where
+        I: IntoIterator<Item = B>,
+        B: Into<Vec<u8>>,
+    {
+        Self::remote_many(NEXUS_DATA_WALRUS_STORAGE_TAG, many)
+    }
+
+    /// Data stored by the remote storage backend identified by `storage`.
+    pub fn remote_one(storage: &[u8], data: impl Into<Vec<u8>>) -> Self {
+        Self::from_parts(storage, data.into(), Vec::new())
+    }
+
+    pub fn remote_many<I, B>(storage: &[u8], many: I) -> Self
     where
         I: IntoIterator<Item = B>,
         B: Into<Vec<u8>>,
     {
         Self::from_parts(
-            NEXUS_DATA_WALRUS_STORAGE_TAG,
+            storage,
             Vec::new(),
             many.into_iter().map(Into::into).collect(),
         )
@@ -87,5 +105,10 @@ mod tests {
         assert!(walrus.is_walrus());
         assert_eq!(walrus.one, b"blob-id".to_vec());
         assert!(walrus.many.is_empty());
+
+        let remote = NexusData::remote_many(b"s3", [b"key".to_vec()]);
+        assert!(!remote.is_inline() && !remote.is_walrus());
+        assert_eq!(remote.storage_tag(), b"s3");
+        assert_eq!(remote.many, vec![b"key".to_vec()]);
     }
 }
@@ -322,8 +322,8 @@ impl<'a> NexusPtbBuilder<'a> {
                     as fn() -> Result<CallTarget, sui_move_call::CallSpecError>,
             ),
             storage => anyhow::bail!(
-                "unsupported NexusData storage tag: {}",
-                hex::encode(storage)
+                "NexusData storage '{}' cannot be passed on-chain, only inline and walrus data can",
+                String::from_utf8_lossy(storage)
             ),
         };
 
@@ -11,6 +11,8 @@ use {
             gas::GasActions,
             scheduler::SchedulerActions,
             signer::{ExecutedTransaction, Signer},
+            simulation::SimulatedTransaction,
+            transaction_signer::TransactionSigner,
             workflow::WorkflowActions,
         },
         sui,
@@ -125,13 +127,14 @@ impl AddressBalanceGas {
 /// Builder for [`NexusClient`].
 #[derive(Default)]
 pub struct NexusClientBuilder {
-    pk: Option<sui::crypto::Ed25519PrivateKey>,
+    transaction_signer: Option<Arc<dyn TransactionSigner>>,
     rpc_url: Option<String>,
     gas_coins: Vec<sui::types::ObjectReference>,
     gas_budget: Option<u64>,
     address_balance_gas: Option<AddressBalanceGas>,
     nexus_objects: Option<NexusObjects>,
     transaction_timeout: Option<Duration>,
+    budget_margin: Option<u64>,
 }
 
 impl NexusClientBuilder {
@@ -142,7 +145,16 @@ impl NexusClientBuilder {
 
     /// Add a private key to the builder.
     pub fn with_private_key(mut self, pk: sui::crypto::Ed25519PrivateKey) -> Self {
-        self.pk = Some(pk);
+        self.transaction_signer = Some(Arc::new(pk));
+        self
+    }
+
+    /// Sign transactions with a custom [`TransactionSigner`] backend, for
+    /// example an [`ExternalProcessSigner`](crate::nexus::transaction_signer::ExternalProcessSigner)
+    /// or a [`MultisigSigner`](crate::nexus::transaction_signer::MultisigSigner).
+    /// Replaces any private key set via [`Self::with_private_key`].
+    pub fn with_signer(mut self, signer: impl TransactionSigner + 'static) -> Self {
+        self.transaction_signer = Some(Arc::new(signer));
         self
     }
 
@@ -188,6 +200,14 @@ impl NexusClientBuilder {
         self
     }
 
+    /// Simulate every transaction before submission and size its gas budget
+    /// to the estimate plus `margin_percent` percent, capped at the configured
+    /// budget. Transactions that fail in simulation are not executed.
+    pub fn with_budget_estimation(mut self, margin_percent: u64) -> Self {
+        self.budget_margin = Some(margin_percent);
+        self
+    }
+
     /// Builds the [`NexusClient`].
     ///
     /// # Errors
@@ -196,9 +216,9 @@ impl NexusClientBuilder {
     /// missing or both gas sources are configured. Returns [`NexusError::Rpc`]
     /// when the client or coin based gas context cannot be initialized.
     pub async fn build(self) -> Result<NexusClient, NexusError> {
-        let pk = self
-            .pk
-            .ok_or_else(|| NexusError::Configuration("User's private key is required".into()))?;
+        let transaction_signer = self.transaction_signer.ok_or_else(|| {
+            NexusError::Configuration("User's private key or signer is required".into())
+        })?;
 
         let rpc_url = self
             .rpc_url
@@ -247,19 +267,23 @@ impl NexusClientBuilder {
         };
         let gas = Gas { source };
 
-        let signer = Signer::new(
+        let signer = Signer::with_transaction_signer(
             Arc::clone(&client),
-            pk,
+            transaction_signer,
             self.transaction_timeout.unwrap_or(Duration::from_secs(5)),
             Arc::clone(&nexus_objects),
         );
 
+        let crawler =
+            Crawler::new(client).with_tool_registry(*nexus_objects.tool_registry.object_id());
+
         Ok(NexusClient {
             signer,
             gas,
             nexus_objects,
-            crawler: Crawler::new(client),
+            crawler,
             rpc_url,
+            budget_margin: self.budget_margin,
         })
     }
 }
@@ -277,6 +301,8 @@ pub struct NexusClient {
     pub(super) crawler: Crawler,
     /// RPC URL used by the client.
     pub(super) rpc_url: String,
+    /// Safety margin in percent for simulated gas budgets, if enabled.
+    pub(super) budget_margin: Option<u64>,
 }
 
 impl NexusClient {
@@ -306,6 +332,13 @@ impl NexusClient {
         }
     }
 
+    /// Return a [`LeaderActions`] instance for leader operator actions.
+    pub fn leader(&self) -> crate::nexus::leader::LeaderActions {
+        crate::nexus::leader::LeaderActions {
+            client: self.clone(),
+        }
+    }
+
     /// Return a [`NetworkAuthActions`] instance for tool network-auth operations.
     pub fn network_auth(&self) -> crate::nexus::network_auth::NetworkAuthActions {
         crate::nexus::network_auth::NetworkAuthActions {
@@ -371,6 +404,10 @@ impl NexusClient {
     /// Submits a programmable transaction through this client's configured
     /// [`Gas`] source.
     ///
+    /// When budget estimation is enabled the transaction is simulated first
+    /// and its budget is sized to the estimate. Use
+    /// [`Self::simulate_transaction`] to only simulate it.
+    ///
     /// # Errors
     ///
     /// Returns [`NexusError`] when transaction construction, signing, or
@@ -383,19 +420,17 @@ impl NexusClient {
         match &self.gas.source {
             GasSource::Coin(pool) => {
                 let mut gas_coin = pool.acquire_gas_coin().await;
-                let tx = sui::types::Transaction {
-                    kind: sui::types::TransactionKind::ProgrammableTransaction(tx),
-                    sender: address,
-                    gas_payment: sui::types::GasPayment {
-                        objects: vec![gas_coin.clone()],
-                        owner: address,
-                        price: pool.reference_gas_price,
-                        budget: pool.budget,
-                    },
-                    expiration: sui::types::TransactionExpiration::None,
+                let coin = gas_coin.clone();
+                let build = |budget| {
+                    coin_transaction(tx.clone(), address, &coin, pool.reference_gas_price, budget)
                 };
-                let signature = self.signer.sign_tx(&tx).await?;
-                let response = self.signer.execute_tx(tx, signature, &mut gas_coin).await;
+                // Release the coin on every path, including signing failures.
+                let response = async {
+                    let tx = self.prepare_transaction(build, pool.budget).await?;
+                    let signature = self.signer.sign_tx(&tx).await?;
+                    self.signer.execute_tx(tx, signature, &mut gas_coin).await
+                }
+                .await;
                 pool.release_gas_coin(gas_coin).await;
                 response
             }
@@ -403,13 +438,80 @@ impl NexusClient {
                 let mut client = self.signer.client.lock().await.clone();
                 let context = fetch_submission_context(&mut client).await?;
                 let nonce = gas.allocate_nonce()?;
-                let tx = finish_transaction(tx, address, gas.budget, context, nonce);
+                let build =
+                    |budget| finish_transaction(tx.clone(), address, budget, context, nonce);
+                let tx = self.prepare_transaction(build, gas.budget).await?;
                 let signature = self.signer.sign_tx(&tx).await?;
                 self.signer.execute_tx_without_gas_coin(tx, signature).await
             }
         }
     }
 
+    /// Simulates a programmable transaction with this client's configured
+    /// [`Gas`] source without executing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NexusError`] when the transaction cannot be built or the
+    /// simulation request fails. A transaction that would fail on chain is
+    /// reported through [`SimulatedTransaction::failure`].
+    pub async fn simulate_transaction(
+        &self,
+        tx: sui::types::ProgrammableTransaction,
+        address: sui::types::Address,
+    ) -> Result<SimulatedTransaction, NexusError> {
+        match &self.gas.source {
+            GasSource::Coin(pool) => {
+                let gas_coin = pool.acquire_gas_coin().await;
+                let tx = coin_transaction(
+                    tx,
+                    address,
+                    &gas_coin,
+                    pool.reference_gas_price,
+                    pool.budget,
+                );
+                let simulated = self.signer.simulate_tx(tx).await;
+                pool.release_gas_coin(gas_coin).await;
+                simulated
+            }
+            GasSource::AddressBalance(gas) => {
+                let mut client = self.signer.client.lock().await.clone();
+                let context = fetch_submission_context(&mut client).await?;
+                let nonce = gas.allocate_nonce()?;
+                let tx = finish_transaction(tx, address, gas.budget, context, nonce);
+                self.signer.simulate_tx(tx).await
+            }
+        }
+    }
+
+    /// Simulates the transaction built with the configured `budget` when
+    /// budget estimation is enabled and returns the transaction to execute.
+    async fn prepare_transaction(
+        &self,
+        build: impl Fn(u64) -> sui::types::Transaction,
+        budget: u64,
+    ) -> Result<sui::types::Transaction, NexusError> {
+        let tx = build(budget);
+
+        let Some(margin) = self.budget_margin else {
+            return Ok(tx);
+        };
+
+        let simulated = self.signer.simulate_tx(tx).await?;
+
+        if let Some(abort) = simulated.move_abort() {
+            return Err(self.signer.resolve_move_abort(abort).await.into());
+        }
+
+        if let Some(failure) = simulated.failure() {
+            return Err(NexusError::Wallet(anyhow::anyhow!(
+                "Transaction simulation failed: {failure}"
+            )));
+        }
+
+        Ok(build(simulated.gas.budget_with_margin(margin).min(budget)))
+    }
+
     // == Helpers reused by multiple actions ==
 
     /// Fetch all [`ToolGas`] derived objects that are relevant to the provided
@@ -486,6 +588,27 @@ impl NexusClient {
     }
 }
 
+/// Builds a transaction paying for gas with an owned coin.
+fn coin_transaction(
+    tx: sui::types::ProgrammableTransaction,
+    sender: sui::types::Address,
+    gas_coin: &sui::types::ObjectReference,
+    price: u64,
+    budget: u64,
+) -> sui::types::Transaction {
+    sui::types::Transaction {
+        kind: sui::types::TransactionKind::ProgrammableTransaction(tx),
+        sender,
+        gas_payment: sui::types::GasPayment {
+            objects: vec![gas_coin.clone()],
+            owner: sender,
+            price,
+            budget,
+        },
+        expiration: sui::types::TransactionExpiration::None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -606,6 +729,27 @@ mod tests {
         assert_eq!(client.get_reference_gas_price(), None);
     }
 
+    #[tokio::test]
+    async fn builder_with_custom_signer_uses_its_address() {
+        let signer = crate::nexus::transaction_signer::InMemorySigner::from(
+            sui::crypto::Secp256k1PrivateKey::generate(rand::thread_rng()),
+        );
+        let address = signer.address();
+        let rpc_url = sui_mocks::grpc::mock_server(Default::default());
+
+        let client = NexusClientBuilder::new()
+            .with_signer(signer)
+            .with_rpc_url(&rpc_url)
+            .with_nexus_objects(sui_mocks::mock_nexus_objects())
+            .with_address_balance_gas(7_000)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(client.signer.get_active_address(), address);
+        assert_eq!(client.signer.transaction_signer().address(), address);
+    }
+
     #[tokio::test]
     async fn builder_rejects_two_gas_sources() {
         let pk = sui::crypto::Ed25519PrivateKey::generate(rand::thread_rng());
@@ -821,6 +965,215 @@ mod tests {
         assert_eq!(gas_coin.digest(), gas_coin_ref.digest());
     }
 
+    fn mock_gas_used(computation_cost: u64, storage_cost: u64) -> sui::types::GasCostSummary {
+        sui::types::GasCostSummary {
+            computation_cost,
+            storage_cost,
+            storage_rebate: 0,
+            non_refundable_storage_fee: 0,
+        }
+    }
+
+    fn empty_ptb() -> sui::types::ProgrammableTransaction {
+        sui::types::ProgrammableTransaction {
+            inputs: vec![],
+            commands: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn simulate_transaction_does_not_execute() {
+        let mut ledger_service_mock = sui_mocks::grpc::MockLedgerService::new();
+        let mut tx_service_mock = sui_mocks::grpc::MockTransactionExecutionService::new();
+        sui_mocks::grpc::mock_reference_gas_price(&mut ledger_service_mock, 1000);
+        sui_mocks::grpc::mock_simulate_transaction(
+            &mut tx_service_mock,
+            sui::types::ExecutionStatus::Success,
+            mock_gas_used(2_000, 3_000),
+        );
+        tx_service_mock.expect_execute_transaction().never();
+
+        let rpc_url = sui_mocks::grpc::mock_server(sui_mocks::grpc::ServerMocks {
+            ledger_service_mock: Some(ledger_service_mock),
+            execution_service_mock: Some(tx_service_mock),
+            ..Default::default()
+        });
+        let client = NexusClientBuilder::new()
+            .with_private_key(sui::crypto::Ed25519PrivateKey::generate(rand::thread_rng()))
+            .with_rpc_url(&rpc_url)
+            .with_nexus_objects(sui_mocks::mock_nexus_objects())
+            .with_gas(vec![sui_mocks::mock_sui_object_ref()], 10_000)
+            .build()
+            .await
+            .unwrap();
+        let sender = client.signer.get_active_address();
+
+        let simulated = client
+            .simulate_transaction(empty_ptb(), sender)
+            .await
+            .unwrap();
+
+        assert!(simulated.is_success());
+        assert_eq!(simulated.budget, 10_000);
+        assert_eq!(simulated.gas.required_budget(), 5_000);
+
+        // The gas coin is released for the next submission.
+        let simulated = client
+            .simulate_transaction(empty_ptb(), sender)
+            .await
+            .unwrap();
+        assert_eq!(simulated.gas.required_budget(), 5_000);
+    }
+
+    #[tokio::test]
+    async fn budget_estimation_sizes_budget_from_simulation() {
+        let mut rng = rand::thread_rng();
+        let digest = sui::types::Digest::generate(&mut rng);
+        let gas_coin_ref = sui_mocks::mock_sui_object_ref();
+
+        let mut ledger_service_mock = sui_mocks::grpc::MockLedgerService::new();
+        let mut tx_service_mock = sui_mocks::grpc::MockTransactionExecutionService::new();
+        let mut sub_service_mock = sui_mocks::grpc::MockSubscriptionService::new();
+        sui_mocks::grpc::mock_reference_gas_price(&mut ledger_service_mock, 1000);
+        sui_mocks::grpc::mock_simulate_transaction(
+            &mut tx_service_mock,
+            sui::types::ExecutionStatus::Success,
+            mock_gas_used(1_000, 1_000),
+        );
+        sui_mocks::grpc::mock_execute_transaction_and_wait_for_checkpoint_matching(
+            &mut tx_service_mock,
+            &mut sub_service_mock,
+            &mut ledger_service_mock,
+            digest,
+            gas_coin_ref.clone(),
+            vec![],
+            vec![],
+            vec![],
+            |request| {
+                let transaction = request.transaction.as_ref().unwrap();
+                let transaction = sui::types::Transaction::try_from(transaction).unwrap();
+                // 2_000 required plus a 50% margin.
+                assert_eq!(transaction.gas_payment.budget, 3_000);
+            },
+        );
+
+        let rpc_url = sui_mocks::grpc::mock_server(sui_mocks::grpc::ServerMocks {
+            ledger_service_mock: Some(ledger_service_mock),
+            execution_service_mock: Some(tx_service_mock),
+            subscription_service_mock: Some(sub_service_mock),
+            ..Default::default()
+        });
+        let client = NexusClientBuilder::new()
+            .with_private_key(sui::crypto::Ed25519PrivateKey::generate(&mut rng))
+            .with_rpc_url(&rpc_url)
+            .with_nexus_objects(sui_mocks::mock_nexus_objects())
+            .with_gas(vec![gas_coin_ref], 1_000_000)
+            .with_budget_estimation(50)
+            .build()
+            .await
+            .unwrap();
+        let sender = client.signer.get_active_address();
+
+        let response = client
+            .submit_transaction(empty_ptb(), sender)
+            .await
+            .unwrap();
+        assert_eq!(response.digest, digest);
+    }
+
+    #[tokio::test]
+    async fn budget_estimation_does_not_execute_failing_transactions() {
+        let mut ledger_service_mock = sui_mocks::grpc::MockLedgerService::new();
+        let mut tx_service_mock = sui_mocks::grpc::MockTransactionExecutionService::new();
+        sui_mocks::grpc::mock_reference_gas_price(&mut ledger_service_mock, 1000);
+        sui_mocks::grpc::mock_simulate_transaction(
+            &mut tx_service_mock,
+            sui::types::ExecutionStatus::Failure {
+                error: sui::types::ExecutionError::InsufficientGas,
+                command: None,
+            },
+            mock_gas_used(1_000, 0),
+        );
+        tx_service_mock.expect_execute_transaction().never();
+
+        let rpc_url = sui_mocks::grpc::mock_server(sui_mocks::grpc::ServerMocks {
+            ledger_service_mock: Some(ledger_service_mock),
+            execution_service_mock: Some(tx_service_mock),
+            ..Default::default()
+        });
+        let client = NexusClientBuilder::new()
+            .with_private_key(sui::crypto::Ed25519PrivateKey::generate(rand::thread_rng()))
+            .with_rpc_url(&rpc_url)
+            .with_nexus_objects(sui_mocks::mock_nexus_objects())
+            .with_gas(vec![sui_mocks::mock_sui_object_ref()], 1_000)
+            .with_budget_estimation(20)
+            .build()
+            .await
+            .unwrap();
+        let sender = client.signer.get_active_address();
+
+        let err = client
+            .submit_transaction(empty_ptb(), sender)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Transaction simulation failed: InsufficientGas"));
+    }
+
+    #[tokio::test]
+    async fn budget_estimation_surfaces_move_aborts() {
+        let mut ledger_service_mock = sui_mocks::grpc::MockLedgerService::new();
+        let mut tx_service_mock = sui_mocks::grpc::MockTransactionExecutionService::new();
+        let package = sui_mocks::mock_sui_address();
+        sui_mocks::grpc::mock_reference_gas_price(&mut ledger_service_mock, 1000);
+        sui_mocks::grpc::mock_simulate_transaction(
+            &mut tx_service_mock,
+            sui::types::ExecutionStatus::Failure {
+                error: sui::types::ExecutionError::MoveAbort {
+                    location: sui::types::MoveLocation {
+                        package,
+                        module: sui::types::Identifier::from_static("tool_registry"),
+                        function: 1,
+                        instruction: 4,
+                        function_name: Some(sui::types::Identifier::from_static("register_tool_")),
+                    },
+                    code: 3,
+                },
+                command: Some(0),
+            },
+            mock_gas_used(1_000, 0),
+        );
+        tx_service_mock.expect_execute_transaction().never();
+
+        let rpc_url = sui_mocks::grpc::mock_server(sui_mocks::grpc::ServerMocks {
+            ledger_service_mock: Some(ledger_service_mock),
+            execution_service_mock: Some(tx_service_mock),
+            ..Default::default()
+        });
+        let client = NexusClientBuilder::new()
+            .with_private_key(sui::crypto::Ed25519PrivateKey::generate(rand::thread_rng()))
+            .with_rpc_url(&rpc_url)
+            .with_nexus_objects(sui_mocks::mock_nexus_objects())
+            .with_gas(vec![sui_mocks::mock_sui_object_ref()], 1_000)
+            .with_budget_estimation(20)
+            .build()
+            .await
+            .unwrap();
+        let sender = client.signer.get_active_address();
+
+        let err = client
+            .submit_transaction(empty_ptb(), sender)
+            .await
+            .unwrap_err();
+        let abort = err.move_abort().expect("Move abort expected");
+        assert_eq!(abort.package, package);
+        assert!(abort.is_in("tool_registry", "register_tool_"));
+        assert_eq!(abort.code, 3);
+        assert_eq!(abort.name, None);
+        assert_eq!(abort.command_index, Some(0));
+    }
+
     #[tokio::test]
     async fn execute_tx_without_gas_coin_does_not_refresh_an_object() {
         let mut rng = rand::thread_rng();
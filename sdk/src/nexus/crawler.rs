@@ -5,6 +5,8 @@ use {
     crate::{
         move_bindings::sui_framework::table_vec::TableVec,
         sui::{self, traits::FieldMaskUtil},
+        types::Tool,
+        ToolFqn,
     },
     anyhow::{anyhow, bail, Context as _},
     serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize},
@@ -44,6 +46,8 @@ where
 #[derive(Clone)]
 pub struct Crawler {
     client: Arc<Mutex<sui::grpc::Client>>,
+    /// Registry that tools are resolved from by [`Crawler::get_tool`].
+    tool_registry_id: Option<sui::types::Address>,
 }
 
 #[derive(Debug)]
@@ -80,7 +84,27 @@ pub struct TransactionUpdate {
 
 impl Crawler {
     pub fn new(client: Arc<Mutex<sui::grpc::Client>>) -> Self {
-        Self { client }
+        Self {
+            client,
+            tool_registry_id: None,
+        }
+    }
+
+    /// Resolve tools from the tool registry with the given object ID.
+    pub fn with_tool_registry(mut self, tool_registry_id: sui::types::Address) -> Self {
+        self.tool_registry_id = Some(tool_registry_id);
+        self
+    }
+
+    /// Fetch the tool registered under `tool_fqn` in the tool registry set by
+    /// [`Crawler::with_tool_registry`].
+    pub async fn get_tool(&self, tool_fqn: &ToolFqn) -> anyhow::Result<Response<Tool>> {
+        let tool_registry_id = self
+            .tool_registry_id
+            .ok_or_else(|| anyhow!("Crawler has no tool registry to resolve '{tool_fqn}' from"))?;
+        let tool_id = Tool::derive_id(tool_registry_id, tool_fqn)?;
+
+        self.get_object::<Tool>(tool_id).await
     }
 
     /// Fetch a published Move package descriptor for ABI inspection.
@@ -101,6 +125,30 @@ impl Crawler {
             .ok_or_else(|| anyhow!("Package '{package_id}' was not returned"))
     }
 
+    /// Fetch the compiled bytecode of `module` in a published package.
+    pub async fn get_module_bytecode(
+        &self,
+        package_id: sui::types::Address,
+        module: &str,
+    ) -> anyhow::Result<Vec<u8>> {
+        let object = self
+            .fetch_object(package_id, sui::grpc::FieldMask::from_paths(["bcs"]))
+            .await?;
+        let object = sui::types::Object::try_from(&object)
+            .map_err(|e| anyhow!("Could not decode package '{package_id}': {e}"))?;
+
+        let sui::types::ObjectData::Package(package) = object.data() else {
+            bail!("Object '{package_id}' is not a package");
+        };
+
+        package
+            .modules
+            .iter()
+            .find(|(name, _)| name.as_str() == module)
+            .map(|(_, bytecode)| bytecode.clone())
+            .ok_or_else(|| anyhow!("Module '{module}' not found in package '{package_id}'"))
+    }
+
     async fn get_object_parsed<T>(
         &self,
         object_id: sui::types::Address,
@@ -225,6 +273,26 @@ impl Crawler {
         Ok(hex::encode(&digest.as_bytes()[..4]))
     }
 
+    /// Fetch the lowest checkpoint the connected RPC still serves and the
+    /// latest executed checkpoint.
+    pub async fn get_checkpoint_bounds(&self) -> anyhow::Result<(u64, u64)> {
+        let mut client = self.client.lock().await;
+        let response = client
+            .ledger_client()
+            .get_service_info(sui::grpc::GetServiceInfoRequest::default())
+            .await
+            .map_err(|e| anyhow!("failed to fetch service info from the connected RPC: {e}"))?
+            .into_inner();
+        let latest = response.checkpoint_height.ok_or_else(|| {
+            anyhow!("connected RPC did not return a checkpoint height in service info")
+        })?;
+
+        Ok((
+            response.lowest_available_checkpoint.unwrap_or_default(),
+            latest,
+        ))
+    }
+
     /// Fetch an object's metadata only, omitting its content.
     pub async fn get_object_metadata(
         &self,
@@ -0,0 +1,425 @@
+//! Cron expressions evaluated in a timezone, used to expand calendar based
+//! schedules into scheduler occurrences client-side.
+//!
+//! Expressions use the standard five fields `minute hour day-of-month month
+//! day-of-week`. Each field accepts `*`, numbers, ranges (`1-5`), lists
+//! (`1,15`) and steps (`*/15`, `0-30/10`). Months and days of the week can
+//! also be given by their three letter English names (`JAN`, `MON`) and both
+//! `0` and `7` mean Sunday. The `@yearly`, `@annually`, `@monthly`,
+//! `@weekly`, `@daily`, `@midnight` and `@hourly` shortcuts are supported.
+//!
+//! As in Vixie cron, if both the day-of-month and the day-of-week fields are
+//! restricted, a day matches when either of them matches.
+//!
+//! Fire times are computed in the schedule's timezone. Local times skipped by
+//! a daylight saving transition do not fire and local times repeated by one
+//! fire only once, at their first instance.
+
+use {
+    anyhow::{anyhow, bail, Result as AnyResult},
+    jiff::{
+        civil::Date,
+        tz::{AmbiguousOffset, Offset, TimeZone},
+        Timestamp,
+    },
+};
+
+/// How many days ahead to look for the next fire time before giving up.
+/// Covers a full 28-year cycle of weekdays and leap years so that schedules
+/// like `0 0 29 2 MON` are still found.
+const MAX_SEARCH_DAYS: usize = 366 * 28;
+
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// A parsed cron expression together with the timezone it is evaluated in.
+#[derive(Clone, Debug)]
+pub struct CronSchedule {
+    expression: String,
+    timezone_name: String,
+    timezone: TimeZone,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Whether the day-of-month field is restricted, i.e. not starting with `*`.
+    days_of_month_restricted: bool,
+    /// Whether the day-of-week field is restricted, i.e. not starting with `*`.
+    days_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    /// Parse a cron `expression` evaluated in `timezone`. The timezone is
+    /// either `UTC`, a fixed offset such as `+05:30` or an IANA name such as
+    /// `Europe/Prague`, which is looked up in the system timezone database.
+    pub fn parse(expression: &str, timezone: &str) -> AnyResult<Self> {
+        let expanded = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other if other.starts_with('@') => bail!("Unknown cron shortcut '{other}'"),
+            other => other,
+        };
+
+        let fields = expanded.split_whitespace().collect::<Vec<_>>();
+
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            bail!(
+                "Cron expression '{expression}' must have 5 fields (minute hour day-of-month month day-of-week), found {}",
+                fields.len()
+            );
+        };
+
+        let mut days_of_week = parse_field(day_of_week, "day-of-week", 0, 7, &WEEKDAY_NAMES)?;
+
+        // Both 0 and 7 are Sunday.
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            timezone_name: timezone.to_string(),
+            timezone: parse_timezone(timezone)?,
+            minutes: parse_field(minute, "minute", 0, 59, &[])?,
+            hours: parse_field(hour, "hour", 0, 23, &[])?,
+            days_of_month: parse_field(day_of_month, "day-of-month", 1, 31, &[])?,
+            months: parse_field(month, "month", 1, 12, &MONTH_NAMES)?,
+            days_of_week,
+            days_of_month_restricted: !day_of_month.starts_with('*'),
+            days_of_week_restricted: !day_of_week.starts_with('*'),
+        })
+    }
+
+    /// The cron expression this schedule was parsed from.
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// The timezone this schedule is evaluated in, as given to [`Self::parse`].
+    pub fn timezone(&self) -> &str {
+        &self.timezone_name
+    }
+
+    /// The first fire time strictly after `after_ms`, in milliseconds since
+    /// the epoch. Returns [`None`] if the schedule never fires again.
+    pub fn next_after(&self, after_ms: u64) -> Option<u64> {
+        let after = Timestamp::from_millisecond(i64::try_from(after_ms).ok()?).ok()?;
+        let mut date = after.to_zoned(self.timezone.clone()).date();
+
+        for _ in 0..MAX_SEARCH_DAYS {
+            if self.matches_date(date) {
+                for hour in bits(self.hours) {
+                    for minute in bits(self.minutes) {
+                        let datetime = date.at(hour as i8, minute as i8, 0, 0);
+                        let ambiguous = self.timezone.to_ambiguous_timestamp(datetime);
+
+                        // This local time does not exist on this day.
+                        if matches!(ambiguous.offset(), AmbiguousOffset::Gap { .. }) {
+                            continue;
+                        }
+
+                        let Ok(timestamp) = ambiguous.earlier() else {
+                            continue;
+                        };
+
+                        if timestamp > after {
+                            return u64::try_from(timestamp.as_millisecond()).ok();
+                        }
+                    }
+                }
+            }
+
+            date = date.tomorrow().ok()?;
+        }
+
+        None
+    }
+
+    /// Iterate over all fire times strictly after `after_ms`.
+    pub fn occurrences_after(&self, after_ms: u64) -> CronOccurrences<'_> {
+        CronOccurrences {
+            schedule: self,
+            cursor: Some(after_ms),
+        }
+    }
+
+    /// All fire times in `(after_ms, until_ms]`.
+    pub fn occurrences_between(&self, after_ms: u64, until_ms: u64) -> Vec<u64> {
+        self.occurrences_after(after_ms)
+            .take_while(|start_ms| *start_ms <= until_ms)
+            .collect()
+    }
+
+    /// Format `timestamp_ms` as a local date and time in the schedule's
+    /// timezone, e.g. `2024-03-10T03:00:00-04:00[America/New_York]`.
+    pub fn local_time(&self, timestamp_ms: u64) -> Option<String> {
+        let timestamp = Timestamp::from_millisecond(i64::try_from(timestamp_ms).ok()?).ok()?;
+
+        Some(timestamp.to_zoned(self.timezone.clone()).to_string())
+    }
+
+    fn matches_date(&self, date: Date) -> bool {
+        let month = has_bit(self.months, date.month() as u32);
+        let day_of_month = has_bit(self.days_of_month, date.day() as u32);
+        let day_of_week = has_bit(
+            self.days_of_week,
+            date.weekday().to_sunday_zero_offset() as u32,
+        );
+
+        let day = if self.days_of_month_restricted && self.days_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        };
+
+        month && day
+    }
+}
+
+impl std::fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.expression, self.timezone_name)
+    }
+}
+
+/// Iterator over the fire times of a [`CronSchedule`], created by
+/// [`CronSchedule::occurrences_after`].
+pub struct CronOccurrences<'a> {
+    schedule: &'a CronSchedule,
+    cursor: Option<u64>,
+}
+
+impl Iterator for CronOccurrences<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.schedule.next_after(self.cursor?);
+
+        self.cursor = next;
+
+        next
+    }
+}
+
+/// Parse a single cron field into a bit set of allowed values.
+fn parse_field(field: &str, label: &str, min: u32, max: u32, names: &[&str]) -> AnyResult<u64> {
+    let value = |value: &str| -> AnyResult<u32> {
+        if let Some(index) = names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(value))
+        {
+            // Month names start at 1, weekday names at 0.
+            return Ok(index as u32 + min);
+        }
+
+        value
+            .parse::<u32>()
+            .map_err(|_| anyhow!("Invalid {label} value '{value}'"))
+    };
+
+    let mut bits = 0u64;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| anyhow!("Invalid {label} step '{step}'"))?;
+
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (value(start)?, value(end)?),
+            // `5/15` means every 15 starting at 5.
+            None if step.is_some() => (value(range)?, max),
+            None => {
+                let value = value(range)?;
+
+                (value, value)
+            }
+        };
+
+        if start < min || end > max || start > end {
+            bail!("Invalid {label} range '{range}', values must be within {min}-{max}");
+        }
+
+        for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            bits |= 1 << value;
+        }
+    }
+
+    Ok(bits)
+}
+
+fn parse_timezone(timezone: &str) -> AnyResult<TimeZone> {
+    if timezone.eq_ignore_ascii_case("utc") || timezone == "Z" {
+        return Ok(TimeZone::UTC);
+    }
+
+    if let Some(offset) = parse_offset(timezone) {
+        return Ok(TimeZone::fixed(offset));
+    }
+
+    TimeZone::get(timezone).map_err(|e| anyhow!("Unknown timezone '{timezone}': {e}"))
+}
+
+/// Parse a fixed `+HH:MM` or `-HH:MM` UTC offset.
+fn parse_offset(offset: &str) -> Option<Offset> {
+    let (sign, rest) = match offset.as_bytes().first()? {
+        b'+' => (1, &offset[1..]),
+        b'-' => (-1, &offset[1..]),
+        _ => return None,
+    };
+
+    let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+    let hours = hours.parse::<i32>().ok().filter(|hours| *hours <= 25)?;
+    let minutes = minutes
+        .parse::<i32>()
+        .ok()
+        .filter(|minutes| *minutes < 60)?;
+
+    Offset::from_seconds(sign * (hours * 3_600 + minutes * 60)).ok()
+}
+
+fn has_bit(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+fn bits(bits: u64) -> impl Iterator<Item = u32> {
+    (0..64).filter(move |value| has_bit(bits, *value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Friday 2026-10-16 10:00 UTC.
+    const FRIDAY_10_UTC: u64 = 1_792_144_800_000;
+
+    #[test]
+    fn test_weekdays_at_nine_utc() {
+        let schedule = CronSchedule::parse("0 9 * * MON-FRI", "UTC").unwrap();
+
+        assert_eq!(
+            schedule
+                .occurrences_after(FRIDAY_10_UTC)
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec![1_792_400_400_000, 1_792_486_800_000]
+        );
+    }
+
+    #[test]
+    fn test_first_of_the_month() {
+        let schedule = CronSchedule::parse("@monthly", "UTC").unwrap();
+
+        assert_eq!(
+            schedule.occurrences_between(FRIDAY_10_UTC, 1_796_083_200_000),
+            vec![1_793_491_200_000, 1_796_083_200_000]
+        );
+    }
+
+    #[test]
+    fn test_day_of_month_or_day_of_week() {
+        let midnight = FRIDAY_10_UTC + 14 * 3_600_000;
+
+        let either = CronSchedule::parse("0 0 13 * FRI", "UTC").unwrap();
+        assert_eq!(either.next_after(midnight), Some(1_792_713_600_000));
+
+        let day_of_month = CronSchedule::parse("0 0 13 * *", "UTC").unwrap();
+        assert_eq!(day_of_month.next_after(midnight), Some(1_794_528_000_000));
+    }
+
+    #[test]
+    fn test_fixed_offset() {
+        let schedule = CronSchedule::parse("0 9 * * *", "+05:30").unwrap();
+
+        assert_eq!(schedule.next_after(FRIDAY_10_UTC), Some(1_792_207_800_000));
+        assert_eq!(schedule.to_string(), "0 9 * * * (+05:30)");
+        assert!(schedule
+            .local_time(1_792_207_800_000)
+            .unwrap()
+            .starts_with("2026-10-17T09:00:00+05:30"));
+    }
+
+    #[test]
+    fn test_daylight_saving_transitions() {
+        // Requires the system timezone database.
+        let Ok(gap) = CronSchedule::parse("30 2 * * *", "America/New_York") else {
+            return;
+        };
+
+        // 2026-03-08 02:30 does not exist in New York and is skipped.
+        assert_eq!(gap.next_after(1_772_868_600_000), Some(1_773_037_800_000));
+
+        // 2026-11-01 01:30 happens twice and only fires once.
+        let fold = CronSchedule::parse("30 1 * * *", "America/New_York").unwrap();
+
+        assert_eq!(
+            fold.occurrences_after(1_793_505_600_000)
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec![1_793_511_000_000, 1_793_601_000_000]
+        );
+    }
+
+    #[test]
+    fn test_field_syntax() {
+        let schedule = CronSchedule::parse("*/20 8-10,22 * JAN,jul 0", "UTC").unwrap();
+
+        assert_eq!(schedule.minutes, 1 | (1 << 20) | (1 << 40));
+        assert_eq!(schedule.hours, (1 << 8) | (1 << 9) | (1 << 10) | (1 << 22));
+        assert_eq!(schedule.months, (1 << 1) | (1 << 7));
+        assert_eq!(schedule.days_of_week, 1);
+
+        let sunday = CronSchedule::parse("0 0 * * 7", "UTC").unwrap();
+        assert_eq!(sunday.days_of_week, 1);
+
+        let stepped = CronSchedule::parse("5/30 * * * *", "UTC").unwrap();
+        assert_eq!(stepped.minutes, (1 << 5) | (1 << 35));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for (expression, timezone, error) in [
+            ("0 9 * *", "UTC", "must have 5 fields"),
+            ("60 * * * *", "UTC", "Invalid minute range"),
+            ("0 0 0 * *", "UTC", "Invalid day-of-month range"),
+            ("*/0 * * * *", "UTC", "Invalid minute step"),
+            ("0 0 * FOO *", "UTC", "Invalid month value 'FOO'"),
+            ("0 0 * * 5-1", "UTC", "Invalid day-of-week range"),
+            ("@fortnightly", "UTC", "Unknown cron shortcut"),
+            ("0 0 * * *", "Not/AZone", "Unknown timezone"),
+        ] {
+            let error_message = CronSchedule::parse(expression, timezone)
+                .unwrap_err()
+                .to_string();
+
+            assert!(
+                error_message.contains(error),
+                "'{expression}': {error_message}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_never_fires() {
+        let schedule = CronSchedule::parse("0 0 31 2 *", "UTC").unwrap();
+
+        assert_eq!(schedule.next_after(FRIDAY_10_UTC), None);
+        assert_eq!(schedule.occurrences_after(FRIDAY_10_UTC).count(), 0);
+    }
+}
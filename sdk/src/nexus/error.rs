@@ -1,6 +1,9 @@
 //! Common error types for Nexus-related functionality.
 
-use thiserror::Error;
+use {
+    crate::{nexus::move_abort::MoveAbort, sui},
+    thiserror::Error,
+};
 
 #[derive(Debug, Error)]
 pub enum NexusError {
@@ -20,4 +23,60 @@ pub enum NexusError {
     Channel(anyhow::Error),
     #[error("Storage error: {0}")]
     Storage(anyhow::Error),
+    /// The transaction aborted in Move. `name` is the abort constant if it
+    /// could be resolved, see [`crate::nexus::move_abort`].
+    #[error("Move abort: {}", describe_move_abort(.package, .module, .function, .code, .name, .command_index))]
+    MoveAbort {
+        package: sui::types::Address,
+        module: String,
+        function: Option<String>,
+        code: u64,
+        name: Option<String>,
+        command_index: Option<u64>,
+    },
+}
+
+impl NexusError {
+    /// The structured Move abort, if this error is one.
+    pub fn move_abort(&self) -> Option<MoveAbort> {
+        let NexusError::MoveAbort {
+            package,
+            module,
+            function,
+            code,
+            name,
+            command_index,
+        } = self
+        else {
+            return None;
+        };
+
+        Some(MoveAbort {
+            package: *package,
+            module: module.clone(),
+            function: function.clone(),
+            code: *code,
+            name: name.clone(),
+            command_index: *command_index,
+        })
+    }
+}
+
+fn describe_move_abort(
+    package: &sui::types::Address,
+    module: &str,
+    function: &Option<String>,
+    code: &u64,
+    name: &Option<String>,
+    command_index: &Option<u64>,
+) -> String {
+    MoveAbort {
+        package: *package,
+        module: module.to_string(),
+        function: function.clone(),
+        code: *code,
+        name: name.clone(),
+        command_index: *command_index,
+    }
+    .to_string()
 }
@@ -0,0 +1,364 @@
+//! Leader operator actions exposed through [`NexusClient`].
+//!
+//! Wraps the `nexus_registry::leader` transaction builders so that node operators can register,
+//! activate and suspend their leader, rotate its `network_auth` message-signing key and inspect
+//! its on-chain state without assembling PTBs by hand.
+
+use {
+    crate::{
+        events::NexusEventKind,
+        move_bindings::{
+            registry::leader::{self as leader_move, LeaderRegistry},
+            sui_framework::object::ID,
+        },
+        nexus::{
+            client::NexusClient,
+            error::NexusError,
+            network_auth::{RegisteredLeaderKey, ToolKeyList},
+            registry::find_owned_capability_by_what_for,
+        },
+        sui,
+        transactions::leader as leader_tx,
+    },
+    ed25519_dalek::SigningKey,
+};
+
+pub struct RegisterLeaderResult {
+    pub tx_digest: sui::types::Digest,
+    /// Leader capability issued by the registration, if the event was emitted.
+    pub leader_cap_id: Option<sui::types::Address>,
+}
+
+pub struct ActivateLeaderResult {
+    pub tx_digest: sui::types::Digest,
+    /// Claim token recorded for this activation, if the event was emitted.
+    pub claim_token: Option<Vec<u8>>,
+}
+
+pub struct SuspendLeaderResult {
+    pub tx_digest: sui::types::Digest,
+    /// Claim token the suspension was conditioned on.
+    pub token: Vec<u8>,
+}
+
+/// Activation state of a registered leader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeaderState {
+    Active,
+    Suspended,
+    Slashed,
+}
+
+impl From<&leader_move::LeaderStatus> for LeaderState {
+    fn from(value: &leader_move::LeaderStatus) -> Self {
+        match value {
+            leader_move::LeaderStatus::Active => LeaderState::Active,
+            leader_move::LeaderStatus::Suspended => LeaderState::Suspended,
+            leader_move::LeaderStatus::Slashed => LeaderState::Slashed,
+        }
+    }
+}
+
+impl std::fmt::Display for LeaderState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LeaderState::Active => write!(f, "active"),
+            LeaderState::Suspended => write!(f, "suspended"),
+            LeaderState::Slashed => write!(f, "slashed"),
+        }
+    }
+}
+
+/// Snapshot of a leader's record in the `LeaderRegistry`.
+#[derive(Clone, Debug)]
+pub struct LeaderInfo {
+    pub leader_cap_id: sui::types::Address,
+    pub state: LeaderState,
+    /// Total `$US` staked with this leader.
+    pub stake_us: u64,
+    pub total_shares: u64,
+    /// Token of the current activation claim, empty if never claimed.
+    pub claim_token: Vec<u8>,
+    pub metadata: Vec<(String, String)>,
+    /// Registry-wide minimum stake in `$US`.
+    pub min_stake_us: u64,
+    /// Registry-wide maximum budget (in MIST) a leader may spend on a single transaction.
+    pub max_transaction_budget: u64,
+    /// Registered `network_auth` message-signing keys, `None` if no binding exists.
+    pub keys: Option<ToolKeyList>,
+}
+
+impl LeaderInfo {
+    fn from_record(
+        leader_cap_id: sui::types::Address,
+        registry: &LeaderRegistry,
+        leader: &leader_move::Leader,
+        keys: Option<ToolKeyList>,
+    ) -> Self {
+        Self {
+            leader_cap_id,
+            state: LeaderState::from(&leader.status),
+            stake_us: leader.stake_manager.pool.value,
+            total_shares: leader.stake_manager.total_shares,
+            claim_token: leader.claim_token.clone(),
+            metadata: leader
+                .meta
+                .data
+                .contents
+                .iter()
+                .map(|entry| {
+                    (
+                        String::from_utf8_lossy(&entry.key.bytes).into_owned(),
+                        String::from_utf8_lossy(&entry.value.bytes).into_owned(),
+                    )
+                })
+                .collect(),
+            min_stake_us: registry.min_stake_us,
+            max_transaction_budget: registry.max_transaction_budget(),
+            keys,
+        }
+    }
+}
+
+pub struct LeaderActions {
+    pub(super) client: NexusClient,
+}
+
+impl LeaderActions {
+    /// Fetch and decode the `LeaderRegistry`.
+    pub async fn fetch_registry(&self) -> Result<LeaderRegistry, NexusError> {
+        self.client
+            .crawler()
+            .get_object::<LeaderRegistry>(*self.client.nexus_objects.leader_registry.object_id())
+            .await
+            .map(|response| response.data)
+            .map_err(|e| NexusError::Rpc(anyhow::anyhow!("Failed to fetch leader registry: {e}")))
+    }
+
+    /// Find the leader capability issued by this registry to `owner`.
+    pub async fn find_leader_cap(
+        &self,
+        owner: sui::types::Address,
+    ) -> Result<Option<sui::types::Address>, NexusError> {
+        let registry = self.fetch_registry().await?;
+        let what_for = registry.capabilities.leader_cap_issuer.what_for.bytes;
+
+        find_owned_capability_by_what_for(
+            self.client.rpc_url(),
+            owner,
+            leader_tx::over_network_cap_struct_tag(&self.client.nexus_objects),
+            what_for,
+        )
+        .await
+        .map(|cap| cap.map(|cap| *cap.object_id()))
+        .map_err(NexusError::Rpc)
+    }
+
+    /// Register the sender as a leader, staking `stake_us` from the owned `$US` coin.
+    pub async fn register(
+        &self,
+        stake_coin: sui::types::Address,
+        stake_us: u64,
+    ) -> Result<RegisterLeaderResult, NexusError> {
+        let address = self.client.signer.get_active_address();
+        let stake_coin = self
+            .client
+            .crawler()
+            .get_object_metadata(stake_coin)
+            .await
+            .map(|resp| resp.object_ref())
+            .map_err(|e| {
+                NexusError::Rpc(anyhow::anyhow!("Failed to fetch `$US` coin metadata: {e}"))
+            })?;
+
+        let tx =
+            leader_tx::register_for_self_ptb(&self.client.nexus_objects, &stake_coin, stake_us)
+                .map_err(NexusError::TransactionBuilding)?;
+
+        let response = self.client.submit_transaction(tx, address).await?;
+
+        let leader_cap_id = response.events.iter().find_map(|event| match &event.data {
+            NexusEventKind::LeaderCapIssued(e) => Some(e.leader_cap_id.bytes),
+            _ => None,
+        });
+
+        Ok(RegisterLeaderResult {
+            tx_digest: response.digest,
+            leader_cap_id,
+        })
+    }
+
+    /// Activate the leader and claim it with this transaction's digest token.
+    pub async fn activate(
+        &self,
+        leader_cap: sui::types::Address,
+    ) -> Result<ActivateLeaderResult, NexusError> {
+        let address = self.client.signer.get_active_address();
+        let leader_cap = self.fetch_leader_cap_ref(leader_cap).await?;
+
+        let tx =
+            leader_tx::activate_and_claim_for_self_ptb(&self.client.nexus_objects, &leader_cap)
+                .map_err(NexusError::TransactionBuilding)?;
+
+        let response = self.client.submit_transaction(tx, address).await?;
+
+        let claim_token = response.events.iter().find_map(|event| match &event.data {
+            NexusEventKind::LeaderClaimed(e) => Some(e.claim_token.clone()),
+            _ => None,
+        });
+
+        Ok(ActivateLeaderResult {
+            tx_digest: response.digest,
+            claim_token,
+        })
+    }
+
+    /// Suspend the leader if `token` still matches its active claim.
+    ///
+    /// If no token is provided, the current claim token is read from the registry.
+    pub async fn suspend(
+        &self,
+        leader_cap: sui::types::Address,
+        token: Option<Vec<u8>>,
+    ) -> Result<SuspendLeaderResult, NexusError> {
+        let address = self.client.signer.get_active_address();
+        let token = match token {
+            Some(token) => token,
+            None => self.status(leader_cap).await?.claim_token,
+        };
+        let leader_cap = self.fetch_leader_cap_ref(leader_cap).await?;
+
+        let tx = leader_tx::suspend_if_token_for_self_ptb(
+            &self.client.nexus_objects,
+            &leader_cap,
+            token.clone(),
+        )
+        .map_err(NexusError::TransactionBuilding)?;
+
+        let response = self.client.submit_transaction(tx, address).await?;
+
+        Ok(SuspendLeaderResult {
+            tx_digest: response.digest,
+            token,
+        })
+    }
+
+    /// Read the leader's registry record together with its registered message keys.
+    pub async fn status(&self, leader_cap: sui::types::Address) -> Result<LeaderInfo, NexusError> {
+        let registry = self.fetch_registry().await?;
+
+        let leader = self
+            .client
+            .crawler()
+            .get_optional_dynamic_field::<ID, leader_move::Leader>(
+                registry.records.id(),
+                ID::new(leader_cap),
+            )
+            .await
+            .map_err(|e| NexusError::Rpc(anyhow::anyhow!("Failed to fetch leader record: {e}")))?
+            .ok_or_else(|| {
+                NexusError::Configuration(format!(
+                    "Leader cap '{leader_cap}' is not registered in the leader registry"
+                ))
+            })?;
+
+        let keys = self
+            .client
+            .network_auth()
+            .list_leader_keys(leader_cap)
+            .await?;
+
+        Ok(LeaderInfo::from_record(
+            leader_cap, &registry, &leader, keys,
+        ))
+    }
+
+    /// Register a new message-signing key for the leader, creating its `KeyBinding` if needed.
+    pub async fn rotate_key(
+        &self,
+        leader_cap: sui::types::Address,
+        signing_key: SigningKey,
+        description: Option<Vec<u8>>,
+    ) -> Result<RegisteredLeaderKey, NexusError> {
+        self.client
+            .network_auth()
+            .register_leader_message_key(leader_cap, signing_key, description)
+            .await
+    }
+
+    async fn fetch_leader_cap_ref(
+        &self,
+        leader_cap: sui::types::Address,
+    ) -> Result<sui::types::ObjectReference, NexusError> {
+        self.client
+            .crawler()
+            .get_object_metadata(leader_cap)
+            .await
+            .map(|resp| resp.object_ref())
+            .map_err(|e| {
+                NexusError::Rpc(anyhow::anyhow!("Failed to fetch leader cap metadata: {e}"))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            move_bindings::{
+                move_std::string::String as MoveString,
+                sui_framework::{
+                    balance::Balance,
+                    table::Table as MoveTable,
+                    vec_map::{Entry as VecMapEntry, VecMap},
+                },
+            },
+            test_utils::sui_mocks,
+        },
+    };
+
+    #[test]
+    fn test_leader_info_from_record() {
+        let leader_cap_id = sui_mocks::mock_sui_address();
+        let registry = LeaderRegistry::new_for_test(
+            sui_mocks::mock_sui_address(),
+            sui_mocks::mock_sui_address(),
+        );
+        let string = |value: &str| MoveString {
+            bytes: value.as_bytes().to_vec(),
+        };
+        let leader = leader_move::Leader {
+            status: leader_move::LeaderStatus::Suspended,
+            meta: leader_move::Metadata {
+                data: VecMap {
+                    contents: vec![VecMapEntry {
+                        key: string("region"),
+                        value: string("eu-west"),
+                    }],
+                },
+            },
+            stake_manager: leader_move::StakeManager {
+                pool: Balance::new(5_000),
+                total_shares: 4_000,
+                positions: MoveTable::new(sui_mocks::mock_sui_address(), 1),
+            },
+            claim_token: vec![7; 32],
+        };
+
+        let info = LeaderInfo::from_record(leader_cap_id, &registry, &leader, None);
+
+        assert_eq!(info.leader_cap_id, leader_cap_id);
+        assert_eq!(info.state, LeaderState::Suspended);
+        assert_eq!(info.state.to_string(), "suspended");
+        assert_eq!(info.stake_us, 5_000);
+        assert_eq!(info.total_shares, 4_000);
+        assert_eq!(info.claim_token, vec![7; 32]);
+        assert_eq!(
+            info.metadata,
+            vec![("region".to_string(), "eu-west".to_string())]
+        );
+        assert_eq!(info.max_transaction_budget, 10_000_000_000);
+        assert!(info.keys.is_none());
+    }
+}
@@ -7,13 +7,20 @@
 pub mod address_balance;
 pub mod client;
 pub mod crawler;
+pub mod cron;
 pub mod error;
 pub mod gas;
+pub mod leader;
+pub mod move_abort;
 pub mod network_auth;
 pub mod registered_key;
 pub mod registry;
 pub mod scheduler;
+#[cfg(feature = "signed_http_client")]
+pub mod signed_tool_client;
 pub mod signer;
+pub mod simulation;
 pub mod tap;
 pub mod tool;
+pub mod transaction_signer;
 pub mod workflow;
@@ -0,0 +1,392 @@
+//! Structured Move aborts raised by Nexus transactions.
+//!
+//! Aborts are decoded from the transaction effects into a [`MoveAbort`] and
+//! surfaced as [`NexusError::MoveAbort`]. Move 2024 `#[error]` constants
+//! (clever errors) encode the index of the constant's identifier in the abort
+//! code, so their names, such as `EToolNotRegistered`, can be recovered from
+//! the module bytecode with [`module_identifier`]. Plain `u64` abort codes
+//! carry no name.
+
+use crate::{nexus::error::NexusError, sui};
+
+/// Magic bytes every compiled Move module starts with.
+const MOVE_MAGIC: [u8; 4] = [0xa1, 0x1c, 0xeb, 0x0b];
+
+/// Kind of the identifier table in the Move binary format.
+const IDENTIFIERS_TABLE_KIND: u8 = 0x7;
+
+/// Index used by clever errors that do not reference an identifier.
+const NO_IDENTIFIER: u16 = 0xffff;
+
+/// Decoded parts of a Move 2024 clever error abort code.
+///
+/// The code is laid out as
+/// `| 1 | reserved (15) | line (16) | identifier index (16) | constant index (16) |`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CleverError {
+    pub line: u16,
+    pub identifier_index: Option<u16>,
+    pub constant_index: Option<u16>,
+}
+
+impl CleverError {
+    /// Decode a clever error, `None` if `code` is a plain abort code.
+    pub fn decode(code: u64) -> Option<Self> {
+        if code & (1 << 63) == 0 {
+            return None;
+        }
+
+        let index = |shift: u32| Some((code >> shift) as u16).filter(|i| *i != NO_IDENTIFIER);
+
+        Some(Self {
+            line: (code >> 32) as u16,
+            identifier_index: index(16),
+            constant_index: index(0),
+        })
+    }
+}
+
+/// A Move abort raised by a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MoveAbort {
+    pub package: sui::types::Address,
+    pub module: String,
+    pub function: Option<String>,
+    pub code: u64,
+    /// Name of the abort constant, if it could be resolved.
+    pub name: Option<String>,
+    /// Index of the PTB command that aborted.
+    pub command_index: Option<u64>,
+}
+
+impl MoveAbort {
+    /// Extract the Move abort from a failed execution status, if any.
+    pub fn from_status(status: &sui::types::ExecutionStatus) -> Option<Self> {
+        let sui::types::ExecutionStatus::Failure {
+            error: sui::types::ExecutionError::MoveAbort { location, code },
+            command,
+        } = status
+        else {
+            return None;
+        };
+
+        Some(Self {
+            package: location.package,
+            module: location.module.to_string(),
+            function: location.function_name.as_ref().map(ToString::to_string),
+            code: *code,
+            name: None,
+            command_index: *command,
+        })
+    }
+
+    /// The decoded clever error, if the code was raised by an `#[error]`
+    /// constant.
+    pub fn clever_error(&self) -> Option<CleverError> {
+        CleverError::decode(self.code)
+    }
+
+    /// Resolve [`Self::name`] from the bytecode of the aborting module.
+    pub fn with_module_bytecode(mut self, bytecode: &[u8]) -> Self {
+        if let Some(index) = self.clever_error().and_then(|e| e.identifier_index) {
+            self.name = module_identifier(bytecode, index.into()).or(self.name);
+        }
+
+        self
+    }
+
+    /// Whether the abort was raised in `module` by a function whose name
+    /// starts with `function_prefix`.
+    pub fn is_in(&self, module: &str, function_prefix: &str) -> bool {
+        self.module == module
+            && self
+                .function
+                .as_deref()
+                .is_some_and(|f| f.starts_with(function_prefix))
+    }
+
+    /// The constant name as words, `EToolNotRegistered` and
+    /// `E_TOOL_NOT_REGISTERED` both read `Tool not registered`.
+    pub fn humanized_name(&self) -> Option<String> {
+        let name = self.name.as_deref()?;
+        let name = name
+            .strip_prefix("E_")
+            .or_else(|| {
+                name.strip_prefix('E')
+                    .filter(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+            })
+            .unwrap_or(name);
+
+        let words = split_words(name);
+        let mut sentence = words.join(" ");
+
+        if let Some(first) = sentence.get_mut(..1) {
+            first.make_ascii_uppercase();
+        }
+
+        Some(sentence)
+    }
+}
+
+impl std::fmt::Display for MoveAbort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}::{}", self.package, self.module)?;
+
+        if let Some(function) = &self.function {
+            write!(f, "::{function}")?;
+        }
+
+        write!(f, " aborted with code {}", self.code)?;
+
+        match (&self.name, self.clever_error()) {
+            (Some(name), Some(clever)) => write!(f, " ({name}, line {})", clever.line)?,
+            (Some(name), None) => write!(f, " ({name})")?,
+            (None, Some(clever)) => write!(f, " (line {})", clever.line)?,
+            (None, None) => (),
+        }
+
+        if let Some(command) = self.command_index {
+            write!(f, " in command {command}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<MoveAbort> for NexusError {
+    fn from(abort: MoveAbort) -> Self {
+        NexusError::MoveAbort {
+            package: abort.package,
+            module: abort.module,
+            function: abort.function,
+            code: abort.code,
+            name: abort.name,
+            command_index: abort.command_index,
+        }
+    }
+}
+
+/// Read the identifier at `index` from a compiled Move module.
+///
+/// Returns `None` if the bytecode is malformed or the index is out of range.
+pub fn module_identifier(bytecode: &[u8], index: usize) -> Option<String> {
+    let mut cursor = bytecode.strip_prefix(&MOVE_MAGIC)?;
+    // Skip the binary format version.
+    cursor = cursor.get(4..)?;
+
+    let table_count = read_uleb128(&mut cursor)?;
+    let mut identifiers = None;
+
+    for _ in 0..table_count {
+        let (kind, rest) = cursor.split_first()?;
+        cursor = rest;
+        let offset = read_uleb128(&mut cursor)?;
+        let length = read_uleb128(&mut cursor)?;
+
+        if *kind == IDENTIFIERS_TABLE_KIND {
+            identifiers = Some((offset as usize, length as usize));
+        }
+    }
+
+    // Table offsets are relative to the end of the table headers.
+    let (offset, length) = identifiers?;
+    let mut table = cursor.get(offset..offset.checked_add(length)?)?;
+
+    for _ in 0..index {
+        let length = read_uleb128(&mut table)? as usize;
+        table = table.get(length..)?;
+    }
+
+    let length = read_uleb128(&mut table)? as usize;
+
+    String::from_utf8(table.get(..length)?.to_vec()).ok()
+}
+
+fn read_uleb128(cursor: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+
+    for shift in (0..64).step_by(7) {
+        let (byte, rest) = cursor.split_first()?;
+        *cursor = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+/// Split a `CamelCase` or `SNAKE_CASE` name into lowercase words, keeping
+/// acronyms such as `DAG` intact.
+fn split_words(name: &str) -> Vec<String> {
+    if name.contains('_') {
+        return name
+            .split('_')
+            .filter(|word| !word.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect();
+    }
+
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = vec![];
+    let mut word = String::new();
+
+    for (i, c) in chars.iter().enumerate() {
+        let prev_lower = i > 0 && chars[i - 1].is_ascii_lowercase();
+        let next_lower = chars.get(i + 1).is_some_and(char::is_ascii_lowercase);
+
+        if c.is_ascii_uppercase() && !word.is_empty() && (prev_lower || next_lower) {
+            words.push(std::mem::take(&mut word));
+        }
+
+        word.push(*c);
+    }
+
+    words.push(word);
+
+    words
+        .into_iter()
+        .map(|word| {
+            if word.len() > 1 && word.chars().all(|c| c.is_ascii_uppercase()) {
+                word
+            } else {
+                word.to_ascii_lowercase()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test_utils::sui_mocks};
+
+    /// Minimal module bytecode holding only an identifier table.
+    fn module_with_identifiers(identifiers: &[&str]) -> Vec<u8> {
+        let mut table = vec![];
+        for identifier in identifiers {
+            table.push(identifier.len() as u8);
+            table.extend_from_slice(identifier.as_bytes());
+        }
+
+        let mut bytecode = MOVE_MAGIC.to_vec();
+        bytecode.extend_from_slice(&6u32.to_le_bytes());
+        // One table: identifiers at offset 0.
+        bytecode.extend_from_slice(&[1, IDENTIFIERS_TABLE_KIND, 0, table.len() as u8]);
+        bytecode.extend(table);
+        bytecode
+    }
+
+    fn abort(code: u64) -> MoveAbort {
+        MoveAbort {
+            package: sui_mocks::mock_sui_address(),
+            module: "tool_registry".to_string(),
+            function: Some("register_off_chain_tool_".to_string()),
+            code,
+            name: None,
+            command_index: Some(1),
+        }
+    }
+
+    #[test]
+    fn test_from_status_decodes_move_abort() {
+        let package = sui_mocks::mock_sui_address();
+        let status = sui::types::ExecutionStatus::Failure {
+            error: sui::types::ExecutionError::MoveAbort {
+                location: sui::types::MoveLocation {
+                    package,
+                    module: sui::types::Identifier::from_static("scheduler"),
+                    function: 3,
+                    instruction: 12,
+                    function_name: Some(sui::types::Identifier::from_static("new_task")),
+                },
+                code: 7,
+            },
+            command: Some(2),
+        };
+
+        let abort = MoveAbort::from_status(&status).unwrap();
+
+        assert_eq!(abort.package, package);
+        assert_eq!(abort.module, "scheduler");
+        assert_eq!(abort.function.as_deref(), Some("new_task"));
+        assert_eq!(abort.code, 7);
+        assert_eq!(abort.command_index, Some(2));
+        assert!(abort.is_in("scheduler", "new_"));
+        assert!(!abort.is_in("tool_registry", "new_"));
+        assert!(MoveAbort::from_status(&sui::types::ExecutionStatus::Success).is_none());
+    }
+
+    #[test]
+    fn test_clever_error_name_is_resolved_from_bytecode() {
+        let bytecode = module_with_identifiers(&["tool_registry", "EToolNotRegistered"]);
+        let code = (1 << 63) | (42 << 32) | (1 << 16) | 3;
+
+        let clever = CleverError::decode(code).unwrap();
+        assert_eq!(clever.line, 42);
+        assert_eq!(clever.identifier_index, Some(1));
+        assert_eq!(clever.constant_index, Some(3));
+
+        let resolved = abort(code).with_module_bytecode(&bytecode);
+        assert_eq!(resolved.name.as_deref(), Some("EToolNotRegistered"));
+        assert_eq!(
+            resolved.humanized_name().as_deref(),
+            Some("Tool not registered")
+        );
+        assert!(resolved
+            .to_string()
+            .ends_with("register_off_chain_tool_ aborted with code 9223372217243467779 (EToolNotRegistered, line 42) in command 1"));
+    }
+
+    #[test]
+    fn test_plain_codes_and_malformed_bytecode_keep_no_name() {
+        let bytecode = module_with_identifiers(&["tool_registry"]);
+
+        assert!(CleverError::decode(7).is_none());
+        assert!(abort(7).with_module_bytecode(&bytecode).name.is_none());
+
+        // Index out of range, missing identifier and garbage bytecode.
+        let out_of_range = (1 << 63) | (5 << 16);
+        let no_identifier = (1 << 63) | (0xffff << 16);
+        assert!(abort(out_of_range)
+            .with_module_bytecode(&bytecode)
+            .name
+            .is_none());
+        assert!(abort(no_identifier)
+            .with_module_bytecode(&bytecode)
+            .name
+            .is_none());
+        assert!(module_identifier(&[1, 2, 3], 0).is_none());
+        assert_eq!(
+            module_identifier(&bytecode, 0).as_deref(),
+            Some("tool_registry")
+        );
+    }
+
+    #[test]
+    fn test_humanized_name() {
+        let named = |name: &str| MoveAbort {
+            name: Some(name.to_string()),
+            ..abort(1)
+        };
+
+        assert_eq!(
+            named("EInsufficientPayment").humanized_name().as_deref(),
+            Some("Insufficient payment")
+        );
+        assert_eq!(
+            named("E_TASK_NOT_FOUND").humanized_name().as_deref(),
+            Some("Task not found")
+        );
+        assert_eq!(
+            named("EDAGNotFound").humanized_name().as_deref(),
+            Some("DAG not found")
+        );
+        assert_eq!(
+            named("Expired").humanized_name().as_deref(),
+            Some("Expired")
+        );
+        assert!(abort(1).humanized_name().is_none());
+    }
+}
@@ -1,7 +1,8 @@
 //! Tool focused helpers for the `nexus_registry::network_auth` package.
 //!
 //! This module is designed for tool operators and other off chain clients that need to:
-//! - register or rotate a ToolId message signing key on chain, and
+//! - register or rotate a ToolId message signing key on chain,
+//! - register or rotate a leader message signing key on chain, and
 //! - export a tool side allowlist of permitted leaders for the signed HTTP runtime.
 //!
 //! # Background: what is registered on chain?
@@ -59,6 +60,21 @@ pub struct RegisteredToolKey {
     pub binding_object_id: sui::types::Address,
 }
 
+/// Result returned after registering a leader message-signing key.
+#[derive(Clone, Debug)]
+pub struct RegisteredLeaderKey {
+    /// Transaction digest that performed the registration.
+    pub tx_digest: sui::types::Digest,
+    /// Leader capability object ID the key is bound to.
+    pub leader_cap_id: sui::types::Address,
+    /// Registered key ID carried in signed HTTP transport headers.
+    pub leader_kid: u64,
+    /// Registered Ed25519 public key bytes.
+    pub public_key: [u8; 32],
+    /// Deterministic binding object ID under the on-chain `NetworkAuth` registry.
+    pub binding_object_id: sui::types::Address,
+}
+
 /// An individual key entry returned by [`NetworkAuthActions::list_tool_keys`].
 #[derive(Clone, Debug)]
 pub struct ToolKeyEntry {
@@ -148,7 +164,8 @@ impl NetworkAuthActions {
             Some(b) => (Some(b.object_ref()), b.data.next_key_id),
         };
 
-        let (public_key, pop_sig) = tool_key_material(&identity, next_key_id, &tool_signing_key)?;
+        let (public_key, pop_sig) =
+            identity_key_material(&identity, next_key_id, &tool_signing_key)?;
 
         // Resolve owner cap object ref for PTB.
         let owner_cap_ref = self
@@ -218,9 +235,6 @@ impl NetworkAuthActions {
         tool_fqn: &ToolFqn,
     ) -> Result<Option<ToolKeyList>, NexusError> {
         let objects = &self.client.nexus_objects;
-        let codec =
-            NetworkAuthCodec::new(objects.registry_pkg_id, *objects.network_auth.object_id());
-
         let tool_id =
             Tool::derive_id(*objects.tool_registry.object_id(), tool_fqn).map_err(|e| {
                 NexusError::Parsing(anyhow::anyhow!(
@@ -228,7 +242,90 @@ impl NetworkAuthActions {
                 ))
             })?;
         let identity = IdentityKey::tool(tool_id);
-        let binding_object_id = codec.binding_object_id(&identity)?;
+
+        self.list_keys(&identity).await
+    }
+
+    /// Query all registered message-signing keys for a leader capability.
+    ///
+    /// Returns `None` if no `KeyBinding` exists for the leader yet.
+    pub async fn list_leader_keys(
+        &self,
+        leader_cap_id: sui::types::Address,
+    ) -> Result<Option<ToolKeyList>, NexusError> {
+        self.list_keys(&IdentityKey::leader(leader_cap_id)).await
+    }
+
+    /// Register (or rotate) a leader message-signing key under `network_auth`.
+    ///
+    /// Mirrors [`Self::register_tool_message_key`] for the leader identity of
+    /// `leader_cap_over_network`.
+    pub async fn register_leader_message_key(
+        &self,
+        leader_cap_over_network: sui::types::Address,
+        leader_signing_key: SigningKey,
+        description: Option<Vec<u8>>,
+    ) -> Result<RegisteredLeaderKey, NexusError> {
+        let address = self.client.signer.get_active_address();
+        let objects = &self.client.nexus_objects;
+
+        let identity = IdentityKey::leader(leader_cap_over_network);
+        let binding_object_id = self.binding_object_id(&identity)?;
+
+        let binding = self.try_get_key_binding(binding_object_id).await?;
+        let (binding_ref, next_key_id) = match binding {
+            None => (None, 0),
+            Some(b) => (Some(b.object_ref()), b.data.next_key_id),
+        };
+
+        let (public_key, pop_sig) =
+            identity_key_material(&identity, next_key_id, &leader_signing_key)?;
+
+        let leader_cap = self
+            .client
+            .crawler()
+            .get_object_metadata(leader_cap_over_network)
+            .await
+            .map(|r| r.object_ref())
+            .map_err(|e| {
+                NexusError::Rpc(anyhow::anyhow!(
+                    "failed to fetch leader cap metadata ({leader_cap_over_network}): {e}"
+                ))
+            })?;
+
+        let tx = match binding_ref {
+            None => transactions::network_auth::create_leader_binding_and_register_key_ptb(
+                objects,
+                &leader_cap,
+                public_key,
+                pop_sig,
+                description,
+            ),
+            Some(binding_ref) => {
+                transactions::network_auth::register_leader_key_on_existing_binding_ptb(
+                    objects,
+                    &binding_ref,
+                    &leader_cap,
+                    public_key,
+                    pop_sig,
+                )
+            }
+        }
+        .map_err(NexusError::TransactionBuilding)?;
+
+        let response = self.client.submit_transaction(tx, address).await?;
+
+        Ok(RegisteredLeaderKey {
+            tx_digest: response.digest,
+            leader_cap_id: leader_cap_over_network,
+            leader_kid: next_key_id,
+            public_key,
+            binding_object_id,
+        })
+    }
+
+    async fn list_keys(&self, identity: &IdentityKey) -> Result<Option<ToolKeyList>, NexusError> {
+        let binding_object_id = self.binding_object_id(identity)?;
 
         let binding = match self.try_get_key_binding(binding_object_id).await? {
             None => return Ok(None),
@@ -245,7 +342,7 @@ impl NetworkAuthActions {
             .await
             .map_err(|e| {
                 NexusError::Rpc(anyhow::anyhow!(
-                    "failed to fetch tool key records ({binding_object_id}): {e}"
+                    "failed to fetch key records ({binding_object_id}): {e}"
                 ))
             })?;
 
@@ -776,7 +873,7 @@ pub fn initial_tool_registration(
         ))
     })?;
     let identity = IdentityKey::tool(tool_id);
-    let (public_key, pop_signature) = tool_key_material(&identity, 0, signing_key)?;
+    let (public_key, pop_signature) = identity_key_material(&identity, 0, signing_key)?;
 
     Ok(OffChainToolRegistration {
         meta,
@@ -786,7 +883,7 @@ pub fn initial_tool_registration(
     })
 }
 
-fn tool_key_material(
+fn identity_key_material(
     identity: &IdentityKey,
     key_id: u64,
     signing_key: &SigningKey,
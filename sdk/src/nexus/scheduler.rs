@@ -2,9 +2,13 @@
 
 use {
     crate::{
-        events::NexusEventKind,
+        events::{NexusEvent, NexusEventKind},
         move_bindings::{
-            interface::{agent as agent_move, authorization::AgentVertexAuthorizationTemplate},
+            interface::{
+                agent as agent_move,
+                authorization::AgentVertexAuthorizationTemplate,
+                payment as payment_move,
+            },
             move_std::type_name::TypeName,
             primitives::{
                 automaton as automaton_move,
@@ -17,15 +21,21 @@ use {
         nexus::{
             client::NexusClient,
             crawler::{Crawler, DynamicFieldReference, Response},
+            cron::CronSchedule,
             error::NexusError,
             signer::ExecutedTransaction,
         },
-        sui,
+        sui::{
+            self,
+            events::{CheckpointProgress, EventPage},
+        },
         transactions::scheduler as scheduler_tx,
         types::{AgentId, NexusObjects, SkillId},
     },
     anyhow::{anyhow, bail},
-    std::collections::HashMap,
+    futures::{stream, StreamExt, TryStreamExt},
+    serde::{Deserialize, Serialize},
+    std::collections::{BTreeSet, HashMap},
     sui_move::MoveStruct,
 };
 
@@ -50,6 +60,10 @@ pub struct SchedulerActions {
 }
 
 /// Supported generator types for a scheduled task.
+///
+/// Cron schedules have no generator of their own. They are expanded
+/// client-side into occurrences for a [`GeneratorKind::Queue`] task, see
+/// [`SchedulerActions::schedule_cron`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum GeneratorKind {
     Queue,
@@ -181,6 +195,149 @@ pub struct PeriodicScheduleConfig {
     pub priority_fee_percentage: Option<u64>,
 }
 
+/// Upper bound on cron occurrences enqueued by a single transaction, keeping
+/// each PTB well below the Sui command and input limits.
+pub const MAX_CRON_OCCURRENCES_PER_TX: usize = 256;
+
+/// Checkpoints replayed by [`SchedulerActions::discover_task_ids`] when no
+/// start checkpoint is given.
+pub const DEFAULT_TASK_DISCOVERY_CHECKPOINTS: u64 = 100_000;
+
+/// Task summaries fetched at once by [`SchedulerActions::list_tasks`].
+const TASK_SUMMARY_CONCURRENCY: usize = 16;
+
+/// Configuration for expanding a [`CronSchedule`] into queue occurrences.
+#[derive(Clone, Debug)]
+pub struct CronScheduleConfig {
+    pub schedule: CronSchedule,
+    pub deadline_offset_ms: Option<u64>,
+    pub priority_fee_percentage: Option<u64>,
+}
+
+/// Lifecycle state of a scheduled task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskStatus {
+    Active,
+    Paused,
+    Canceled,
+    Completed,
+    Failed,
+}
+
+impl From<&scheduler_move::State> for TaskStatus {
+    fn from(value: &scheduler_move::State) -> Self {
+        match value {
+            scheduler_move::State::Active => TaskStatus::Active,
+            scheduler_move::State::Paused => TaskStatus::Paused,
+            scheduler_move::State::Canceled => TaskStatus::Canceled,
+            scheduler_move::State::Completed => TaskStatus::Completed,
+            scheduler_move::State::Failed => TaskStatus::Failed,
+        }
+    }
+}
+
+impl std::fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskStatus::Active => write!(f, "active"),
+            TaskStatus::Paused => write!(f, "paused"),
+            TaskStatus::Canceled => write!(f, "canceled"),
+            TaskStatus::Completed => write!(f, "completed"),
+            TaskStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// Filters applied by [`SchedulerActions::list_tasks`].
+#[derive(Clone, Debug, Default)]
+pub struct TaskFilter {
+    /// Only keep tasks in this state.
+    pub status: Option<TaskStatus>,
+    /// Only keep tasks whose metadata contains all of these entries.
+    pub metadata: Vec<(String, String)>,
+    /// Checkpoint to start discovering tasks from. Defaults to
+    /// [`DEFAULT_TASK_DISCOVERY_CHECKPOINTS`] before the current checkpoint.
+    pub from_checkpoint: Option<u64>,
+}
+
+impl TaskFilter {
+    /// Whether `summary` passes this filter.
+    pub fn matches(&self, summary: &TaskSummary) -> bool {
+        let status = self.status.is_none_or(|status| status == summary.status);
+        let metadata = self
+            .metadata
+            .iter()
+            .all(|entry| summary.metadata.contains(entry));
+
+        status && metadata
+    }
+}
+
+/// Tasks created for an owner, discovered incrementally from task creation
+/// events by [`SchedulerActions::sync_task_index`].
+///
+/// Persisting the index between runs bounds each sync to the checkpoints
+/// produced since the previous one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskIndex {
+    /// Owner whose tasks are indexed.
+    pub owner: sui::types::Address,
+    /// Task creation events processed so far.
+    pub progress: CheckpointProgress,
+    /// Tasks created for `owner`.
+    pub task_ids: BTreeSet<sui::types::Address>,
+}
+
+impl TaskIndex {
+    /// An empty index that starts discovery at `from_checkpoint`.
+    pub fn new(owner: sui::types::Address, from_checkpoint: u64) -> Self {
+        Self {
+            owner,
+            progress: CheckpointProgress {
+                checkpoint: from_checkpoint,
+                ..Default::default()
+            },
+            task_ids: BTreeSet::new(),
+        }
+    }
+
+    /// Record the tasks created for the owner in `page`, skipping events that
+    /// were processed before.
+    pub fn record(&mut self, page: &EventPage<NexusEvent>) {
+        for (event, id) in page.events.iter().zip(&page.event_ids) {
+            if self.progress.contains(id) {
+                continue;
+            }
+
+            if let NexusEventKind::ScheduledSkillExecutionCreated(e) = &event.data {
+                if e.owner == self.owner {
+                    self.task_ids.insert(e.task.into());
+                }
+            }
+        }
+
+        self.progress.advance(page);
+    }
+}
+
+/// Overview of a scheduled task returned by [`SchedulerActions::list_tasks`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskSummary {
+    pub task_id: sui::types::Address,
+    pub owner: sui::types::Address,
+    pub status: TaskStatus,
+    /// Generator producing the task's occurrences, if one is registered.
+    pub generator: Option<GeneratorKind>,
+    /// Start time of the next pending occurrence.
+    pub next_occurrence_ms: Option<u64>,
+    /// Occurrences a periodic generator still produces. [`None`] for queue
+    /// generators and unbounded periodic schedules.
+    pub remaining_iterations: Option<u64>,
+    pub metadata: Vec<(String, String)>,
+    /// SUI left in the task's TAP payment reserve, in MIST.
+    pub reserve_balance_mist: Option<u64>,
+}
+
 pub struct PeriodicScheduleResult {
     pub tx_digest: sui::types::Digest,
     pub event: Option<NexusEventKind>,
@@ -507,6 +664,265 @@ impl SchedulerActions {
         })
     }
 
+    /// Enqueue every fire time of the cron schedule in `(*after_ms, until_ms]`
+    /// as an absolute occurrence of a queue generator task.
+    ///
+    /// Fire times are batched into PTBs of at most
+    /// [`MAX_CRON_OCCURRENCES_PER_TX`] occurrences, each submitted with a
+    /// single gas estimate. `after_ms` is advanced past each batch once it is
+    /// enqueued, so calling this again with the same cursor and a later
+    /// `until_ms` keeps the queue topped up without duplicates, even after an
+    /// error.
+    pub async fn schedule_cron(
+        &self,
+        task_id: sui::types::Address,
+        config: &CronScheduleConfig,
+        after_ms: &mut u64,
+        until_ms: u64,
+    ) -> Result<Vec<(u64, ScheduleExecutionResult)>, NexusError> {
+        let occurrences = config.schedule.occurrences_between(*after_ms, until_ms);
+
+        if occurrences.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // Validate the shared deadline once, as every fire time uses it.
+        OccurrenceRequest::new(
+            Some(occurrences[0]),
+            None,
+            None,
+            config.deadline_offset_ms,
+            config.priority_fee_percentage,
+            true,
+        )?;
+
+        let address = self.client.signer.get_active_address();
+        let objects = &self.client.nexus_objects;
+        let mut scheduled = vec![];
+
+        for batch in occurrences.chunks(MAX_CRON_OCCURRENCES_PER_TX) {
+            // The task version changes with every batch.
+            let task = self.fetch_task(task_id).await?;
+            let tx = scheduler_tx::add_occurrences_absolute_for_task_for_self_ptb(
+                objects,
+                &task.object_ref(),
+                batch,
+                config.deadline_offset_ms,
+                config.priority_fee_percentage,
+            )
+            .map_err(NexusError::TransactionBuilding)?;
+            let response = self.client.submit_transaction(tx, address).await?;
+            let mut events = extract_occurrence_events(&response).into_iter();
+
+            for start_ms in batch {
+                scheduled.push((
+                    *start_ms,
+                    ScheduleExecutionResult {
+                        tx_digest: response.digest,
+                        event: events.next(),
+                    },
+                ));
+            }
+
+            *after_ms = *batch.last().expect("chunks are never empty");
+        }
+
+        Ok(scheduled)
+    }
+
+    /// The start of the latest occurrence queued for a task, used to resume
+    /// [`SchedulerActions::schedule_cron`] without enqueuing duplicates.
+    pub async fn last_queued_ms(
+        &self,
+        task_id: sui::types::Address,
+    ) -> Result<Option<u64>, NexusError> {
+        let task = self.fetch_task(task_id).await?.data;
+        let generator = fetch_generator_state(
+            self.client.crawler(),
+            &self.client.nexus_objects,
+            task.constraints.dfa.id.id.bytes,
+        )
+        .await
+        .map_err(NexusError::Rpc)?;
+
+        Ok(generator.as_ref().and_then(GeneratorState::last_queued_ms))
+    }
+
+    /// Discover IDs of tasks created for `owner` by replaying task creation
+    /// events from `from_checkpoint` up to and including the current
+    /// checkpoint. Without `from_checkpoint`, only the last
+    /// [`DEFAULT_TASK_DISCOVERY_CHECKPOINTS`] checkpoints are replayed; keep a
+    /// [`TaskIndex`] to find older tasks without replaying them every time.
+    pub async fn discover_task_ids(
+        &self,
+        owner: sui::types::Address,
+        from_checkpoint: Option<u64>,
+    ) -> Result<Vec<sui::types::Address>, NexusError> {
+        let mut index = self.new_task_index(owner, from_checkpoint).await?;
+
+        self.sync_task_index(&mut index).await?;
+
+        Ok(index.task_ids.into_iter().collect())
+    }
+
+    /// An empty [`TaskIndex`] for `owner` starting at `from_checkpoint`, or
+    /// [`DEFAULT_TASK_DISCOVERY_CHECKPOINTS`] before the current checkpoint.
+    pub async fn new_task_index(
+        &self,
+        owner: sui::types::Address,
+        from_checkpoint: Option<u64>,
+    ) -> Result<TaskIndex, NexusError> {
+        let from_checkpoint = match from_checkpoint {
+            Some(checkpoint) => checkpoint,
+            None => {
+                let (lowest_checkpoint, latest_checkpoint) = self
+                    .client
+                    .crawler()
+                    .get_checkpoint_bounds()
+                    .await
+                    .map_err(NexusError::Rpc)?;
+
+                latest_checkpoint
+                    .saturating_sub(DEFAULT_TASK_DISCOVERY_CHECKPOINTS)
+                    .max(lowest_checkpoint)
+            }
+        };
+
+        Ok(TaskIndex::new(owner, from_checkpoint))
+    }
+
+    /// Record tasks created for the index owner from the index progress up to
+    /// and including the current checkpoint.
+    pub async fn sync_task_index(&self, index: &mut TaskIndex) -> Result<(), NexusError> {
+        let (lowest_checkpoint, latest_checkpoint) = self
+            .client
+            .crawler()
+            .get_checkpoint_bounds()
+            .await
+            .map_err(NexusError::Rpc)?;
+
+        let mut pages = self
+            .client
+            .event_ingestor()
+            .start(Some(index.progress.checkpoint.max(lowest_checkpoint)))
+            .map_err(|e| NexusError::Rpc(e.into()))?;
+
+        // Dropping the receiver stops the ingestor.
+        while let Some(page) = pages.recv().await {
+            let page = page.map_err(|e| NexusError::Rpc(e.into()))?;
+
+            index.record(&page);
+
+            // The watermark page of the latest checkpoint precedes its events.
+            if page.checkpoint > latest_checkpoint {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// List tasks owned by `owner` that pass `filter`.
+    pub async fn list_tasks(
+        &self,
+        owner: sui::types::Address,
+        filter: &TaskFilter,
+    ) -> Result<Vec<TaskSummary>, NexusError> {
+        let task_ids = self
+            .discover_task_ids(owner, filter.from_checkpoint)
+            .await?;
+
+        self.filter_tasks(owner, task_ids, filter).await
+    }
+
+    /// Sync `index` and list the indexed tasks that pass `filter`.
+    /// `filter.from_checkpoint` is ignored in favour of the index progress.
+    pub async fn list_indexed_tasks(
+        &self,
+        index: &mut TaskIndex,
+        filter: &TaskFilter,
+    ) -> Result<Vec<TaskSummary>, NexusError> {
+        self.sync_task_index(index).await?;
+
+        self.filter_tasks(index.owner, index.task_ids.iter().copied(), filter)
+            .await
+    }
+
+    /// Fetch the summaries of `task_ids` concurrently, keeping their order.
+    pub async fn fetch_task_summaries(
+        &self,
+        task_ids: impl IntoIterator<Item = sui::types::Address>,
+    ) -> Result<Vec<TaskSummary>, NexusError> {
+        stream::iter(task_ids)
+            .map(|task_id| self.fetch_task_summary(task_id))
+            .buffered(TASK_SUMMARY_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    async fn filter_tasks(
+        &self,
+        owner: sui::types::Address,
+        task_ids: impl IntoIterator<Item = sui::types::Address>,
+        filter: &TaskFilter,
+    ) -> Result<Vec<TaskSummary>, NexusError> {
+        let mut tasks = self.fetch_task_summaries(task_ids).await?;
+
+        tasks.retain(|summary| summary.owner == owner && filter.matches(summary));
+
+        Ok(tasks)
+    }
+
+    /// Fetch a task together with its generator state and payment reserve.
+    pub async fn fetch_task_summary(
+        &self,
+        task_id: sui::types::Address,
+    ) -> Result<TaskSummary, NexusError> {
+        let crawler = self.client.crawler();
+        let task = self.fetch_task(task_id).await?.data;
+
+        let generator = fetch_generator_state(
+            crawler,
+            &self.client.nexus_objects,
+            task.constraints.dfa.id.id.bytes,
+        )
+        .await
+        .map_err(NexusError::Rpc)?;
+        let reserve_balance_mist = fetch_payment_reserve_balance(crawler, task_id)
+            .await
+            .map_err(NexusError::Rpc)?;
+
+        let (generator, next_occurrence_ms, remaining_iterations) = match &generator {
+            Some(state) => {
+                let (kind, next_occurrence_ms, remaining_iterations) = state.progress();
+                (Some(kind), next_occurrence_ms, remaining_iterations)
+            }
+            None => (None, None, None),
+        };
+
+        Ok(TaskSummary {
+            task_id,
+            owner: task.owner,
+            status: TaskStatus::from(&task.state),
+            generator,
+            next_occurrence_ms,
+            remaining_iterations,
+            metadata: task
+                .metadata
+                .values
+                .contents
+                .iter()
+                .map(|entry| {
+                    (
+                        String::from_utf8_lossy(&entry.key.bytes).into_owned(),
+                        String::from_utf8_lossy(&entry.value.bytes).into_owned(),
+                    )
+                })
+                .collect(),
+            reserve_balance_mist,
+        })
+    }
+
     pub(crate) async fn enqueue_occurrence(
         &self,
         task: &Response<Task>,
@@ -685,6 +1101,154 @@ pub async fn fetch_active_scheduler_start_ms(
     }
 }
 
+/// Collect the tasks created for `owner` from a page of events and return
+/// whether all events up to `latest_checkpoint` have been seen.
+///
+/// The ingestor announces a checkpoint with an empty page before delivering
+/// its events, so `latest_checkpoint` is only complete once a later
+/// checkpoint shows up.
+/// Generator state registered on a task's constraints automaton.
+pub enum GeneratorState {
+    Queue(scheduler_move::QueueGeneratorState),
+    Periodic(scheduler_move::PeriodicGeneratorState),
+}
+
+impl GeneratorState {
+    /// The generator kind, the start of the next pending occurrence and the
+    /// number of occurrences a bounded periodic generator still produces.
+    pub fn progress(&self) -> (GeneratorKind, Option<u64>, Option<u64>) {
+        match self {
+            GeneratorState::Queue(state) => {
+                let next = state
+                    .active
+                    .as_option()
+                    .map(|entry| entry.occurrence.start_time_ms)
+                    .or_else(|| {
+                        state
+                            .pending
+                            .entries
+                            .iter()
+                            .map(|entry| entry.value.occurrence.start_time_ms)
+                            .min()
+                    });
+
+                (GeneratorKind::Queue, next, None)
+            }
+            GeneratorState::Periodic(state) => {
+                let next = state
+                    .active
+                    .as_option()
+                    .map(|occurrence| occurrence.start_time_ms)
+                    .or_else(|| state.next_start_ms.as_option().copied());
+                let remaining = state
+                    .max_iterations
+                    .as_option()
+                    .map(|max| max.saturating_sub(state.generated));
+
+                (GeneratorKind::Periodic, next, remaining)
+            }
+        }
+    }
+
+    /// The start of the latest occurrence already queued on a queue
+    /// generator. Periodic generators do not queue occurrences.
+    pub fn last_queued_ms(&self) -> Option<u64> {
+        match self {
+            GeneratorState::Queue(state) => state
+                .active
+                .as_option()
+                .map(|entry| entry.occurrence.start_time_ms)
+                .into_iter()
+                .chain(
+                    state
+                        .pending
+                        .entries
+                        .iter()
+                        .map(|entry| entry.value.occurrence.start_time_ms),
+                )
+                .max(),
+            GeneratorState::Periodic(_) => None,
+        }
+    }
+}
+
+/// Fetch whichever generator state is registered on the configured automaton
+/// of a task's constraints policy.
+pub async fn fetch_generator_state(
+    crawler: &Crawler,
+    objects: &NexusObjects,
+    configured_automaton_id: sui::types::Address,
+) -> anyhow::Result<Option<GeneratorState>> {
+    let configs = fetch_scheduler_config_fields(crawler, configured_automaton_id).await?;
+
+    if let Some(field) = find_scheduler_generator_state_field::<
+        scheduler_move::QueueGeneratorWitness,
+        scheduler_move::QueueGeneratorState,
+    >(&configs, objects)
+    {
+        let state = crawler
+            .get_dynamic_field_value_by_id::<SchedulerConfigKey, scheduler_move::QueueGeneratorState>(
+                field.field_id,
+            )
+            .await
+            .map_err(|error| {
+                anyhow!("decode QueueGeneratorState as generated BCS Move layout: {error}")
+            })?;
+
+        return Ok(Some(GeneratorState::Queue(state)));
+    }
+
+    if let Some(field) = find_scheduler_generator_state_field::<
+        scheduler_move::PeriodicGeneratorWitness,
+        scheduler_move::PeriodicGeneratorState,
+    >(&configs, objects)
+    {
+        let state = crawler
+            .get_dynamic_field_value_by_id::<
+                SchedulerConfigKey,
+                scheduler_move::PeriodicGeneratorState,
+            >(field.field_id)
+            .await
+            .map_err(|error| {
+                anyhow!("decode PeriodicGeneratorState as generated BCS Move layout: {error}")
+            })?;
+
+        return Ok(Some(GeneratorState::Periodic(state)));
+    }
+
+    Ok(None)
+}
+
+/// Fetch the remaining funds of the TAP payment reserve stored under a task,
+/// if it has one.
+async fn fetch_payment_reserve_balance(
+    crawler: &Crawler,
+    task_id: sui::types::Address,
+) -> anyhow::Result<Option<u64>> {
+    let fields = crawler
+        .get_dynamic_object_field_refs_matching_key::<scheduler_move::ScheduledPaymentReserveFieldKey>(
+            task_id,
+        )
+        .await?;
+
+    // Other marker keys under the task share the same BCS shape so only
+    // accept children that decode as this task's reserve.
+    for field in fields {
+        let Ok(reserve) = crawler
+            .get_object::<payment_move::ScheduledPaymentReserve>(field.child_id)
+            .await
+        else {
+            continue;
+        };
+
+        if reserve.data.scheduled_task_id == task_id {
+            return Ok(Some(reserve.data.remaining_funds.value));
+        }
+    }
+
+    Ok(None)
+}
+
 async fn fetch_scheduler_config_fields(
     crawler: &Crawler,
     configured_automaton_id: sui::types::Address,
@@ -835,11 +1399,20 @@ pub(crate) fn extract_task_id(
 }
 
 pub(crate) fn extract_occurrence_event(response: &ExecutedTransaction) -> Option<NexusEventKind> {
-    response.events.iter().find_map(|event| match &event.data {
-        NexusEventKind::RequestScheduledOccurrence(_) => Some(event.data.clone()),
-        NexusEventKind::OccurrenceScheduled(_) => Some(event.data.clone()),
-        _ => None,
-    })
+    extract_occurrence_events(response).into_iter().next()
+}
+
+/// Occurrence events of a transaction in emission order.
+fn extract_occurrence_events(response: &ExecutedTransaction) -> Vec<NexusEventKind> {
+    response
+        .events
+        .iter()
+        .filter_map(|event| match &event.data {
+            NexusEventKind::RequestScheduledOccurrence(_) => Some(event.data.clone()),
+            NexusEventKind::OccurrenceScheduled(_) => Some(event.data.clone()),
+            _ => None,
+        })
+        .collect()
 }
 
 fn validate_schedule_options(
@@ -943,7 +1516,7 @@ mod tests {
                 sui_framework::vec_map,
             },
             nexus::{client::NexusClient, error::NexusError, signer::ExecutedTransaction},
-            sui,
+            sui::{self, events::EventId},
             test_utils::{nexus_mocks, sui_mocks},
             types::NexusObjects,
         },
@@ -2000,6 +2573,223 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_periodic_generator_progress() {
+        let state = GeneratorState::Periodic(scheduler_move::PeriodicGeneratorState {
+            active: MoveOption::from_option(None),
+            next_start_ms: MoveOption::from_option(Some(5_000)),
+            period_ms: 1_000,
+            deadline_offset_ms: MoveOption::from_option(None),
+            max_iterations: MoveOption::from_option(Some(5)),
+            generated: 2,
+            last_emitted_start_ms: MoveOption::from_option(Some(4_000)),
+            priority_fee_percentage: 0,
+        });
+
+        assert_eq!(
+            state.progress(),
+            (GeneratorKind::Periodic, Some(5_000), Some(3))
+        );
+    }
+
+    #[test]
+    fn test_task_index_records_each_creation_once() {
+        let mut rng = rand::thread_rng();
+        let owner = sui::types::Address::generate(&mut rng);
+        let task_id = sui::types::Address::generate(&mut rng);
+        let created = |owner, task_id| NexusEvent {
+            id: (sui::types::Digest::generate(&mut rand::thread_rng()), 0),
+            generics: vec![],
+            data: NexusEventKind::ScheduledSkillExecutionCreated(
+                ScheduledSkillExecutionCreatedEvent {
+                    task: object_id(task_id),
+                    owner,
+                },
+            ),
+            distribution: None,
+        };
+        let event_id = |event_index| EventId {
+            checkpoint: 10,
+            transaction_digest: "digest".to_string(),
+            event_index,
+        };
+        let page = EventPage {
+            events: vec![
+                created(owner, task_id),
+                created(
+                    sui::types::Address::generate(&mut rng),
+                    sui::types::Address::generate(&mut rng),
+                ),
+            ],
+            event_ids: vec![event_id(0), event_id(1)],
+            checkpoint: 10,
+        };
+        let mut index = TaskIndex::new(owner, 5);
+
+        index.record(&page);
+
+        assert_eq!(index.task_ids, BTreeSet::from([task_id]));
+        assert_eq!(index.progress.checkpoint, 10);
+
+        // A task that is no longer indexed is not added back by a replay.
+        index.task_ids.clear();
+        index.record(&page);
+
+        assert!(index.task_ids.is_empty());
+
+        // The index survives a round trip through its persisted form.
+        let json = serde_json::to_string(&index).unwrap();
+        assert_eq!(serde_json::from_str::<TaskIndex>(&json).unwrap(), index);
+    }
+
+    #[test]
+    fn test_task_filter_matches_status_and_metadata() {
+        let summary = TaskSummary {
+            task_id: sui::types::Address::from_static("0x1"),
+            owner: sui::types::Address::from_static("0x2"),
+            status: TaskStatus::Paused,
+            generator: Some(GeneratorKind::Queue),
+            next_occurrence_ms: None,
+            remaining_iterations: None,
+            metadata: vec![
+                ("team".to_string(), "ops".to_string()),
+                ("env".to_string(), "prod".to_string()),
+            ],
+            reserve_balance_mist: Some(10),
+        };
+
+        assert!(TaskFilter::default().matches(&summary));
+        assert!(TaskFilter {
+            status: Some(TaskStatus::Paused),
+            metadata: vec![("env".to_string(), "prod".to_string())],
+            ..Default::default()
+        }
+        .matches(&summary));
+        assert!(!TaskFilter {
+            status: Some(TaskStatus::Active),
+            ..Default::default()
+        }
+        .matches(&summary));
+        assert!(!TaskFilter {
+            metadata: vec![("env".to_string(), "dev".to_string())],
+            ..Default::default()
+        }
+        .matches(&summary));
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_schedule_cron() {
+        let mut rng = rand::thread_rng();
+        let mut ledger_service_mock = sui_mocks::grpc::MockLedgerService::new();
+        let mut execution_service_mock = sui_mocks::grpc::MockTransactionExecutionService::new();
+        let mut subscription_service_mock = sui_mocks::grpc::MockSubscriptionService::new();
+
+        sui_mocks::grpc::mock_reference_gas_price(&mut ledger_service_mock, 1_000);
+
+        let digest = sui::types::Digest::generate(&mut rng);
+        let gas_coin_ref = sui_mocks::mock_sui_object_ref();
+
+        let nexus_objects = sui_mocks::mock_nexus_objects();
+        let task_id = sui::types::Address::generate(&mut rng);
+        let owner = sui::types::Address::generate(&mut rng);
+
+        // Task fetch
+        let task_object = mock_task_object(task_id, owner);
+        let task_ref =
+            sui::types::ObjectReference::new(task_id, 1, sui::types::Digest::generate(&mut rng));
+        mock_get_task_object(&mut ledger_service_mock, task_ref, owner, task_object);
+
+        let generator = queue_generator_symbol(nexus_objects.scheduler_pkg_id);
+        let scheduled_event = NexusEventKind::OccurrenceScheduled(OccurrenceScheduledEvent {
+            task: object_id(task_id),
+            generator: generator.clone(),
+        });
+
+        // All fire times in the window share one transaction.
+        sui_mocks::grpc::mock_execute_transaction_and_wait_for_checkpoint_matching(
+            &mut execution_service_mock,
+            &mut subscription_service_mock,
+            &mut ledger_service_mock,
+            digest,
+            gas_coin_ref.clone(),
+            vec![],
+            vec![],
+            vec![
+                event_bcs(
+                    &nexus_objects,
+                    nexus_objects.scheduler_pkg_id,
+                    scheduled_event.clone(),
+                );
+                3
+            ],
+            |request| {
+                let transaction = request.transaction.as_ref().expect("submitted transaction");
+                let transaction = sui::types::Transaction::try_from(transaction)
+                    .expect("submitted transaction decodes");
+                let sui::types::TransactionKind::ProgrammableTransaction(ptb) = transaction.kind
+                else {
+                    panic!("expected programmable transaction");
+                };
+                let calls = ptb
+                    .commands
+                    .iter()
+                    .filter(|command| {
+                        matches!(
+                            command,
+                            sui::types::Command::MoveCall(call)
+                                if call.function.as_str() == "add_occurrence_absolute_for_task"
+                        )
+                    })
+                    .count();
+                assert_eq!(calls, 3);
+            },
+        );
+
+        let (_url, nexus_client) = mock_nexus_client_with_server(
+            ledger_service_mock,
+            execution_service_mock,
+            subscription_service_mock,
+            nexus_objects.clone(),
+        )
+        .await;
+
+        let config = CronScheduleConfig {
+            schedule: CronSchedule::parse("@hourly", "UTC").unwrap(),
+            deadline_offset_ms: Some(500),
+            priority_fee_percentage: None,
+        };
+
+        // 11:00, 12:00 and 13:00 fall into (10:00:00.001, 13:30].
+        let mut cursor = 36_000_001;
+
+        let scheduled = nexus_client
+            .scheduler()
+            .schedule_cron(task_id, &config, &mut cursor, 48_600_000)
+            .await
+            .expect("cron occurrences enqueued");
+
+        assert_eq!(
+            scheduled
+                .iter()
+                .map(|(start_ms, _)| *start_ms)
+                .collect::<Vec<_>>(),
+            vec![39_600_000, 43_200_000, 46_800_000]
+        );
+        assert!(scheduled
+            .iter()
+            .all(|(_, result)| result.tx_digest == digest && result.event.is_some()));
+        assert_eq!(cursor, 46_800_000);
+
+        // Nothing new is due within the same window.
+        let scheduled = nexus_client
+            .scheduler()
+            .schedule_cron(task_id, &config, &mut cursor, 48_600_000)
+            .await
+            .expect("no occurrences due");
+
+        assert!(scheduled.is_empty());
+    }
+
     #[tokio::test]
     async fn test_scheduler_add_occurrence_with_offsets() {
         let mut rng = rand::thread_rng();
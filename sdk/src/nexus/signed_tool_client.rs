@@ -0,0 +1,449 @@
+//! Leader side client for invoking tools over signed HTTP.
+//!
+//! [`SignedToolClient`] performs the caller half of the [`crate::signed_http::v2`] protocol the
+//! same way a leader does:
+//! - hashes the canonical tool inputs with
+//!   [`registered_key::canonical_tool_inputs_sha256`],
+//! - derives the deterministic invocation nonce from the [`ToolInvocation`],
+//! - signs the request with a registered leader message key and `POST`s it to `/invoke`,
+//! - verifies the `X-Nexus-Tool-Signature` over the exact result bytes against the tool key
+//!   registered in `network_auth` and decodes the BCS [`TaggedOutput`].
+
+use {
+    crate::{
+        move_bindings::{
+            primitives::{data::NexusData, tagged_output::TaggedOutput},
+            registry::network_auth::IdentityKey,
+        },
+        nexus::{error::NexusError, network_auth::NetworkAuthReader, registered_key},
+        signed_http::v2::{
+            error::SignedHttpError,
+            wire::{self, ResponseHeadersRef},
+        },
+        sui,
+    },
+    ed25519_dalek::{Signer as _, SigningKey},
+    serde_json::Value,
+    std::collections::HashMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum SignedToolClientError {
+    #[error("{0}")]
+    Nexus(#[from] NexusError),
+    #[error("Failed to prepare tool request: {0}")]
+    Request(anyhow::Error),
+    #[error("Failed to reach tool: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Tool responded with {status}: {body}")]
+    Tool {
+        status: reqwest::StatusCode,
+        body: String,
+    },
+    #[error("Tool '{0}' has no active message-signing key registered")]
+    MissingToolKey(sui::types::Address),
+    #[error("Tool response failed verification: {0}")]
+    Verification(#[from] SignedHttpError),
+    #[error("Failed to decode tool result: {0}")]
+    Decode(#[from] bcs::Error),
+}
+
+/// Identity of one logical tool invocation from which the deterministic nonce is derived.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ToolInvocation {
+    pub execution_id: sui::types::Address,
+    pub walk_index: u64,
+    pub vertex_name: String,
+    pub iteration: u64,
+}
+
+impl ToolInvocation {
+    /// Nonce carried in `X-Nexus-Nonce` and bound by the tool signature.
+    pub fn nonce(&self) -> anyhow::Result<[u8; registered_key::SHA256_LEN]> {
+        registered_key::invocation_nonce(
+            self.execution_id,
+            self.walk_index,
+            self.vertex_name.as_bytes(),
+            self.iteration,
+        )
+    }
+}
+
+/// Verified result of a signed tool invocation.
+#[derive(Clone, Debug)]
+pub struct SignedToolResponse {
+    /// Decoded tool output.
+    pub output: TaggedOutput,
+    /// Exact BCS bytes of `output` covered by the tool signature.
+    pub result_bytes: Vec<u8>,
+    pub input_hash: [u8; registered_key::SHA256_LEN],
+    pub nonce: [u8; registered_key::SHA256_LEN],
+    pub leader_signature: [u8; registered_key::ED25519_SIGNATURE_LEN],
+    pub tool_signature: [u8; registered_key::ED25519_SIGNATURE_LEN],
+}
+
+pub struct SignedToolClient {
+    http: reqwest::Client,
+    reader: NetworkAuthReader,
+    leader_cap_id: sui::types::Address,
+    leader_key_id: u64,
+    signing_key: SigningKey,
+}
+
+impl SignedToolClient {
+    /// Create a client signing requests as `leader_cap_id` with its registered key
+    /// `leader_key_id`. Tool keys are resolved through `reader`.
+    pub fn new(
+        reader: NetworkAuthReader,
+        leader_cap_id: sui::types::Address,
+        leader_key_id: u64,
+        signing_key: SigningKey,
+    ) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            reader,
+            leader_cap_id,
+            leader_key_id,
+            signing_key,
+        }
+    }
+
+    /// Use a preconfigured [`reqwest::Client`], for example one with timeouts or custom TLS.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Fetch the active Ed25519 message-signing key registered for `tool_id`.
+    pub async fn tool_public_key(
+        &self,
+        tool_id: sui::types::Address,
+    ) -> Result<[u8; 32], SignedToolClientError> {
+        self.reader
+            .try_get_active_key_binding(&IdentityKey::tool(tool_id))
+            .await?
+            .and_then(|resolved| resolved.active_key)
+            .map(|key| key.public_key)
+            .ok_or(SignedToolClientError::MissingToolKey(tool_id))
+    }
+
+    /// Invoke the tool served at `tool_url` and verify its response against the key
+    /// registered on-chain for `tool_id`.
+    pub async fn invoke(
+        &self,
+        tool_url: &reqwest::Url,
+        tool_id: sui::types::Address,
+        invocation: &ToolInvocation,
+        inputs: &HashMap<String, NexusData>,
+    ) -> Result<SignedToolResponse, SignedToolClientError> {
+        let tool_public_key = self.tool_public_key(tool_id).await?;
+
+        self.invoke_with_tool_key(tool_url, tool_public_key, invocation, inputs)
+            .await
+    }
+
+    /// Invoke the tool served at `tool_url` and verify its response against an already
+    /// known tool public key.
+    pub async fn invoke_with_tool_key(
+        &self,
+        tool_url: &reqwest::Url,
+        tool_public_key: [u8; 32],
+        invocation: &ToolInvocation,
+        inputs: &HashMap<String, NexusData>,
+    ) -> Result<SignedToolResponse, SignedToolClientError> {
+        let input_hash = registered_key::canonical_tool_inputs_sha256(inputs)
+            .map_err(SignedToolClientError::Request)?;
+        let nonce = invocation.nonce().map_err(SignedToolClientError::Request)?;
+        let body = invoke_body(inputs).map_err(SignedToolClientError::Request)?;
+
+        let encoded = wire::sign_request(
+            self.leader_cap_id.to_string(),
+            self.leader_key_id,
+            input_hash,
+            nonce,
+            &self.signing_key,
+        );
+        // Ed25519 signatures are deterministic so this is the signature sent above.
+        let leader_signature = self.signing_key.sign(&input_hash).to_bytes();
+
+        let mut request = self.http.post(invoke_url(tool_url)?).json(&body);
+
+        for (name, value) in encoded.to_pairs() {
+            request = request.header(name, value);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let result_bytes = response.bytes().await?.to_vec();
+
+        if !status.is_success() {
+            return Err(SignedToolClientError::Tool {
+                status,
+                body: String::from_utf8_lossy(&result_bytes).into_owned(),
+            });
+        }
+
+        let tool_signature = wire::verify_response(
+            ResponseHeadersRef::from_getter(|name| {
+                headers.get(name).and_then(|value| value.to_str().ok())
+            }),
+            &leader_signature,
+            &nonce,
+            &result_bytes,
+            tool_public_key,
+        )?;
+
+        Ok(SignedToolResponse {
+            output: bcs::from_bytes(&result_bytes)?,
+            result_bytes,
+            input_hash,
+            nonce,
+            leader_signature,
+            tool_signature,
+        })
+    }
+}
+
+fn invoke_url(tool_url: &reqwest::Url) -> Result<reqwest::Url, SignedToolClientError> {
+    let mut url = tool_url.clone();
+
+    url.path_segments_mut()
+        .map_err(|_| {
+            SignedToolClientError::Request(anyhow::anyhow!(
+                "Tool URL '{tool_url}' cannot be used as a base URL"
+            ))
+        })?
+        .pop_if_empty()
+        .push("invoke");
+
+    Ok(url)
+}
+
+/// JSON body sent to the tool. Inline payloads are passed as their JSON values
+/// and Walrus payloads as `{"$nexus_walrus": ...}` references holding the blob
+/// IDs as strings, see [`NexusData::WALRUS_INPUT_KEY`].
+fn invoke_body(inputs: &HashMap<String, NexusData>) -> anyhow::Result<Value> {
+    let mut body = serde_json::Map::with_capacity(inputs.len());
+
+    for (port, data) in inputs {
+        if !data.is_inline() && !data.is_walrus() {
+            anyhow::bail!(
+                "Input port '{port}' uses unsupported storage '{}'",
+                String::from_utf8_lossy(data.storage_tag())
+            );
+        }
+
+        let decode = |bytes: &[u8]| -> anyhow::Result<Value> {
+            if data.is_walrus() {
+                let blob_id = std::str::from_utf8(bytes).map_err(|e| {
+                    anyhow::anyhow!("Input port '{port}' blob ID is not valid UTF-8: {e}")
+                })?;
+
+                return Ok(Value::String(blob_id.to_string()));
+            }
+
+            serde_json::from_slice::<Value>(bytes)
+                .map_err(|e| anyhow::anyhow!("Input port '{port}' payload is not valid JSON: {e}"))
+        };
+
+        let value = if data.many.is_empty() && !data.one.is_empty() {
+            decode(&data.one)?
+        } else {
+            Value::Array(
+                data.many
+                    .iter()
+                    .map(|bytes| decode(bytes))
+                    .collect::<anyhow::Result<_>>()?,
+            )
+        };
+
+        let value = if data.is_walrus() {
+            serde_json::json!({ (NexusData::WALRUS_INPUT_KEY): value })
+        } else {
+            value
+        };
+
+        body.insert(port.clone(), value);
+    }
+
+    Ok(Value::Object(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::move_bindings::{
+            primitives::data::{DataTypeHint, TypedNexusData},
+            sui_framework::vec_map::{Entry as VecMapEntry, VecMap},
+        },
+        assert_matches::assert_matches,
+        base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _},
+        mockito::{Matcher, Server},
+        serde_json::json,
+    };
+
+    fn client(leader: SigningKey) -> SignedToolClient {
+        // The reader is never queried when the tool key is passed explicitly.
+        let reader = NetworkAuthReader::from_rpc_url(
+            "http://127.0.0.1:1",
+            sui::types::Address::from_static("0x1"),
+            sui::types::Address::from_static("0x2"),
+        )
+        .unwrap();
+
+        SignedToolClient::new(reader, sui::types::Address::from_static("0x42"), 3, leader)
+    }
+
+    fn invocation() -> ToolInvocation {
+        ToolInvocation {
+            execution_id: sui::types::Address::from_static("0x99"),
+            walk_index: 1,
+            vertex_name: "summarize".to_string(),
+            iteration: 0,
+        }
+    }
+
+    fn output() -> TaggedOutput {
+        TaggedOutput {
+            tag: b"ok".to_vec(),
+            named_payload: VecMap {
+                contents: vec![VecMapEntry {
+                    key: b"summary".to_vec(),
+                    value: TypedNexusData {
+                        type_hint: DataTypeHint::String,
+                        data: NexusData::inline_one(b"short".to_vec()),
+                    },
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn invoke_body_decodes_inline_and_walrus_inputs() {
+        let inputs = HashMap::from([
+            (
+                "text".to_string(),
+                NexusData::inline_one(b"\"hello\"".to_vec()),
+            ),
+            (
+                "numbers".to_string(),
+                NexusData::inline_many([b"1".to_vec(), b"2".to_vec()]),
+            ),
+            (
+                "document".to_string(),
+                NexusData::walrus_one(b"blob-id".to_vec()),
+            ),
+        ]);
+
+        assert_eq!(
+            invoke_body(&inputs).unwrap(),
+            json!({
+                "text": "hello",
+                "numbers": [1, 2],
+                "document": { "$nexus_walrus": "blob-id" },
+            })
+        );
+
+        let invalid =
+            HashMap::from([("text".to_string(), NexusData::inline_one(b"hello".to_vec()))]);
+        assert!(invoke_body(&invalid).is_err());
+    }
+
+    #[tokio::test]
+    async fn invoke_signs_request_and_verifies_tool_signature() {
+        let leader = SigningKey::from_bytes(&[1; 32]);
+        let tool = SigningKey::from_bytes(&[2; 32]);
+        let inputs = HashMap::from([(
+            "text".to_string(),
+            NexusData::inline_one(b"\"hello\"".to_vec()),
+        )]);
+        let invocation = invocation();
+
+        let input_hash = registered_key::canonical_tool_inputs_sha256(&inputs).unwrap();
+        let nonce = invocation.nonce().unwrap();
+        let leader_signature = leader.sign(&input_hash).to_bytes();
+        let result_bytes = bcs::to_bytes(&output()).unwrap();
+        let signed = wire::sign_response(&leader_signature, &nonce, &result_bytes, &tool);
+
+        let mut server = Server::new_async().await;
+        let mut mock = server
+            .mock("POST", "/tool/invoke")
+            .match_header(wire::HEADER_SIGNATURE_VERSION, wire::SIGNATURE_VERSION_V2)
+            .match_header(
+                wire::HEADER_LEADER_ID,
+                sui::types::Address::from_static("0x42")
+                    .to_string()
+                    .as_str(),
+            )
+            .match_header(wire::HEADER_LEADER_KEY_ID, "3")
+            .match_header(
+                wire::HEADER_INPUT_HASH,
+                URL_SAFE_NO_PAD.encode(input_hash).as_str(),
+            )
+            .match_header(wire::HEADER_NONCE, URL_SAFE_NO_PAD.encode(nonce).as_str())
+            .match_body(Matcher::Json(json!({ "text": "hello" })))
+            .with_status(200);
+
+        for (name, value) in signed.to_pairs() {
+            mock = mock.with_header(name, &value);
+        }
+
+        let mock = mock.with_body(result_bytes.clone()).create_async().await;
+
+        let url = reqwest::Url::parse(&format!("{}/tool/", server.url())).unwrap();
+        let response = client(leader)
+            .invoke_with_tool_key(&url, tool.verifying_key().to_bytes(), &invocation, &inputs)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(response.output.tag, b"ok".to_vec());
+        assert_eq!(response.result_bytes, result_bytes);
+        assert_eq!(response.input_hash, input_hash);
+        assert_eq!(response.nonce, nonce);
+        assert_eq!(response.leader_signature, leader_signature);
+
+        // A response signed by a different key is rejected.
+        let result = client(SigningKey::from_bytes(&[1; 32]))
+            .invoke_with_tool_key(
+                &url,
+                SigningKey::from_bytes(&[3; 32]).verifying_key().to_bytes(),
+                &invocation,
+                &inputs,
+            )
+            .await;
+
+        assert_matches!(
+            result,
+            Err(SignedToolClientError::Verification(
+                SignedHttpError::InvalidSignature
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn invoke_surfaces_tool_errors() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", "/invoke")
+            .with_status(422)
+            .with_body(r#"{"error":"input_deserialization_error"}"#)
+            .create_async()
+            .await;
+
+        let url = reqwest::Url::parse(&server.url()).unwrap();
+        let result = client(SigningKey::from_bytes(&[1; 32]))
+            .invoke_with_tool_key(&url, [0; 32], &invocation(), &HashMap::new())
+            .await;
+
+        assert_matches!(
+            result,
+            Err(SignedToolClientError::Tool { status, body })
+                if status == reqwest::StatusCode::UNPROCESSABLE_ENTITY
+                    && body.contains("input_deserialization_error")
+        );
+    }
+}
@@ -3,7 +3,13 @@
 use {
     crate::{
         events::{NexusEvent, NexusEventQuery},
-        nexus::{crawler::Crawler, error::NexusError},
+        nexus::{
+            crawler::Crawler,
+            error::NexusError,
+            move_abort::MoveAbort,
+            simulation::SimulatedTransaction,
+            transaction_signer::TransactionSigner,
+        },
         sui::{self, traits::*},
         types::NexusObjects,
     },
@@ -22,11 +28,11 @@ pub struct ExecutedTransaction {
 }
 
 /// The Signer struct capable of signing and executing transactions based on the
-/// provided [`sui::crypto::Ed25519PrivateKey`].
+/// provided [`TransactionSigner`] backend.
 #[derive(Clone)]
 pub struct Signer {
     pub(super) client: Arc<Mutex<sui::grpc::Client>>,
-    pub(super) pk: sui::crypto::Ed25519PrivateKey,
+    pub(super) transaction_signer: Arc<dyn TransactionSigner>,
     pub(super) transaction_timeout: Duration,
     pub(super) nexus_objects: Arc<NexusObjects>,
 }
@@ -34,13 +40,28 @@ pub struct Signer {
 impl Signer {
     pub fn new(
         client: Arc<Mutex<sui::grpc::Client>>,
-        pk: sui::crypto::Ed25519PrivateKey,
+        transaction_signer: impl TransactionSigner + 'static,
+        transaction_timeout: Duration,
+        nexus_objects: Arc<NexusObjects>,
+    ) -> Self {
+        Self::with_transaction_signer(
+            client,
+            Arc::new(transaction_signer),
+            transaction_timeout,
+            nexus_objects,
+        )
+    }
+
+    /// Create a signer from an already shared [`TransactionSigner`].
+    pub fn with_transaction_signer(
+        client: Arc<Mutex<sui::grpc::Client>>,
+        transaction_signer: Arc<dyn TransactionSigner>,
         transaction_timeout: Duration,
         nexus_objects: Arc<NexusObjects>,
     ) -> Self {
         Self {
             client,
-            pk,
+            transaction_signer,
             transaction_timeout,
             nexus_objects,
         }
@@ -48,7 +69,12 @@ impl Signer {
 
     /// Get the active address from the signer.
     pub fn get_active_address(&self) -> sui::types::Address {
-        self.pk.public_key().derive_address()
+        self.transaction_signer.address()
+    }
+
+    /// The backend used to sign transactions.
+    pub fn transaction_signer(&self) -> &Arc<dyn TransactionSigner> {
+        &self.transaction_signer
     }
 
     /// Sign a transaction block using the signer.
@@ -56,9 +82,7 @@ impl Signer {
         &self,
         tx: &sui::types::Transaction,
     ) -> Result<sui::types::UserSignature, NexusError> {
-        self.pk
-            .sign_transaction(tx)
-            .map_err(|e| NexusError::Wallet(anyhow::anyhow!(e)))
+        self.transaction_signer.sign_transaction(tx).await
     }
 
     /// Executes a coin based transaction and refreshes its owned gas coin.
@@ -115,31 +139,17 @@ impl Signer {
             )));
         };
 
+        if let Some(abort) = MoveAbort::from_status(effects.status()) {
+            return Err(self.resolve_move_abort(abort).await.into());
+        }
+
         if let sui::types::ExecutionStatus::Failure { error, command } = effects.status() {
             return Err(NexusError::Wallet(anyhow::anyhow!(
                 "Transaction execution failed: {error:?} in command: {command:?}"
             )));
         }
 
-        // Deserialize events.
-        let Ok(events) = sui::types::TransactionEvents::try_from(response.events()) else {
-            return Err(NexusError::Wallet(anyhow::anyhow!(
-                "Failed to read transaction events."
-            )));
-        };
-
-        let event_query = NexusEventQuery::new(Arc::clone(&self.nexus_objects));
-        let nexus_events = events
-            .0
-            .iter()
-            .enumerate()
-            .filter_map(|(index, event)| {
-                event_query
-                    .decode_sui_event(index as u64, digest, event)
-                    .transpose()
-            })
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|error| NexusError::Parsing(error.into()))?;
+        let nexus_events = self.decode_events(response.events(), digest)?;
 
         // Deserialize objects.
         let Ok(objects) = response
@@ -163,6 +173,100 @@ impl Signer {
         })
     }
 
+    /// Simulate a transaction through Sui's simulate endpoint without signing
+    /// or executing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NexusError`] when the simulation request fails or its
+    /// response cannot be decoded. A transaction that would fail on chain is
+    /// not an error, see [`SimulatedTransaction::failure`].
+    pub async fn simulate_tx(
+        &self,
+        tx: sui::types::Transaction,
+    ) -> Result<SimulatedTransaction, NexusError> {
+        let mut client = self.client.lock().await.clone();
+        let budget = tx.gas_payment.budget;
+
+        let request = sui::grpc::SimulateTransactionRequest::default()
+            .with_transaction(tx)
+            .with_read_mask(sui::grpc::FieldMask::from_paths([
+                "transaction.effects.bcs",
+                "transaction.events.events",
+            ]));
+
+        let response = client
+            .execution_client()
+            .simulate_transaction(request)
+            .await
+            .map_err(|e| NexusError::Rpc(e.into()))?
+            .into_inner();
+        let simulated = response.transaction();
+
+        let Ok(sui::types::TransactionEffects::V2(effects)) =
+            sui::types::TransactionEffects::try_from(simulated.effects())
+        else {
+            return Err(NexusError::Wallet(anyhow::anyhow!(
+                "Failed to read simulated transaction effects."
+            )));
+        };
+
+        let events = self.decode_events(simulated.events(), effects.transaction_digest)?;
+
+        Ok(SimulatedTransaction::new(*effects, events, budget))
+    }
+
+    /// Resolve the abort constant name from the aborting module's bytecode.
+    ///
+    /// This is best effort, the abort is returned unchanged if its code does
+    /// not name a constant or the module cannot be fetched.
+    pub async fn resolve_move_abort(&self, abort: MoveAbort) -> MoveAbort {
+        if abort
+            .clever_error()
+            .and_then(|e| e.identifier_index)
+            .is_none()
+        {
+            return abort;
+        }
+
+        let crawler = Crawler::new(Arc::clone(&self.client));
+
+        match crawler
+            .get_module_bytecode(abort.package, &abort.module)
+            .await
+        {
+            Ok(bytecode) => abort.with_module_bytecode(&bytecode),
+            Err(_) => abort,
+        }
+    }
+
+    /// Decode the Nexus events out of the transaction events in a response.
+    fn decode_events(
+        &self,
+        events: &sui::grpc::TransactionEvents,
+        digest: sui::types::Digest,
+    ) -> Result<Vec<NexusEvent>, NexusError> {
+        let Ok(events) = sui::types::TransactionEvents::try_from(events) else {
+            return Err(NexusError::Wallet(anyhow::anyhow!(
+                "Failed to read transaction events."
+            )));
+        };
+
+        let event_query = NexusEventQuery::new(Arc::clone(&self.nexus_objects));
+
+        events
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(index, event)| {
+                event_query
+                    .decode_sui_event(index as u64, digest, event)
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|error| NexusError::Parsing(error.into()))
+    }
+
     /// Execute a transaction while subscribing to a checkpoint stream to confirm
     /// its inclusion in a checkpoint.
     async fn execute_tx_and_wait_for_checkpoint(
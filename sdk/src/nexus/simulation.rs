@@ -0,0 +1,190 @@
+//! Results of simulating a transaction through Sui's simulate endpoint before
+//! it is signed and executed.
+//!
+//! See [`NexusClient::simulate_transaction`](crate::nexus::client::NexusClient::simulate_transaction).
+
+use crate::{events::NexusEvent, nexus::move_abort::MoveAbort, sui};
+
+/// Gas estimate derived from the simulated transaction effects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GasEstimate {
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    pub non_refundable_storage_fee: u64,
+}
+
+impl GasEstimate {
+    /// Budget the transaction has to cover. The storage rebate is only
+    /// credited after execution so it is not subtracted here.
+    pub fn required_budget(&self) -> u64 {
+        self.computation_cost.saturating_add(self.storage_cost)
+    }
+
+    /// Net amount charged to the sender after the storage rebate. Negative if
+    /// the transaction frees more storage than it uses.
+    pub fn net_cost(&self) -> i64 {
+        let net = i128::from(self.computation_cost) + i128::from(self.storage_cost)
+            - i128::from(self.storage_rebate);
+
+        net.clamp(i64::MIN.into(), i64::MAX.into()) as i64
+    }
+
+    /// [`Self::required_budget`] increased by `margin_percent` percent.
+    pub fn budget_with_margin(&self, margin_percent: u64) -> u64 {
+        let budget = u128::from(self.required_budget()) * (100 + u128::from(margin_percent)) / 100;
+
+        u64::try_from(budget).unwrap_or(u64::MAX)
+    }
+}
+
+impl From<&sui::types::GasCostSummary> for GasEstimate {
+    fn from(summary: &sui::types::GasCostSummary) -> Self {
+        Self {
+            computation_cost: summary.computation_cost,
+            storage_cost: summary.storage_cost,
+            storage_rebate: summary.storage_rebate,
+            non_refundable_storage_fee: summary.non_refundable_storage_fee,
+        }
+    }
+}
+
+/// Outcome of simulating a transaction without executing it.
+#[derive(Clone, Debug)]
+pub struct SimulatedTransaction {
+    pub effects: sui::types::TransactionEffectsV2,
+    pub events: Vec<NexusEvent>,
+    pub gas: GasEstimate,
+    /// Gas budget the transaction was simulated with.
+    pub budget: u64,
+}
+
+impl SimulatedTransaction {
+    pub fn new(
+        effects: sui::types::TransactionEffectsV2,
+        events: Vec<NexusEvent>,
+        budget: u64,
+    ) -> Self {
+        let gas = GasEstimate::from(&effects.gas_used);
+
+        Self {
+            effects,
+            events,
+            gas,
+            budget,
+        }
+    }
+
+    /// Whether the transaction would execute successfully.
+    pub fn is_success(&self) -> bool {
+        matches!(self.effects.status, sui::types::ExecutionStatus::Success)
+    }
+
+    /// The Move abort the transaction would fail with, if any.
+    pub fn move_abort(&self) -> Option<MoveAbort> {
+        MoveAbort::from_status(&self.effects.status)
+    }
+
+    /// Human readable failure reason, `None` if the simulation succeeded.
+    pub fn failure(&self) -> Option<String> {
+        let sui::types::ExecutionStatus::Failure { error, command } = &self.effects.status else {
+            return None;
+        };
+
+        if let Some(abort) = self.move_abort() {
+            return Some(abort.to_string());
+        }
+
+        Some(match command {
+            Some(command) => format!("{error:?} in command {command}"),
+            None => format!("{error:?}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test_utils::sui_mocks};
+
+    fn effects(status: sui::types::ExecutionStatus) -> sui::types::TransactionEffectsV2 {
+        sui::types::TransactionEffectsV2 {
+            status,
+            epoch: 1,
+            gas_used: sui::types::GasCostSummary {
+                computation_cost: 1_000,
+                storage_cost: 3_000,
+                storage_rebate: 500,
+                non_refundable_storage_fee: 5,
+            },
+            transaction_digest: sui::types::Digest::generate(rand::thread_rng()),
+            gas_object_index: None,
+            events_digest: None,
+            dependencies: vec![],
+            lamport_version: 1,
+            changed_objects: vec![],
+            unchanged_consensus_objects: vec![],
+            auxiliary_data_digest: None,
+        }
+    }
+
+    #[test]
+    fn test_gas_estimate_budget_with_margin() {
+        let simulated =
+            SimulatedTransaction::new(effects(sui::types::ExecutionStatus::Success), vec![], 10);
+
+        assert!(simulated.is_success());
+        assert!(simulated.failure().is_none());
+        assert_eq!(simulated.gas.required_budget(), 4_000);
+        assert_eq!(simulated.gas.net_cost(), 3_500);
+        assert_eq!(simulated.gas.budget_with_margin(0), 4_000);
+        assert_eq!(simulated.gas.budget_with_margin(25), 5_000);
+
+        let huge = GasEstimate {
+            computation_cost: u64::MAX,
+            ..Default::default()
+        };
+        assert_eq!(huge.budget_with_margin(50), u64::MAX);
+    }
+
+    #[test]
+    fn test_move_abort_is_decoded() {
+        let package = sui_mocks::mock_sui_address();
+        let status = sui::types::ExecutionStatus::Failure {
+            error: sui::types::ExecutionError::MoveAbort {
+                location: sui::types::MoveLocation {
+                    package,
+                    module: sui::types::Identifier::from_static("tool_registry"),
+                    function: 3,
+                    instruction: 12,
+                    function_name: Some(sui::types::Identifier::from_static("register")),
+                },
+                code: 7,
+            },
+            command: Some(1),
+        };
+        let simulated = SimulatedTransaction::new(effects(status), vec![], 10);
+
+        let abort = simulated.move_abort().unwrap();
+        assert_eq!(abort.module, "tool_registry");
+        assert_eq!(abort.function.as_deref(), Some("register"));
+        assert_eq!(abort.code, 7);
+        assert_eq!(abort.command_index, Some(1));
+        assert!(!simulated.is_success());
+        assert_eq!(
+            simulated.failure().unwrap(),
+            format!("{package}::tool_registry::register aborted with code 7 in command 1")
+        );
+    }
+
+    #[test]
+    fn test_non_abort_failure_is_reported() {
+        let status = sui::types::ExecutionStatus::Failure {
+            error: sui::types::ExecutionError::InsufficientGas,
+            command: None,
+        };
+        let simulated = SimulatedTransaction::new(effects(status), vec![], 10);
+
+        assert!(simulated.move_abort().is_none());
+        assert_eq!(simulated.failure().unwrap(), "InsufficientGas");
+    }
+}
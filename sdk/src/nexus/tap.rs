@@ -40,6 +40,7 @@ use {
             crawler::{Crawler, Response},
             error::NexusError,
             signer::ExecutedTransaction,
+            simulation::SimulatedTransaction,
         },
         sui,
         transactions::{
@@ -393,6 +394,16 @@ impl TapActions {
         })
     }
 
+    /// Simulate [`TapActions::create_agent`] without executing the
+    /// transaction.
+    pub async fn simulate_create_agent(&self) -> Result<SimulatedTransaction, NexusError> {
+        let address = self.client.signer.get_active_address();
+        let tx = tap_tx::create_agent_for_self_ptb(&self.client.nexus_objects, address)
+            .map_err(NexusError::TransactionBuilding)?;
+
+        self.client.simulate_transaction(tx, address).await
+    }
+
     /// Create a standard Talus agent through the configured TAP registry.
     pub async fn create_agent(&self) -> Result<CreateAgentResult, NexusError> {
         let address = self.client.signer.get_active_address();
@@ -437,6 +448,19 @@ impl TapActions {
         })
     }
 
+    /// Simulate [`TapActions::register_skill`] without executing the
+    /// transaction.
+    pub async fn simulate_register_skill(
+        &self,
+        agent_id: AgentId,
+        artifact: &TapPublishArtifact,
+    ) -> Result<SimulatedTransaction, NexusError> {
+        let address = self.client.signer.get_active_address();
+        let tx = self.register_skill_tx(agent_id, artifact).await?;
+
+        self.client.simulate_transaction(tx, address).await
+    }
+
     /// Register a skill from a publish artifact.
     pub async fn register_skill(
         &self,
@@ -444,24 +468,7 @@ impl TapActions {
         artifact: &TapPublishArtifact,
     ) -> Result<RegisterSkillResult, NexusError> {
         let address = self.client.signer.get_active_address();
-        let nexus_objects = &self.client.nexus_objects;
-        let agent_object = self
-            .client
-            .crawler()
-            .get_object_metadata(agent_id)
-            .await
-            .map_err(NexusError::Rpc)?;
-        let dag = self
-            .client
-            .crawler()
-            .get_object_metadata(artifact.dag_id)
-            .await
-            .map_err(NexusError::Rpc)?
-            .object_ref();
-        let agent =
-            agent_input_from_metadata(&agent_object).map_err(NexusError::TransactionBuilding)?;
-        let tx = tap_tx::register_skill_ptb(nexus_objects, agent, &dag, artifact)
-            .map_err(NexusError::TransactionBuilding)?;
+        let tx = self.register_skill_tx(agent_id, artifact).await?;
 
         let response = self.client.submit_transaction(tx, address).await?;
         let event = find_event(&response, |kind| match kind {
@@ -482,6 +489,31 @@ impl TapActions {
         })
     }
 
+    async fn register_skill_tx(
+        &self,
+        agent_id: AgentId,
+        artifact: &TapPublishArtifact,
+    ) -> Result<sui::types::ProgrammableTransaction, NexusError> {
+        let nexus_objects = &self.client.nexus_objects;
+        let agent_object = self
+            .client
+            .crawler()
+            .get_object_metadata(agent_id)
+            .await
+            .map_err(NexusError::Rpc)?;
+        let dag = self
+            .client
+            .crawler()
+            .get_object_metadata(artifact.dag_id)
+            .await
+            .map_err(NexusError::Rpc)?
+            .object_ref();
+        let agent =
+            agent_input_from_metadata(&agent_object).map_err(NexusError::TransactionBuilding)?;
+        tap_tx::register_skill_ptb(nexus_objects, agent, &dag, artifact)
+            .map_err(NexusError::TransactionBuilding)
+    }
+
     /// Fetch live skill requirements from the configured TAP registry.
     pub async fn get_skill_requirements(
         &self,
@@ -506,6 +538,20 @@ impl TapActions {
         })
     }
 
+    /// Simulate [`TapActions::update_skill_from_artifact`] without executing
+    /// the transaction.
+    pub async fn simulate_update_skill_from_artifact(
+        &self,
+        agent_id: AgentId,
+        skill_id: SkillId,
+        artifact: &TapPublishArtifact,
+    ) -> Result<SimulatedTransaction, NexusError> {
+        let address = self.client.signer.get_active_address();
+        let tx = self.update_skill_tx(agent_id, skill_id, artifact).await?;
+
+        self.client.simulate_transaction(tx, address).await
+    }
+
     /// Update an existing skill's current contract from a publish artifact.
     pub async fn update_skill_from_artifact(
         &self,
@@ -514,26 +560,7 @@ impl TapActions {
         artifact: &TapPublishArtifact,
     ) -> Result<UpdateSkillResult, NexusError> {
         let address = self.client.signer.get_active_address();
-        let nexus_objects = &self.client.nexus_objects;
-        let agent_object = self
-            .client
-            .crawler()
-            .get_object_metadata(agent_id)
-            .await
-            .map_err(NexusError::Rpc)?;
-        let dag = self
-            .client
-            .crawler()
-            .get_object_metadata(artifact.dag_id)
-            .await
-            .map_err(NexusError::Rpc)?
-            .object_ref();
-
-        let agent =
-            agent_input_from_metadata(&agent_object).map_err(NexusError::TransactionBuilding)?;
-        let tx =
-            tap_tx::update_skill_from_artifact_ptb(nexus_objects, agent, &dag, skill_id, artifact)
-                .map_err(NexusError::TransactionBuilding)?;
+        let tx = self.update_skill_tx(agent_id, skill_id, artifact).await?;
 
         let response = self.client.submit_transaction(tx, address).await?;
         let event = response
@@ -562,6 +589,33 @@ impl TapActions {
         })
     }
 
+    async fn update_skill_tx(
+        &self,
+        agent_id: AgentId,
+        skill_id: SkillId,
+        artifact: &TapPublishArtifact,
+    ) -> Result<sui::types::ProgrammableTransaction, NexusError> {
+        let nexus_objects = &self.client.nexus_objects;
+        let agent_object = self
+            .client
+            .crawler()
+            .get_object_metadata(agent_id)
+            .await
+            .map_err(NexusError::Rpc)?;
+        let dag = self
+            .client
+            .crawler()
+            .get_object_metadata(artifact.dag_id)
+            .await
+            .map_err(NexusError::Rpc)?
+            .object_ref();
+
+        let agent =
+            agent_input_from_metadata(&agent_object).map_err(NexusError::TransactionBuilding)?;
+        tap_tx::update_skill_from_artifact_ptb(nexus_objects, agent, &dag, skill_id, artifact)
+            .map_err(NexusError::TransactionBuilding)
+    }
+
     /// Deposit `amount` MIST into the agent's payment vault, splitting from
     /// the transaction gas coin. The vault is shared so any address can
     /// deposit; withdrawal stays gated on mutable agent custody.
@@ -896,6 +950,18 @@ impl TapActions {
         })
     }
 
+    /// Simulate [`TapActions::bind_agent_skill`] without executing the
+    /// transaction.
+    pub async fn simulate_bind_agent_skill(
+        &self,
+        params: BindAgentSkillParams,
+    ) -> Result<SimulatedTransaction, NexusError> {
+        let address = self.client.signer.get_active_address();
+        let tx = self.bind_agent_skill_tx(&params.artifact).await?;
+
+        self.client.simulate_transaction(tx, address).await
+    }
+
     /// Create a standard Talus agent and register its first skill atomically.
     pub async fn bind_agent_skill(
         &self,
@@ -905,16 +971,7 @@ impl TapActions {
 
         let address = self.client.signer.get_active_address();
         let nexus_objects = &self.client.nexus_objects;
-        let dag = self
-            .client
-            .crawler()
-            .get_object_metadata(artifact.dag_id)
-            .await
-            .map_err(NexusError::Rpc)?
-            .object_ref();
-
-        let tx = tap_tx::bind_agent_skill_ptb(nexus_objects, &dag, &artifact)
-            .map_err(NexusError::TransactionBuilding)?;
+        let tx = self.bind_agent_skill_tx(&artifact).await?;
 
         let response = self.client.submit_transaction(tx, address).await?;
 
@@ -966,6 +1023,22 @@ impl TapActions {
         })
     }
 
+    async fn bind_agent_skill_tx(
+        &self,
+        artifact: &TapPublishArtifact,
+    ) -> Result<sui::types::ProgrammableTransaction, NexusError> {
+        let dag = self
+            .client
+            .crawler()
+            .get_object_metadata(artifact.dag_id)
+            .await
+            .map_err(NexusError::Rpc)?
+            .object_ref();
+
+        tap_tx::bind_agent_skill_ptb(&self.client.nexus_objects, &dag, artifact)
+            .map_err(NexusError::TransactionBuilding)
+    }
+
     /// Poll a [`ExecutionPayment`] until it reaches a terminal state
     /// (accomplished, refunded, or a non-pending [`ExecutionPaymentFinalState`])
     /// or `timeout` elapses.
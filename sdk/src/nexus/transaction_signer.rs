@@ -0,0 +1,603 @@
+//! Pluggable signing backends for [`Signer`](crate::nexus::signer::Signer).
+//!
+//! A [`TransactionSigner`] owns a Sui address and produces the
+//! [`sui::types::UserSignature`] for transactions sent from it. The following
+//! backends are provided:
+//!
+//! - [`InMemorySigner`] holds an Ed25519, Secp256k1 or Secp256r1 private key.
+//! - [`ExternalProcessSigner`] delegates signing to an external program (for
+//!   example a KMS or HSM bridge) using a JSON-over-stdio protocol.
+//! - [`MultisigSigner`] collects partial signatures from member signers and
+//!   aggregates them into a Sui multisig.
+//!
+//! # External process protocol
+//!
+//! For every transaction the program is spawned once and receives a single
+//! JSON line on stdin:
+//!
+//! ```json
+//! {"version":1,"method":"sign_transaction","address":"0x..","tx_bytes":"<base64>","signing_digest":"<base64>"}
+//! ```
+//!
+//! `tx_bytes` is the BCS encoded transaction and `signing_digest` is the
+//! 32-byte intent digest that has to be signed. The program must reply with a
+//! single JSON line on stdout, either `{"signature":"<base64>"}` holding a
+//! serialized [`sui::types::UserSignature`] (`flag || signature || public key`)
+//! or `{"error":"<message>"}`.
+
+use {
+    crate::{
+        nexus::error::NexusError,
+        sui::{self, traits::*},
+    },
+    base64::{prelude::BASE64_STANDARD, Engine},
+    serde::{Deserialize, Serialize},
+    std::{future::Future, path::PathBuf, pin::Pin, process::Stdio, sync::Arc, time::Duration},
+    tokio::io::AsyncWriteExt,
+};
+
+/// Version of the external process signing protocol.
+pub const EXTERNAL_SIGNER_PROTOCOL_VERSION: u8 = 1;
+
+/// Default time an [`ExternalProcessSigner`] waits for a signature.
+pub const DEFAULT_EXTERNAL_SIGNER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Future returned by [`TransactionSigner::sign_transaction`].
+pub type SignatureFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<sui::types::UserSignature, NexusError>> + Send + 'a>>;
+
+/// A backend capable of signing transactions for a single Sui address.
+pub trait TransactionSigner: Send + Sync {
+    /// Address that transactions signed by this signer are sent from.
+    fn address(&self) -> sui::types::Address;
+
+    /// Produce the user signature for the given transaction.
+    fn sign_transaction<'a>(&'a self, tx: &'a sui::types::Transaction) -> SignatureFuture<'a>;
+}
+
+impl<T: TransactionSigner + ?Sized> TransactionSigner for Arc<T> {
+    fn address(&self) -> sui::types::Address {
+        (**self).address()
+    }
+
+    fn sign_transaction<'a>(&'a self, tx: &'a sui::types::Transaction) -> SignatureFuture<'a> {
+        (**self).sign_transaction(tx)
+    }
+}
+
+impl TransactionSigner for sui::crypto::Ed25519PrivateKey {
+    fn address(&self) -> sui::types::Address {
+        self.public_key().derive_address()
+    }
+
+    fn sign_transaction<'a>(&'a self, tx: &'a sui::types::Transaction) -> SignatureFuture<'a> {
+        let signature = SuiSigner::sign_transaction(self, tx)
+            .map_err(|e| NexusError::Wallet(anyhow::anyhow!(e)));
+
+        Box::pin(async move { signature })
+    }
+}
+
+// == InMemorySigner ==
+
+/// Private key held in memory, for any of the supported key schemes.
+#[derive(Clone)]
+pub enum InMemorySigner {
+    Ed25519(sui::crypto::Ed25519PrivateKey),
+    Secp256k1(sui::crypto::Secp256k1PrivateKey),
+    Secp256r1(sui::crypto::Secp256r1PrivateKey),
+}
+
+impl InMemorySigner {
+    /// Parse a base64 encoded private key as stored in a Sui keystore, that
+    /// is `flag || key` where the flag is `0x00` (Ed25519), `0x01`
+    /// (Secp256k1) or `0x02` (Secp256r1). 32 raw bytes are read as Ed25519.
+    pub fn from_base64(encoded: &str) -> Result<Self, NexusError> {
+        let bytes = BASE64_STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| NexusError::Parsing(anyhow::anyhow!("Invalid base64 private key: {e}")))?;
+
+        Self::from_bytes(&bytes)
+    }
+
+    /// Parse a private key from `flag || key` bytes or 32 raw Ed25519 bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, NexusError> {
+        let (flag, key) = match bytes.len() {
+            32 => (sui::types::SignatureScheme::Ed25519.to_u8(), bytes),
+            33 => (bytes[0], &bytes[1..]),
+            len => {
+                return Err(NexusError::Parsing(anyhow::anyhow!(
+                    "Invalid private key length: expected 32 or 33 bytes, got {len}"
+                )))
+            }
+        };
+
+        let key: [u8; 32] = key.try_into().expect("Key length checked above");
+        let invalid_key = |e| NexusError::Parsing(anyhow::anyhow!("Invalid private key: {e}"));
+
+        match sui::types::SignatureScheme::from_byte(flag) {
+            Ok(sui::types::SignatureScheme::Ed25519) => {
+                Ok(Self::Ed25519(sui::crypto::Ed25519PrivateKey::new(key)))
+            }
+            Ok(sui::types::SignatureScheme::Secp256k1) => Ok(Self::Secp256k1(
+                sui::crypto::Secp256k1PrivateKey::new(key).map_err(invalid_key)?,
+            )),
+            Ok(sui::types::SignatureScheme::Secp256r1) => Ok(Self::Secp256r1(
+                sui::crypto::Secp256r1PrivateKey::new(key).map_err(invalid_key)?,
+            )),
+            _ => Err(NexusError::Parsing(anyhow::anyhow!(
+                "Unsupported key scheme flag 0x{flag:02x}"
+            ))),
+        }
+    }
+
+    /// Key scheme of the held private key.
+    pub fn scheme(&self) -> sui::types::SignatureScheme {
+        match self {
+            Self::Ed25519(_) => sui::types::SignatureScheme::Ed25519,
+            Self::Secp256k1(_) => sui::types::SignatureScheme::Secp256k1,
+            Self::Secp256r1(_) => sui::types::SignatureScheme::Secp256r1,
+        }
+    }
+}
+
+impl From<sui::crypto::Ed25519PrivateKey> for InMemorySigner {
+    fn from(pk: sui::crypto::Ed25519PrivateKey) -> Self {
+        Self::Ed25519(pk)
+    }
+}
+
+impl From<sui::crypto::Secp256k1PrivateKey> for InMemorySigner {
+    fn from(pk: sui::crypto::Secp256k1PrivateKey) -> Self {
+        Self::Secp256k1(pk)
+    }
+}
+
+impl From<sui::crypto::Secp256r1PrivateKey> for InMemorySigner {
+    fn from(pk: sui::crypto::Secp256r1PrivateKey) -> Self {
+        Self::Secp256r1(pk)
+    }
+}
+
+impl TransactionSigner for InMemorySigner {
+    fn address(&self) -> sui::types::Address {
+        match self {
+            Self::Ed25519(pk) => pk.public_key().derive_address(),
+            Self::Secp256k1(pk) => pk.public_key().derive_address(),
+            Self::Secp256r1(pk) => pk.public_key().derive_address(),
+        }
+    }
+
+    fn sign_transaction<'a>(&'a self, tx: &'a sui::types::Transaction) -> SignatureFuture<'a> {
+        let signature = match self {
+            Self::Ed25519(pk) => SuiSigner::sign_transaction(pk, tx),
+            Self::Secp256k1(pk) => SuiSigner::sign_transaction(pk, tx),
+            Self::Secp256r1(pk) => SuiSigner::sign_transaction(pk, tx),
+        }
+        .map_err(|e| NexusError::Wallet(anyhow::anyhow!(e)));
+
+        Box::pin(async move { signature })
+    }
+}
+
+// == ExternalProcessSigner ==
+
+#[derive(Serialize)]
+struct ExternalSignRequest<'a> {
+    version: u8,
+    method: &'a str,
+    address: sui::types::Address,
+    tx_bytes: String,
+    signing_digest: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ExternalSignResponse {
+    Signature { signature: String },
+    Error { error: String },
+}
+
+/// Signs transactions by spawning an external program that speaks the
+/// JSON-over-stdio protocol described in the [module docs](self).
+#[derive(Clone, Debug)]
+pub struct ExternalProcessSigner {
+    program: PathBuf,
+    args: Vec<String>,
+    address: sui::types::Address,
+    timeout: Duration,
+}
+
+impl ExternalProcessSigner {
+    /// Create a signer that runs `program` to sign for `address`.
+    pub fn new(program: impl Into<PathBuf>, address: sui::types::Address) -> Self {
+        Self {
+            program: program.into(),
+            args: vec![],
+            address,
+            timeout: DEFAULT_EXTERNAL_SIGNER_TIMEOUT,
+        }
+    }
+
+    /// Arguments passed to the program on every invocation.
+    pub fn with_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// How long to wait for the program to reply before killing it.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    async fn request_signature(
+        &self,
+        tx: &sui::types::Transaction,
+    ) -> Result<sui::types::UserSignature, NexusError> {
+        let tx_bytes = bcs::to_bytes(tx).map_err(|e| NexusError::Parsing(e.into()))?;
+        let request = ExternalSignRequest {
+            version: EXTERNAL_SIGNER_PROTOCOL_VERSION,
+            method: "sign_transaction",
+            address: self.address,
+            tx_bytes: BASE64_STANDARD.encode(tx_bytes),
+            signing_digest: BASE64_STANDARD.encode(tx.signing_digest()),
+        };
+        let mut line = serde_json::to_vec(&request).map_err(|e| NexusError::Parsing(e.into()))?;
+        line.push(b'\n');
+
+        let program = self.program.display();
+        let mut child = tokio::process::Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                NexusError::Wallet(anyhow::anyhow!("Failed to spawn signer '{program}': {e}"))
+            })?;
+
+        let mut stdin = child
+            .stdin
+            .take()
+            .expect("Stdin is piped when spawning the signer");
+
+        // A signer that stops reading stdin must not block past the timeout
+        // either, so the request is written under the same deadline.
+        let exchange = async {
+            match stdin.write_all(&line).await {
+                Ok(()) => {}
+                // The exit status and stderr explain why the program stopped.
+                Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => {}
+                Err(e) => {
+                    return Err(NexusError::Wallet(anyhow::anyhow!(
+                        "Failed to write to signer '{program}': {e}"
+                    )))
+                }
+            }
+            // Close stdin so that the program sees EOF after the request.
+            drop(stdin);
+
+            child.wait_with_output().await.map_err(|e| {
+                NexusError::Wallet(anyhow::anyhow!(
+                    "Failed to wait for signer '{program}': {e}"
+                ))
+            })
+        };
+
+        let output = tokio::time::timeout(self.timeout, exchange)
+            .await
+            .map_err(|_| {
+                NexusError::Timeout(anyhow::anyhow!(
+                    "Signer '{program}' did not reply within {:?}",
+                    self.timeout
+                ))
+            })??;
+
+        if !output.status.success() {
+            return Err(NexusError::Wallet(anyhow::anyhow!(
+                "Signer '{program}' exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let reply = stdout.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+
+        let signature = match serde_json::from_str::<ExternalSignResponse>(reply) {
+            Ok(ExternalSignResponse::Signature { signature }) => signature,
+            Ok(ExternalSignResponse::Error { error }) => {
+                return Err(NexusError::Wallet(anyhow::anyhow!(
+                    "Signer '{program}' refused to sign: {error}"
+                )))
+            }
+            Err(e) => {
+                return Err(NexusError::Parsing(anyhow::anyhow!(
+                    "Invalid reply from signer '{program}': {e}"
+                )))
+            }
+        };
+
+        let bytes = BASE64_STANDARD.decode(signature.trim()).map_err(|e| {
+            NexusError::Parsing(anyhow::anyhow!("Invalid base64 signature from signer: {e}"))
+        })?;
+
+        sui::types::UserSignature::from_bytes(&bytes)
+            .map_err(|e| NexusError::Parsing(anyhow::anyhow!("Invalid signature from signer: {e}")))
+    }
+}
+
+impl TransactionSigner for ExternalProcessSigner {
+    fn address(&self) -> sui::types::Address {
+        self.address
+    }
+
+    fn sign_transaction<'a>(&'a self, tx: &'a sui::types::Transaction) -> SignatureFuture<'a> {
+        Box::pin(self.request_signature(tx))
+    }
+}
+
+// == MultisigSigner ==
+
+/// Signs transactions for a Sui multisig address by collecting partial
+/// signatures from its members and aggregating them.
+///
+/// Members that fail to sign are skipped as long as the remaining signatures
+/// still reach the committee threshold.
+#[derive(Clone)]
+pub struct MultisigSigner {
+    committee: sui::types::MultisigCommittee,
+    members: Vec<Arc<dyn TransactionSigner>>,
+}
+
+impl MultisigSigner {
+    /// Create a signer for the given committee without any members.
+    pub fn new(committee: sui::types::MultisigCommittee) -> Self {
+        Self {
+            committee,
+            members: vec![],
+        }
+    }
+
+    /// Add a member that signs on behalf of one of the committee keys.
+    pub fn with_member(mut self, member: impl TransactionSigner + 'static) -> Self {
+        self.members.push(Arc::new(member));
+        self
+    }
+
+    /// The multisig committee this signer aggregates for.
+    pub fn committee(&self) -> &sui::types::MultisigCommittee {
+        &self.committee
+    }
+
+    /// Aggregate signatures collected out of band, for example from offline
+    /// co-signers, together with the signatures of the configured members.
+    pub async fn sign_with_partials(
+        &self,
+        tx: &sui::types::Transaction,
+        partials: impl IntoIterator<Item = sui::types::UserSignature>,
+    ) -> Result<sui::types::UserSignature, NexusError> {
+        let mut aggregator = sui::crypto::multisig::MultisigAggregator::new_with_transaction(
+            self.committee.clone(),
+            tx,
+        );
+        let mut failures = vec![];
+
+        for partial in partials {
+            if let Err(e) = aggregator.add_signature(partial) {
+                failures.push(format!("partial signature: {e}"));
+            }
+        }
+
+        let signatures =
+            futures::future::join_all(self.members.iter().map(|m| m.sign_transaction(tx))).await;
+
+        for (member, signature) in self.members.iter().zip(signatures) {
+            let result = signature.and_then(|signature| {
+                aggregator
+                    .add_signature(signature)
+                    .map_err(|e| NexusError::Wallet(anyhow::anyhow!(e)))
+            });
+
+            if let Err(e) = result {
+                failures.push(format!("{}: {e}", member.address()));
+            }
+        }
+
+        let aggregated = aggregator.finish().map_err(|e| {
+            NexusError::Wallet(anyhow::anyhow!(
+                "Failed to aggregate multisig signature: {e}; failed signers: [{}]",
+                failures.join(", ")
+            ))
+        })?;
+
+        Ok(sui::types::UserSignature::Multisig(aggregated))
+    }
+}
+
+impl TransactionSigner for MultisigSigner {
+    fn address(&self) -> sui::types::Address {
+        self.committee.derive_address()
+    }
+
+    fn sign_transaction<'a>(&'a self, tx: &'a sui::types::Transaction) -> SignatureFuture<'a> {
+        Box::pin(self.sign_with_partials(tx, []))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::test_utils::sui_mocks, std::os::unix::fs::PermissionsExt};
+
+    fn mock_transaction(sender: sui::types::Address) -> sui::types::Transaction {
+        sui::types::Transaction {
+            kind: sui::types::TransactionKind::ProgrammableTransaction(
+                sui::types::ProgrammableTransaction {
+                    inputs: vec![],
+                    commands: vec![],
+                },
+            ),
+            sender,
+            gas_payment: sui::types::GasPayment {
+                objects: vec![sui_mocks::mock_sui_object_ref()],
+                owner: sender,
+                price: 1000,
+                budget: 1000,
+            },
+            expiration: sui::types::TransactionExpiration::None,
+        }
+    }
+
+    fn write_script(dir: &tempfile::TempDir, body: &str) -> PathBuf {
+        let path = dir.path().join("signer.sh");
+        std::fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_in_memory_signer_parses_all_schemes() {
+        let key = [7u8; 32];
+        let encode = |flag: u8| BASE64_STANDARD.encode([&[flag][..], &key].concat());
+        let ed25519_address = sui::crypto::Ed25519PrivateKey::new(key)
+            .public_key()
+            .derive_address();
+
+        let ed25519 = InMemorySigner::from_base64(&encode(0x00)).unwrap();
+        assert_eq!(ed25519.scheme(), sui::types::SignatureScheme::Ed25519);
+        assert_eq!(ed25519.address(), ed25519_address);
+
+        let raw = InMemorySigner::from_base64(&BASE64_STANDARD.encode(key)).unwrap();
+        assert_eq!(raw.scheme(), sui::types::SignatureScheme::Ed25519);
+        assert_eq!(raw.address(), ed25519_address);
+
+        let secp256k1 = InMemorySigner::from_base64(&encode(0x01)).unwrap();
+        assert_eq!(secp256k1.scheme(), sui::types::SignatureScheme::Secp256k1);
+
+        let secp256r1 = InMemorySigner::from_base64(&encode(0x02)).unwrap();
+        assert_eq!(secp256r1.scheme(), sui::types::SignatureScheme::Secp256r1);
+
+        // The same secret derives a different address under each scheme.
+        assert_ne!(secp256k1.address(), ed25519_address);
+        assert_ne!(secp256r1.address(), secp256k1.address());
+
+        let err = InMemorySigner::from_base64(&encode(0x05)).err().unwrap();
+        assert!(err.to_string().contains("Unsupported key scheme flag 0x05"));
+
+        let err = InMemorySigner::from_bytes(&[0; 16]).err().unwrap();
+        assert!(err.to_string().contains("Invalid private key length"));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_signer_signs_with_each_scheme() {
+        let mut rng = rand::thread_rng();
+        let signers: Vec<InMemorySigner> = vec![
+            sui::crypto::Ed25519PrivateKey::generate(&mut rng).into(),
+            sui::crypto::Secp256k1PrivateKey::generate(&mut rng).into(),
+            sui::crypto::Secp256r1PrivateKey::generate(&mut rng).into(),
+        ];
+
+        for signer in signers {
+            let tx = mock_transaction(signer.address());
+            let signature = signer.sign_transaction(&tx).await.unwrap();
+
+            assert_eq!(signature.scheme(), signer.scheme());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_external_process_signer_round_trip() {
+        let pk = sui::crypto::Ed25519PrivateKey::generate(rand::thread_rng());
+        let tx = mock_transaction(pk.public_key().derive_address());
+        let expected = SuiSigner::sign_transaction(&pk, &tx).unwrap();
+
+        // The script checks the request shape and replies with a precomputed
+        // signature.
+        let dir = tempfile::tempdir().unwrap();
+        let script = write_script(
+            &dir,
+            &format!(
+                "read -r line\ncase \"$line\" in *'\"method\":\"sign_transaction\"'*) ;; *) echo '{{\"error\":\"bad request\"}}'; exit 0;; esac\necho '{{\"signature\":\"{}\"}}'",
+                BASE64_STANDARD.encode(expected.to_bytes())
+            ),
+        );
+
+        let signer = ExternalProcessSigner::new(script, pk.public_key().derive_address());
+        let signature = signer.sign_transaction(&tx).await.unwrap();
+
+        assert_eq!(signature, expected);
+    }
+
+    #[tokio::test]
+    async fn test_external_process_signer_errors() {
+        let address = sui_mocks::mock_sui_address();
+        let tx = mock_transaction(address);
+        let dir = tempfile::tempdir().unwrap();
+
+        let refusing = write_script(&dir, "read -r line\necho '{\"error\":\"key locked\"}'");
+        let err = ExternalProcessSigner::new(&refusing, address)
+            .sign_transaction(&tx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("key locked"));
+
+        let failing = write_script(&dir, "echo boom >&2\nexit 3");
+        let err = ExternalProcessSigner::new(&failing, address)
+            .sign_transaction(&tx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("boom"));
+
+        let hanging = write_script(&dir, "sleep 5");
+        let err = ExternalProcessSigner::new(&hanging, address)
+            .with_timeout(Duration::from_millis(100))
+            .sign_transaction(&tx)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, NexusError::Timeout(_)));
+    }
+
+    #[tokio::test]
+    async fn test_multisig_signer_aggregates_threshold() {
+        let mut rng = rand::thread_rng();
+        let first = sui::crypto::Ed25519PrivateKey::generate(&mut rng);
+        let second = sui::crypto::Secp256k1PrivateKey::generate(&mut rng);
+        let third = sui::crypto::Secp256r1PrivateKey::generate(&mut rng);
+
+        let committee = sui::types::MultisigCommittee::new(
+            vec![
+                sui::types::MultisigMember::new(
+                    sui::types::MultisigMemberPublicKey::Ed25519(first.public_key()),
+                    1,
+                ),
+                sui::types::MultisigMember::new(
+                    sui::types::MultisigMemberPublicKey::Secp256k1(second.public_key()),
+                    1,
+                ),
+                sui::types::MultisigMember::new(
+                    sui::types::MultisigMemberPublicKey::Secp256r1(third.public_key()),
+                    1,
+                ),
+            ],
+            2,
+        );
+
+        // One member signs locally, the second signature is collected out of band.
+        let signer = MultisigSigner::new(committee.clone()).with_member(first);
+        assert_eq!(signer.address(), committee.derive_address());
+
+        let tx = mock_transaction(signer.address());
+        let partial = SuiSigner::sign_transaction(&second, &tx).unwrap();
+
+        let signature = signer.sign_with_partials(&tx, [partial]).await.unwrap();
+        assert!(matches!(signature, sui::types::UserSignature::Multisig(_)));
+
+        // A single signature does not reach the threshold.
+        let err = signer.sign_transaction(&tx).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Failed to aggregate multisig signature"));
+    }
+}
@@ -6,7 +6,10 @@
 
 #[cfg(feature = "walrus")]
 use crate::{
-    move_bindings::interface::{agent::SkillDagBinding, graph::InputPort},
+    move_bindings::{
+        interface::{agent::SkillDagBinding, graph::InputPort},
+        sui_framework::vec_map::Entry as VecMapEntry,
+    },
     types::{
         payment_source_from_address,
         quote_priority_payment_budget,
@@ -51,6 +54,7 @@ use {
             client::NexusClient,
             crawler::{Crawler, ObjectUpdateReference, TransactionUpdate},
             error::NexusError,
+            simulation::SimulatedTransaction,
             tap,
         },
         sui,
@@ -103,6 +107,15 @@ pub struct TapExecutionSubmitMetadata {
     pub payment_max_budget_mist: u64,
 }
 
+/// A default agent DAG execution transaction ready to be submitted or
+/// simulated.
+#[cfg(feature = "walrus")]
+struct DefaultAgentDagTransaction {
+    tx: sui::types::ProgrammableTransaction,
+    owned_payment_coin: Option<sui::types::Address>,
+    tap_execution: TapExecutionSubmitMetadata,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub struct ToolGasAbortCandidate {
     pub tool_fqn: crate::ToolFqn,
@@ -433,6 +446,32 @@ fn resolve_default_agent_dag_executor(
     resolve_default_tap_dag_executor(registry)
 }
 
+/// Length of a Walrus blob ID, used for the placeholder references of
+/// payloads that are not uploaded when simulating.
+#[cfg(feature = "walrus")]
+const PLACEHOLDER_REFERENCE_LEN: usize = 43;
+
+/// Replace the payloads of remote `data` with placeholder references so a
+/// transaction can be simulated without uploading them.
+#[cfg(feature = "walrus")]
+fn placeholder_references(mut data: NexusData) -> NexusData {
+    if data.is_inline() {
+        return data;
+    }
+
+    let placeholder = || vec![b'0'; PLACEHOLDER_REFERENCE_LEN];
+
+    if !data.one.is_empty() {
+        data.one = placeholder();
+    }
+
+    for reference in &mut data.many {
+        *reference = placeholder();
+    }
+
+    data
+}
+
 pub struct InspectExecutionResult {
     pub next_event: UnboundedReceiver<NexusEvent>,
     pub poller: JoinHandle<Result<(), NexusError>>,
@@ -1292,6 +1331,175 @@ pub async fn fetch_dag_outputs_bcs(
         .await
 }
 
+/// Read a published DAG object back into a [`DagSpec`].
+///
+/// Vertices, edges and outputs are sorted so that the result is stable. Edges
+/// are ordered by their source and then their target port. The
+/// default entry group is folded back into vertex entry ports as it is when
+/// parsing a DAG file without explicit entry groups.
+pub async fn fetch_dag_spec(
+    crawler: &Crawler,
+    dag_object_id: sui::types::Address,
+) -> anyhow::Result<DagSpec> {
+    let dag = crawler
+        .get_object::<dag_move::DAG>(dag_object_id)
+        .await?
+        .data;
+    let vertices = fetch_dag_vertices_bcs(crawler, &dag).await?;
+    let edges = fetch_dag_edges_bcs(crawler, &dag).await?;
+    let outputs = fetch_dag_outputs_bcs(crawler, &dag).await?;
+    let default_values = fetch_dag_default_values_bcs::<NexusData>(crawler, &dag).await?;
+
+    // Entry ports are stored per entry group.
+    let mut entry_ports = HashMap::<String, Vec<String>>::new();
+    let mut entry_groups = vec![];
+
+    for group in &dag.entry_groups.contents {
+        let mut group_vertices = vec![];
+
+        for entry in &group.value.contents {
+            let ports = entry_ports
+                .entry(entry.key.name.as_str().to_owned())
+                .or_default();
+
+            for port in &entry.value.contents {
+                let port = port.name.as_str().to_owned();
+
+                if !ports.contains(&port) {
+                    ports.push(port);
+                }
+            }
+
+            group_vertices.push(entry.key.name.as_str().to_owned());
+        }
+
+        group_vertices.sort();
+
+        entry_groups.push(crate::types::DagEntryGroup {
+            name: group.key.name.as_str().to_owned(),
+            vertices: group_vertices,
+        });
+    }
+
+    if entry_groups.len() == 1 && entry_groups[0].name == crate::types::DEFAULT_ENTRY_GROUP {
+        entry_groups.clear();
+    }
+
+    entry_groups.sort_by(|left, right| left.name.cmp(&right.name));
+
+    let mut dag_vertices = vertices
+        .into_iter()
+        .map(|(vertex, info)| {
+            let tool_fqn = info.kind.tool_fqn()?;
+            let kind = match info.kind {
+                graph_move::VertexKind::OnChain { .. } => {
+                    crate::types::DagVertexKind::OnChain { tool_fqn }
+                }
+                graph_move::VertexKind::OffChain { .. } => {
+                    crate::types::DagVertexKind::OffChain { tool_fqn }
+                }
+            };
+            let name = vertex.name.as_str().to_owned();
+            let mut ports = entry_ports.remove(&name).unwrap_or_default();
+
+            ports.sort();
+
+            Ok(crate::types::DagVertex {
+                kind,
+                entry_ports: ports
+                    .into_iter()
+                    .map(|name| crate::types::DagEntryPort { name })
+                    .collect(),
+                name,
+                post_failure_action: info.post_failure_action.as_option().cloned(),
+                verifier: Some(&info.verifier_mode)
+                    .filter(|mode| **mode != ToolVerifierMode::None)
+                    .cloned(),
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    dag_vertices.sort_by(|left, right| left.name.cmp(&right.name));
+
+    let mut dag_edges = edges
+        .into_iter()
+        .flat_map(|(vertex, edges)| {
+            edges.into_iter().map(move |edge| crate::types::DagEdge {
+                from: crate::types::DagOutput {
+                    vertex: vertex.name.as_str().to_owned(),
+                    output_variant: edge.from.variant.name.as_str().to_owned(),
+                    output_port: edge.from.port.name.as_str().to_owned(),
+                },
+                to: crate::types::DagInput {
+                    vertex: edge.to.vertex.name.as_str().to_owned(),
+                    input_port: edge.to.port.name.as_str().to_owned(),
+                },
+                kind: edge.kind,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    dag_edges.sort_by(|left, right| {
+        (
+            &left.from.vertex,
+            &left.from.output_variant,
+            &left.from.output_port,
+            &left.to.vertex,
+            &left.to.input_port,
+        )
+            .cmp(&(
+                &right.from.vertex,
+                &right.from.output_variant,
+                &right.from.output_port,
+                &right.to.vertex,
+                &right.to.input_port,
+            ))
+    });
+
+    let mut dag_outputs = outputs
+        .into_iter()
+        .flat_map(|(vertex, outputs)| {
+            outputs
+                .into_iter()
+                .map(move |output| crate::types::DagOutput {
+                    vertex: vertex.name.as_str().to_owned(),
+                    output_variant: output.variant.name.as_str().to_owned(),
+                    output_port: output.port.name.as_str().to_owned(),
+                })
+        })
+        .collect::<Vec<_>>();
+
+    dag_outputs.sort_by(|left, right| {
+        (&left.vertex, &left.output_variant, &left.output_port).cmp(&(
+            &right.vertex,
+            &right.output_variant,
+            &right.output_port,
+        ))
+    });
+
+    let mut dag_default_values = default_values
+        .into_iter()
+        .map(|(input, value)| crate::types::DagDefaultValue {
+            vertex: input.vertex.name.as_str().to_owned(),
+            input_port: input.port.name.as_str().to_owned(),
+            value,
+        })
+        .collect::<Vec<_>>();
+
+    dag_default_values.sort_by(|left, right| {
+        (&left.vertex, &left.input_port).cmp(&(&right.vertex, &right.input_port))
+    });
+
+    Ok(DagSpec {
+        vertices: dag_vertices,
+        edges: dag_edges,
+        default_values: dag_default_values,
+        post_failure_action: dag.post_failure_action.as_option().cloned(),
+        entry_groups,
+        outputs: dag_outputs,
+    })
+}
+
 pub async fn offchain_success_requires_tool_verification(
     crawler: &Crawler,
     dag_object_id: sui::types::Address,
@@ -1349,6 +1557,19 @@ pub async fn should_settle_tool_err_eval_gas(
 }
 
 impl WorkflowActions {
+    /// Simulate publishing the provided [`DagSpec`] specification without
+    /// executing the transaction.
+    pub async fn simulate_publish(
+        &self,
+        dag_spec: DagSpec,
+    ) -> Result<SimulatedTransaction, NexusError> {
+        let address = self.client.signer.get_active_address();
+        let tx = dag::publish_ptb(&self.client.nexus_objects, dag_spec)
+            .map_err(NexusError::TransactionBuilding)?;
+
+        self.client.simulate_transaction(tx, address).await
+    }
+
     /// Publish the provided [`DagSpec`] specification.
     pub async fn publish(&self, dag_spec: DagSpec) -> Result<PublishResult, NexusError> {
         let address = self.client.signer.get_active_address();
@@ -1391,6 +1612,17 @@ impl WorkflowActions {
         })
     }
 
+    /// Fetch a published DAG and convert it back into a [`DagSpec`]. See
+    /// [`fetch_dag_spec`].
+    pub async fn fetch_dag(
+        &self,
+        dag_object_id: sui::types::Address,
+    ) -> Result<DagSpec, NexusError> {
+        fetch_dag_spec(self.client.crawler(), dag_object_id)
+            .await
+            .map_err(NexusError::Rpc)
+    }
+
     /// Execute a published DAG through the configured standard default agent.
     ///
     /// The `entry_data` [`HashMap`] already holds information about the storage
@@ -1456,6 +1688,127 @@ impl WorkflowActions {
 
         // == Craft and submit the execute DAG transaction ==
 
+        let address = self.client.signer.get_active_address();
+        let nexus_objects = &self.client.nexus_objects;
+        let DefaultAgentDagTransaction {
+            tx,
+            owned_payment_coin,
+            tap_execution,
+        } = self
+            .default_agent_dag_transaction(
+                dag_object_id,
+                input_data,
+                priority_fee_percentage,
+                entry_group,
+                options,
+            )
+            .await?;
+        let response = self.client.submit_transaction(tx, address).await?;
+        if let Some(payment_coin_id) = owned_payment_coin {
+            if let Some(updated_payment_coin) = response
+                .objects
+                .iter()
+                .find(|object| object.object_id() == payment_coin_id)
+            {
+                if let Some(payment_gas_pool) = self.client.gas.coin_pool() {
+                    payment_gas_pool
+                        .release_gas_coin(sui::types::ObjectReference::new(
+                            updated_payment_coin.object_id(),
+                            updated_payment_coin.version(),
+                            updated_payment_coin.digest(),
+                        ))
+                        .await;
+                }
+            }
+        }
+
+        // == Find the created DAG execution object ID ==
+
+        let execution_tag =
+            crate::move_bindings::struct_tag::<execution_move::DAGExecution>(nexus_objects);
+        let execution_object_id = response
+            .objects
+            .into_iter()
+            .find_map(|obj| {
+                let sui::types::ObjectType::Struct(object_type) = obj.object_type() else {
+                    return None;
+                };
+
+                if nexus_objects.is_workflow_package(*object_type.address())
+                    && object_type.module() == execution_tag.module()
+                    && object_type.name() == execution_tag.name()
+                {
+                    Some(obj.object_id())
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| {
+                NexusError::Parsing(anyhow!("DAG execution object ID not found in TX response"))
+            })?;
+
+        Ok(ExecuteResult {
+            tx_digest: response.digest,
+            execution_object_id,
+            tx_checkpoint: response.checkpoint,
+            tap_execution: Some(tap_execution),
+        })
+    }
+
+    /// Simulate [`WorkflowActions::execute_default_agent_dag`] without
+    /// executing the transaction.
+    ///
+    /// Remote payloads in `entry_data` are not uploaded. The simulation uses
+    /// placeholder references of a Walrus blob ID's length in their place.
+    #[cfg(feature = "walrus")]
+    pub async fn simulate_default_agent_dag(
+        &self,
+        dag_object_id: sui::types::Address,
+        entry_data: HashMap<String, VecMap<InputPort, NexusData>>,
+        priority_fee_percentage: Option<u64>,
+        entry_group: Option<&str>,
+        options: AgentDagExecuteOptions,
+    ) -> Result<SimulatedTransaction, NexusError> {
+        let address = self.client.signer.get_active_address();
+        let input_data = entry_data
+            .into_iter()
+            .map(|(vertex, ports_data)| {
+                let contents = ports_data
+                    .contents
+                    .into_iter()
+                    .map(|entry| VecMapEntry {
+                        key: entry.key,
+                        value: placeholder_references(entry.value),
+                    })
+                    .collect();
+
+                (vertex, VecMap { contents })
+            })
+            .collect();
+        let DefaultAgentDagTransaction { tx, .. } = self
+            .default_agent_dag_transaction(
+                dag_object_id,
+                input_data,
+                priority_fee_percentage,
+                entry_group,
+                options,
+            )
+            .await?;
+
+        self.client.simulate_transaction(tx, address).await
+    }
+
+    /// Build the default agent DAG execution transaction for committed
+    /// `input_data`.
+    #[cfg(feature = "walrus")]
+    async fn default_agent_dag_transaction(
+        &self,
+        dag_object_id: sui::types::Address,
+        input_data: HashMap<String, VecMap<InputPort, NexusData>>,
+        priority_fee_percentage: Option<u64>,
+        entry_group: Option<&str>,
+        options: AgentDagExecuteOptions,
+    ) -> Result<DefaultAgentDagTransaction, NexusError> {
         let address = self.client.signer.get_active_address();
         let nexus_objects = &self.client.nexus_objects;
         let dag = self
@@ -1522,61 +1875,17 @@ impl WorkflowActions {
             &tools_gas,
         )
         .map_err(NexusError::TransactionBuilding)?;
-        let response = self.client.submit_transaction(tx, address).await?;
-        if let Some(payment_coin_id) = owned_payment_coin {
-            if let Some(updated_payment_coin) = response
-                .objects
-                .iter()
-                .find(|object| object.object_id() == payment_coin_id)
-            {
-                if let Some(payment_gas_pool) = self.client.gas.coin_pool() {
-                    payment_gas_pool
-                        .release_gas_coin(sui::types::ObjectReference::new(
-                            updated_payment_coin.object_id(),
-                            updated_payment_coin.version(),
-                            updated_payment_coin.digest(),
-                        ))
-                        .await;
-                }
-            }
-        }
-
-        // == Find the created DAG execution object ID ==
-
-        let execution_tag =
-            crate::move_bindings::struct_tag::<execution_move::DAGExecution>(nexus_objects);
-        let execution_object_id = response
-            .objects
-            .into_iter()
-            .find_map(|obj| {
-                let sui::types::ObjectType::Struct(object_type) = obj.object_type() else {
-                    return None;
-                };
 
-                if nexus_objects.is_workflow_package(*object_type.address())
-                    && object_type.module() == execution_tag.module()
-                    && object_type.name() == execution_tag.name()
-                {
-                    Some(obj.object_id())
-                } else {
-                    None
-                }
-            })
-            .ok_or_else(|| {
-                NexusError::Parsing(anyhow!("DAG execution object ID not found in TX response"))
-            })?;
-
-        Ok(ExecuteResult {
-            tx_digest: response.digest,
-            execution_object_id,
-            tx_checkpoint: response.checkpoint,
-            tap_execution: Some(TapExecutionSubmitMetadata {
+        Ok(DefaultAgentDagTransaction {
+            tx,
+            owned_payment_coin,
+            tap_execution: TapExecutionSubmitMetadata {
                 agent_id: default_executor.target.agent_id,
                 skill_id: default_executor.target.skill_id,
                 dag_id: dag.object_id,
                 skill_revision_key: default_executor.skill_revision.key,
                 payment_max_budget_mist: payment_budget.payment_max_budget_mist,
-            }),
+            },
         })
     }
 
@@ -2752,6 +3061,231 @@ mod tests {
         Crawler::new(Arc::new(Mutex::new(client)))
     }
 
+    fn dag_edge_bcs(
+        variant: &str,
+        port: &str,
+        to_vertex: &str,
+        to_port: &str,
+        kind: graph_move::EdgeKind,
+    ) -> graph_move::Edge {
+        graph_move::Edge {
+            from: graph_move::OutputVariantPort {
+                variant: output_variant(variant),
+                port: graph_move::OutputPort {
+                    name: MoveString::from(port),
+                },
+            },
+            to: graph_move::VertexInputPort {
+                vertex: graph_move::Vertex::new(to_vertex),
+                port: graph_move::InputPort {
+                    name: MoveString::from(to_port),
+                },
+            },
+            kind,
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_dag_spec_orders_edges_by_source_and_target() {
+        let tool_fqn = fqn!("xyz.dummy.tool@1");
+        let dag_ref = sui_mocks::mock_sui_object_ref();
+        let vertex_refs = ["0xf1", "0xf2", "0xf3"]
+            .map(|id| sui_mocks::object_ref_for_id(sui::types::Address::from_static(id)));
+        let edge_refs = ["0xe1", "0xe2"]
+            .map(|id| sui_mocks::object_ref_for_id(sui::types::Address::from_static(id)));
+        let output_ref = sui_mocks::object_ref_for_id(sui::types::Address::from_static("0xd1"));
+        let mut ledger_service_mock = sui_mocks::grpc::MockLedgerService::new();
+        let mut state_service_mock = sui_mocks::grpc::MockStateService::new();
+
+        sui_mocks::grpc::mock_get_object_bcs(
+            &mut ledger_service_mock,
+            dag_ref.clone(),
+            sui::types::Owner::Shared(0),
+            bcs::to_bytes(&dag_bcs(3)).expect("DAG BCS should serialize"),
+        );
+
+        // Vertices, listed out of order.
+        let vertices = ["split", "join", "notify"];
+        sui_mocks::grpc::mock_list_dynamic_fields(
+            &mut state_service_mock,
+            vertices
+                .iter()
+                .zip(&vertex_refs)
+                .map(|(name, field_ref)| (graph_move::Vertex::new(*name), *field_ref.object_id()))
+                .collect(),
+        );
+        sui_mocks::grpc::mock_get_dynamic_table_values_bcs(
+            &mut ledger_service_mock,
+            vertices
+                .iter()
+                .zip(&vertex_refs)
+                .map(|(name, field_ref)| {
+                    (
+                        field_ref.clone(),
+                        sui::types::Owner::Shared(0),
+                        graph_move::Vertex::new(*name),
+                        offchain_vertex_node_bcs(&tool_fqn),
+                    )
+                })
+                .collect(),
+        );
+
+        // Edges share their source port and only differ in their target.
+        let edges = vec![
+            (
+                graph_move::Vertex::new("split"),
+                vec![
+                    dag_edge_bcs(
+                        "ok",
+                        "items",
+                        "notify",
+                        "text",
+                        graph_move::EdgeKind::Normal,
+                    ),
+                    dag_edge_bcs(
+                        "ok",
+                        "items",
+                        "join",
+                        "right",
+                        graph_move::EdgeKind::ForEach,
+                    ),
+                    dag_edge_bcs("ok", "items", "join", "left", graph_move::EdgeKind::Normal),
+                ],
+            ),
+            (
+                graph_move::Vertex::new("join"),
+                vec![dag_edge_bcs(
+                    "ok",
+                    "joined",
+                    "notify",
+                    "extra",
+                    graph_move::EdgeKind::Normal,
+                )],
+            ),
+        ];
+        sui_mocks::grpc::mock_list_dynamic_fields(
+            &mut state_service_mock,
+            edges
+                .iter()
+                .zip(&edge_refs)
+                .map(|((vertex, _), field_ref)| (vertex.clone(), *field_ref.object_id()))
+                .collect(),
+        );
+        sui_mocks::grpc::mock_get_dynamic_table_values_bcs(
+            &mut ledger_service_mock,
+            edges
+                .into_iter()
+                .zip(&edge_refs)
+                .map(|((vertex, edges), field_ref)| {
+                    (
+                        field_ref.clone(),
+                        sui::types::Owner::Shared(0),
+                        vertex,
+                        edges,
+                    )
+                })
+                .collect(),
+        );
+
+        // Outputs.
+        sui_mocks::grpc::mock_list_dynamic_fields(
+            &mut state_service_mock,
+            vec![(graph_move::Vertex::new("notify"), *output_ref.object_id())],
+        );
+        sui_mocks::grpc::mock_get_dynamic_table_values_bcs(
+            &mut ledger_service_mock,
+            vec![(
+                output_ref,
+                sui::types::Owner::Shared(0),
+                graph_move::Vertex::new("notify"),
+                vec![graph_move::OutputVariantPort {
+                    variant: output_variant("ok"),
+                    port: graph_move::OutputPort {
+                        name: MoveString::from("sent"),
+                    },
+                }],
+            )],
+        );
+
+        // Default values.
+        sui_mocks::grpc::mock_list_dynamic_fields::<graph_move::VertexInputPort>(
+            &mut state_service_mock,
+            vec![],
+        );
+        sui_mocks::grpc::mock_get_dynamic_table_values_bcs::<graph_move::VertexInputPort, NexusData>(
+            &mut ledger_service_mock,
+            vec![],
+        );
+
+        let crawler = crawler_from_mocks(ledger_service_mock, state_service_mock).await;
+
+        let spec = fetch_dag_spec(&crawler, *dag_ref.object_id())
+            .await
+            .expect("DAG spec should be fetched");
+
+        assert_eq!(
+            spec.vertices
+                .iter()
+                .map(|vertex| vertex.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["join", "notify", "split"]
+        );
+        assert_eq!(
+            spec.edges
+                .iter()
+                .map(|edge| {
+                    (
+                        edge.from.vertex.as_str(),
+                        edge.from.output_port.as_str(),
+                        edge.to.vertex.as_str(),
+                        edge.to.input_port.as_str(),
+                        &edge.kind,
+                    )
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "join",
+                    "joined",
+                    "notify",
+                    "extra",
+                    &graph_move::EdgeKind::Normal
+                ),
+                (
+                    "split",
+                    "items",
+                    "join",
+                    "left",
+                    &graph_move::EdgeKind::Normal
+                ),
+                (
+                    "split",
+                    "items",
+                    "join",
+                    "right",
+                    &graph_move::EdgeKind::ForEach
+                ),
+                (
+                    "split",
+                    "items",
+                    "notify",
+                    "text",
+                    &graph_move::EdgeKind::Normal
+                ),
+            ]
+        );
+        assert_eq!(
+            spec.outputs,
+            vec![crate::types::DagOutput {
+                vertex: "notify".to_string(),
+                output_variant: "ok".to_string(),
+                output_port: "sent".to_string(),
+            }]
+        );
+        assert!(spec.default_values.is_empty());
+        assert!(spec.entry_groups.is_empty());
+    }
+
     #[tokio::test]
     async fn fetch_committed_tool_result_for_walk_returns_none_when_absent() {
         let execution_id = sui::types::Address::from_static("0xe1");
@@ -3288,6 +3822,60 @@ mod tests {
         assert_eq!(result.tx_checkpoint, 1);
     }
 
+    #[tokio::test]
+    async fn test_workflow_actions_simulate_publish() {
+        let nexus_objects = sui_mocks::mock_nexus_objects();
+
+        let mut ledger_service_mock = sui_mocks::grpc::MockLedgerService::new();
+        let mut tx_service_mock = sui_mocks::grpc::MockTransactionExecutionService::new();
+
+        sui_mocks::grpc::mock_reference_gas_price(&mut ledger_service_mock, 1000);
+        sui_mocks::grpc::mock_simulate_transaction(
+            &mut tx_service_mock,
+            sui::types::ExecutionStatus::Success,
+            sui::types::GasCostSummary {
+                computation_cost: 1_000,
+                storage_cost: 2_000,
+                storage_rebate: 0,
+                non_refundable_storage_fee: 0,
+            },
+        );
+        tx_service_mock.expect_execute_transaction().never();
+
+        let rpc_url = sui_mocks::grpc::mock_server(sui_mocks::grpc::ServerMocks {
+            ledger_service_mock: Some(ledger_service_mock),
+            execution_service_mock: Some(tx_service_mock),
+            ..Default::default()
+        });
+
+        let client = nexus_mocks::mock_nexus_client(&nexus_objects, &rpc_url).await;
+
+        let simulated = client
+            .workflow()
+            .simulate_publish(DagSpec::default())
+            .await
+            .expect("Failed to simulate DAG publish");
+
+        assert!(simulated.is_success());
+        assert_eq!(simulated.gas.required_budget(), 3_000);
+    }
+
+    #[cfg(feature = "walrus")]
+    #[test]
+    fn placeholder_references_replace_only_remote_payloads() {
+        let inline = inline_bytes(b"data");
+        assert_eq!(placeholder_references(inline.clone()), inline);
+
+        let one = placeholder_references(NexusData::walrus_one(b"payload".to_vec()));
+        assert!(one.is_walrus());
+        assert_eq!(one.one, vec![b'0'; PLACEHOLDER_REFERENCE_LEN]);
+        assert!(one.many.is_empty());
+
+        let many = placeholder_references(NexusData::walrus_many([b"a".to_vec(), b"b".to_vec()]));
+        assert!(many.one.is_empty());
+        assert_eq!(many.many, vec![vec![b'0'; PLACEHOLDER_REFERENCE_LEN]; 2]);
+    }
+
     #[cfg(feature = "walrus")]
     #[tokio::test]
     async fn test_workflow_actions_execute() {
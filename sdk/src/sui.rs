@@ -8,7 +8,12 @@ pub mod types {
 }
 
 pub mod crypto {
-    pub use sui_crypto::{ed25519::Ed25519PrivateKey, *};
+    pub use sui_crypto::{
+        ed25519::Ed25519PrivateKey,
+        secp256k1::Secp256k1PrivateKey,
+        secp256r1::Secp256r1PrivateKey,
+        *,
+    };
 }
 
 pub mod grpc {
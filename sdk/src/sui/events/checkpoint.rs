@@ -0,0 +1,422 @@
+use {
+    super::ingestor::{EventIngestionError, EventPage, EventPageReceiver},
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeSet, future::Future, path::PathBuf},
+    thiserror::Error,
+};
+
+/// Failure produced by a [`CheckpointStore`].
+#[derive(Debug, Error)]
+pub enum CheckpointStoreError {
+    /// Reading or writing the stored progress failed.
+    #[error("Checkpoint store I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    /// The stored progress could not be encoded or decoded.
+    #[error("Checkpoint progress encoding is invalid: {0}")]
+    Encoding(#[from] serde_json::Error),
+    /// The storage backend failed.
+    #[error("Checkpoint store backend failed: {0}")]
+    Backend(#[source] anyhow::Error),
+}
+
+/// Identity of one Sui event.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct EventId {
+    /// Checkpoint containing the event.
+    pub checkpoint: u64,
+    /// Transaction that emitted the event.
+    pub transaction_digest: String,
+    /// Position of the event in the transaction.
+    pub event_index: u32,
+}
+
+/// Progress acknowledged by the consumer of an event stream.
+///
+/// Pages are acknowledged in order, so every event before `checkpoint` has
+/// been processed. A checkpoint can span several pages, so the events already
+/// acknowledged at `checkpoint` are kept to skip them after a restart.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointProgress {
+    /// Highest acknowledged checkpoint.
+    pub checkpoint: u64,
+    /// Events acknowledged at `checkpoint`.
+    pub events: BTreeSet<EventId>,
+}
+
+impl CheckpointProgress {
+    /// Whether the event was acknowledged before.
+    pub fn contains(&self, id: &EventId) -> bool {
+        id.checkpoint < self.checkpoint
+            || (id.checkpoint == self.checkpoint && self.events.contains(id))
+    }
+
+    /// Record `page` as acknowledged.
+    pub fn advance<T>(&mut self, page: &EventPage<T>) {
+        self.advance_to(page.checkpoint);
+
+        for id in &page.event_ids {
+            self.advance_to(id.checkpoint);
+
+            if id.checkpoint == self.checkpoint {
+                self.events.insert(id.clone());
+            }
+        }
+    }
+
+    fn advance_to(&mut self, checkpoint: u64) {
+        if checkpoint > self.checkpoint {
+            self.checkpoint = checkpoint;
+            self.events.clear();
+        }
+    }
+}
+
+/// Persists [`CheckpointProgress`] so that ingestion resumes where the
+/// consumer left off after a restart.
+pub trait CheckpointStore: Send + Sync + 'static {
+    /// Loads the stored progress, if any.
+    fn load(
+        &self,
+    ) -> impl Future<Output = Result<Option<CheckpointProgress>, CheckpointStoreError>> + Send;
+
+    /// Replaces the stored progress.
+    fn save(
+        &self,
+        progress: &CheckpointProgress,
+    ) -> impl Future<Output = Result<(), CheckpointStoreError>> + Send;
+}
+
+/// Stores progress as JSON in a single file.
+///
+/// The file is replaced atomically so that a crash while saving keeps the
+/// previous progress.
+#[derive(Clone, Debug)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    /// Creates a store backed by the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self) -> Result<Option<CheckpointProgress>, CheckpointStoreError> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn save(&self, progress: &CheckpointProgress) -> Result<(), CheckpointStoreError> {
+        let bytes = serde_json::to_vec(progress)?;
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        tokio::fs::write(&tmp, bytes).await?;
+        tokio::fs::rename(&tmp, &self.path).await?;
+
+        Ok(())
+    }
+}
+
+/// Stores progress in a SQLite database, one row per named stream.
+#[cfg(feature = "events_sqlite")]
+#[derive(Clone)]
+pub struct SqliteCheckpointStore {
+    connection: std::sync::Arc<std::sync::Mutex<rusqlite::Connection>>,
+    stream: String,
+}
+
+#[cfg(feature = "events_sqlite")]
+impl SqliteCheckpointStore {
+    /// Opens or creates the database at `path` and stores progress for the
+    /// given `stream` name, so that several ingestors can share a database.
+    pub fn open(
+        path: impl AsRef<std::path::Path>,
+        stream: impl Into<String>,
+    ) -> Result<Self, CheckpointStoreError> {
+        let connection = rusqlite::Connection::open(path)
+            .map_err(|error| CheckpointStoreError::Backend(error.into()))?;
+
+        connection
+            .execute(
+                "CREATE TABLE IF NOT EXISTS event_checkpoints (
+                    stream TEXT PRIMARY KEY,
+                    checkpoint INTEGER NOT NULL,
+                    events TEXT NOT NULL
+                )",
+                (),
+            )
+            .map_err(|error| CheckpointStoreError::Backend(error.into()))?;
+
+        Ok(Self {
+            connection: std::sync::Arc::new(std::sync::Mutex::new(connection)),
+            stream: stream.into(),
+        })
+    }
+
+    async fn with_connection<R, F>(&self, f: F) -> Result<R, CheckpointStoreError>
+    where
+        R: Send + 'static,
+        F: FnOnce(&rusqlite::Connection, &str) -> Result<R, CheckpointStoreError> + Send + 'static,
+    {
+        let connection = self.connection.clone();
+        let stream = self.stream.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let connection = connection.lock().map_err(|_| {
+                CheckpointStoreError::Backend(anyhow::anyhow!("SQLite connection lock poisoned"))
+            })?;
+
+            f(&connection, &stream)
+        })
+        .await
+        .map_err(|error| CheckpointStoreError::Backend(error.into()))?
+    }
+}
+
+#[cfg(feature = "events_sqlite")]
+impl CheckpointStore for SqliteCheckpointStore {
+    async fn load(&self) -> Result<Option<CheckpointProgress>, CheckpointStoreError> {
+        use rusqlite::OptionalExtension as _;
+
+        self.with_connection(|connection, stream| {
+            let row = connection
+                .query_row(
+                    "SELECT checkpoint, events FROM event_checkpoints WHERE stream = ?1",
+                    [stream],
+                    |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+                )
+                .optional()
+                .map_err(|error| CheckpointStoreError::Backend(error.into()))?;
+
+            let Some((checkpoint, events)) = row else {
+                return Ok(None);
+            };
+
+            Ok(Some(CheckpointProgress {
+                checkpoint: u64::try_from(checkpoint)
+                    .map_err(|error| CheckpointStoreError::Backend(error.into()))?,
+                events: serde_json::from_str(&events)?,
+            }))
+        })
+        .await
+    }
+
+    async fn save(&self, progress: &CheckpointProgress) -> Result<(), CheckpointStoreError> {
+        let checkpoint = i64::try_from(progress.checkpoint)
+            .map_err(|error| CheckpointStoreError::Backend(error.into()))?;
+        let events = serde_json::to_string(&progress.events)?;
+
+        self.with_connection(move |connection, stream| {
+            connection
+                .execute(
+                    "INSERT INTO event_checkpoints (stream, checkpoint, events)
+                     VALUES (?1, ?2, ?3)
+                     ON CONFLICT (stream) DO UPDATE
+                     SET checkpoint = excluded.checkpoint, events = excluded.events",
+                    rusqlite::params![stream, checkpoint, events],
+                )
+                .map_err(|error| CheckpointStoreError::Backend(error.into()))?;
+
+            Ok(())
+        })
+        .await
+    }
+}
+
+/// Receives pages from an ingestor resumed with
+/// [`super::EventIngestor::resume`].
+///
+/// Events acknowledged before are skipped, so each event is delivered at
+/// least once and, as long as the consumer acknowledges a page only after
+/// processing it, never processed twice.
+pub struct CheckpointedEventReceiver<T, S> {
+    pub(super) pages: EventPageReceiver<T>,
+    pub(super) store: S,
+    pub(super) progress: CheckpointProgress,
+}
+
+impl<T, S: CheckpointStore> CheckpointedEventReceiver<T, S> {
+    /// Receives the next page that contains unacknowledged events or new
+    /// progress.
+    pub async fn recv(&mut self) -> Option<Result<EventPage<T>, EventIngestionError>> {
+        loop {
+            let page = match self.pages.recv().await? {
+                Ok(page) => page,
+                Err(error) => return Some(Err(error)),
+            };
+
+            let had_events = !page.events.is_empty();
+            let (events, event_ids): (Vec<_>, Vec<_>) = page
+                .events
+                .into_iter()
+                .zip(page.event_ids)
+                .filter(|(_, id)| !self.progress.contains(id))
+                .unzip();
+
+            // Every event of this page was acknowledged before the restart.
+            if had_events && events.is_empty() && page.checkpoint <= self.progress.checkpoint {
+                continue;
+            }
+
+            return Some(Ok(EventPage {
+                events,
+                event_ids,
+                checkpoint: page.checkpoint,
+            }));
+        }
+    }
+
+    /// Commits `page` to the store. Pages must be acknowledged in the order
+    /// they were received.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventIngestionError::Checkpoint`] when the store fails. The
+    /// page is then delivered again after a restart.
+    pub async fn ack(&mut self, page: &EventPage<T>) -> Result<(), EventIngestionError> {
+        let mut progress = self.progress.clone();
+        progress.advance(page);
+
+        self.store.save(&progress).await?;
+        self.progress = progress;
+
+        Ok(())
+    }
+
+    /// Progress acknowledged so far.
+    pub fn progress(&self) -> &CheckpointProgress {
+        &self.progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(checkpoint: u64, event_index: u32) -> EventId {
+        EventId {
+            checkpoint,
+            transaction_digest: "digest".to_owned(),
+            event_index,
+        }
+    }
+
+    fn page(checkpoint: u64, ids: Vec<EventId>) -> EventPage<u32> {
+        EventPage {
+            events: ids.iter().map(|id| id.event_index).collect(),
+            event_ids: ids,
+            checkpoint,
+        }
+    }
+
+    #[test]
+    fn progress_keeps_events_of_the_latest_checkpoint() {
+        let mut progress = CheckpointProgress::default();
+
+        progress.advance(&page(5, vec![id(5, 0)]));
+        progress.advance(&page(5, vec![id(5, 1)]));
+
+        assert_eq!(progress.checkpoint, 5);
+        assert!(progress.contains(&id(5, 0)));
+        assert!(progress.contains(&id(4, 9)));
+        assert!(!progress.contains(&id(5, 2)));
+
+        progress.advance(&page(6, vec![]));
+
+        assert_eq!(progress.checkpoint, 6);
+        assert!(progress.events.is_empty());
+        assert!(progress.contains(&id(5, 2)));
+    }
+
+    #[tokio::test]
+    async fn file_store_round_trips_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileCheckpointStore::new(dir.path().join("nested").join("progress.json"));
+
+        assert_eq!(store.load().await.unwrap(), None);
+
+        let mut progress = CheckpointProgress::default();
+        progress.advance(&page(7, vec![id(7, 3)]));
+        store.save(&progress).await.unwrap();
+
+        assert_eq!(store.load().await.unwrap(), Some(progress));
+    }
+
+    #[cfg(feature = "events_sqlite")]
+    #[tokio::test]
+    async fn sqlite_store_round_trips_and_resumes_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("progress.sqlite");
+        let store = SqliteCheckpointStore::open(&path, "nexus").unwrap();
+
+        assert_eq!(store.load().await.unwrap(), None);
+
+        let mut progress = CheckpointProgress::default();
+        progress.advance(&page(7, vec![id(7, 3)]));
+        store.save(&progress).await.unwrap();
+
+        assert_eq!(store.load().await.unwrap(), Some(progress.clone()));
+
+        // Saving again replaces the row of the stream.
+        progress.advance(&page(8, vec![id(8, 0), id(8, 1)]));
+        store.save(&progress).await.unwrap();
+        drop(store);
+
+        // A reopened database resumes from the last saved progress and keeps
+        // streams apart.
+        let resumed = SqliteCheckpointStore::open(&path, "nexus").unwrap();
+        assert_eq!(resumed.load().await.unwrap(), Some(progress));
+
+        let other = SqliteCheckpointStore::open(&path, "other").unwrap();
+        assert_eq!(other.load().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn receiver_skips_acknowledged_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileCheckpointStore::new(dir.path().join("progress.json"));
+        let (send_page, pages) = tokio::sync::mpsc::channel(8);
+
+        let mut progress = CheckpointProgress::default();
+        progress.advance(&page(5, vec![id(5, 0)]));
+
+        let mut receiver = CheckpointedEventReceiver {
+            pages,
+            store: store.clone(),
+            progress,
+        };
+
+        for page in [
+            page(5, vec![id(5, 0)]),
+            page(5, vec![id(5, 1)]),
+            page(6, vec![id(6, 0)]),
+        ] {
+            send_page.send(Ok(page)).await.unwrap();
+        }
+        drop(send_page);
+
+        let first = receiver.recv().await.unwrap().unwrap();
+        assert_eq!(first.event_ids, [id(5, 1)]);
+        receiver.ack(&first).await.unwrap();
+
+        let second = receiver.recv().await.unwrap().unwrap();
+        assert_eq!(second.event_ids, [id(6, 0)]);
+        receiver.ack(&second).await.unwrap();
+
+        assert!(receiver.recv().await.is_none());
+
+        let stored = store.load().await.unwrap().unwrap();
+        assert_eq!(stored.checkpoint, 6);
+        assert!(stored.contains(&id(6, 0)));
+    }
+}
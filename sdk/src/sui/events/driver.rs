@@ -1,5 +1,6 @@
 use {
     super::{
+        checkpoint::EventId,
         ingestor::{EventIngestionError, EventIngestor, EventPage},
         metrics::*,
         query::EventQuery,
@@ -262,6 +263,7 @@ impl<Q: EventQuery> EventIngestor<Q> {
                     .send_page(
                         EventPage {
                             events: Vec::new(),
+                            event_ids: Vec::new(),
                             checkpoint,
                         },
                         send_page,
@@ -300,18 +302,33 @@ impl<Q: EventQuery> EventIngestor<Q> {
         EVENT_PARSE_DURATION.observe(decode_started.elapsed().as_secs_f64());
         let event = decoded.map_err(|source| EventIngestionError::Decode {
             checkpoint,
-            transaction_digest,
+            transaction_digest: transaction_digest.clone(),
             event_index,
             source: anyhow::Error::new(source),
         })?;
         let events = event.into_iter().collect::<Vec<_>>();
+        let event_ids = events
+            .iter()
+            .map(|_| EventId {
+                checkpoint,
+                transaction_digest: transaction_digest.clone(),
+                event_index,
+            })
+            .collect();
         EVENTS_PER_PAGE.observe(events.len() as f64);
         Self::advance_checkpoint(resume_checkpoint, checkpoint);
         let should_send = !events.is_empty()
             || highest_output_checkpoint.is_none_or(|current| checkpoint > current);
         if should_send {
             if !self
-                .send_page(EventPage { events, checkpoint }, send_page)
+                .send_page(
+                    EventPage {
+                        events,
+                        event_ids,
+                        checkpoint,
+                    },
+                    send_page,
+                )
                 .await
             {
                 return Ok(false);
@@ -1,5 +1,8 @@
 use {
-    super::query::EventQuery,
+    super::{
+        checkpoint::{CheckpointStore, CheckpointStoreError, CheckpointedEventReceiver, EventId},
+        query::EventQuery,
+    },
     crate::sui,
     sui_rpc::field::FieldMaskUtil as _,
     thiserror::Error,
@@ -44,6 +47,9 @@ pub enum EventIngestionError {
         #[source]
         source: anyhow::Error,
     },
+    /// Loading or committing progress failed.
+    #[error("Event checkpoint store failed: {0}")]
+    Checkpoint(#[from] CheckpointStoreError),
 }
 
 impl EventIngestionError {
@@ -74,6 +80,8 @@ impl EventIngestionError {
 pub struct EventPage<T> {
     /// Events emitted for this page.
     pub events: Vec<T>,
+    /// Identity of each event in `events`, in the same order.
+    pub event_ids: Vec<EventId>,
     /// Checkpoint observed for this page.
     pub checkpoint: u64,
 }
@@ -154,6 +162,35 @@ impl<Q: EventQuery> EventIngestor<Q> {
         Ok(next_page)
     }
 
+    /// Starts ingestion from the progress saved in `store`, or from
+    /// `from_checkpoint` if nothing was saved yet.
+    ///
+    /// Events acknowledged through the returned receiver are not delivered
+    /// again after a restart.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EventIngestionError::Checkpoint`] when the progress cannot
+    /// be loaded and the errors of [`Self::start`].
+    pub async fn resume<S: CheckpointStore>(
+        self,
+        store: S,
+        from_checkpoint: Option<u64>,
+    ) -> Result<CheckpointedEventReceiver<Q::Output, S>, EventIngestionError> {
+        let progress = store.load().await?;
+        let start = progress
+            .as_ref()
+            .map(|progress| progress.checkpoint)
+            .or(from_checkpoint);
+        let pages = self.start(start)?;
+
+        Ok(CheckpointedEventReceiver {
+            pages,
+            store,
+            progress: progress.unwrap_or_default(),
+        })
+    }
+
     fn effective_read_mask(mut read_mask: sui::grpc::FieldMask) -> sui::grpc::FieldMask {
         read_mask
             .paths
@@ -1,11 +1,22 @@
 //! Generic queries and ingestion for Sui events.
 
+mod checkpoint;
 mod driver;
 mod ingestor;
 mod metrics;
 mod query;
 
+#[cfg(feature = "events_sqlite")]
+pub use checkpoint::SqliteCheckpointStore;
 pub use {
+    checkpoint::{
+        CheckpointProgress,
+        CheckpointStore,
+        CheckpointStoreError,
+        CheckpointedEventReceiver,
+        EventId,
+        FileCheckpointStore,
+    },
     ingestor::{EventIngestionError, EventIngestor, EventPage, EventPageReceiver},
     query::{EventQuery, RawEventQuery},
 };
@@ -420,6 +420,104 @@ async fn reconnect_replays_from_the_last_inclusive_checkpoint() {
     assert_eq!(subscription_calls.load(Ordering::SeqCst), 2);
 }
 
+#[tokio::test]
+async fn resume_replays_from_stored_progress_and_skips_acknowledged_events() {
+    let mut acknowledged = sui::grpc::Event::default();
+    acknowledged.set_checkpoint(20);
+    acknowledged.set_transaction_digest(sui::types::Digest::ZERO);
+    acknowledged.set_event_index(0);
+
+    let mut pending = acknowledged.clone();
+    pending.set_event_index(1);
+    let expected_event = pending.clone();
+
+    let mut subscription = sui_mocks::grpc::MockSubscriptionService::new();
+    subscription
+        .expect_subscribe_events()
+        .once()
+        .returning(move |_request| {
+            let first = subscription_frame(None, watermark(b"live", None));
+            let stream = futures::stream::iter([Ok(first)]).chain(futures::stream::pending());
+            Ok(tonic::Response::new(
+                Box::pin(stream) as sui_mocks::grpc::BoxEventStream
+            ))
+        });
+
+    let mut ledger = sui_mocks::grpc::MockLedgerService::new();
+    ledger
+        .expect_list_events()
+        .once()
+        .returning(move |request| {
+            assert_eq!(request.into_inner().start_checkpoint, Some(20));
+
+            let mut first = sui::grpc::ListEventsResponse::default();
+            first.set_event(acknowledged.clone());
+            first.set_watermark(watermark(b"first", Some(20)));
+
+            let frames = [
+                Ok(first),
+                Ok(list_frame(
+                    Some(pending.clone()),
+                    watermark(b"live", Some(20)),
+                    sui::grpc::QueryEndReason::CursorBound,
+                )),
+            ];
+            Ok(tonic::Response::new(
+                Box::pin(futures::stream::iter(frames)) as sui_mocks::grpc::BoxListEventsStream,
+            ))
+        });
+
+    let rpc_url = sui_mocks::grpc::mock_server(sui_mocks::grpc::ServerMocks {
+        ledger_service_mock: Some(ledger),
+        subscription_service_mock: Some(subscription),
+        ..Default::default()
+    });
+
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileCheckpointStore::new(dir.path().join("progress.json"));
+    store
+        .save(&CheckpointProgress {
+            checkpoint: 20,
+            events: [EventId {
+                checkpoint: 20,
+                transaction_digest: sui::types::Digest::ZERO.to_string(),
+                event_index: 0,
+            }]
+            .into(),
+        })
+        .await
+        .unwrap();
+
+    let query = RawEventQuery::new(
+        sui::grpc::EventFilter::default(),
+        sui::grpc::FieldMask::default(),
+    );
+    let mut pages = EventIngestor::new(&rpc_url, query)
+        .resume(store.clone(), Some(1))
+        .await
+        .expect("ingestor should resume");
+
+    let page = timeout(Duration::from_secs(2), async {
+        loop {
+            let page = pages.recv().await.unwrap().unwrap();
+            if !page.events.is_empty() {
+                return page;
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(page.events, [expected_event]);
+    assert_eq!(page.event_ids[0].event_index, 1);
+
+    pages.ack(&page).await.unwrap();
+
+    let stored = store.load().await.unwrap().unwrap();
+    assert_eq!(stored.checkpoint, 20);
+    assert_eq!(stored.events.len(), 2);
+}
+
 struct FailingQuery {
     decode_calls: Arc<AtomicUsize>,
 }
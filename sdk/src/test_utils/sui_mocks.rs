@@ -526,6 +526,48 @@ pub mod grpc {
         }
     }
 
+    /// Configures the simulate endpoint to report `status` and `gas_used` for
+    /// every simulated transaction.
+    pub fn mock_simulate_transaction(
+        tx_service: &mut MockTransactionExecutionService,
+        status: sui::types::ExecutionStatus,
+        gas_used: sui::types::GasCostSummary,
+    ) {
+        tx_service
+            .expect_simulate_transaction()
+            .returning(move |_request| {
+                let mut response = sui::grpc::SimulateTransactionResponse::default();
+                let mut tx = sui::grpc::ExecutedTransaction::default();
+
+                let mut effects = sui::grpc::TransactionEffects::default();
+                let effect = sui::types::TransactionEffectsV2 {
+                    status: status.clone(),
+                    epoch: 1,
+                    gas_used: gas_used.clone(),
+                    transaction_digest: sui::types::Digest::generate(rand::thread_rng()),
+                    gas_object_index: None,
+                    events_digest: None,
+                    dependencies: vec![],
+                    lamport_version: 1,
+                    changed_objects: vec![],
+                    unchanged_consensus_objects: vec![],
+                    auxiliary_data_digest: None,
+                };
+                effects.set_bcs(
+                    bcs::to_bytes(&sui::types::TransactionEffects::V2(Box::new(effect))).unwrap(),
+                );
+                tx.set_effects(effects);
+
+                let mut tx_events = sui::grpc::TransactionEvents::default();
+                tx_events.set_events(vec![]);
+                tx.set_events(tx_events);
+
+                response.set_transaction(tx);
+
+                Ok(tonic::Response::new(response))
+            });
+    }
+
     pub fn mock_reference_gas_price(
         ledger_service: &mut MockLedgerService,
         reference_gas_price: u64,
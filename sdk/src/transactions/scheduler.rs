@@ -680,26 +680,47 @@ pub(crate) fn add_occurrence_absolute_for_task_for_self_ptb(
     start_time_ms: u64,
     deadline_offset_ms: Option<u64>,
     priority_fee_percentage: Option<u64>,
+) -> anyhow::Result<ProgrammableTransaction> {
+    add_occurrences_absolute_for_task_for_self_ptb(
+        objects,
+        task,
+        &[start_time_ms],
+        deadline_offset_ms,
+        priority_fee_percentage,
+    )
+}
+
+/// Build a PTB that enqueues one occurrence per absolute start time, in order.
+/// The task, leader registry and clock inputs are shared by every call.
+pub(crate) fn add_occurrences_absolute_for_task_for_self_ptb(
+    objects: &NexusObjects,
+    task: &sui::types::ObjectReference,
+    start_times_ms: &[u64],
+    deadline_offset_ms: Option<u64>,
+    priority_fee_percentage: Option<u64>,
 ) -> anyhow::Result<ProgrammableTransaction> {
     move_boundary::ptb(objects, |tx| {
         let task = shared_task_arg(tx, task)?;
-        let start_time_ms = tx.arg(&start_time_ms)?;
         let deadline_offset_ms = tx.arg(&MoveOption::from_option(deadline_offset_ms))?;
         let priority_fee_percentage =
             tx.arg(&move_priority_fee_percentage(priority_fee_percentage)?)?;
         let leader_registry = tx.shared_object(&objects.leader_registry, false)?;
         let clock = tx.clock()?;
-        tx.call_target(
-            scheduler_binding::add_occurrence_absolute_for_task_target,
-            vec![
-                task,
-                start_time_ms,
-                deadline_offset_ms,
-                priority_fee_percentage,
-                leader_registry,
-                clock,
-            ],
-        )?;
+
+        for start_time_ms in start_times_ms {
+            let start_time_ms = tx.arg(start_time_ms)?;
+            tx.call_target(
+                scheduler_binding::add_occurrence_absolute_for_task_target,
+                vec![
+                    task,
+                    start_time_ms,
+                    deadline_offset_ms,
+                    priority_fee_percentage,
+                    leader_registry,
+                    clock,
+                ],
+            )?;
+        }
         Ok(())
     })
 }
@@ -1005,6 +1026,37 @@ mod tests {
         assert_priority_option(&ptb, &call.arguments[3], None);
     }
 
+    #[test]
+    fn queue_occurrences_share_one_ptb_in_start_order() {
+        let objects = mock_nexus_objects();
+        let ptb = add_occurrences_absolute_for_task_for_self_ptb(
+            &objects,
+            &mock_sui_object_ref(),
+            &[1_000, 2_000, 3_000],
+            Some(500),
+            None,
+        )
+        .expect("queue occurrences PTB should build");
+
+        let starts = ptb
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                Command::MoveCall(call)
+                    if call.function.as_str() == "add_occurrence_absolute_for_task" =>
+                {
+                    Some(
+                        bcs::from_bytes::<u64>(pure_input(&ptb, &call.arguments[1]))
+                            .expect("start time should decode"),
+                    )
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(starts, vec![1_000, 2_000, 3_000]);
+    }
+
     #[test]
     fn periodic_schedule_encodes_explicit_priority_as_move_option() {
         let objects = mock_nexus_objects();
@@ -0,0 +1,293 @@
+//! On-disk cache of Walrus blobs.
+//!
+//! Walrus blobs are immutable, so a blob fetched once can be served from disk
+//! for as long as it is cached. Every blob is stored in a file named after its
+//! blob ID. The least recently used blobs are evicted once the cache grows
+//! past its size limit, using file modification times as access times.
+//!
+//! The cache does not check that content matches its blob ID. Whatever is put
+//! into it is served as is, so callers only cache blobs they trust or have
+//! verified.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::SystemTime,
+};
+
+/// Size limit of a [`BlobCache`] unless configured otherwise, 1 GiB.
+pub const DEFAULT_BLOB_CACHE_MAX_BYTES: u64 = 1 << 30;
+
+/// Content addressed on-disk cache of Walrus blobs with LRU eviction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlobCache {
+    root: PathBuf,
+    max_bytes: u64,
+    offline: bool,
+}
+
+impl BlobCache {
+    /// Cache blobs under `root`, holding at most
+    /// [`DEFAULT_BLOB_CACHE_MAX_BYTES`].
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            max_bytes: DEFAULT_BLOB_CACHE_MAX_BYTES,
+            offline: false,
+        }
+    }
+
+    /// Evict blobs once the cache holds more than `max_bytes`. Blobs larger
+    /// than the limit are never cached.
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Serve blobs only from the cache and never contact Walrus.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Whether `blob_id` is cached.
+    pub async fn contains(&self, blob_id: &str) -> bool {
+        match self.path(blob_id) {
+            Some(path) => tokio::fs::try_exists(path).await.unwrap_or(false),
+            None => false,
+        }
+    }
+
+    /// The cached content of `blob_id`, marking it as recently used.
+    pub async fn get(&self, blob_id: &str) -> Option<Vec<u8>> {
+        let path = self.path(blob_id)?;
+        let bytes = tokio::fs::read(&path).await.ok()?;
+
+        touch(path).await;
+
+        Some(bytes)
+    }
+
+    /// Copy the cached content of `blob_id` to `output`, marking it as
+    /// recently used. Returns whether the blob was cached.
+    pub async fn copy_to(&self, blob_id: &str, output: &Path) -> io::Result<bool> {
+        let Some(path) = self.path(blob_id) else {
+            return Ok(false);
+        };
+
+        match tokio::fs::copy(&path, output).await {
+            Ok(_) => {
+                touch(path).await;
+
+                Ok(true)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Cache `bytes` as the content of `blob_id`.
+    pub async fn put(&self, blob_id: &str, bytes: &[u8]) -> io::Result<()> {
+        if bytes.len() as u64 > self.max_bytes {
+            return Ok(());
+        }
+
+        let Some(path) = self.path(blob_id) else {
+            return Ok(());
+        };
+
+        let temp_path = self.temp_path(blob_id);
+
+        tokio::fs::create_dir_all(&self.root).await?;
+        tokio::fs::write(&temp_path, bytes).await?;
+        self.commit(&temp_path, &path).await
+    }
+
+    /// Cache the file at `source` as the content of `blob_id`.
+    pub async fn put_file(&self, blob_id: &str, source: &Path) -> io::Result<()> {
+        if tokio::fs::metadata(source).await?.len() > self.max_bytes {
+            return Ok(());
+        }
+
+        let Some(path) = self.path(blob_id) else {
+            return Ok(());
+        };
+
+        let temp_path = self.temp_path(blob_id);
+
+        tokio::fs::create_dir_all(&self.root).await?;
+        tokio::fs::copy(source, &temp_path).await?;
+        self.commit(&temp_path, &path).await
+    }
+
+    /// Remove all cached blobs.
+    pub async fn clear(&self) -> io::Result<()> {
+        match tokio::fs::remove_dir_all(&self.root).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Move a fully written temporary file in place and enforce the size
+    /// limit.
+    async fn commit(&self, temp_path: &Path, path: &Path) -> io::Result<()> {
+        if let Err(e) = tokio::fs::rename(temp_path, path).await {
+            let _ = tokio::fs::remove_file(temp_path).await;
+
+            return Err(e);
+        }
+
+        let root = self.root.clone();
+        let max_bytes = self.max_bytes;
+
+        tokio::task::spawn_blocking(move || evict(&root, max_bytes))
+            .await
+            .map_err(io::Error::other)?
+    }
+
+    /// Blob IDs are URL safe base64, anything else is not cached so that IDs
+    /// can never escape the cache directory.
+    fn path(&self, blob_id: &str) -> Option<PathBuf> {
+        let is_valid = !blob_id.is_empty()
+            && blob_id.len() <= 128
+            && blob_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+        is_valid.then(|| self.root.join(blob_id))
+    }
+
+    fn temp_path(&self, blob_id: &str) -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        self.root.join(format!(
+            ".{blob_id}.{}.{}.tmp",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+}
+
+/// Mark a cached blob as recently used.
+async fn touch(path: PathBuf) {
+    let _ = tokio::task::spawn_blocking(move || {
+        std::fs::File::options()
+            .write(true)
+            .open(path)?
+            .set_modified(SystemTime::now())
+    })
+    .await;
+}
+
+/// Remove the least recently used blobs until the cache holds at most
+/// `max_bytes`.
+fn evict(root: &Path, max_bytes: u64) -> io::Result<()> {
+    let mut blobs = Vec::new();
+    let mut total = 0;
+
+    for entry in std::fs::read_dir(root)? {
+        let entry = entry?;
+
+        // Skip files that are still being written.
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+
+        let metadata = entry.metadata()?;
+
+        if !metadata.is_file() {
+            continue;
+        }
+
+        total += metadata.len();
+        blobs.push((
+            metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            metadata.len(),
+            entry.path(),
+        ));
+    }
+
+    blobs.sort();
+
+    for (_, len, path) in blobs {
+        if total <= max_bytes {
+            break;
+        }
+
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => total -= len,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::time::Duration};
+
+    #[tokio::test]
+    async fn test_blob_cache_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BlobCache::new(dir.path().join("cache"));
+
+        assert!(!cache.contains("blob").await);
+        assert_eq!(cache.get("blob").await, None);
+
+        cache.put("blob", b"content").await.unwrap();
+        assert!(cache.contains("blob").await);
+        assert_eq!(cache.get("blob").await.unwrap(), b"content");
+
+        let output = dir.path().join("output");
+        assert!(cache.copy_to("blob", &output).await.unwrap());
+        assert_eq!(tokio::fs::read(&output).await.unwrap(), b"content");
+        assert!(!cache.copy_to("missing", &output).await.unwrap());
+
+        // Invalid blob ids are never cached.
+        cache.put("../escape", b"content").await.unwrap();
+        assert!(!dir.path().join("escape").exists());
+        assert_eq!(cache.get("../escape").await, None);
+
+        cache.clear().await.unwrap();
+        assert!(!cache.contains("blob").await);
+    }
+
+    #[tokio::test]
+    async fn test_blob_cache_evicts_least_recently_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BlobCache::new(dir.path()).with_max_bytes(10);
+
+        cache.put("first", b"1234").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        cache.put("second", b"1234").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // Reading `first` makes `second` the least recently used blob.
+        assert!(cache.get("first").await.is_some());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        cache.put("third", b"1234").await.unwrap();
+
+        assert!(cache.contains("first").await);
+        assert!(!cache.contains("second").await);
+        assert!(cache.contains("third").await);
+
+        // Blobs over the limit are not cached at all.
+        cache.put("huge", &[0; 11]).await.unwrap();
+        assert!(!cache.contains("huge").await);
+        assert!(cache.contains("first").await);
+    }
+}
@@ -1,11 +1,22 @@
 use {
-    crate::walrus::models::*,
+    crate::walrus::{models::*, BlobCache, BlobContent, BlobVerifier},
     futures_util::StreamExt,
-    reqwest::Client,
+    reqwest::{header, Body, Client, Response},
     serde::{de::DeserializeOwned, Serialize},
-    std::{io, path::PathBuf},
+    std::{
+        future::Future,
+        io,
+        path::{Path, PathBuf},
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
+        time::Duration,
+    },
     thiserror::Error,
     tokio::{fs::File, io::AsyncWriteExt},
+    tokio_retry::RetryIf,
+    tokio_util::io::ReaderStream,
 };
 
 /// Publisher and Aggregator URLs are from <https://github.com/MystenLabs/walrus/blob/232d27ff7b3c2ba08aa4e10729b095f300b46384/docs/book/assets/operators.json>
@@ -65,16 +76,128 @@ pub enum WalrusError {
     /// Error processing stream data
     #[error("Failed to process data stream: {0}")]
     StreamError(#[from] reqwest::Error),
+
+    /// Downloaded content does not match the requested blob ID
+    #[error("Blob {blob_id} served by {url} does not match its blob ID")]
+    BlobIdMismatch {
+        /// The requested blob ID
+        blob_id: String,
+        /// Aggregator that served the blob
+        url: String,
+    },
+
+    /// Error checking downloaded content against its blob ID
+    #[error("Failed to verify blob {blob_id}: {message}")]
+    VerificationError {
+        /// The requested blob ID
+        blob_id: String,
+        /// Error message from the verifier
+        message: String,
+    },
+
+    /// Blob is not in the cache of an offline client
+    #[error("Blob {blob_id} is not cached and the Walrus client is offline")]
+    NotCached {
+        /// The requested blob ID
+        blob_id: String,
+    },
+
+    /// Operation needs the Walrus network but the client is offline
+    #[error("Cannot {operation} while the Walrus client is offline")]
+    Offline {
+        /// The attempted operation
+        operation: &'static str,
+    },
+}
+
+impl WalrusError {
+    /// Whether the failed request may succeed when it is sent again or to
+    /// another endpoint. Connection errors, timeouts, rate limiting, server
+    /// errors and corrupted downloads are retried.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RequestError { source, .. } | Self::StreamError(source) => !source.is_builder(),
+            Self::ApiError { status_code, .. } => {
+                matches!(*status_code, 408 | 429) || *status_code >= 500
+            }
+            Self::BlobIdMismatch { .. } => true,
+            Self::FileReadError { .. }
+            | Self::FileWriteError { .. }
+            | Self::SerializationError(_)
+            | Self::VerificationError { .. }
+            | Self::NotCached { .. }
+            | Self::Offline { .. } => false,
+        }
+    }
 }
 
 /// Result type used throughout the Walrus client
 pub type Result<T> = std::result::Result<T, WalrusError>;
 
+/// How a [`WalrusClient`] retries failed requests.
+///
+/// Every attempt tries the configured endpoints in order and moves on to the
+/// next one on a retryable error. Once all endpoints failed, the client waits
+/// for an exponentially growing backoff before the next attempt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WalrusRetryPolicy {
+    /// How many times all endpoints are tried again after the first attempt.
+    pub retries: usize,
+    /// Backoff before the first retry. Doubled for every following retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of the backoff.
+    pub max_backoff: Duration,
+}
+
+impl Default for WalrusRetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl WalrusRetryPolicy {
+    /// Try every endpoint exactly once.
+    pub fn none() -> Self {
+        Self {
+            retries: 0,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    fn backoffs(&self) -> impl Iterator<Item = Duration> {
+        let max_backoff = self.max_backoff;
+
+        std::iter::successors(
+            Some(self.initial_backoff.min(max_backoff)),
+            move |backoff| Some(backoff.saturating_mul(2).min(max_backoff)),
+        )
+        .take(self.retries)
+    }
+}
+
 /// Builder for WalrusClient configuration
 pub struct WalrusClientBuilder {
     client: Client,
-    publisher_url: String,
-    aggregator_url: String,
+    publisher_urls: Vec<String>,
+    aggregator_urls: Vec<String>,
+    retry_policy: WalrusRetryPolicy,
+    blob_verifier: Option<Arc<dyn BlobVerifier>>,
+    blob_cache: Option<BlobCache>,
 }
 
 impl Default for WalrusClientBuilder {
@@ -82,8 +205,11 @@ impl Default for WalrusClientBuilder {
     fn default() -> Self {
         Self {
             client: Client::new(),
-            publisher_url: WALRUS_PUBLISHER_URL.to_string(),
-            aggregator_url: WALRUS_AGGREGATOR_URL.to_string(),
+            publisher_urls: vec![WALRUS_PUBLISHER_URL.to_string()],
+            aggregator_urls: vec![WALRUS_AGGREGATOR_URL.to_string()],
+            retry_policy: WalrusRetryPolicy::default(),
+            blob_verifier: None,
+            blob_cache: None,
         }
     }
 }
@@ -102,13 +228,67 @@ impl WalrusClientBuilder {
 
     /// Set a custom publisher URL
     pub fn with_publisher_url(mut self, url: &str) -> Self {
-        self.publisher_url = url.to_string();
+        self.publisher_urls = vec![url.to_string()];
+        self
+    }
+
+    /// Set custom publisher URLs, tried in order. An empty list is ignored.
+    pub fn with_publisher_urls<I, S>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let urls = urls.into_iter().map(Into::into).collect::<Vec<_>>();
+
+        if !urls.is_empty() {
+            self.publisher_urls = urls;
+        }
+
         self
     }
 
     /// Set a custom aggregator URL
     pub fn with_aggregator_url(mut self, url: &str) -> Self {
-        self.aggregator_url = url.to_string();
+        self.aggregator_urls = vec![url.to_string()];
+        self
+    }
+
+    /// Set custom aggregator URLs, tried in order. An empty list is ignored.
+    pub fn with_aggregator_urls<I, S>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let urls = urls.into_iter().map(Into::into).collect::<Vec<_>>();
+
+        if !urls.is_empty() {
+            self.aggregator_urls = urls;
+        }
+
+        self
+    }
+
+    /// Set how failed requests are retried
+    pub fn with_retry_policy(mut self, retry_policy: WalrusRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Check downloaded blobs against their blob ID. Blobs that do not match
+    /// are requested again from the next aggregator.
+    pub fn with_blob_verifier(mut self, blob_verifier: Arc<dyn BlobVerifier>) -> Self {
+        self.blob_verifier = Some(blob_verifier);
+        self
+    }
+
+    /// Serve downloads from an on-disk cache and cache uploaded and
+    /// downloaded blobs. An offline cache never contacts Walrus.
+    ///
+    /// Cached blobs are served without being checked again. With a
+    /// [`BlobVerifier`] only blobs that passed it are cached, without one the
+    /// cache trusts the aggregators and publishers blobs came from.
+    pub fn with_blob_cache(mut self, blob_cache: BlobCache) -> Self {
+        self.blob_cache = Some(blob_cache);
         self
     }
 
@@ -116,8 +296,11 @@ impl WalrusClientBuilder {
     pub fn build(self) -> WalrusClient {
         WalrusClient {
             client: self.client,
-            publisher_url: self.publisher_url,
-            aggregator_url: self.aggregator_url,
+            publisher_urls: self.publisher_urls,
+            aggregator_urls: self.aggregator_urls,
+            retry_policy: self.retry_policy,
+            blob_verifier: self.blob_verifier,
+            blob_cache: self.blob_cache,
         }
     }
 }
@@ -125,8 +308,11 @@ impl WalrusClientBuilder {
 /// Client for interacting with the Walrus decentralized blob storage system
 pub struct WalrusClient {
     client: Client,
-    publisher_url: String,
-    aggregator_url: String,
+    publisher_urls: Vec<String>,
+    aggregator_urls: Vec<String>,
+    retry_policy: WalrusRetryPolicy,
+    blob_verifier: Option<Arc<dyn BlobVerifier>>,
+    blob_cache: Option<BlobCache>,
 }
 
 impl Default for WalrusClient {
@@ -136,6 +322,35 @@ impl Default for WalrusClient {
     }
 }
 
+/// Content of a blob being uploaded.
+#[derive(Clone, Copy)]
+enum UploadSource<'a> {
+    Bytes(&'a [u8]),
+    Json(&'a [u8]),
+    File(&'a Path),
+}
+
+impl UploadSource<'_> {
+    /// Build a fresh request body, files are streamed from disk with their
+    /// length as content length.
+    async fn body(self) -> Result<(Body, Option<u64>)> {
+        match self {
+            Self::Bytes(bytes) | Self::Json(bytes) => Ok((bytes.to_vec().into(), None)),
+            Self::File(path) => {
+                let read_error = |source| WalrusError::FileReadError {
+                    path: path.to_path_buf(),
+                    source,
+                };
+
+                let file = File::open(path).await.map_err(read_error)?;
+                let len = file.metadata().await.map_err(read_error)?.len();
+
+                Ok((Body::wrap_stream(ReaderStream::new(file)), Some(len)))
+            }
+        }
+    }
+}
+
 impl WalrusClient {
     /// Create a new WalrusClient with default configuration
     pub fn new() -> Self {
@@ -147,7 +362,13 @@ impl WalrusClient {
         WalrusClientBuilder::default()
     }
 
-    /// Upload a file to Walrus
+    /// Whether the client only serves blobs from its cache
+    pub fn is_offline(&self) -> bool {
+        self.blob_cache.as_ref().is_some_and(BlobCache::is_offline)
+    }
+
+    /// Upload a file to Walrus. The file is streamed from disk and read again
+    /// for every retry.
     ///
     /// # Arguments
     /// * `file_path` - Path to the file to upload
@@ -162,16 +383,13 @@ impl WalrusClient {
         epochs: u8,
         send_to: Option<String>,
     ) -> Result<StorageInfo> {
-        // Read file content
-        let file_content =
-            tokio::fs::read(file_path)
-                .await
-                .map_err(|e| WalrusError::FileReadError {
-                    path: file_path.clone(),
-                    source: e,
-                })?;
-
-        self.upload_bytes(file_content, epochs, send_to).await
+        self.put_blob(
+            UploadSource::File(file_path),
+            epochs,
+            send_to,
+            "Failed to upload file",
+        )
+        .await
     }
 
     /// Upload JSON data to Walrus.
@@ -194,42 +412,13 @@ impl WalrusClient {
     ) -> Result<StorageInfo> {
         let json_content = serde_json::to_vec(data).map_err(WalrusError::SerializationError)?;
 
-        let mut url = format!("{}/v1/blobs?epochs={}", self.publisher_url, epochs);
-        if let Some(address) = send_to {
-            url.push_str(&format!("&send_object_to={address}"));
-        }
-
-        let response = self
-            .client
-            .put(&url)
-            .header("Content-Type", "application/json")
-            .body(json_content)
-            .send()
-            .await
-            .map_err(|e| WalrusError::RequestError {
-                message: "Failed to upload JSON data".to_string(),
-                source: e,
-            })?;
-
-        if !response.status().is_success() {
-            let status_code = response.status().as_u16();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(WalrusError::ApiError {
-                status_code,
-                message: error_text,
-            });
-        }
-
-        let storage_info =
-            response
-                .json::<StorageInfo>()
-                .await
-                .map_err(|e| WalrusError::RequestError {
-                    message: "Failed to parse response".to_string(),
-                    source: e,
-                })?;
-
-        Ok(storage_info)
+        self.put_blob(
+            UploadSource::Json(&json_content),
+            epochs,
+            send_to,
+            "Failed to upload JSON data",
+        )
+        .await
     }
 
     /// Upload bytes to Walrus.
@@ -247,99 +436,89 @@ impl WalrusClient {
         epochs: u8,
         send_to: Option<String>,
     ) -> Result<StorageInfo> {
-        // Construct API URL with query parameters
-        let mut url = format!("{}/v1/blobs?epochs={}", self.publisher_url, epochs);
-        if let Some(address) = send_to {
-            url.push_str(&format!("&send_object_to={address}"));
-        }
-
-        // Send PUT request with raw blob content
-        let response = self
-            .client
-            .put(&url)
-            .body(data.into())
-            .send()
-            .await
-            .map_err(|e| WalrusError::RequestError {
-                message: "Failed to upload bytes".to_string(),
-                source: e,
-            })?;
-
-        if !response.status().is_success() {
-            let status_code = response.status().as_u16();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(WalrusError::ApiError {
-                status_code,
-                message: error_text,
-            });
-        }
-
-        let storage_info =
-            response
-                .json::<StorageInfo>()
-                .await
-                .map_err(|e| WalrusError::RequestError {
-                    message: "Failed to parse response".to_string(),
-                    source: e,
-                })?;
-
-        Ok(storage_info)
+        let data = data.into();
+
+        self.put_blob(
+            UploadSource::Bytes(&data),
+            epochs,
+            send_to,
+            "Failed to upload bytes",
+        )
+        .await
     }
 
-    /// Download a file from Walrus
+    /// Download a file from Walrus, streaming it to a temporary file next to
+    /// `output`. The temporary file is written again from the start if the
+    /// download is retried and only replaces `output` once it is verified, so
+    /// a failed download never leaves a partial or unverified `output`.
     ///
     /// # Arguments
     /// * `blob_id` - The blob ID of the file to download
     /// * `output` - Path where the downloaded file should be saved
     pub async fn download_file(&self, blob_id: &str, output: &PathBuf) -> Result<()> {
-        // Construct download URL
-        let url = format!("{}/v1/blobs/{}", self.aggregator_url, blob_id);
+        if let Some(cache) = &self.blob_cache {
+            let cached =
+                cache
+                    .copy_to(blob_id, output)
+                    .await
+                    .map_err(|e| WalrusError::FileWriteError {
+                        path: output.clone(),
+                        source: e,
+                    })?;
+
+            if cached {
+                return Ok(());
+            }
+
+            self.ensure_online(blob_id)?;
+        }
 
-        // Send GET request
-        let response =
-            self.client
-                .get(&url)
-                .send()
-                .await
-                .map_err(|e| WalrusError::RequestError {
-                    message: "Failed to download blob".to_string(),
-                    source: e,
-                })?;
+        let temp_path = &temp_path_beside(output);
 
-        if !response.status().is_success() {
-            let status_code = response.status().as_u16();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(WalrusError::ApiError {
-                status_code,
-                message: error_text,
-            });
-        }
+        let downloaded = self
+            .with_failover(&self.aggregator_urls, |aggregator_url| async move {
+                let response = self.get_blob(aggregator_url, blob_id).await?;
 
-        // Stream the response body to file
-        let mut file = File::create(output)
-            .await
-            .map_err(|e| WalrusError::FileWriteError {
-                path: output.clone(),
-                source: e,
-            })?;
+                let write_error = |source| WalrusError::FileWriteError {
+                    path: temp_path.clone(),
+                    source,
+                };
+
+                // Stream the response body to the temporary file
+                let mut file = File::create(temp_path).await.map_err(write_error)?;
+                let mut stream = response.bytes_stream();
 
-        let mut stream = response.bytes_stream();
+                while let Some(chunk_result) = stream.next().await {
+                    let chunk = chunk_result.map_err(WalrusError::StreamError)?;
+                    file.write_all(&chunk).await.map_err(write_error)?;
+                }
+                file.flush().await.map_err(write_error)?;
 
-        while let Some(chunk_result) = stream.next().await {
-            let chunk = chunk_result.map_err(WalrusError::StreamError)?;
-            file.write_all(&chunk)
+                self.verify(aggregator_url, blob_id, BlobContent::File(temp_path))
+                    .await
+            })
+            .await;
+
+        let downloaded = match downloaded {
+            Ok(()) => tokio::fs::rename(temp_path, output)
                 .await
-                .map_err(|e| WalrusError::FileWriteError {
+                .map_err(|source| WalrusError::FileWriteError {
                     path: output.clone(),
-                    source: e,
-                })?;
+                    source,
+                }),
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = downloaded {
+            let _ = tokio::fs::remove_file(temp_path).await;
+
+            return Err(e);
+        }
+
+        // Failing to cache a blob does not fail its download.
+        if let Some(cache) = &self.blob_cache {
+            let _ = cache.put_file(blob_id, output).await;
         }
-        file.flush()
-            .await
-            .map_err(|e| WalrusError::FileWriteError {
-                path: output.clone(),
-                source: e,
-            })?;
 
         Ok(())
     }
@@ -352,39 +531,38 @@ impl WalrusClient {
     /// # Returns
     /// * `Result<Vec<u8>>` - The file content as bytes
     pub async fn read_file(&self, blob_id: &str) -> Result<Vec<u8>> {
-        // Construct download URL
-        let url = format!("{}/v1/blobs/{}", self.aggregator_url, blob_id);
+        if let Some(cache) = &self.blob_cache {
+            if let Some(bytes) = cache.get(blob_id).await {
+                return Ok(bytes);
+            }
 
-        // Send GET request
-        let response =
-            self.client
-                .get(&url)
-                .send()
-                .await
-                .map_err(|e| WalrusError::RequestError {
-                    message: "Failed to download blob".to_string(),
-                    source: e,
-                })?;
-
-        if !response.status().is_success() {
-            let status_code = response.status().as_u16();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(WalrusError::ApiError {
-                status_code,
-                message: error_text,
-            });
+            self.ensure_online(blob_id)?;
         }
 
-        // Get the bytes directly from the response
-        let bytes = response
-            .bytes()
-            .await
-            .map_err(|e| WalrusError::RequestError {
-                message: "Failed to read response bytes".to_string(),
-                source: e,
-            })?;
+        let bytes = self
+            .with_failover(&self.aggregator_urls, |aggregator_url| async move {
+                let bytes = self
+                    .get_blob(aggregator_url, blob_id)
+                    .await?
+                    .bytes()
+                    .await
+                    .map_err(|e| WalrusError::RequestError {
+                        message: "Failed to read response bytes".to_string(),
+                        source: e,
+                    })?;
+
+                self.verify(aggregator_url, blob_id, BlobContent::Bytes(&bytes))
+                    .await?;
+
+                Ok(bytes.to_vec())
+            })
+            .await?;
+
+        if let Some(cache) = &self.blob_cache {
+            let _ = cache.put(blob_id, &bytes).await;
+        }
 
-        Ok(bytes.to_vec())
+        Ok(bytes)
     }
 
     /// Download and parse JSON data from Walrus.
@@ -407,13 +585,23 @@ impl WalrusClient {
     /// # Returns
     /// * `Result<bool>` - True if the blob exists, false otherwise
     pub async fn verify_blob(&self, blob_id: &str) -> Result<bool> {
-        // Construct URL to check blob existence
-        let url = format!("{}/v1/blobs/{}", self.aggregator_url, blob_id);
+        if let Some(cache) = &self.blob_cache {
+            if cache.contains(blob_id).await {
+                return Ok(true);
+            }
+
+            if cache.is_offline() {
+                return Err(WalrusError::Offline {
+                    operation: "check blob existence",
+                });
+            }
+        }
 
-        // Send HEAD request to check if blob exists
-        let response =
-            self.client
-                .head(&url)
+        self.with_failover(&self.aggregator_urls, |aggregator_url| async move {
+            // Send HEAD request to check if blob exists
+            let response = self
+                .client
+                .head(format!("{aggregator_url}/v1/blobs/{blob_id}"))
                 .send()
                 .await
                 .map_err(|e| WalrusError::RequestError {
@@ -421,6 +609,211 @@ impl WalrusClient {
                     source: e,
                 })?;
 
-        Ok(response.status().is_success())
+            let status = response.status();
+
+            if status.is_server_error() || status.as_u16() == 429 {
+                return Err(WalrusError::ApiError {
+                    status_code: status.as_u16(),
+                    message: status.to_string(),
+                });
+            }
+
+            Ok(status.is_success())
+        })
+        .await
+    }
+
+    /// Store a blob through the first publisher that accepts it.
+    async fn put_blob(
+        &self,
+        source: UploadSource<'_>,
+        epochs: u8,
+        send_to: Option<String>,
+        message: &str,
+    ) -> Result<StorageInfo> {
+        if self.is_offline() {
+            return Err(WalrusError::Offline {
+                operation: "upload blobs",
+            });
+        }
+
+        let send_to = send_to.as_deref();
+
+        let storage_info = self
+            .with_failover(&self.publisher_urls, |publisher_url| async move {
+                // Construct API URL with query parameters
+                let mut url = format!("{publisher_url}/v1/blobs?epochs={epochs}");
+                if let Some(address) = send_to {
+                    url.push_str(&format!("&send_object_to={address}"));
+                }
+
+                let (body, content_length) = source.body().await?;
+                let mut request = self.client.put(&url);
+
+                if let Some(content_length) = content_length {
+                    request = request.header(header::CONTENT_LENGTH, content_length);
+                }
+
+                if let UploadSource::Json(_) = source {
+                    request = request.header(header::CONTENT_TYPE, "application/json");
+                }
+
+                let response =
+                    request
+                        .body(body)
+                        .send()
+                        .await
+                        .map_err(|e| WalrusError::RequestError {
+                            message: message.to_string(),
+                            source: e,
+                        })?;
+
+                check_status(response)
+                    .await?
+                    .json::<StorageInfo>()
+                    .await
+                    .map_err(|e| WalrusError::RequestError {
+                        message: "Failed to parse response".to_string(),
+                        source: e,
+                    })
+            })
+            .await?;
+
+        // Blobs are immutable, so uploaded content can be served from cache
+        // once it is known to match the blob ID returned by the publisher.
+        if let (Some(cache), Some(blob_id)) = (&self.blob_cache, storage_info.blob_id()) {
+            let content = match source {
+                UploadSource::Bytes(bytes) | UploadSource::Json(bytes) => BlobContent::Bytes(bytes),
+                UploadSource::File(path) => BlobContent::File(path),
+            };
+
+            let verified = match &self.blob_verifier {
+                Some(blob_verifier) => blob_verifier
+                    .verify(blob_id, content)
+                    .await
+                    .unwrap_or(false),
+                None => true,
+            };
+
+            if verified {
+                let _ = match content {
+                    BlobContent::Bytes(bytes) => cache.put(blob_id, bytes).await,
+                    BlobContent::File(path) => cache.put_file(blob_id, path).await,
+                };
+            }
+        }
+
+        Ok(storage_info)
+    }
+
+    /// Fail reads of uncached blobs while offline.
+    fn ensure_online(&self, blob_id: &str) -> Result<()> {
+        if self.is_offline() {
+            return Err(WalrusError::NotCached {
+                blob_id: blob_id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Request a blob from a single aggregator.
+    async fn get_blob(&self, aggregator_url: &str, blob_id: &str) -> Result<Response> {
+        let response = self
+            .client
+            .get(format!("{aggregator_url}/v1/blobs/{blob_id}"))
+            .send()
+            .await
+            .map_err(|e| WalrusError::RequestError {
+                message: "Failed to download blob".to_string(),
+                source: e,
+            })?;
+
+        check_status(response).await
     }
+
+    /// Check downloaded content with the configured [`BlobVerifier`], if any.
+    async fn verify(
+        &self,
+        aggregator_url: &str,
+        blob_id: &str,
+        content: BlobContent<'_>,
+    ) -> Result<()> {
+        let Some(blob_verifier) = &self.blob_verifier else {
+            return Ok(());
+        };
+
+        match blob_verifier.verify(blob_id, content).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(WalrusError::BlobIdMismatch {
+                blob_id: blob_id.to_string(),
+                url: aggregator_url.to_string(),
+            }),
+            Err(e) => Err(WalrusError::VerificationError {
+                blob_id: blob_id.to_string(),
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    /// Run `request` against each of `urls` in order until one succeeds,
+    /// retrying all of them according to the [`WalrusRetryPolicy`].
+    async fn with_failover<'a, T, F, Fut>(&self, urls: &'a [String], request: F) -> Result<T>
+    where
+        F: Fn(&'a str) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let request = &request;
+
+        RetryIf::spawn(
+            self.retry_policy.backoffs(),
+            move || async move {
+                let mut last_error = None;
+
+                for url in urls {
+                    match request(url.as_str()).await {
+                        Ok(value) => return Ok(value),
+                        Err(e) if e.is_retryable() => last_error = Some(e),
+                        Err(e) => return Err(e),
+                    }
+                }
+
+                Err(last_error.expect("Walrus client has at least one endpoint"))
+            },
+            WalrusError::is_retryable,
+        )
+        .await
+    }
+}
+
+/// Hidden file in the directory of `output` that a download is written to
+/// until it is verified.
+fn temp_path_beside(output: &Path) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let name = output
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    output.with_file_name(format!(
+        ".{name}.{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// Turn non-success responses into [`WalrusError::ApiError`].
+async fn check_status(response: Response) -> Result<Response> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+
+    let status_code = response.status().as_u16();
+    let error_text = response.text().await.unwrap_or_default();
+
+    Err(WalrusError::ApiError {
+        status_code,
+        message: error_text,
+    })
 }
@@ -0,0 +1,404 @@
+//! Pluggable remote storage backends for `NexusData` payloads.
+//!
+//! [`NexusData`](crate::move_bindings::primitives::data::NexusData) carries a
+//! storage tag next to its payloads. `inline` data lives on-chain and
+//! `walrus` data is handled by [`WalrusStore`], built from the Walrus fields
+//! of [`StorageConf`], as is `walrus-encrypted` data, see
+//! [`crate::walrus::encryption`]. Any other tag is resolved through the
+//! [`DataStoreRegistry`] in [`StorageConf::stores`] so that S3-compatible,
+//! IPFS or local filesystem backends can be added without touching
+//! `commit`/`fetch`.
+
+use {
+    crate::walrus::{
+        BlobCache,
+        BlobVerifier,
+        SharedBlobVerifier,
+        StorageConf,
+        StorageInfo,
+        WalrusClient,
+        WalrusRetryPolicy,
+        WALRUS_MAX_EPOCHS,
+    },
+    sha2::{Digest, Sha256},
+    std::{collections::BTreeMap, future::Future, path::PathBuf, pin::Pin, sync::Arc},
+};
+
+/// Storage tag of the [`LocalFsStore`].
+pub const LOCAL_FS_STORAGE_TAG: &[u8] = b"local";
+
+pub type DataStoreFuture<'a, T> = Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>;
+
+/// Backend storing `NexusData` payloads outside of the chain.
+pub trait DataStore: Send + Sync {
+    /// Storage tag of the `NexusData` this store is responsible for.
+    fn tag(&self) -> &[u8];
+
+    /// Store a payload and return the reference that is kept in its place.
+    fn put<'a>(&'a self, payload: Vec<u8>) -> DataStoreFuture<'a, Vec<u8>>;
+
+    /// Load the payload behind a reference returned by [`DataStore::put`].
+    fn get<'a>(&'a self, reference: &'a [u8]) -> DataStoreFuture<'a, Vec<u8>>;
+}
+
+/// [`DataStore`]s keyed by their storage tag.
+#[derive(Clone, Default)]
+pub struct DataStoreRegistry {
+    stores: BTreeMap<Vec<u8>, Arc<dyn DataStore>>,
+}
+
+impl DataStoreRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a store, replacing any store with the same tag.
+    pub fn with_store(mut self, store: impl DataStore + 'static) -> Self {
+        self.register(Arc::new(store));
+        self
+    }
+
+    /// Register a store, replacing any store with the same tag.
+    pub fn register(&mut self, store: Arc<dyn DataStore>) {
+        self.stores.insert(store.tag().to_vec(), store);
+    }
+
+    pub fn get(&self, tag: &[u8]) -> Option<Arc<dyn DataStore>> {
+        self.stores.get(tag).cloned()
+    }
+
+    pub fn tags(&self) -> impl Iterator<Item = &[u8]> {
+        self.stores.keys().map(Vec::as_slice)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stores.is_empty()
+    }
+}
+
+impl PartialEq for DataStoreRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        self.stores.len() == other.stores.len()
+            && self.stores.iter().zip(&other.stores).all(
+                |((tag, store), (other_tag, other_store))| {
+                    tag == other_tag && Arc::ptr_eq(store, other_store)
+                },
+            )
+    }
+}
+
+impl Eq for DataStoreRegistry {}
+
+impl std::fmt::Debug for DataStoreRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.tags().map(String::from_utf8_lossy))
+            .finish()
+    }
+}
+
+impl StorageConf {
+    /// The store responsible for `tag`. Registered stores take precedence over
+    /// the built-in Walrus store.
+    pub fn data_store(&self, tag: &[u8]) -> anyhow::Result<Arc<dyn DataStore>> {
+        if let Some(store) = self.stores.get(tag) {
+            return Ok(store);
+        }
+
+        if tag == WalrusStore::TAG {
+            return Ok(Arc::new(WalrusStore::new(self)));
+        }
+
+        anyhow::bail!(
+            "No data store is configured for storage '{}'",
+            String::from_utf8_lossy(tag)
+        )
+    }
+
+    /// Register `store` with this configuration.
+    pub fn with_data_store(mut self, store: impl DataStore + 'static) -> Self {
+        self.stores.register(Arc::new(store));
+        self
+    }
+}
+
+/// Walrus backed [`DataStore`]. References are the UTF-8 blob ids.
+#[derive(Clone)]
+pub struct WalrusStore {
+    publisher_urls: Vec<String>,
+    aggregator_urls: Vec<String>,
+    save_for_epochs: Option<u8>,
+    retry_policy: WalrusRetryPolicy,
+    blob_verifier: Option<Arc<dyn BlobVerifier>>,
+    cache: Option<BlobCache>,
+}
+
+impl WalrusStore {
+    pub const TAG: &'static [u8] = b"walrus";
+
+    pub fn new(conf: &StorageConf) -> Self {
+        Self {
+            publisher_urls: conf
+                .walrus_publisher_url
+                .iter()
+                .chain(&conf.walrus_fallback_publisher_urls)
+                .cloned()
+                .collect(),
+            aggregator_urls: conf
+                .walrus_aggregator_url
+                .iter()
+                .chain(&conf.walrus_fallback_aggregator_urls)
+                .cloned()
+                .collect(),
+            save_for_epochs: conf.walrus_save_for_epochs,
+            retry_policy: conf.walrus_retry_policy,
+            blob_verifier: conf
+                .walrus_blob_verifier
+                .as_ref()
+                .map(SharedBlobVerifier::as_arc),
+            cache: conf.walrus_cache.clone(),
+        }
+    }
+
+    /// Check downloaded blobs against their blob ID. Register the store with
+    /// [`StorageConf::with_data_store`] to use it for `walrus` data.
+    pub fn with_blob_verifier(mut self, blob_verifier: impl BlobVerifier + 'static) -> Self {
+        self.blob_verifier = Some(Arc::new(blob_verifier));
+        self
+    }
+
+    fn client(&self) -> WalrusClient {
+        let mut builder = WalrusClient::builder()
+            .with_publisher_urls(self.publisher_urls.clone())
+            .with_aggregator_urls(self.aggregator_urls.clone())
+            .with_retry_policy(self.retry_policy);
+
+        if let Some(blob_verifier) = &self.blob_verifier {
+            builder = builder.with_blob_verifier(blob_verifier.clone());
+        }
+
+        if let Some(cache) = &self.cache {
+            builder = builder.with_blob_cache(cache.clone());
+        }
+
+        builder.build()
+    }
+}
+
+impl std::fmt::Debug for WalrusStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WalrusStore")
+            .field("publisher_urls", &self.publisher_urls)
+            .field("aggregator_urls", &self.aggregator_urls)
+            .field("save_for_epochs", &self.save_for_epochs)
+            .field("retry_policy", &self.retry_policy)
+            .field("verifies_blobs", &self.blob_verifier.is_some())
+            .field("cache", &self.cache)
+            .finish()
+    }
+}
+
+impl DataStore for WalrusStore {
+    fn tag(&self) -> &[u8] {
+        Self::TAG
+    }
+
+    fn put<'a>(&'a self, payload: Vec<u8>) -> DataStoreFuture<'a, Vec<u8>> {
+        Box::pin(async move {
+            if self.publisher_urls.is_empty() {
+                anyhow::bail!("Walrus publisher URL is not set in storage config");
+            }
+
+            let store_for_epochs = self.save_for_epochs.ok_or_else(|| {
+                anyhow::anyhow!("Walrus save for epochs is not set in storage config")
+            })?;
+
+            if store_for_epochs > WALRUS_MAX_EPOCHS {
+                anyhow::bail!(
+                    "Walrus save for epochs exceeds maximum allowed ({WALRUS_MAX_EPOCHS})"
+                );
+            }
+
+            let response = self
+                .client()
+                .upload_bytes(payload, store_for_epochs, None)
+                .await?;
+
+            Ok(blob_id_from_storage_info(response)?.into_bytes())
+        })
+    }
+
+    fn get<'a>(&'a self, reference: &'a [u8]) -> DataStoreFuture<'a, Vec<u8>> {
+        Box::pin(async move {
+            let offline = self.cache.as_ref().is_some_and(BlobCache::is_offline);
+
+            if self.aggregator_urls.is_empty() && !offline {
+                anyhow::bail!("Walrus aggregator URL is not set in storage config");
+            }
+
+            let blob_id = std::str::from_utf8(reference)
+                .map_err(|e| anyhow::anyhow!("Walrus blob id must be UTF-8: {e}"))?;
+
+            Ok(self.client().read_file(blob_id).await?)
+        })
+    }
+}
+
+/// Content addressed [`DataStore`] keeping payloads in a local directory,
+/// mostly useful for tests and local development. References are the hex
+/// encoded SHA-256 of the payload.
+#[derive(Clone, Debug)]
+pub struct LocalFsStore {
+    root: PathBuf,
+    tag: Vec<u8>,
+}
+
+impl LocalFsStore {
+    /// Store payloads under `root` with the [`LOCAL_FS_STORAGE_TAG`] tag.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            tag: LOCAL_FS_STORAGE_TAG.to_vec(),
+        }
+    }
+
+    /// Use a different storage tag, for example to mimic another backend.
+    pub fn with_tag(mut self, tag: impl Into<Vec<u8>>) -> Self {
+        self.tag = tag.into();
+        self
+    }
+
+    fn path(&self, reference: &[u8]) -> anyhow::Result<PathBuf> {
+        let name = std::str::from_utf8(reference)
+            .ok()
+            .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid local data store reference '{}'",
+                    String::from_utf8_lossy(reference)
+                )
+            })?;
+
+        Ok(self.root.join(name))
+    }
+}
+
+impl DataStore for LocalFsStore {
+    fn tag(&self) -> &[u8] {
+        &self.tag
+    }
+
+    fn put<'a>(&'a self, payload: Vec<u8>) -> DataStoreFuture<'a, Vec<u8>> {
+        Box::pin(async move {
+            let reference = hex::encode(Sha256::digest(&payload)).into_bytes();
+            let path = self.path(&reference)?;
+
+            tokio::fs::create_dir_all(&self.root)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to create {}: {e}", self.root.display()))?;
+            tokio::fs::write(&path, payload)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to write {}: {e}", path.display()))?;
+
+            Ok(reference)
+        })
+    }
+
+    fn get<'a>(&'a self, reference: &'a [u8]) -> DataStoreFuture<'a, Vec<u8>> {
+        Box::pin(async move {
+            let path = self.path(reference)?;
+
+            tokio::fs::read(&path)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to read {}: {e}", path.display()))
+        })
+    }
+}
+
+fn blob_id_from_storage_info(info: StorageInfo) -> anyhow::Result<String> {
+    info.newly_created
+        .map(|created| created.blob_object.blob_id)
+        .ok_or_else(|| {
+            anyhow::anyhow!("Failed to store data on Walrus: no newly created blob info")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::walrus::{BlobContent, BlobVerifierFuture},
+        mockito::Server,
+    };
+
+    struct RejectingVerifier;
+
+    impl BlobVerifier for RejectingVerifier {
+        fn verify<'a>(&'a self, _: &'a str, _: BlobContent<'a>) -> BlobVerifierFuture<'a> {
+            Box::pin(async { Ok(false) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_local_fs_store_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LocalFsStore::new(dir.path().join("blobs"));
+
+        let reference = store.put(b"payload".to_vec()).await.unwrap();
+        assert_eq!(reference.len(), 64);
+        assert_eq!(store.get(&reference).await.unwrap(), b"payload");
+
+        // Content addressed, storing the same payload twice is idempotent.
+        assert_eq!(store.put(b"payload".to_vec()).await.unwrap(), reference);
+
+        assert!(store.get(b"../etc/passwd").await.is_err());
+        assert!(store.get(b"abcdef").await.is_err());
+    }
+
+    #[test]
+    fn test_storage_conf_resolves_stores_by_tag() {
+        let conf = StorageConf::default()
+            .with_data_store(LocalFsStore::new("/tmp/nexus").with_tag(b"s3".to_vec()));
+
+        assert_eq!(conf.data_store(b"s3").unwrap().tag(), b"s3");
+        assert_eq!(conf.data_store(b"walrus").unwrap().tag(), b"walrus");
+        assert!(conf
+            .data_store(b"ipfs")
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("No data store is configured for storage 'ipfs'"));
+        assert_eq!(format!("{:?}", conf.stores), r#"["s3"]"#);
+    }
+
+    #[tokio::test]
+    async fn test_walrus_store_uses_configured_blob_verifier() {
+        let mut server = Server::new_async().await;
+        let get = server
+            .mock("GET", "/v1/blobs/blob_id")
+            .expect(2)
+            .with_status(200)
+            .with_body("tampered")
+            .create_async()
+            .await;
+        let conf = StorageConf {
+            walrus_aggregator_url: Some(server.url()),
+            walrus_retry_policy: WalrusRetryPolicy::none(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            WalrusStore::new(&conf).get(b"blob_id").await.unwrap(),
+            b"tampered"
+        );
+
+        let conf = StorageConf {
+            walrus_blob_verifier: Some(SharedBlobVerifier::new(RejectingVerifier)),
+            ..conf
+        };
+
+        assert!(WalrusStore::new(&conf).get(b"blob_id").await.is_err());
+
+        get.assert_async().await;
+    }
+}
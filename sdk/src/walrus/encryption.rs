@@ -0,0 +1,404 @@
+//! Client-side envelope encryption of `NexusData` payloads stored on Walrus.
+//!
+//! Walrus publishers and aggregators are public, so anything uploaded in plain
+//! text is world-readable. When [`crate::walrus::StorageConf::encryption`] has
+//! an active key, every payload is sealed with a fresh AES-256-GCM data key
+//! which is in turn wrapped with the active [`WalrusKey`]. The uploaded blob is
+//!
+//! ```text
+//! "NXE1" | key id length (u8) | key id | wrap nonce (12) | wrapped data key (48) | nonce (12) | ciphertext
+//! ```
+//!
+//! and the payload is stored under the [`ENCRYPTED_WALRUS_STORAGE_TAG`] storage
+//! tag with the reference `<key id>:<blob id>`, so that readers know which key
+//! to unwrap the data key with without downloading the blob first. Readers
+//! without keys fail on the storage tag instead of requesting a blob that does
+//! not exist.
+//!
+//! Keys can be shared with specific tools, see [`WalrusKeyring::for_tool`].
+
+use {
+    crate::types::SecretValue,
+    aes_gcm::{
+        aead::{Aead, KeyInit, Payload},
+        Aes256Gcm,
+        Key,
+        Nonce,
+    },
+    base64::{prelude::BASE64_STANDARD, Engine},
+    rand::{rngs::OsRng, RngCore},
+    std::collections::{BTreeMap, BTreeSet},
+    zeroize::Zeroizing,
+};
+
+/// Storage tag of `NexusData` whose payloads are stored encrypted on Walrus.
+pub const ENCRYPTED_WALRUS_STORAGE_TAG: &[u8] = b"walrus-encrypted";
+
+const ENVELOPE_MAGIC: &[u8; 4] = b"NXE1";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const MAX_KEY_ID_LEN: usize = 64;
+
+/// Named AES-256 key used to wrap the per-payload data keys.
+#[derive(Clone, PartialEq, Eq)]
+pub struct WalrusKey {
+    id: String,
+    secret: Zeroizing<[u8; KEY_LEN]>,
+    /// FQNs of the tools this key is shared with.
+    tools: BTreeSet<String>,
+}
+
+impl WalrusKey {
+    /// Generate a new random key.
+    pub fn generate(id: impl Into<String>) -> anyhow::Result<Self> {
+        let mut secret = Zeroizing::new([0u8; KEY_LEN]);
+        OsRng.fill_bytes(secret.as_mut());
+
+        Self::from_parts(id.into(), secret)
+    }
+
+    /// Create a key from its base64 encoded 32 bytes.
+    pub fn from_base64(id: impl Into<String>, encoded: &str) -> anyhow::Result<Self> {
+        let bytes = Zeroizing::new(
+            BASE64_STANDARD
+                .decode(encoded.trim())
+                .map_err(|e| anyhow::anyhow!("Walrus encryption key must be base64: {e}"))?,
+        );
+
+        let secret: [u8; KEY_LEN] = bytes.as_slice().try_into().map_err(|_| {
+            anyhow::anyhow!(
+                "Walrus encryption key must be {KEY_LEN} bytes, got {}",
+                bytes.len()
+            )
+        })?;
+
+        Self::from_parts(id.into(), Zeroizing::new(secret))
+    }
+
+    /// Share this key with the tool identified by `tool_fqn`.
+    pub fn with_tool(mut self, tool_fqn: impl Into<String>) -> Self {
+        self.tools.insert(tool_fqn.into());
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// FQNs of the tools this key is shared with.
+    pub fn tools(&self) -> &BTreeSet<String> {
+        &self.tools
+    }
+
+    pub fn is_shared_with(&self, tool_fqn: &str) -> bool {
+        self.tools.contains(tool_fqn)
+    }
+
+    /// The base64 encoded key so that it can be handed to a tool.
+    pub fn to_base64(&self) -> SecretValue {
+        SecretValue::from(BASE64_STANDARD.encode(self.secret.as_ref()))
+    }
+
+    fn from_parts(id: String, secret: Zeroizing<[u8; KEY_LEN]>) -> anyhow::Result<Self> {
+        let valid = !id.is_empty()
+            && id.len() <= MAX_KEY_ID_LEN
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+        if !valid {
+            anyhow::bail!(
+                "Invalid Walrus encryption key id '{id}': expected up to {MAX_KEY_ID_LEN} ASCII letters, digits, '-', '_' or '.'"
+            );
+        }
+
+        Ok(Self {
+            id,
+            secret,
+            tools: BTreeSet::new(),
+        })
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(self.secret.as_ref()))
+    }
+}
+
+impl std::fmt::Debug for WalrusKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WalrusKey")
+            .field("id", &self.id)
+            .field("secret", &"[Redacted: *****]")
+            .field("tools", &self.tools)
+            .finish()
+    }
+}
+
+/// Set of [`WalrusKey`]s used to decrypt payloads, one of which may be active
+/// and used to encrypt new payloads.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WalrusKeyring {
+    keys: BTreeMap<String, WalrusKey>,
+    active: Option<String>,
+}
+
+impl WalrusKeyring {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a key that is only used for decryption. Replaces any key with the
+    /// same id.
+    pub fn with_key(mut self, key: WalrusKey) -> Self {
+        self.keys.insert(key.id.clone(), key);
+        self
+    }
+
+    /// Add a key and use it to encrypt new payloads.
+    pub fn with_active_key(mut self, key: WalrusKey) -> Self {
+        self.active = Some(key.id.clone());
+        self.with_key(key)
+    }
+
+    /// Key used to encrypt new payloads, `None` if payloads are uploaded in
+    /// plain text.
+    pub fn active_key(&self) -> Option<&WalrusKey> {
+        self.active.as_ref().and_then(|id| self.keys.get(id))
+    }
+
+    pub fn key(&self, id: &str) -> Option<&WalrusKey> {
+        self.keys.get(id)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &WalrusKey> {
+        self.keys.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Keyring restricted to the keys shared with `tool_fqn`. The active key is
+    /// kept only if it is shared with the tool.
+    pub fn for_tool(&self, tool_fqn: &str) -> Self {
+        let keys = self
+            .keys
+            .iter()
+            .filter(|(_, key)| key.is_shared_with(tool_fqn))
+            .map(|(id, key)| (id.clone(), key.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let active = self.active.clone().filter(|id| keys.contains_key(id));
+
+        Self { keys, active }
+    }
+
+    /// Decrypt an envelope sealed with the key `key_id`.
+    pub fn open(&self, key_id: &str, envelope: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let key = self.key(key_id).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Walrus payload is encrypted with key '{key_id}' which is not configured"
+            )
+        })?;
+
+        open(key, envelope)
+    }
+}
+
+/// Encrypt `plaintext` into an envelope wrapped with `key`.
+pub fn seal(key: &WalrusKey, plaintext: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let header = envelope_header(key.id());
+
+    let mut data_key = Zeroizing::new([0u8; KEY_LEN]);
+    OsRng.fill_bytes(data_key.as_mut());
+
+    let wrap_nonce = random_nonce();
+    let wrapped_key = key
+        .cipher()
+        .encrypt(
+            Nonce::from_slice(&wrap_nonce),
+            Payload {
+                msg: data_key.as_ref(),
+                aad: &header,
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to wrap Walrus data key"))?;
+
+    let nonce = random_nonce();
+    let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(data_key.as_ref()))
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad: &header,
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt Walrus payload"))?;
+
+    let mut envelope = header;
+    envelope.extend_from_slice(&wrap_nonce);
+    envelope.extend_from_slice(&wrapped_key);
+    envelope.extend_from_slice(&nonce);
+    envelope.extend_from_slice(&ciphertext);
+
+    Ok(envelope)
+}
+
+/// Decrypt an envelope produced by [`seal`].
+pub fn open(key: &WalrusKey, envelope: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let header = envelope_header(key.id());
+
+    let Some(rest) = envelope.strip_prefix(header.as_slice()) else {
+        anyhow::bail!(
+            "Walrus payload is not an envelope encrypted with key '{}'",
+            key.id()
+        );
+    };
+
+    let wrapped_len = KEY_LEN + TAG_LEN;
+
+    if rest.len() < NONCE_LEN + wrapped_len + NONCE_LEN + TAG_LEN {
+        anyhow::bail!("Encrypted Walrus payload is truncated");
+    }
+
+    let (wrap_nonce, rest) = rest.split_at(NONCE_LEN);
+    let (wrapped_key, rest) = rest.split_at(wrapped_len);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let data_key = Zeroizing::new(
+        key.cipher()
+            .decrypt(
+                Nonce::from_slice(wrap_nonce),
+                Payload {
+                    msg: wrapped_key,
+                    aad: &header,
+                },
+            )
+            .map_err(|_| {
+                anyhow::anyhow!("Failed to unwrap Walrus data key with key '{}'", key.id())
+            })?,
+    );
+
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&data_key))
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: &header,
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to decrypt Walrus payload"))
+}
+
+/// Reference to an encrypted blob, `<key id>:<blob id>`.
+pub fn encrypted_reference(key_id: &str, blob_id: &str) -> String {
+    format!("{key_id}:{blob_id}")
+}
+
+/// Split a reference to an encrypted blob into the key id and the blob id.
+pub fn parse_reference(reference: &str) -> anyhow::Result<(&str, &str)> {
+    match reference.split_once(':') {
+        Some((key_id, blob_id)) if !key_id.is_empty() && !blob_id.is_empty() => {
+            Ok((key_id, blob_id))
+        }
+        _ => anyhow::bail!("Malformed encrypted Walrus reference '{reference}'"),
+    }
+}
+
+fn envelope_header(key_id: &str) -> Vec<u8> {
+    let mut header = Vec::with_capacity(ENVELOPE_MAGIC.len() + 1 + key_id.len());
+    header.extend_from_slice(ENVELOPE_MAGIC);
+    // Key ids are validated to be at most `MAX_KEY_ID_LEN` bytes.
+    header.push(key_id.len() as u8);
+    header.extend_from_slice(key_id.as_bytes());
+    header
+}
+
+fn random_nonce() -> [u8; NONCE_LEN] {
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    nonce
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open_roundtrip() {
+        let key = WalrusKey::generate("team-a").unwrap();
+        let keyring = WalrusKeyring::new().with_active_key(key.clone());
+
+        let envelope = seal(&key, b"customer data").unwrap();
+        assert!(envelope.starts_with(b"NXE1"));
+        assert!(!envelope
+            .windows(b"customer data".len())
+            .any(|w| w == b"customer data"));
+
+        assert_eq!(keyring.open("team-a", &envelope).unwrap(), b"customer data");
+
+        // Every payload gets its own data key and nonces.
+        assert_ne!(seal(&key, b"customer data").unwrap(), envelope);
+    }
+
+    #[test]
+    fn test_open_rejects_wrong_key_and_tampering() {
+        let key = WalrusKey::generate("team-a").unwrap();
+        let other = WalrusKey::from_base64("team-a", &BASE64_STANDARD.encode([7u8; 32])).unwrap();
+        let mut envelope = seal(&key, b"customer data").unwrap();
+
+        assert!(open(&other, &envelope)
+            .unwrap_err()
+            .to_string()
+            .contains("Failed to unwrap Walrus data key"));
+        assert!(WalrusKeyring::new()
+            .open("team-a", &envelope)
+            .unwrap_err()
+            .to_string()
+            .contains("key 'team-a' which is not configured"));
+
+        let last = envelope.len() - 1;
+        envelope[last] ^= 1;
+        assert!(open(&key, &envelope).is_err());
+        assert!(open(&key, &envelope[..20]).is_err());
+    }
+
+    #[test]
+    fn test_key_ids_and_encoding() {
+        let key = WalrusKey::generate("team-a.v1").unwrap();
+        let decoded = WalrusKey::from_base64("team-a.v1", key.to_base64().peek()).unwrap();
+        assert_eq!(decoded, key);
+        assert!(!format!("{key:?}").contains(key.to_base64().peek()));
+
+        assert!(WalrusKey::generate("").is_err());
+        assert!(WalrusKey::generate("with:colon").is_err());
+        assert!(WalrusKey::from_base64("k", "AAAA").is_err());
+    }
+
+    #[test]
+    fn test_keyring_for_tool_keeps_shared_keys() {
+        let shared = WalrusKey::generate("shared")
+            .unwrap()
+            .with_tool("xyz.demo.tool@1");
+        let private = WalrusKey::generate("private").unwrap();
+        let keyring = WalrusKeyring::new()
+            .with_key(shared)
+            .with_active_key(private);
+
+        let for_tool = keyring.for_tool("xyz.demo.tool@1");
+        assert!(for_tool.key("shared").is_some());
+        assert!(for_tool.key("private").is_none());
+        assert!(for_tool.active_key().is_none());
+        assert!(keyring.for_tool("xyz.other.tool@1").is_empty());
+    }
+
+    #[test]
+    fn test_parse_reference() {
+        assert_eq!(
+            parse_reference(&encrypted_reference("k1", "blob")).unwrap(),
+            ("k1", "blob")
+        );
+        assert!(parse_reference("blob").is_err());
+        assert!(parse_reference(":blob").is_err());
+    }
+}
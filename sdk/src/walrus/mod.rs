@@ -6,13 +6,24 @@
 //! - Downloading files from the Walrus network
 //! - Reading bytes from the Walrus network
 //! - Verifying the existence of files in the Walrus network
+//! - Retrying failed requests across several publishers and aggregators
+//! - Checking downloaded blobs against their blob ID through [`BlobVerifier`]
+//! - Caching blobs on disk with [`BlobCache`], optionally serving only from cache
+//! - Encrypting `NexusData` payloads before they are stored on Walrus
+//! - Storing `NexusData` payloads in other backends through [`data_store::DataStore`]
 
+mod cache;
 mod client;
+#[cfg(feature = "types")]
+pub mod data_store;
+#[cfg(feature = "types")]
+pub mod encryption;
 mod models;
 #[cfg(feature = "types")]
 mod nexus_data;
+mod verifier;
 
 // Re-exports
 #[cfg(feature = "types")]
 pub use nexus_data::*;
-pub use {client::*, models::*};
+pub use {cache::*, client::*, models::*, verifier::*};
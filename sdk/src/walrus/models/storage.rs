@@ -28,3 +28,18 @@ pub struct StorageInfo {
     #[serde(rename = "alreadyCertified")]
     pub already_certified: Option<AlreadyCertified>,
 }
+
+impl StorageInfo {
+    /// ID of the stored blob, whether it was newly created or already
+    /// certified.
+    pub fn blob_id(&self) -> Option<&str> {
+        self.newly_created
+            .as_ref()
+            .map(|created| created.blob_object.blob_id.as_str())
+            .or_else(|| {
+                self.already_certified
+                    .as_ref()
+                    .map(|certified| certified.blob_id.as_str())
+            })
+    }
+}
@@ -1,4 +1,8 @@
-//! Walrus helpers for raw `NexusData` payloads.
+//! Remote storage helpers for raw `NexusData` payloads.
+//!
+//! Payloads of non-inline `NexusData` are stored through the
+//! [`DataStore`](crate::walrus::data_store::DataStore) responsible for their
+//! storage tag, see [`StorageConf::data_store`].
 
 use {
     crate::{
@@ -7,93 +11,126 @@ use {
             primitives::data::NexusData,
             sui_framework::vec_map::{Entry as VecMapEntry, VecMap},
         },
-        walrus::{StorageInfo, WalrusClient, WALRUS_MAX_EPOCHS},
+        walrus::{
+            data_store::{DataStore, DataStoreRegistry, WalrusStore},
+            encryption::{self, WalrusKey, WalrusKeyring},
+            BlobCache,
+            SharedBlobVerifier,
+            WalrusRetryPolicy,
+        },
     },
-    futures_util::future::try_join_all,
+    futures_util::{future::try_join_all, stream, StreamExt, TryStreamExt},
+    std::sync::Arc,
 };
 
+/// How many `many` payloads of a single `NexusData` are transferred at once
+/// when [`StorageConf::max_concurrent_transfers`] is not set.
+pub const DEFAULT_MAX_CONCURRENT_TRANSFERS: usize = 4;
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct StorageConf {
     pub walrus_publisher_url: Option<String>,
     pub walrus_aggregator_url: Option<String>,
     pub walrus_save_for_epochs: Option<u8>,
+    /// Publishers tried in order when `walrus_publisher_url` fails.
+    pub walrus_fallback_publisher_urls: Vec<String>,
+    /// Aggregators tried in order when `walrus_aggregator_url` fails.
+    pub walrus_fallback_aggregator_urls: Vec<String>,
+    /// How failed Walrus requests are retried.
+    pub walrus_retry_policy: WalrusRetryPolicy,
+    /// On-disk cache of Walrus blobs. Fetches are only served from this
+    /// cache if it is offline.
+    pub walrus_cache: Option<BlobCache>,
+    /// Checks blobs downloaded by the built-in Walrus store against their
+    /// blob ID. Downloads are not verified when unset.
+    pub walrus_blob_verifier: Option<SharedBlobVerifier>,
+    /// How many `many` payloads are uploaded or downloaded at once. Defaults
+    /// to [`DEFAULT_MAX_CONCURRENT_TRANSFERS`].
+    pub max_concurrent_transfers: Option<usize>,
+    /// Keys used to encrypt Walrus payloads before upload and decrypt them on
+    /// fetch. Encrypted payloads are tagged
+    /// [`encryption::ENCRYPTED_WALRUS_STORAGE_TAG`]. Payloads are uploaded in
+    /// plain text if there is no active key.
+    pub encryption: Option<WalrusKeyring>,
+    /// Stores for storage tags other than `inline` and `walrus`. A store
+    /// registered for `walrus` replaces the built-in Walrus store.
+    pub stores: DataStoreRegistry,
+}
+
+impl StorageConf {
+    /// How many `many` payloads are transferred at once, at least one.
+    pub fn transfer_concurrency(&self) -> usize {
+        self.max_concurrent_transfers
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_TRANSFERS)
+            .max(1)
+    }
 }
 
 impl NexusData {
     pub async fn fetch(mut self, conf: &StorageConf) -> anyhow::Result<Self> {
-        if !self.is_walrus() {
+        if self.is_inline() {
             return Ok(self);
         }
 
-        let walrus_aggregator_url = conf
-            .walrus_aggregator_url
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Walrus aggregator URL is not set in storage config"))?;
-
-        let client = WalrusClient::builder()
-            .with_aggregator_url(walrus_aggregator_url)
-            .build();
+        let (store, keyring) = resolve_store(self.storage_tag(), conf)?;
 
         if !self.one.is_empty() && !self.many.is_empty() {
             anyhow::bail!("NexusData cannot contain both one and many payloads");
         }
 
         if !self.one.is_empty() {
-            let blob_id = blob_id_from_bytes(&self.one)?;
-            self.one = client.read_file(&blob_id).await?;
+            self.one = download(store.as_ref(), &self.one, keyring).await?;
             return Ok(self);
         }
 
-        let mut data = Vec::with_capacity(self.many.len());
-        for key in &self.many {
-            let blob_id = blob_id_from_bytes(key)?;
-            data.push(client.read_file(&blob_id).await?);
-        }
+        let store = store.as_ref();
+
+        let data = stream::iter(&self.many)
+            .map(|key| download(store, key, keyring))
+            .buffered(conf.transfer_concurrency())
+            .try_collect::<Vec<_>>()
+            .await?;
         self.many = data;
         Ok(self)
     }
 
     pub async fn commit(mut self, conf: &StorageConf) -> anyhow::Result<Self> {
-        if !self.is_walrus() {
+        if self.is_inline() {
             return Ok(self);
         }
 
-        let walrus_publisher_url = conf
-            .walrus_publisher_url
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Walrus publisher URL is not set in storage config"))?;
-
-        let store_for_epochs = conf.walrus_save_for_epochs.ok_or_else(|| {
-            anyhow::anyhow!("Walrus save for epochs is not set in storage config")
-        })?;
-
-        if store_for_epochs > WALRUS_MAX_EPOCHS {
-            anyhow::bail!("Walrus save for epochs exceeds maximum allowed ({WALRUS_MAX_EPOCHS})");
-        }
-
-        let client = WalrusClient::builder()
-            .with_publisher_url(walrus_publisher_url)
-            .build();
+        let (store, keyring) = resolve_store(self.storage_tag(), conf)?;
+        let key = match keyring {
+            Some(keyring) => Some(keyring.active_key().ok_or_else(|| {
+                anyhow::anyhow!("Encrypted Walrus payloads require an active encryption key")
+            })?),
+            None if self.is_walrus() => {
+                conf.encryption.as_ref().and_then(WalrusKeyring::active_key)
+            }
+            None => None,
+        };
 
         if !self.one.is_empty() && !self.many.is_empty() {
             anyhow::bail!("NexusData cannot contain both one and many payloads");
         }
 
+        if key.is_some() {
+            self.storage = encryption::ENCRYPTED_WALRUS_STORAGE_TAG.to_vec();
+        }
+
         if !self.one.is_empty() {
-            let response = client
-                .upload_bytes(std::mem::take(&mut self.one), store_for_epochs, None)
-                .await?;
-            self.one = blob_id_from_storage_info(response)?.into_bytes();
+            let payload = std::mem::take(&mut self.one);
+            self.one = upload(store.as_ref(), payload, key).await?;
             return Ok(self);
         }
 
-        let payloads = std::mem::take(&mut self.many);
-        let mut keys = Vec::with_capacity(payloads.len());
-        for payload in payloads {
-            let response = client.upload_bytes(payload, store_for_epochs, None).await?;
-            keys.push(blob_id_from_storage_info(response)?.into_bytes());
-        }
-        self.many = keys;
+        let store = store.as_ref();
+
+        self.many = stream::iter(std::mem::take(&mut self.many))
+            .map(|payload| upload(store, payload, key))
+            .buffered(conf.transfer_concurrency())
+            .try_collect()
+            .await?;
         Ok(self)
     }
 }
@@ -160,18 +197,60 @@ async fn fetch_entries<P>(
     .await
 }
 
-fn blob_id_from_bytes(bytes: &[u8]) -> anyhow::Result<String> {
-    std::str::from_utf8(bytes)
-        .map(str::to_owned)
-        .map_err(|e| anyhow::anyhow!("Walrus blob id must be UTF-8: {e}"))
+/// The store holding payloads tagged `tag`, and the keys to decrypt them with
+/// if they are stored encrypted on Walrus.
+fn resolve_store<'a>(
+    tag: &[u8],
+    conf: &'a StorageConf,
+) -> anyhow::Result<(Arc<dyn DataStore>, Option<&'a WalrusKeyring>)> {
+    if tag != encryption::ENCRYPTED_WALRUS_STORAGE_TAG {
+        return Ok((conf.data_store(tag)?, None));
+    }
+
+    let keyring = conf.encryption.as_ref().ok_or_else(|| {
+        anyhow::anyhow!(
+            "Payload is stored encrypted on Walrus but no encryption keys are set in storage config"
+        )
+    })?;
+
+    Ok((conf.data_store(WalrusStore::TAG)?, Some(keyring)))
+}
+
+/// Store a payload, encrypting it with `key` if any, and return the reference
+/// to keep in place of the payload.
+async fn upload(
+    store: &dyn DataStore,
+    payload: Vec<u8>,
+    key: Option<&WalrusKey>,
+) -> anyhow::Result<Vec<u8>> {
+    let Some(key) = key else {
+        return store.put(payload).await;
+    };
+
+    let reference = store.put(encryption::seal(key, &payload)?).await?;
+    let reference = std::str::from_utf8(&reference)
+        .map_err(|e| anyhow::anyhow!("Encrypted payload reference must be UTF-8: {e}"))?;
+
+    Ok(encryption::encrypted_reference(key.id(), reference).into_bytes())
 }
 
-fn blob_id_from_storage_info(info: StorageInfo) -> anyhow::Result<String> {
-    info.newly_created
-        .map(|created| created.blob_object.blob_id)
-        .ok_or_else(|| {
-            anyhow::anyhow!("Failed to store data on Walrus: no newly created blob info")
-        })
+/// Load the payload behind a reference, decrypting it with `keyring` if the
+/// payload is stored encrypted.
+async fn download(
+    store: &dyn DataStore,
+    reference: &[u8],
+    keyring: Option<&WalrusKeyring>,
+) -> anyhow::Result<Vec<u8>> {
+    let Some(keyring) = keyring else {
+        return store.get(reference).await;
+    };
+
+    let reference = std::str::from_utf8(reference)
+        .map_err(|e| anyhow::anyhow!("Encrypted payload reference must be UTF-8: {e}"))?;
+    let (key_id, blob_id) = encryption::parse_reference(reference)?;
+    let bytes = store.get(blob_id.as_bytes()).await?;
+
+    keyring.open(key_id, &bytes)
 }
 
 #[cfg(test)]
@@ -183,10 +262,24 @@ mod tests {
                 move_std::ascii::String as MoveString,
                 sui_framework::vec_map::Entry as VecMapEntry,
             },
-            walrus::{BlobObject, BlobStorage, NewlyCreated},
+            walrus::{
+                data_store::{DataStoreFuture, LocalFsStore, LOCAL_FS_STORAGE_TAG},
+                encryption::WalrusKey,
+                BlobObject,
+                BlobStorage,
+                NewlyCreated,
+                StorageInfo,
+            },
+        },
+        mockito::{Matcher, Server, ServerGuard},
+        std::{
+            collections::HashMap,
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+            time::Duration,
         },
-        mockito::{Server, ServerGuard},
-        std::collections::HashMap,
     };
 
     async fn setup_mock_server_and_conf() -> anyhow::Result<(ServerGuard, StorageConf)> {
@@ -197,6 +290,7 @@ mod tests {
             walrus_publisher_url: Some(server_url.clone()),
             walrus_aggregator_url: Some(server_url),
             walrus_save_for_epochs: Some(2),
+            ..Default::default()
         };
 
         Ok((server, storage_conf))
@@ -327,6 +421,185 @@ mod tests {
         mock_get.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn walrus_one_encrypts_with_active_key() {
+        let (mut server, mut storage_conf) = setup_mock_server_and_conf()
+            .await
+            .expect("mock server should start");
+        let key = WalrusKey::generate("team-a").expect("key id is valid");
+        storage_conf.encryption = Some(WalrusKeyring::new().with_active_key(key.clone()));
+
+        let mock_put_response = StorageInfo {
+            newly_created: Some(NewlyCreated {
+                blob_object: BlobObject {
+                    blob_id: "enc_blob_id".to_string(),
+                    id: "enc_object_id".to_string(),
+                    storage: BlobStorage { end_epoch: 200 },
+                },
+            }),
+            already_certified: None,
+        };
+
+        let mock_put = server
+            .mock("PUT", "/v1/blobs?epochs=2")
+            .match_body(Matcher::Regex("^NXE1".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&mock_put_response).expect("must serialize"))
+            .create_async()
+            .await;
+
+        let mock_get = server
+            .mock("GET", "/v1/blobs/enc_blob_id")
+            .expect(2)
+            .with_status(200)
+            .with_body(encryption::seal(&key, b"secret payload").expect("must seal"))
+            .create_async()
+            .await;
+
+        let committed = NexusData::walrus_one(b"secret payload".to_vec())
+            .commit(&storage_conf)
+            .await
+            .expect("walrus commit should succeed");
+        assert_eq!(
+            committed,
+            NexusData::remote_one(
+                encryption::ENCRYPTED_WALRUS_STORAGE_TAG,
+                b"team-a:enc_blob_id".to_vec()
+            )
+        );
+
+        let fetched = committed
+            .clone()
+            .fetch(&storage_conf)
+            .await
+            .expect("walrus fetch should decrypt");
+        assert_eq!(
+            fetched,
+            NexusData::remote_one(
+                encryption::ENCRYPTED_WALRUS_STORAGE_TAG,
+                b"secret payload".to_vec()
+            )
+        );
+
+        // Readers without the key cannot fetch the payload.
+        storage_conf.encryption = None;
+        let err = committed
+            .fetch(&storage_conf)
+            .await
+            .expect_err("fetch without the key must fail");
+        assert!(err.to_string().contains("stored encrypted on Walrus"));
+
+        // Fetched payloads are not re-uploaded in plain text.
+        let err = fetched
+            .commit(&storage_conf)
+            .await
+            .expect_err("commit without the key must fail");
+        assert!(err.to_string().contains("stored encrypted on Walrus"));
+
+        mock_put.assert_async().await;
+        mock_get.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn registered_store_handles_its_storage_tag() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let storage_conf = StorageConf::default().with_data_store(LocalFsStore::new(dir.path()));
+        let data =
+            NexusData::remote_many(LOCAL_FS_STORAGE_TAG, [b"left".to_vec(), b"right".to_vec()]);
+
+        let committed = data
+            .clone()
+            .commit(&storage_conf)
+            .await
+            .expect("local commit should succeed");
+        assert_eq!(committed.storage_tag(), LOCAL_FS_STORAGE_TAG);
+        assert_ne!(committed, data);
+
+        let fetched = committed
+            .fetch(&storage_conf)
+            .await
+            .expect("local fetch should succeed");
+        assert_eq!(fetched, data);
+
+        let err = data
+            .commit(&StorageConf::default())
+            .await
+            .expect_err("unregistered storage must fail");
+        assert!(err
+            .to_string()
+            .contains("No data store is configured for storage 'local'"));
+    }
+
+    /// Reverses payloads and records how many transfers run at once.
+    #[derive(Default)]
+    struct ConcurrencyProbe {
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl ConcurrencyProbe {
+        async fn transfer(&self, mut bytes: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(in_flight, Ordering::SeqCst);
+
+            // Later payloads finish first, order must still be preserved.
+            tokio::time::sleep(Duration::from_millis(40 - 10 * bytes[0] as u64)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            bytes.reverse();
+            Ok(bytes)
+        }
+    }
+
+    impl DataStore for Arc<ConcurrencyProbe> {
+        fn tag(&self) -> &[u8] {
+            b"probe"
+        }
+
+        fn put<'a>(&'a self, payload: Vec<u8>) -> DataStoreFuture<'a, Vec<u8>> {
+            Box::pin(self.transfer(payload))
+        }
+
+        fn get<'a>(&'a self, reference: &'a [u8]) -> DataStoreFuture<'a, Vec<u8>> {
+            Box::pin(self.transfer(reference.to_vec()))
+        }
+    }
+
+    #[tokio::test]
+    async fn many_payloads_are_transferred_concurrently() {
+        let probe = Arc::new(ConcurrencyProbe::default());
+        let storage_conf = StorageConf {
+            max_concurrent_transfers: Some(2),
+            ..Default::default()
+        }
+        .with_data_store(probe.clone());
+        let payloads = (0..4u8).map(|i| vec![i, b'x']).collect::<Vec<_>>();
+
+        let committed = NexusData::remote_many(b"probe", payloads.clone())
+            .commit(&storage_conf)
+            .await
+            .expect("commit should succeed");
+        assert_eq!(
+            committed.many,
+            payloads
+                .iter()
+                .map(|payload| vec![payload[1], payload[0]])
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(probe.peak.load(Ordering::SeqCst), 2);
+
+        let fetched = committed
+            .fetch(&StorageConf {
+                max_concurrent_transfers: Some(1),
+                ..storage_conf
+            })
+            .await
+            .expect("fetch should succeed");
+        assert_eq!(fetched.many, payloads);
+        assert_eq!(probe.peak.load(Ordering::SeqCst), 2);
+    }
+
     #[tokio::test]
     async fn input_ports_commit_and_fetch_preserve_keys() {
         let storage_conf = StorageConf::default();
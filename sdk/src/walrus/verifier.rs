@@ -0,0 +1,176 @@
+//! Verification of downloaded Walrus blobs against their blob ID.
+//!
+//! A Walrus blob ID commits to the erasure coded slivers of the blob, not to a
+//! plain hash of its content, so checking a download requires re-encoding it
+//! with the parameters of the Walrus committee. [`WalrusCliBlobVerifier`]
+//! delegates this to the `walrus blob-id` command. Other strategies can be
+//! plugged into [`WalrusClientBuilder::with_blob_verifier`](crate::walrus::WalrusClientBuilder::with_blob_verifier)
+//! or, wrapped in a [`SharedBlobVerifier`], into `StorageConf::walrus_blob_verifier`.
+
+use {
+    std::{
+        future::Future,
+        path::{Path, PathBuf},
+        pin::Pin,
+        sync::Arc,
+    },
+    tokio::process::Command,
+};
+
+pub type BlobVerifierFuture<'a> = Pin<Box<dyn Future<Output = anyhow::Result<bool>> + Send + 'a>>;
+
+/// Content of a downloaded blob.
+#[derive(Clone, Copy, Debug)]
+pub enum BlobContent<'a> {
+    Bytes(&'a [u8]),
+    File(&'a Path),
+}
+
+/// Checks that downloaded content matches the blob ID it was requested by.
+pub trait BlobVerifier: Send + Sync {
+    /// Whether `content` is the blob identified by `blob_id`. Errors mean that
+    /// the check itself could not be performed.
+    fn verify<'a>(&'a self, blob_id: &'a str, content: BlobContent<'a>) -> BlobVerifierFuture<'a>;
+}
+
+/// A [`BlobVerifier`] that can be cloned into storage configurations. Two
+/// handles are equal if they share the same verifier.
+#[derive(Clone)]
+pub struct SharedBlobVerifier(Arc<dyn BlobVerifier>);
+
+impl SharedBlobVerifier {
+    pub fn new(blob_verifier: impl BlobVerifier + 'static) -> Self {
+        Self(Arc::new(blob_verifier))
+    }
+
+    pub fn as_arc(&self) -> Arc<dyn BlobVerifier> {
+        self.0.clone()
+    }
+}
+
+impl PartialEq for SharedBlobVerifier {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedBlobVerifier {}
+
+impl std::fmt::Debug for SharedBlobVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SharedBlobVerifier")
+    }
+}
+
+/// [`BlobVerifier`] computing blob IDs with the `walrus` CLI.
+#[derive(Clone, Debug)]
+pub struct WalrusCliBlobVerifier {
+    program: PathBuf,
+    n_shards: Option<u16>,
+}
+
+impl Default for WalrusCliBlobVerifier {
+    fn default() -> Self {
+        Self {
+            program: PathBuf::from("walrus"),
+            n_shards: None,
+        }
+    }
+}
+
+impl WalrusCliBlobVerifier {
+    /// Use the `walrus` binary found in `PATH`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a specific `walrus` binary.
+    pub fn with_program(mut self, program: impl Into<PathBuf>) -> Self {
+        self.program = program.into();
+        self
+    }
+
+    /// Number of shards of the Walrus network. When not set, the CLI reads it
+    /// from the chain which requires a Walrus client configuration.
+    pub fn with_n_shards(mut self, n_shards: u16) -> Self {
+        self.n_shards = Some(n_shards);
+        self
+    }
+
+    async fn blob_id(&self, path: &Path) -> anyhow::Result<String> {
+        let mut command = Command::new(&self.program);
+        command.arg("--json").arg("blob-id").arg(path);
+
+        if let Some(n_shards) = self.n_shards {
+            command.arg("--n-shards").arg(n_shards.to_string());
+        }
+
+        let output = command
+            .output()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to run {}: {e}", self.program.display()))?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "{} blob-id failed: {}",
+                self.program.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        parse_blob_id(&output.stdout)
+    }
+}
+
+impl BlobVerifier for WalrusCliBlobVerifier {
+    fn verify<'a>(&'a self, blob_id: &'a str, content: BlobContent<'a>) -> BlobVerifierFuture<'a> {
+        Box::pin(async move {
+            let computed = match content {
+                BlobContent::File(path) => self.blob_id(path).await?,
+                BlobContent::Bytes(bytes) => {
+                    // The file is created exclusively with a random name and
+                    // removed when dropped.
+                    let file = tempfile::NamedTempFile::new()
+                        .map_err(|e| anyhow::anyhow!("Failed to create a temporary file: {e}"))?;
+
+                    tokio::fs::write(file.path(), bytes).await.map_err(|e| {
+                        anyhow::anyhow!("Failed to write {}: {e}", file.path().display())
+                    })?;
+
+                    self.blob_id(file.path()).await?
+                }
+            };
+
+            Ok(computed == blob_id)
+        })
+    }
+}
+
+/// Extract the blob ID from the JSON output of `walrus --json blob-id`.
+fn parse_blob_id(stdout: &[u8]) -> anyhow::Result<String> {
+    let output = serde_json::from_slice::<serde_json::Value>(stdout)
+        .map_err(|e| anyhow::anyhow!("Unexpected walrus blob-id output: {e}"))?;
+
+    output
+        .get("blobId")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("walrus blob-id output does not contain a blob ID"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_blob_id() {
+        let stdout = br#"{"blobId":"M4hsZGQ1oCktdzegB6HnI6Mi28S2nqOPHxK-W7_4BUk","file":"/tmp/blob","unencodedLength":13}"#;
+
+        assert_eq!(
+            parse_blob_id(stdout).unwrap(),
+            "M4hsZGQ1oCktdzegB6HnI6Mi28S2nqOPHxK-W7_4BUk"
+        );
+        assert!(parse_blob_id(b"Blob ID: abc").is_err());
+        assert!(parse_blob_id(br#"{"file":"/tmp/blob"}"#).is_err());
+    }
+}
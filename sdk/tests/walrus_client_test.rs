@@ -3,15 +3,48 @@
 use {
     anyhow::Result,
     mockito::{Server, ServerGuard},
-    nexus_sdk::walrus::{BlobObject, BlobStorage, NewlyCreated, StorageInfo, WalrusClient},
+    nexus_sdk::walrus::{
+        BlobCache,
+        BlobContent,
+        BlobObject,
+        BlobStorage,
+        BlobVerifier,
+        BlobVerifierFuture,
+        NewlyCreated,
+        StorageInfo,
+        WalrusClient,
+        WalrusError,
+        WalrusRetryPolicy,
+    },
     serde::{Deserialize, Serialize},
-    std::path::PathBuf,
+    std::{path::PathBuf, sync::Arc, time::Duration},
     tempfile::tempdir,
 };
 
 const EPOCHS: u8 = 1;
 const TEST_CONTENT: &[u8] = b"Hello, World!";
 
+/// Retry quickly so that tests exercising failures stay fast.
+fn fast_retry_policy() -> WalrusRetryPolicy {
+    WalrusRetryPolicy::default().with_backoff(Duration::from_millis(1), Duration::from_millis(1))
+}
+
+/// Accepts content equal to the blob id.
+struct BlobIdIsContent;
+
+impl BlobVerifier for BlobIdIsContent {
+    fn verify<'a>(&'a self, blob_id: &'a str, content: BlobContent<'a>) -> BlobVerifierFuture<'a> {
+        Box::pin(async move {
+            let content = match content {
+                BlobContent::Bytes(bytes) => bytes.to_vec(),
+                BlobContent::File(path) => tokio::fs::read(path).await?,
+            };
+
+            Ok(content == blob_id.as_bytes())
+        })
+    }
+}
+
 /// Setup mock server for Walrus testing
 async fn setup_mock_server() -> Result<(ServerGuard, WalrusClient)> {
     // Create mock server
@@ -26,6 +59,7 @@ async fn setup_mock_server() -> Result<(ServerGuard, WalrusClient)> {
         .with_client(client)
         .with_publisher_url(&server_url)
         .with_aggregator_url(&server_url)
+        .with_retry_policy(fast_retry_policy())
         .build();
 
     Ok((server, walrus_client))
@@ -329,20 +363,336 @@ async fn test_read_file() -> Result<()> {
 async fn test_error_handling() -> Result<()> {
     let (mut server, client) = setup_mock_server().await?;
 
-    // Setup mock for server error
+    // Setup mock for server error, retried according to the retry policy
     let mock_error = server
         .mock("GET", "/v1/blobs/error_blob_id")
         .with_status(500)
         .with_body("Internal Server Error")
+        .expect(fast_retry_policy().retries + 1)
+        .create_async()
+        .await;
+
+    // Client errors are not retried
+    let mock_not_found = server
+        .mock("GET", "/v1/blobs/missing_blob_id")
+        .with_status(404)
+        .expect(1)
         .create_async()
         .await;
 
     // Test error handling
     let result = client.read_file("error_blob_id").await;
-    assert!(result.is_err());
+    assert!(matches!(
+        result,
+        Err(WalrusError::ApiError {
+            status_code: 500,
+            ..
+        })
+    ));
+
+    let result = client.read_file("missing_blob_id").await;
+    assert!(matches!(
+        result,
+        Err(WalrusError::ApiError {
+            status_code: 404,
+            ..
+        })
+    ));
 
-    // Verify the request was made
+    // Verify the requests were made
     mock_error.assert_async().await;
+    mock_not_found.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failover_to_next_endpoint() -> Result<()> {
+    let mut failing = Server::new_async().await;
+    let mut healthy = Server::new_async().await;
+
+    let client = WalrusClient::builder()
+        .with_publisher_urls([failing.url(), healthy.url()])
+        .with_aggregator_urls([failing.url(), healthy.url()])
+        .with_retry_policy(WalrusRetryPolicy::none())
+        .build();
+
+    let failing_read = failing
+        .mock("GET", "/v1/blobs/test_blob_id")
+        .with_status(503)
+        .create_async()
+        .await;
+    let healthy_read = healthy
+        .mock("GET", "/v1/blobs/test_blob_id")
+        .with_status(200)
+        .with_body(TEST_CONTENT)
+        .create_async()
+        .await;
+
+    assert_eq!(client.read_file("test_blob_id").await?, TEST_CONTENT);
+
+    let mock_response = StorageInfo {
+        newly_created: Some(NewlyCreated {
+            blob_object: BlobObject {
+                blob_id: "test_blob_id".to_string(),
+                id: "test_object_id".to_string(),
+                storage: BlobStorage { end_epoch: 100 },
+            },
+        }),
+        already_certified: None,
+    };
+
+    let failing_upload = failing
+        .mock("PUT", mockito::Matcher::Regex("/v1/blobs".to_string()))
+        .with_status(429)
+        .create_async()
+        .await;
+    let healthy_upload = healthy
+        .mock("PUT", mockito::Matcher::Regex("/v1/blobs".to_string()))
+        .match_body(std::str::from_utf8(TEST_CONTENT)?)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(serde_json::to_string(&mock_response)?)
+        .create_async()
+        .await;
+
+    // Files are streamed again to the next publisher.
+    let (_dir, file_path) = create_temp_file(TEST_CONTENT).await?;
+    let storage_info = client.upload_file(&file_path, EPOCHS, None).await?;
+    assert_eq!(
+        storage_info.newly_created.unwrap().blob_object.blob_id,
+        "test_blob_id"
+    );
+
+    failing_read.assert_async().await;
+    healthy_read.assert_async().await;
+    failing_upload.assert_async().await;
+    healthy_upload.assert_async().await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_blob_verification() -> Result<()> {
+    let mut corrupted = Server::new_async().await;
+    let mut healthy = Server::new_async().await;
+
+    let client = WalrusClient::builder()
+        .with_aggregator_urls([corrupted.url(), healthy.url()])
+        .with_retry_policy(WalrusRetryPolicy::none())
+        .with_blob_verifier(Arc::new(BlobIdIsContent))
+        .build();
+
+    let corrupted_read = corrupted
+        .mock("GET", "/v1/blobs/expected")
+        .with_status(200)
+        .with_body("corrupted")
+        .expect(2)
+        .create_async()
+        .await;
+    let healthy_read = healthy
+        .mock("GET", "/v1/blobs/expected")
+        .with_status(200)
+        .with_body("expected")
+        .expect(2)
+        .create_async()
+        .await;
+
+    // The corrupted download is discarded in favour of the next aggregator.
+    assert_eq!(client.read_file("expected").await?, b"expected");
+
+    let dir = tempdir()?;
+    let output_path = dir.path().join("downloaded_file.txt");
+    client.download_file("expected", &output_path).await?;
+    assert_eq!(tokio::fs::read(&output_path).await?, b"expected");
+
+    corrupted_read.assert_async().await;
+    healthy_read.assert_async().await;
+
+    // A blob no aggregator serves correctly is reported as a mismatch.
+    let client = WalrusClient::builder()
+        .with_aggregator_url(&corrupted.url())
+        .with_retry_policy(WalrusRetryPolicy::none())
+        .with_blob_verifier(Arc::new(BlobIdIsContent))
+        .build();
+
+    let err = client.read_file("expected").await.unwrap_err();
+    assert!(matches!(err, WalrusError::BlobIdMismatch { ref url, .. } if *url == corrupted.url()));
+
+    // A failed download leaves an existing output and no temporary file behind.
+    tokio::fs::write(&output_path, b"previous").await?;
+
+    let err = client
+        .download_file("expected", &output_path)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, WalrusError::BlobIdMismatch { .. }));
+    assert_eq!(tokio::fs::read(&output_path).await?, b"previous");
+    assert_eq!(std::fs::read_dir(dir.path())?.count(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_blob_cache() -> Result<()> {
+    let mut server = Server::new_async().await;
+    let dir = tempdir()?;
+    let cache = BlobCache::new(dir.path().join("cache"));
+
+    let client = WalrusClient::builder()
+        .with_publisher_url(&server.url())
+        .with_aggregator_url(&server.url())
+        .with_retry_policy(WalrusRetryPolicy::none())
+        .with_blob_cache(cache.clone())
+        .build();
+
+    // Blobs are only downloaded once.
+    let mock_read = server
+        .mock("GET", "/v1/blobs/cached_blob_id")
+        .with_status(200)
+        .with_body(TEST_CONTENT)
+        .expect(1)
+        .create_async()
+        .await;
+
+    assert_eq!(client.read_file("cached_blob_id").await?, TEST_CONTENT);
+    assert_eq!(client.read_file("cached_blob_id").await?, TEST_CONTENT);
+
+    let output_path = dir.path().join("downloaded_file.txt");
+    client.download_file("cached_blob_id", &output_path).await?;
+    assert_eq!(tokio::fs::read(&output_path).await?, TEST_CONTENT);
+    assert!(client.verify_blob("cached_blob_id").await?);
+
+    mock_read.assert_async().await;
+
+    // Uploaded blobs are cached as well.
+    let mock_response = StorageInfo {
+        newly_created: Some(NewlyCreated {
+            blob_object: BlobObject {
+                blob_id: "uploaded_blob_id".to_string(),
+                id: "test_object_id".to_string(),
+                storage: BlobStorage { end_epoch: 100 },
+            },
+        }),
+        already_certified: None,
+    };
+
+    let mock_upload = server
+        .mock("PUT", mockito::Matcher::Regex("/v1/blobs".to_string()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(serde_json::to_string(&mock_response)?)
+        .create_async()
+        .await;
+
+    client
+        .upload_bytes(b"uploaded".to_vec(), EPOCHS, None)
+        .await?;
+    mock_upload.assert_async().await;
+
+    // An offline client only serves cached blobs.
+    let offline = WalrusClient::builder()
+        .with_aggregator_url("http://127.0.0.1:1")
+        .with_blob_cache(cache.with_offline(true))
+        .build();
+
+    assert!(offline.is_offline());
+    assert_eq!(offline.read_file("cached_blob_id").await?, TEST_CONTENT);
+    assert_eq!(offline.read_file("uploaded_blob_id").await?, b"uploaded");
+    assert!(matches!(
+        offline.read_file("unknown_blob_id").await,
+        Err(WalrusError::NotCached { ref blob_id }) if blob_id == "unknown_blob_id"
+    ));
+    assert!(matches!(
+        offline.upload_bytes(b"data".to_vec(), EPOCHS, None).await,
+        Err(WalrusError::Offline { .. })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_blob_cache_only_keeps_verified_blobs() -> Result<()> {
+    let mut server = Server::new_async().await;
+    let dir = tempdir()?;
+    let cache = BlobCache::new(dir.path().join("cache"));
+
+    let client = WalrusClient::builder()
+        .with_publisher_url(&server.url())
+        .with_aggregator_url(&server.url())
+        .with_retry_policy(WalrusRetryPolicy::none())
+        .with_blob_verifier(Arc::new(BlobIdIsContent))
+        .with_blob_cache(cache.clone())
+        .build();
+
+    let mock_read = server
+        .mock("GET", "/v1/blobs/expected")
+        .with_status(200)
+        .with_body("corrupted")
+        .create_async()
+        .await;
+
+    // Content that fails verification is never cached.
+    assert!(matches!(
+        client.read_file("expected").await,
+        Err(WalrusError::BlobIdMismatch { .. })
+    ));
+    assert!(!cache.contains("expected").await);
+
+    mock_read.assert_async().await;
+
+    // Uploads are only cached if the content matches the returned blob ID.
+    let storage_info = |blob_id: &str| StorageInfo {
+        newly_created: Some(NewlyCreated {
+            blob_object: BlobObject {
+                blob_id: blob_id.to_string(),
+                id: "test_object_id".to_string(),
+                storage: BlobStorage { end_epoch: 100 },
+            },
+        }),
+        already_certified: None,
+    };
+
+    let mock_verified_upload = server
+        .mock("PUT", mockito::Matcher::Regex("/v1/blobs".to_string()))
+        .match_body("verified")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(serde_json::to_string(&storage_info("verified"))?)
+        .create_async()
+        .await;
+    let mock_mismatched_upload = server
+        .mock("PUT", mockito::Matcher::Regex("/v1/blobs".to_string()))
+        .match_body("mismatched")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(serde_json::to_string(&storage_info("other"))?)
+        .create_async()
+        .await;
+
+    client
+        .upload_bytes(b"verified".to_vec(), EPOCHS, None)
+        .await?;
+    client
+        .upload_bytes(b"mismatched".to_vec(), EPOCHS, None)
+        .await?;
+
+    mock_verified_upload.assert_async().await;
+    mock_mismatched_upload.assert_async().await;
+
+    assert_eq!(cache.get("verified").await.unwrap(), b"verified");
+    assert!(!cache.contains("other").await);
+
+    // An offline client still fails for blobs that were not cached.
+    let offline = WalrusClient::builder()
+        .with_aggregator_url("http://127.0.0.1:1")
+        .with_blob_cache(cache.with_offline(true))
+        .build();
+
+    assert!(matches!(
+        offline.read_file("expected").await,
+        Err(WalrusError::NotCached { ref blob_id }) if blob_id == "expected"
+    ));
 
     Ok(())
 }
@@ -17,6 +17,51 @@
 //! [`ToolkitRuntimeConfig::invoke_max_body_bytes`]. This is enforced using
 //! `warp::body::content_length_limit`, which rejects requests without `Content-Length`.
 //!
+//! # Metrics
+//! If `expose_metrics` is `true`, the runtime serves Prometheus metrics on `GET /metrics`:
+//! per-tool invocation counts by outcome, invocation latency, signed HTTP authentication failures
+//! and replay cache hits and misses. Metrics are disabled by default.
+//!
+//! # Walrus offloading
+//! If the config includes a `storage` section, output port values whose encoded size exceeds the
+//! configured threshold are uploaded to Walrus and emitted with the `walrus` storage tag. The
+//! threshold can be set for all ports via `offload_threshold_bytes` and overridden per tool and
+//! port via `tools.<tool_id>.ports.<port>`. Top-level input fields of the form
+//! `{"$nexus_walrus": <blob id or array of blob ids>}` are fetched from the aggregator before the
+//! input is deserialized. Failed Walrus requests are retried and fail over to the
+//! `walrus_fallback_publisher_urls` and `walrus_fallback_aggregator_urls`, in order. With
+//! `verify_blobs` set, fetched blobs are checked against their blob ID with the `walrus` CLI,
+//! which must be on the `PATH`.
+//!
+//! Encrypted Walrus payloads (see [`nexus_sdk::walrus::encryption`]) are decrypted with the keys
+//! in `encryption_keys`. Each key is only available to the tools listed in its `tools`, and
+//! offloaded outputs are encrypted with the `encrypt_outputs_with` key for the tools it is shared
+//! with.
+//!
+//! ```json
+//! {
+//!   "version": 2,
+//!   "storage": {
+//!     "walrus_publisher_url": "https://publisher.walrus-testnet.walrus.space",
+//!     "walrus_aggregator_url": "https://aggregator.walrus-testnet.walrus.space",
+//!     "walrus_fallback_aggregator_urls": ["https://aggregator.example.com"],
+//!     "walrus_save_for_epochs": 2,
+//!     "verify_blobs": true,
+//!     "offload_threshold_bytes": 65536,
+//!     "tools": {
+//!       "xyz.dummy.tool@1": { "ports": { "embedding": 0 } }
+//!     },
+//!     "encryption_keys": {
+//!       "team-a": {
+//!         "key": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+//!         "tools": ["xyz.dummy.tool@1"]
+//!       }
+//!     },
+//!     "encrypt_outputs_with": "team-a"
+//!   }
+//! }
+//! ```
+//!
 //! # Signed HTTP (application-layer signatures)
 //! If the config includes a `signed_http` section in `required` mode, the runtime:
 //! - Rejects any `/invoke` request that does not carry valid signature headers.
@@ -99,9 +144,18 @@
 use {
     anyhow::Context as _,
     ed25519_dalek::SigningKey,
-    nexus_sdk::signed_http::{
-        keys::parse_ed25519_signing_key,
-        v2::wire::{AllowedLeaders, AllowedLeadersFileV1},
+    nexus_sdk::{
+        signed_http::{
+            keys::parse_ed25519_signing_key,
+            v2::wire::{AllowedLeaders, AllowedLeadersFileV1},
+        },
+        walrus::{
+            encryption::{WalrusKey, WalrusKeyring},
+            SharedBlobVerifier,
+            StorageConf,
+            WalrusCliBlobVerifier,
+            WALRUS_MAX_EPOCHS,
+        },
     },
     notify::{Event, RecommendedWatcher, RecursiveMode, Watcher},
     serde::Deserialize,
@@ -117,7 +171,7 @@ use {
 /// Env var read by the toolkit runtime to locate its JSON config file.
 pub const ENV_TOOLKIT_CONFIG_PATH: &str = "NEXUS_TOOLKIT_CONFIG_PATH";
 
-const DEFAULT_INVOKE_MAX_BODY_BYTES: u64 = 10 * 1024 * 1024; // 10 MiB
+pub(crate) const DEFAULT_INVOKE_MAX_BODY_BYTES: u64 = 10 * 1024 * 1024; // 10 MiB
 
 /// Signed HTTP mode for the toolkit runtime.
 #[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
@@ -137,10 +191,45 @@ pub enum SignedHttpMode {
 #[derive(Clone)]
 pub struct ToolkitRuntimeConfig {
     invoke_max_body_bytes: u64,
+    expose_metrics: bool,
     signed_http: Option<SignedHttpRuntimeConfig>,
+    storage: Option<StorageRuntimeConfig>,
     source_path: Option<PathBuf>,
 }
 
+/// Walrus storage used to offload large outputs and fetch referenced inputs.
+#[derive(Clone, Debug)]
+pub(crate) struct StorageRuntimeConfig {
+    pub(crate) storage: StorageConf,
+    pub(crate) offload_threshold_bytes: Option<u64>,
+    /// Per tool, per output port thresholds overriding `offload_threshold_bytes`.
+    pub(crate) port_thresholds: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+impl StorageRuntimeConfig {
+    /// Size in bytes above which values of `port` are offloaded, `None` if never.
+    pub(crate) fn offload_threshold(&self, tool_id: &str, port: &str) -> Option<u64> {
+        self.port_thresholds
+            .get(tool_id)
+            .and_then(|ports| ports.get(port))
+            .copied()
+            .or(self.offload_threshold_bytes)
+    }
+
+    /// Storage config for `tool_id`, holding only the encryption keys shared
+    /// with the tool.
+    pub(crate) fn storage_for(&self, tool_id: &str) -> StorageConf {
+        StorageConf {
+            encryption: self
+                .storage
+                .encryption
+                .as_ref()
+                .map(|keyring| keyring.for_tool(tool_id)),
+            ..self.storage.clone()
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct SignedHttpRuntimeConfig {
     pub(crate) allowed_leaders: Arc<AllowedLeaders>,
@@ -195,6 +284,11 @@ impl ToolkitRuntimeConfig {
         self.invoke_max_body_bytes
     }
 
+    /// True if the runtime serves Prometheus metrics on `GET /metrics`.
+    pub fn expose_metrics(&self) -> bool {
+        self.expose_metrics
+    }
+
     /// True if the runtime requires signed HTTP requests.
     pub fn signed_http_is_required(&self) -> bool {
         self.signed_http.is_some()
@@ -211,10 +305,16 @@ impl ToolkitRuntimeConfig {
         self.signed_http.as_ref()
     }
 
+    pub(crate) fn storage(&self) -> Option<&StorageRuntimeConfig> {
+        self.storage.as_ref()
+    }
+
     fn default_for_runtime() -> Self {
         Self {
             invoke_max_body_bytes: DEFAULT_INVOKE_MAX_BODY_BYTES,
+            expose_metrics: false,
             signed_http: None,
+            storage: None,
             source_path: None,
         }
     }
@@ -231,7 +331,64 @@ struct ToolkitRuntimeConfigFileV2 {
     #[serde(default)]
     pub invoke_max_body_bytes: Option<u64>,
     #[serde(default)]
+    pub expose_metrics: bool,
+    #[serde(default)]
     pub signed_http: Option<SignedHttpConfigFileV2>,
+    #[serde(default)]
+    pub storage: Option<StorageConfigFileV2>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StorageConfigFileV2 {
+    #[serde(default)]
+    pub walrus_publisher_url: Option<String>,
+    #[serde(default)]
+    pub walrus_aggregator_url: Option<String>,
+    #[serde(default)]
+    pub walrus_fallback_publisher_urls: Vec<String>,
+    #[serde(default)]
+    pub walrus_fallback_aggregator_urls: Vec<String>,
+    #[serde(default)]
+    pub walrus_save_for_epochs: Option<u8>,
+
+    /// Check fetched blobs against their blob ID with the `walrus` CLI.
+    #[serde(default)]
+    pub verify_blobs: bool,
+
+    /// Output values larger than this are offloaded to Walrus. Never offload when omitted.
+    #[serde(default)]
+    pub offload_threshold_bytes: Option<u64>,
+
+    /// Per-tool overrides, keyed by `tool_id` string.
+    #[serde(default)]
+    pub tools: BTreeMap<String, StorageToolConfigFileV2>,
+
+    /// Walrus encryption keys keyed by key id.
+    #[serde(default)]
+    pub encryption_keys: BTreeMap<String, StorageKeyConfigFileV2>,
+
+    /// Id of the key offloaded outputs are encrypted with.
+    #[serde(default)]
+    pub encrypt_outputs_with: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StorageKeyConfigFileV2 {
+    /// Base64 encoded 32 byte AES-256 key.
+    pub key: String,
+    /// Tools the key is shared with.
+    #[serde(default)]
+    pub tools: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StorageToolConfigFileV2 {
+    /// Offload thresholds in bytes keyed by output port name.
+    #[serde(default)]
+    pub ports: BTreeMap<String, u64>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -285,9 +442,13 @@ impl TryFrom<ToolkitRuntimeConfigFileV2> for ToolkitRuntimeConfig {
             Some(s) => Some(load_signed_http_config(s)?),
         };
 
+        let storage = file.storage.map(load_storage_config).transpose()?;
+
         Ok(Self {
             invoke_max_body_bytes,
+            expose_metrics: file.expose_metrics,
             signed_http,
+            storage,
             source_path: None,
         })
     }
@@ -337,6 +498,66 @@ fn load_signed_http_config(
     })
 }
 
+fn load_storage_config(file: StorageConfigFileV2) -> anyhow::Result<StorageRuntimeConfig> {
+    let port_thresholds = file
+        .tools
+        .into_iter()
+        .map(|(tool_id, tool)| (tool_id, tool.ports))
+        .collect::<BTreeMap<_, _>>();
+    let offloads = file.offload_threshold_bytes.is_some()
+        || port_thresholds.values().any(|ports| !ports.is_empty());
+
+    if offloads {
+        if file.walrus_publisher_url.is_none() {
+            anyhow::bail!("storage.walrus_publisher_url is required when offloading outputs");
+        }
+
+        match file.walrus_save_for_epochs {
+            None => {
+                anyhow::bail!("storage.walrus_save_for_epochs is required when offloading outputs")
+            }
+            Some(epochs) if epochs == 0 || epochs > WALRUS_MAX_EPOCHS => anyhow::bail!(
+                "invalid storage.walrus_save_for_epochs: must be between 1 and {WALRUS_MAX_EPOCHS}"
+            ),
+            Some(_) => {}
+        }
+    }
+
+    let mut keyring = WalrusKeyring::new();
+
+    for (id, key) in file.encryption_keys {
+        let walrus_key = WalrusKey::from_base64(id.as_str(), &key.key)
+            .with_context(|| format!("invalid storage.encryption_keys.{id}"))?;
+
+        keyring = keyring.with_key(key.tools.into_iter().fold(walrus_key, WalrusKey::with_tool));
+    }
+
+    if let Some(id) = file.encrypt_outputs_with {
+        let Some(key) = keyring.key(&id).cloned() else {
+            anyhow::bail!("storage.encrypt_outputs_with refers to unknown key '{id}'");
+        };
+
+        keyring = keyring.with_active_key(key);
+    }
+
+    Ok(StorageRuntimeConfig {
+        storage: StorageConf {
+            walrus_publisher_url: file.walrus_publisher_url,
+            walrus_aggregator_url: file.walrus_aggregator_url,
+            walrus_fallback_publisher_urls: file.walrus_fallback_publisher_urls,
+            walrus_fallback_aggregator_urls: file.walrus_fallback_aggregator_urls,
+            walrus_save_for_epochs: file.walrus_save_for_epochs,
+            walrus_blob_verifier: file
+                .verify_blobs
+                .then(|| SharedBlobVerifier::new(WalrusCliBlobVerifier::new())),
+            encryption: (!keyring.is_empty()).then_some(keyring),
+            ..Default::default()
+        },
+        offload_threshold_bytes: file.offload_threshold_bytes,
+        port_thresholds,
+    })
+}
+
 /// Internal config holder with automatic file watching.
 ///
 /// This is used internally by the runtime to enable hot-reload of configuration
@@ -542,6 +763,136 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn parse_storage_config_with_port_overrides() {
+        let cfg = ToolkitRuntimeConfig::from_json_str(
+            r#"{
+                "version": 2,
+                "storage": {
+                    "walrus_publisher_url": "http://publisher",
+                    "walrus_aggregator_url": "http://aggregator",
+                    "walrus_fallback_aggregator_urls": ["http://fallback"],
+                    "walrus_save_for_epochs": 2,
+                    "verify_blobs": true,
+                    "offload_threshold_bytes": 1024,
+                    "tools": { "xyz.demo.tool@1": { "ports": { "embedding": 0 } } }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let storage = cfg.storage().unwrap();
+        assert_eq!(
+            storage.storage.walrus_aggregator_url.as_deref(),
+            Some("http://aggregator")
+        );
+        assert_eq!(
+            storage.storage.walrus_fallback_aggregator_urls,
+            vec!["http://fallback".to_string()]
+        );
+        assert!(storage.storage.walrus_blob_verifier.is_some());
+        assert_eq!(
+            storage.offload_threshold("xyz.demo.tool@1", "embedding"),
+            Some(0)
+        );
+        assert_eq!(
+            storage.offload_threshold("xyz.demo.tool@1", "summary"),
+            Some(1024)
+        );
+        assert_eq!(
+            storage.offload_threshold("xyz.other.tool@1", "embedding"),
+            Some(1024)
+        );
+
+        // Fetching inputs only needs the aggregator.
+        let cfg = ToolkitRuntimeConfig::from_json_str(
+            r#"{"version": 2, "storage": {"walrus_aggregator_url": "http://aggregator"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            cfg.storage()
+                .unwrap()
+                .offload_threshold("xyz.demo.tool@1", "x"),
+            None
+        );
+        assert!(cfg
+            .storage()
+            .unwrap()
+            .storage
+            .walrus_blob_verifier
+            .is_none());
+    }
+
+    #[test]
+    fn parse_storage_encryption_keys_scoped_to_tools() {
+        let cfg = ToolkitRuntimeConfig::from_json_str(
+            r#"{
+                "version": 2,
+                "storage": {
+                    "walrus_aggregator_url": "http://aggregator",
+                    "encryption_keys": {
+                        "team-a": {
+                            "key": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+                            "tools": ["xyz.demo.tool@1"]
+                        }
+                    },
+                    "encrypt_outputs_with": "team-a"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let storage = cfg.storage().unwrap();
+        let demo = storage.storage_for("xyz.demo.tool@1").encryption.unwrap();
+        assert_eq!(demo.active_key().map(WalrusKey::id), Some("team-a"));
+
+        let other = storage.storage_for("xyz.other.tool@1").encryption.unwrap();
+        assert!(other.is_empty());
+        assert!(other.active_key().is_none());
+
+        let err = ToolkitRuntimeConfig::from_json_str(
+            r#"{"version": 2, "storage": {"encrypt_outputs_with": "missing"}}"#,
+        )
+        .err()
+        .unwrap();
+        assert!(format!("{err:#}").contains("unknown key 'missing'"));
+
+        let err = ToolkitRuntimeConfig::from_json_str(
+            r#"{"version": 2, "storage": {"encryption_keys": {"k": {"key": "AAAA"}}}}"#,
+        )
+        .err()
+        .unwrap();
+        assert!(format!("{err:#}").contains("storage.encryption_keys.k"));
+    }
+
+    #[test]
+    fn storage_config_requires_publisher_and_epochs_when_offloading() {
+        let err = ToolkitRuntimeConfig::from_json_str(
+            r#"{"version": 2, "storage": {"offload_threshold_bytes": 1, "walrus_save_for_epochs": 2}}"#,
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("walrus_publisher_url"));
+
+        let err = ToolkitRuntimeConfig::from_json_str(
+            r#"{"version": 2, "storage": {"walrus_publisher_url": "http://publisher", "walrus_save_for_epochs": 0, "tools": {"xyz.demo.tool@1": {"ports": {"a": 1}}}}}"#,
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("walrus_save_for_epochs"));
+    }
+
+    #[test]
+    fn parse_expose_metrics() {
+        let cfg = ToolkitRuntimeConfig::from_json_str(r#"{"version": 2}"#).unwrap();
+        assert!(!cfg.expose_metrics());
+
+        let cfg = ToolkitRuntimeConfig::from_json_str(r#"{"version": 2, "expose_metrics": true}"#)
+            .unwrap();
+        assert!(cfg.expose_metrics());
+        assert!(!cfg.signed_http_is_required());
+    }
+
     #[test]
     fn parse_signed_http_inline_config() {
         let leader_sk = SigningKey::from_bytes(&[7u8; 32]);
@@ -782,6 +1133,7 @@ mod tests {
 
         // Default config has no signed HTTP
         assert!(!config.signed_http_is_required());
+        assert!(!config.expose_metrics());
         assert_eq!(config.invoke_max_body_bytes(), 10 * 1024 * 1024);
     }
 
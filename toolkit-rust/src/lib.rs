@@ -7,6 +7,7 @@
 //! See more documentation at <https://github.com/Talus-Network/gitbook-docs/blob/production/nexus-sdk/toolkit-rust.md>
 
 mod config;
+mod metrics;
 
 /// Shared test utilities
 #[cfg(test)]
@@ -19,14 +20,16 @@ mod nexus_tool;
 pub mod runtime;
 mod serde_tracked;
 mod signed_http_warp;
+mod storage;
 
 pub use {
     anyhow::Result as AnyResult,
     config::{SignedHttpMode, ToolkitRuntimeConfig, ENV_TOOLKIT_CONFIG_PATH},
     env_logger,
     log::debug,
-    nexus_tool::{AuthContext, NexusTool},
+    nexus_tool::{cancellation_token, AuthContext, NexusTool},
     serde_tracked::*,
+    tokio_util::sync::CancellationToken,
     warp::{self, http::StatusCode},
 };
 // Re-exported for use by the `bootstrap!` macro. Not part of the public API.
@@ -0,0 +1,45 @@
+//! Prometheus metrics recorded by the toolkit runtime.
+//!
+//! Metrics are registered in the default [`prometheus`] registry and served
+//! on `GET /metrics` when `expose_metrics` is set in the toolkit config (see
+//! [`crate::ToolkitRuntimeConfig`]).
+
+lazy_static::lazy_static! {
+    pub(crate) static ref INVOCATIONS: prometheus::IntCounterVec =
+        prometheus::register_int_counter_vec!(
+            "nexus_tool_invocations",
+            "Number of tool invocations by outcome",
+            &["tool", "outcome"]
+        )
+        .unwrap();
+
+    pub(crate) static ref INVOCATION_DURATION: prometheus::HistogramVec =
+        prometheus::register_histogram_vec!(
+            "nexus_tool_invocation_duration",
+            "Duration of tool invocations including input decoding and output encoding [s]",
+            &["tool"],
+            vec![0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0]
+        )
+        .unwrap();
+
+    pub(crate) static ref SIGNATURE_FAILURES: prometheus::IntCounterVec =
+        prometheus::register_int_counter_vec!(
+            "nexus_tool_signature_failures",
+            "Number of `/invoke` requests rejected by signed HTTP authentication",
+            &["tool"]
+        )
+        .unwrap();
+
+    pub(crate) static ref REPLAY_CACHE_LOOKUPS: prometheus::IntCounterVec =
+        prometheus::register_int_counter_vec!(
+            "nexus_tool_replay_cache_lookups",
+            "Number of signed HTTP replay cache lookups by result (hit, miss or in_flight)",
+            &["tool", "result"]
+        )
+        .unwrap();
+}
+
+/// Render all metrics of the default registry in the Prometheus text format.
+pub(crate) fn render() -> anyhow::Result<String> {
+    Ok(prometheus::TextEncoder::new().encode_to_string(&prometheus::gather())?)
+}
@@ -8,6 +8,7 @@ use {
     serde::{de::DeserializeOwned, Serialize},
     serde_json::{json, Value},
     std::{future::Future, time::Duration},
+    tokio_util::sync::CancellationToken,
     warp::http::StatusCode,
 };
 
@@ -25,6 +26,35 @@ use {
 /// - Responder: the node serving the request (in Nexus, the Tool).
 pub type AuthContext = nexus_sdk::signed_http::v2::wire::AuthenticatedRequest;
 
+tokio::task_local! {
+    static INVOCATION_CANCELLATION: CancellationToken;
+}
+
+/// Returns the cancellation token of the invocation running on the current
+/// task.
+///
+/// The runtime cancels this token once [`NexusTool::timeout`] elapses and then
+/// gives the invocation a short grace period to observe it before its future
+/// is dropped. Long-running tools can `select!` on
+/// [`CancellationToken::cancelled`] to release resources or abort outgoing
+/// requests cooperatively.
+///
+/// Outside of an invocation (or in tasks spawned from it) this returns a fresh
+/// token that is never cancelled; pass a clone to spawned tasks instead.
+pub fn cancellation_token() -> CancellationToken {
+    INVOCATION_CANCELLATION
+        .try_with(CancellationToken::clone)
+        .unwrap_or_default()
+}
+
+/// Run `future` with `token` as its [`cancellation_token`].
+pub(crate) async fn with_cancellation_token<F: Future>(
+    token: CancellationToken,
+    future: F,
+) -> F::Output {
+    INVOCATION_CANCELLATION.scope(token, future).await
+}
+
 /// This trait defines the interface for a Nexus Tool. It forces implementation
 /// of the following methods:
 ///
@@ -62,13 +92,17 @@ pub trait NexusTool: Send + Sync + 'static {
     /// Returns the FQN of the Tool.
     fn fqn() -> ToolFqn;
     /// Returns the Tool timeout duration. Defaults to 10 seconds.
+    ///
+    /// The runtime enforces this deadline on every `/invoke` request and
+    /// responds with an `invocation_timeout` error once it elapses.
     fn timeout() -> Duration {
         Duration::from_secs(10)
     }
     /// Invokes the tool with the given input. It is an asynchronous function
     /// that returns the output of the tool.
     ///
-    /// It is used to generate the `/invoke` endpoint.
+    /// It is used to generate the `/invoke` endpoint. See
+    /// [`cancellation_token`] for cooperative cancellation on timeout.
     fn invoke(&self, input: Self::Input) -> impl Future<Output = Self::Output> + Send;
 
     /// Authorize an invocation after it has been authenticated via signed HTTP.
@@ -110,7 +144,27 @@ pub trait NexusTool: Send + Sync + 'static {
     }
     /// Construct a new instance of the tool. This is mainly here so that
     /// dependencies can be injected for testing purposes.
+    ///
+    /// The runtime calls this exactly once at bootstrap and shares the
+    /// instance behind an [`std::sync::Arc`] across all `/health` and
+    /// `/invoke` requests, so this is the place to build connection pools,
+    /// HTTP clients or load models.
     fn new() -> impl Future<Output = Self> + Send;
+    /// Called once after [`NexusTool::new`] and before the server starts
+    /// accepting requests. Returning an error aborts the bootstrap.
+    ///
+    /// Default: no-op.
+    fn on_startup(&self) -> impl Future<Output = AnyResult<()>> + Send {
+        async { Ok(()) }
+    }
+    /// Called once after the server stopped accepting requests and all
+    /// in-flight invocations have drained (or the drain timed out). Use this
+    /// to flush buffers and close connections gracefully.
+    ///
+    /// Default: no-op.
+    fn on_shutdown(&self) -> impl Future<Output = ()> + Send {
+        async {}
+    }
     /// Returns the metadata of the tool. It includes the domain, name, version,
     /// input schema, and output schema.
     ///
@@ -2,8 +2,10 @@
 
 use {
     crate::{
-        config::Config,
+        config::{Config, StorageRuntimeConfig},
+        metrics,
         signed_http_warp::{handle_invoke, InvokeAuth},
+        storage,
         NexusTool,
         ToolkitRuntimeConfig,
     },
@@ -16,7 +18,10 @@ use {
     },
     reqwest::Url,
     serde_json::json,
-    std::sync::Arc,
+    std::{future::Future, pin::Pin, sync::Arc, time::Duration},
+    tokio::sync::watch,
+    tokio_util::sync::CancellationToken,
+    tracing::Instrument,
     warp::{
         filters::{host::Authority, path::FullPath},
         http::{HeaderMap, StatusCode},
@@ -63,6 +68,142 @@ pub fn load_config_() -> anyhow::Result<Arc<ToolkitRuntimeConfig>> {
     ToolkitRuntimeConfig::from_env().map(Arc::new)
 }
 
+/// Construct a [`NexusTool`] once and run its [`NexusTool::on_startup`] hook.
+///
+/// The returned instance is shared by all routes of the tool.
+///
+/// **This is an internal function used by [bootstrap!] macro and should not be
+/// used directly.**
+#[doc(hidden)]
+pub async fn start_tool_<T: NexusTool>() -> anyhow::Result<Arc<T>> {
+    let tool = Arc::new(T::new().await);
+
+    tool.on_startup()
+        .await
+        .map_err(|e| anyhow::anyhow!("Startup hook of tool '{}' failed: {e}", T::fqn()))?;
+
+    Ok(tool)
+}
+
+/// Deferred [`NexusTool::on_shutdown`] call, run once the server has drained.
+#[doc(hidden)]
+pub type ShutdownHook_ = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Create the [`ShutdownHook_`] for a started tool.
+///
+/// **This is an internal function used by [bootstrap!] macro and should not be
+/// used directly.**
+#[doc(hidden)]
+pub fn shutdown_hook_<T: NexusTool>(tool: Arc<T>) -> ShutdownHook_ {
+    Box::pin(async move { tool.on_shutdown().await })
+}
+
+/// Shutdown trigger shared between the server and the drain deadline.
+///
+/// **This is an internal type used by [bootstrap!] macro and should not be
+/// used directly.**
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct ShutdownSignal_ {
+    tx: Arc<watch::Sender<bool>>,
+    rx: watch::Receiver<bool>,
+}
+
+impl ShutdownSignal_ {
+    /// Create a signal that is only triggered manually.
+    pub fn new() -> Self {
+        let (tx, rx) = watch::channel(false);
+
+        Self {
+            tx: Arc::new(tx),
+            rx,
+        }
+    }
+
+    /// Create a signal that is triggered on SIGTERM or Ctrl-C.
+    pub fn from_os() -> Self {
+        let signal = Self::new();
+        let trigger = signal.clone();
+
+        tokio::spawn(async move {
+            wait_for_os_signal().await;
+
+            log::info!("Shutdown signal received, draining in-flight invocations");
+
+            trigger.trigger();
+        });
+
+        signal
+    }
+
+    pub fn trigger(&self) {
+        self.tx.send_replace(true);
+    }
+
+    /// Resolve once the signal has been triggered.
+    pub async fn recv(mut self) {
+        // The sender lives in `self`, so this can only fail if it is dropped
+        // while we wait, which cannot happen.
+        let _ = self.rx.wait_for(|triggered| *triggered).await;
+    }
+}
+
+impl Default for ShutdownSignal_ {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(unix)]
+async fn wait_for_os_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut sigterm = signal(SignalKind::terminate()).expect("Failed to install SIGTERM handler");
+
+    tokio::select! {
+        _ = sigterm.recv() => {}
+        _ = tokio::signal::ctrl_c() => {}
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_os_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Drive a gracefully shutting down `server` future to completion.
+///
+/// Once `signal` is triggered the server stops accepting new connections and
+/// in-flight invocations get at most `grace` to finish. Returns `false` if the
+/// deadline was hit before the server drained.
+///
+/// **This is an internal function used by [bootstrap!] macro and should not be
+/// used directly.**
+#[doc(hidden)]
+pub async fn drain_<S>(server: S, signal: ShutdownSignal_, grace: Duration) -> bool
+where
+    S: Future<Output = ()>,
+{
+    tokio::pin!(server);
+
+    tokio::select! {
+        _ = &mut server => return true,
+        _ = signal.recv() => {}
+    }
+
+    match tokio::time::timeout(grace, server).await {
+        Ok(()) => true,
+        Err(_) => {
+            log::warn!(
+                "In-flight invocations did not drain within {}ms, shutting down anyway",
+                grace.as_millis()
+            );
+
+            false
+        }
+    }
+}
+
 /// Build a placeholder URL for `--meta` output from a tool's [`NexusTool::path()`].
 ///
 /// The URL is a `http://localhost`-based placeholder — the real URL is set
@@ -148,6 +289,23 @@ fn json_bytes_or_fallback(status: StatusCode, value: serde_json::Value) -> (Stat
 /// ./my-tool --meta   # prints JSON array and exits
 /// ```
 ///
+/// ## Tool lifecycle
+/// Each tool is constructed once via [`crate::NexusTool::new`] before the
+/// server starts, followed by [`crate::NexusTool::on_startup`]. The instance is
+/// shared behind an [`std::sync::Arc`] by all requests.
+///
+/// On SIGTERM (or Ctrl-C) the server stops accepting connections and waits for
+/// in-flight invocations to finish, for at most the longest
+/// [`crate::NexusTool::timeout`] of the bundled tools. Afterwards
+/// [`crate::NexusTool::on_shutdown`] is called for every tool and the macro
+/// returns.
+///
+/// ## Metrics and tracing
+/// Set `"expose_metrics": true` in the toolkit config to serve Prometheus
+/// metrics on `GET /metrics`. Every `/invoke` request runs in an `invoke`
+/// tracing span carrying the tool FQN and, for signed requests, the leader ID
+/// and nonce.
+///
 /// ## Request body limits
 /// `/invoke` enforces a `Content-Length` limit via `warp::body::content_length_limit`.
 /// Requests without a `Content-Length` header are rejected.
@@ -239,14 +397,36 @@ macro_rules! bootstrap {
         let toolkit_cfg = $crate::runtime::load_config_()
             .expect("Failed to load Nexus toolkit config");
 
-        // Create routes for each Tool in the bundle.
-        let routes = $crate::runtime::routes_for_with_config_::<$tool>(toolkit_cfg.clone());
+        // Build every Tool in the bundle once and create its routes around
+        // the shared instance.
+        let mut shutdown_hooks: Vec<$crate::runtime::ShutdownHook_> = Vec::new();
+        let tool = $crate::runtime::start_tool_::<$tool>()
+            .await
+            .expect("Failed to start Nexus Tool");
+        shutdown_hooks.push($crate::runtime::shutdown_hook_(tool.clone()));
+        let routes = $crate::runtime::routes_for_with_config_::<$tool>(tool, toolkit_cfg.clone());
         $(
-            let routes = routes.or(
-                $crate::runtime::routes_for_with_config_::<$next_tool>(toolkit_cfg.clone())
-            );
+            let routes = {
+                let tool = $crate::runtime::start_tool_::<$next_tool>()
+                    .await
+                    .expect("Failed to start Nexus Tool");
+                shutdown_hooks.push($crate::runtime::shutdown_hook_(tool.clone()));
+                routes.or(
+                    $crate::runtime::routes_for_with_config_::<$next_tool>(tool, toolkit_cfg.clone())
+                )
+            };
         )*
 
+        // In-flight invocations are bounded by their tool timeout, so the
+        // longest one is enough to drain the server.
+        let drain_timeout = [
+            <$tool as $crate::NexusTool>::timeout()
+            $(, <$next_tool as $crate::NexusTool>::timeout())*
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or_default();
+
         // Collect paths of all tools.
         let mut paths = vec![<$tool as $crate::NexusTool>::path()];
         $(
@@ -263,26 +443,39 @@ macro_rules! bootstrap {
             .and($crate::warp::path("tools"))
             .map(move || $crate::warp::reply::json(&paths));
 
-        let routes = routes.or(default_health_route).or(default_tools_route);
+        // Prometheus metrics are only served when enabled in the toolkit config.
+        let metrics_route = $crate::runtime::metrics_route_(toolkit_cfg.expose_metrics());
+
+        let routes = routes
+            .or(default_health_route)
+            .or(default_tools_route)
+            .or(metrics_route);
         // Serve the routes, terminating TLS directly when a certificate/key pair is configured.
         let tls_config = $crate::runtime::tool_tls_config_(
             ::std::env::var("NEXUS_TOOL_TLS_CERT_PATH").ok(),
             ::std::env::var("NEXUS_TOOL_TLS_KEY_PATH").ok(),
         )
         .expect("Invalid Nexus Tool TLS configuration");
+        let shutdown = $crate::runtime::ShutdownSignal_::from_os();
         match tls_config {
             $crate::runtime::ToolTlsConfig::Disabled => {
-                $crate::warp::serve(routes).run($addr).await
+                let (_, server) = $crate::warp::serve(routes)
+                    .bind_with_graceful_shutdown($addr, shutdown.clone().recv());
+                $crate::runtime::drain_(server, shutdown, drain_timeout).await;
             }
             $crate::runtime::ToolTlsConfig::Enabled { cert_path, key_path } => {
-                $crate::warp::serve(routes)
+                let (_, server) = $crate::warp::serve(routes)
                     .tls()
                     .cert_path(cert_path)
                     .key_path(key_path)
-                    .run($addr)
-                    .await
+                    .bind_with_graceful_shutdown($addr, shutdown.clone().recv());
+                $crate::runtime::drain_(server, shutdown, drain_timeout).await;
             }
         }
+
+        for hook in shutdown_hooks {
+            hook.await;
+        }
     }};
     // Default address.
     ([$($tool:ty),+ $(,)?]) => {{
@@ -328,13 +521,14 @@ mod tls_tests {
     }
 }
 
-/// This function generates the necessary routes for a given [NexusTool] using an already-loaded
-/// [`ToolkitRuntimeConfig`].
+/// This function generates the necessary routes for a given [NexusTool] instance using an
+/// already-loaded [`ToolkitRuntimeConfig`].
 ///
 /// This exists so callers (like [`bootstrap!`]) can load and validate config once per process and
-/// share it across multiple tool route bundles.
+/// share it across multiple tool route bundles. All routes share the provided tool instance.
 #[doc(hidden)]
 pub fn routes_for_with_config_<T: NexusTool>(
+    tool: Arc<T>,
     toolkit_cfg: Arc<ToolkitRuntimeConfig>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     // Wrap config with file watching support
@@ -353,9 +547,11 @@ pub fn routes_for_with_config_<T: NexusTool>(
             filter.and(warp::path(segment.to_string())).boxed()
         });
 
+    let health_tool = tool.clone();
     let health_route = warp::get()
         .and(base_path.clone())
         .and(warp::path("health"))
+        .and(warp::any().map(move || health_tool.clone()))
         .and_then(health_handler::<T>);
 
     // Meta path is tool base URL path and `/meta`.
@@ -369,6 +565,7 @@ pub fn routes_for_with_config_<T: NexusTool>(
         .and_then(meta_handler::<T>);
 
     let invoke_max_body_bytes = toolkit_cfg.invoke_max_body_bytes();
+    let storage = toolkit_cfg.storage().cloned().map(Arc::new);
 
     let tool_id = T::fqn().to_string();
     let invoke_auth = InvokeAuth::new_sync(config, tool_id, T::timeout())
@@ -382,14 +579,15 @@ pub fn routes_for_with_config_<T: NexusTool>(
         .and(warp::body::content_length_limit(invoke_max_body_bytes))
         .and(warp::body::bytes())
         .and(warp::any().map(move || invoke_auth.clone()))
+        .and(warp::any().map(move || tool.clone()))
+        .and(warp::any().map(move || storage.clone()))
+        .and(warp::any().map(move || invoke_max_body_bytes))
         .and_then(invoke_handler::<T>);
 
     health_route.or(meta_route).or(invoke_route)
 }
 
-async fn health_handler<T: NexusTool>() -> Result<impl Reply, Rejection> {
-    let tool = T::new().await;
-
+async fn health_handler<T: NexusTool>(tool: Arc<T>) -> Result<impl Reply, Rejection> {
     let status = tool
         .health()
         .await
@@ -484,20 +682,52 @@ async fn meta_handler<T: NexusTool>(
     ))
 }
 
+/// How long a timed out invocation may take to observe its cancelled
+/// [`crate::cancellation_token`] before its future is dropped.
+const CANCELLATION_GRACE_PERIOD: Duration = Duration::from_millis(250);
+
+/// Outcome of a single run of the invocation pipeline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum InvokeOutcome {
+    Success,
+    InputDeserializationError,
+    PermissionDenied,
+    InputFetchError,
+    Timeout,
+    OutputSerializationError,
+    OutputOffloadError,
+}
+
+impl InvokeOutcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            InvokeOutcome::Success => "success",
+            InvokeOutcome::InputDeserializationError => "input_deserialization_error",
+            InvokeOutcome::PermissionDenied => "permission_denied",
+            InvokeOutcome::InputFetchError => "input_fetch_error",
+            InvokeOutcome::Timeout => "invocation_timeout",
+            InvokeOutcome::OutputSerializationError => "output_serialization_error",
+            InvokeOutcome::OutputOffloadError => "output_offload_error",
+        }
+    }
+}
+
 /// Result of the tool invocation pipeline before being turned into an HTTP response.
 struct InvokePipelineResponse {
     status: StatusCode,
     body: Vec<u8>,
     is_result: bool,
+    outcome: InvokeOutcome,
 }
 
 impl InvokePipelineResponse {
-    fn json(status: StatusCode, value: serde_json::Value) -> Self {
+    fn json(status: StatusCode, outcome: InvokeOutcome, value: serde_json::Value) -> Self {
         let (status, body) = json_bytes_or_fallback(status, value);
         Self {
             status,
             body,
             is_result: false,
+            outcome,
         }
     }
 }
@@ -506,58 +736,176 @@ impl InvokePipelineResponse {
 struct InvokePipeline;
 
 impl InvokePipeline {
+    /// Run the invocation. Fetching Walrus inputs, the tool itself and
+    /// offloading outputs all share the [`NexusTool::timeout`] deadline, and
+    /// fetched inputs may not exceed `max_input_bytes`.
     async fn run<T: NexusTool>(
+        tool: &T,
+        storage: Option<&StorageRuntimeConfig>,
         body_bytes: &[u8],
+        max_input_bytes: u64,
         auth_ctx: Option<crate::AuthContext>,
     ) -> InvokePipelineResponse {
+        // Nothing is fetched on behalf of callers the tool does not accept.
+        if let Some(ctx) = auth_ctx {
+            if let Err(e) = tool.authorize(ctx).await {
+                return InvokePipelineResponse::json(
+                    StatusCode::FORBIDDEN,
+                    InvokeOutcome::PermissionDenied,
+                    json!({
+                        "error": InvokeOutcome::PermissionDenied.as_str(),
+                        "details": e.to_string(),
+                    }),
+                );
+            }
+        }
+
+        let timeout = T::timeout();
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        // Resolve Walrus-referenced inputs before handing them to the tool.
+        let fetched = match storage {
+            Some(storage) => {
+                let fetch = storage::fetch_inputs(
+                    body_bytes,
+                    &T::fqn().to_string(),
+                    storage,
+                    max_input_bytes,
+                );
+
+                match tokio::time::timeout_at(deadline, fetch).await {
+                    Ok(Ok(fetched)) => fetched,
+                    Ok(Err(e)) => {
+                        return InvokePipelineResponse::json(
+                            StatusCode::BAD_GATEWAY,
+                            InvokeOutcome::InputFetchError,
+                            json!({
+                                "error": InvokeOutcome::InputFetchError.as_str(),
+                                "details": e.to_string(),
+                            }),
+                        );
+                    }
+                    Err(_) => return Self::timed_out::<T>(timeout),
+                }
+            }
+            None => None,
+        };
+        let body_bytes = fetched.as_deref().unwrap_or(body_bytes);
+
         let input = match serde_json::from_slice::<crate::WithSerdeErrorPath<T::Input>>(body_bytes)
         {
             Ok(v) => v.0,
             Err(e) => {
                 return InvokePipelineResponse::json(
                     StatusCode::UNPROCESSABLE_ENTITY,
+                    InvokeOutcome::InputDeserializationError,
                     json!({
-                        "error": "input_deserialization_error",
+                        "error": InvokeOutcome::InputDeserializationError.as_str(),
                         "details": e.to_string(),
                     }),
                 );
             }
         };
 
-        let tool = T::new().await;
+        let cancellation = CancellationToken::new();
+        let invocation =
+            crate::nexus_tool::with_cancellation_token(cancellation.clone(), tool.invoke(input));
+        tokio::pin!(invocation);
 
-        if let Some(ctx) = auth_ctx {
-            if let Err(e) = tool.authorize(ctx).await {
+        let output = match tokio::time::timeout_at(deadline, &mut invocation).await {
+            Ok(output) => output,
+            Err(_) => {
+                cancellation.cancel();
+
+                // Give the tool a chance to clean up. Its output is discarded
+                // either way as the caller has already given up.
+                let _ = tokio::time::timeout(CANCELLATION_GRACE_PERIOD, invocation).await;
+
+                return Self::timed_out::<T>(timeout);
+            }
+        };
+
+        let output = match tagged_output(output) {
+            Ok(output) => output,
+            Err(e) => {
                 return InvokePipelineResponse::json(
-                    StatusCode::FORBIDDEN,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    InvokeOutcome::OutputSerializationError,
                     json!({
-                        "error": "permission_denied",
+                        "error": InvokeOutcome::OutputSerializationError.as_str(),
                         "details": e.to_string(),
                     }),
                 );
             }
-        }
+        };
 
-        let output = tool.invoke(input).await;
+        let output = match storage {
+            Some(storage) => {
+                let offload = storage::offload_outputs(output, &T::fqn().to_string(), storage);
+
+                match tokio::time::timeout_at(deadline, offload).await {
+                    Ok(Ok(output)) => output,
+                    Ok(Err(e)) => {
+                        return InvokePipelineResponse::json(
+                            StatusCode::BAD_GATEWAY,
+                            InvokeOutcome::OutputOffloadError,
+                            json!({
+                                "error": InvokeOutcome::OutputOffloadError.as_str(),
+                                "details": e.to_string(),
+                            }),
+                        );
+                    }
+                    Err(_) => return Self::timed_out::<T>(timeout),
+                }
+            }
+            None => output,
+        };
 
-        match encode_tagged_output(output) {
+        match bcs::to_bytes(&output) {
             Ok(body) => InvokePipelineResponse {
                 status: StatusCode::OK,
                 body,
                 is_result: true,
+                outcome: InvokeOutcome::Success,
             },
             Err(e) => InvokePipelineResponse::json(
                 StatusCode::INTERNAL_SERVER_ERROR,
+                InvokeOutcome::OutputSerializationError,
                 json!({
-                    "error": "output_serialization_error",
+                    "error": InvokeOutcome::OutputSerializationError.as_str(),
                     "details": e.to_string(),
                 }),
             ),
         }
     }
+
+    fn timed_out<T: NexusTool>(timeout: Duration) -> InvokePipelineResponse {
+        log::warn!(
+            "Invocation of tool '{}' timed out after {}ms",
+            T::fqn(),
+            timeout.as_millis()
+        );
+
+        InvokePipelineResponse::json(
+            StatusCode::GATEWAY_TIMEOUT,
+            InvokeOutcome::Timeout,
+            json!({
+                "error": InvokeOutcome::Timeout.as_str(),
+                "details": format!("Tool did not respond within {}ms", timeout.as_millis()),
+                "timeout_ms": timeout.as_millis() as u64,
+            }),
+        )
+    }
 }
 
+#[cfg(test)]
 fn encode_tagged_output<T: serde::Serialize>(output: T) -> anyhow::Result<Vec<u8>> {
+    Ok(bcs::to_bytes(&tagged_output(output)?)?)
+}
+
+/// Convert an externally tagged output enum into a [`TaggedOutput`] with
+/// inline port data.
+fn tagged_output<T: serde::Serialize>(output: T) -> anyhow::Result<TaggedOutput> {
     let value = serde_json::to_value(crate::WithSerdeErrorPath(output))?;
     let serde_json::Value::Object(variants) = value else {
         anyhow::bail!("tool output must serialize as an externally tagged enum")
@@ -579,12 +927,12 @@ fn encode_tagged_output<T: serde::Serialize>(output: T) -> anyhow::Result<Vec<u8
         })
         .collect::<anyhow::Result<Vec<_>>>()?;
     named_payload.sort_by(|left, right| left.key.cmp(&right.key));
-    Ok(bcs::to_bytes(&TaggedOutput {
+    Ok(TaggedOutput {
         tag: tag.into_bytes(),
         named_payload: VecMap {
             contents: named_payload,
         },
-    })?)
+    })
 }
 
 fn typed_nexus_data(value: serde_json::Value) -> anyhow::Result<TypedNexusData> {
@@ -633,27 +981,96 @@ async fn invoke_handler<T: NexusTool>(
     headers: HeaderMap,
     body: bytes::Bytes,
     auth: InvokeAuth,
+    tool: Arc<T>,
+    storage: Option<Arc<StorageRuntimeConfig>>,
+    max_input_bytes: u64,
 ) -> Result<warp::reply::Response, Rejection> {
     let body_bytes = body.to_vec();
+    let tool_id = auth.tool_id();
+
+    // Leader ID and nonce are recorded once the request is authenticated.
+    let span = tracing::info_span!(
+        "invoke",
+        tool = tool_id,
+        leader_id = tracing::field::Empty,
+        nonce = tracing::field::Empty,
+    );
 
     let auth_runtime = auth.current().await;
-    Ok(handle_invoke(
+    let response = handle_invoke(
+        tool_id,
         &auth_runtime,
         auth.replay(),
         headers,
         body_bytes,
         |auth_ctx, body_bytes| async move {
-            let pipeline = InvokePipeline::run::<T>(&body_bytes, auth_ctx).await;
+            let started = std::time::Instant::now();
+            let pipeline = InvokePipeline::run(
+                tool.as_ref(),
+                storage.as_deref(),
+                &body_bytes,
+                max_input_bytes,
+                auth_ctx,
+            )
+            .await;
+
+            metrics::INVOCATION_DURATION
+                .with_label_values(&[tool_id])
+                .observe(started.elapsed().as_secs_f64());
+            metrics::INVOCATIONS
+                .with_label_values(&[tool_id, pipeline.outcome.as_str()])
+                .inc();
+
+            tracing::info!(
+                outcome = pipeline.outcome.as_str(),
+                status = pipeline.status.as_u16(),
+                "Invocation finished"
+            );
+
             (pipeline.status, pipeline.body, pipeline.is_result)
         },
     )
-    .await)
+    .instrument(span)
+    .await;
+
+    Ok(response)
+}
+
+/// Serve the default Prometheus registry on `GET /metrics` if `enabled`,
+/// otherwise the route rejects every request.
+///
+/// **This is an internal function used by [bootstrap!] macro and should not be
+/// used directly.**
+#[doc(hidden)]
+pub fn metrics_route_(
+    enabled: bool,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    warp::get()
+        .and(warp::path("metrics"))
+        .and(warp::path::end())
+        .and_then(move || async move {
+            if !enabled {
+                return Err(warp::reject::not_found());
+            }
+
+            let (status, body) = match metrics::render() {
+                Ok(body) => (StatusCode::OK, body),
+                Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+            };
+
+            Ok(warp::reply::with_header(
+                warp::reply::with_status(body, status),
+                "content-type",
+                "text/plain; version=0.0.4",
+            ))
+        })
 }
 
 #[cfg(test)]
 mod tests {
     use {
         super::*,
+        crate::config::DEFAULT_INVOKE_MAX_BODY_BYTES,
         nexus_sdk::{fqn, ToolFqn},
         schemars::JsonSchema,
         serde::{Deserialize, Serialize},
@@ -787,8 +1204,16 @@ mod tests {
 
     #[tokio::test]
     async fn invoke_pipeline_returns_exact_tagged_output_bytes() {
-        let response = InvokePipeline::run::<TestTool>(br#"{"message":"hello"}"#, None).await;
+        let response = InvokePipeline::run(
+            &TestTool,
+            None,
+            br#"{"message":"hello"}"#,
+            DEFAULT_INVOKE_MAX_BODY_BYTES,
+            None,
+        )
+        .await;
         assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.outcome, InvokeOutcome::Success);
         assert!(response.is_result);
         let output: TaggedOutput = bcs::from_bytes(&response.body).unwrap();
         assert_eq!(bcs::to_bytes(&output).unwrap(), response.body);
@@ -796,12 +1221,245 @@ mod tests {
 
     #[tokio::test]
     async fn invalid_input_remains_local_json_error() {
-        let response = InvokePipeline::run::<TestTool>(b"{}", None).await;
+        let response =
+            InvokePipeline::run(&TestTool, None, b"{}", DEFAULT_INVOKE_MAX_BODY_BYTES, None).await;
         assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.outcome, InvokeOutcome::InputDeserializationError);
         assert!(!response.is_result);
         assert!(serde_json::from_slice::<serde_json::Value>(&response.body).is_ok());
     }
 
+    struct FailingStartupTool;
+
+    impl NexusTool for FailingStartupTool {
+        type Input = Input;
+        type Output = Output;
+
+        fn fqn() -> ToolFqn {
+            fqn!("xyz.taluslabs.failing@1")
+        }
+
+        async fn new() -> Self {
+            Self
+        }
+
+        async fn on_startup(&self) -> anyhow::Result<()> {
+            anyhow::bail!("database unavailable")
+        }
+
+        async fn invoke(&self, _: Self::Input) -> Self::Output {
+            unreachable!("tool never starts")
+        }
+
+        async fn health(&self) -> anyhow::Result<StatusCode> {
+            Ok(StatusCode::OK)
+        }
+    }
+
+    static SLOW_TOOL_OBSERVED_CANCELLATION: std::sync::atomic::AtomicBool =
+        std::sync::atomic::AtomicBool::new(false);
+
+    struct SlowTool;
+
+    impl NexusTool for SlowTool {
+        type Input = Input;
+        type Output = Output;
+
+        fn fqn() -> ToolFqn {
+            fqn!("xyz.taluslabs.slow@1")
+        }
+
+        fn timeout() -> Duration {
+            Duration::from_millis(50)
+        }
+
+        async fn new() -> Self {
+            Self
+        }
+
+        async fn invoke(&self, input: Self::Input) -> Self::Output {
+            let cancellation = crate::cancellation_token();
+
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(60)) => {}
+                _ = cancellation.cancelled() => {
+                    SLOW_TOOL_OBSERVED_CANCELLATION
+                        .store(true, std::sync::atomic::Ordering::SeqCst);
+                }
+            }
+
+            Output::Ok {
+                message: input.message,
+                count: 0,
+                flags: vec![],
+                metadata: json!(null),
+            }
+        }
+
+        async fn health(&self) -> anyhow::Result<StatusCode> {
+            Ok(StatusCode::OK)
+        }
+    }
+
+    #[tokio::test]
+    async fn invoke_pipeline_enforces_tool_timeout() {
+        let started = std::time::Instant::now();
+        let response = InvokePipeline::run(
+            &SlowTool,
+            None,
+            br#"{"message":"hello"}"#,
+            DEFAULT_INVOKE_MAX_BODY_BYTES,
+            None,
+        )
+        .await;
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(response.status, StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(response.outcome, InvokeOutcome::Timeout);
+        assert!(!response.is_result);
+        assert!(SLOW_TOOL_OBSERVED_CANCELLATION.load(std::sync::atomic::Ordering::SeqCst));
+
+        let body: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+
+        assert_eq!(body["error"], "invocation_timeout");
+        assert_eq!(body["timeout_ms"], 50);
+    }
+
+    struct DenyingTool;
+
+    impl NexusTool for DenyingTool {
+        type Input = Input;
+        type Output = Output;
+
+        fn fqn() -> ToolFqn {
+            fqn!("xyz.taluslabs.denying@1")
+        }
+
+        async fn new() -> Self {
+            Self
+        }
+
+        async fn authorize(&self, _: crate::AuthContext) -> anyhow::Result<()> {
+            anyhow::bail!("leader not allowed")
+        }
+
+        async fn invoke(&self, _: Self::Input) -> Self::Output {
+            unreachable!("invocation is never authorized")
+        }
+
+        async fn health(&self) -> anyhow::Result<StatusCode> {
+            Ok(StatusCode::OK)
+        }
+    }
+
+    #[tokio::test]
+    async fn invoke_pipeline_authorizes_before_fetching_inputs() {
+        let mut server = mockito::Server::new_async().await;
+        let get = server
+            .mock("GET", "/v1/blobs/blob_id")
+            .expect(0)
+            .create_async()
+            .await;
+        let storage = StorageRuntimeConfig {
+            storage: nexus_sdk::walrus::StorageConf {
+                walrus_aggregator_url: Some(server.url()),
+                ..Default::default()
+            },
+            offload_threshold_bytes: None,
+            port_thresholds: Default::default(),
+        };
+        let auth_ctx = crate::AuthContext {
+            leader_id: "0x1111".to_string(),
+            leader_key_id: 0,
+            input_hash: [0; 32],
+            leader_signature: [0; 64],
+            nonce: [0; 32],
+        };
+
+        let response = InvokePipeline::run(
+            &DenyingTool,
+            Some(&storage),
+            br#"{"message":{"storage":"walrus","data":"blob_id"}}"#,
+            DEFAULT_INVOKE_MAX_BODY_BYTES,
+            Some(auth_ctx),
+        )
+        .await;
+
+        assert_eq!(response.status, StatusCode::FORBIDDEN);
+        assert_eq!(response.outcome, InvokeOutcome::PermissionDenied);
+
+        get.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn cancellation_token_outside_invocation_is_never_cancelled() {
+        assert!(!crate::cancellation_token().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn start_tool_surfaces_startup_hook_errors() {
+        assert!(start_tool_::<TestTool>().await.is_ok());
+
+        let err = start_tool_::<FailingStartupTool>()
+            .await
+            .err()
+            .expect("startup hook error");
+
+        assert!(err.to_string().contains("xyz.taluslabs.failing@1"));
+        assert!(err.to_string().contains("database unavailable"));
+    }
+
+    #[tokio::test]
+    async fn drain_waits_for_in_flight_work_until_deadline() {
+        // Server drains shortly after the signal.
+        let signal = ShutdownSignal_::new();
+        let server_signal = signal.clone();
+        let server = async move {
+            server_signal.recv().await;
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        };
+        signal.trigger();
+
+        assert!(drain_(server, signal, Duration::from_secs(5)).await);
+
+        // Server never drains, deadline is hit.
+        let signal = ShutdownSignal_::new();
+        signal.trigger();
+
+        assert!(
+            !drain_(
+                std::future::pending::<()>(),
+                signal,
+                Duration::from_millis(20)
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn metrics_route_is_opt_in() {
+        let disabled = warp::test::request()
+            .path("/metrics")
+            .reply(&metrics_route_(false))
+            .await;
+        assert_eq!(disabled.status(), StatusCode::NOT_FOUND);
+
+        metrics::INVOCATIONS
+            .with_label_values(&["xyz.metrics.route@1", InvokeOutcome::Timeout.as_str()])
+            .inc();
+
+        let enabled = warp::test::request()
+            .path("/metrics")
+            .reply(&metrics_route_(true))
+            .await;
+        assert_eq!(enabled.status(), StatusCode::OK);
+
+        let body = String::from_utf8(enabled.body().to_vec()).unwrap();
+        assert!(body.contains(
+            r#"nexus_tool_invocations{outcome="invocation_timeout",tool="xyz.metrics.route@1"} 1"#
+        ));
+    }
+
     #[test]
     fn meta_placeholder_url_empty_path() {
         let url = super::meta_placeholder_url_("");
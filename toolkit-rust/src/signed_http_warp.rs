@@ -1,7 +1,7 @@
 //! Warp integration for the minimal signed Tool transport.
 
 use {
-    crate::{config::Config, AuthContext, ToolkitRuntimeConfig},
+    crate::{config::Config, metrics, AuthContext, ToolkitRuntimeConfig},
     ed25519_dalek::SigningKey,
     nexus_sdk::signed_http::v2::{
         error::SignedHttpError,
@@ -168,13 +168,21 @@ impl InvokeAuth {
     pub(crate) fn replay(&self) -> &ReplayCache {
         &self.replay
     }
+
+    pub(crate) fn tool_id(&self) -> &str {
+        &self.tool_id
+    }
 }
 
 /// Handle one `/invoke` request.
 ///
 /// The callback's boolean marks whether its body is a canonical BCS `TaggedOutput`. Only those
 /// result bodies are signed; local HTTP errors remain JSON and never become verifier evidence.
+///
+/// Authentication failures and replay cache lookups are recorded in [`crate::metrics`] under
+/// `tool_id`, and the authenticated leader ID and nonce are recorded on the current tracing span.
 pub(crate) async fn handle_invoke<F, Fut>(
+    tool_id: &str,
     auth: &InvokeAuthRuntime,
     replay: &ReplayCache,
     headers: HeaderMap,
@@ -195,22 +203,48 @@ where
             let authenticated =
                 match authenticate_request(request_headers, runtime.allowed_leaders.as_ref()) {
                     Ok(authenticated) => authenticated,
-                    Err(error) => return auth_failed(error),
+                    Err(error) => {
+                        metrics::SIGNATURE_FAILURES
+                            .with_label_values(&[tool_id])
+                            .inc();
+
+                        return auth_failed(error);
+                    }
                 };
+            let span = tracing::Span::current();
+            span.record("leader_id", authenticated.leader_id.as_str());
+            span.record("nonce", hex::encode(authenticated.nonce).as_str());
+
             match replay.begin(authenticated.nonce, authenticated.input_hash, now_ms()) {
-                ReplayDecision::Return(cached) => cached.into_response(
-                    &authenticated.leader_signature,
-                    &authenticated.nonce,
-                    &runtime.signing_key,
-                ),
-                ReplayDecision::InFlight => json_response(
-                    StatusCode::CONFLICT,
-                    json!({
-                        "error": "request_in_flight",
-                        "details": "request with the same nonce is still processing",
-                    }),
-                ),
+                ReplayDecision::Return(cached) => {
+                    metrics::REPLAY_CACHE_LOOKUPS
+                        .with_label_values(&[tool_id, "hit"])
+                        .inc();
+
+                    cached.into_response(
+                        &authenticated.leader_signature,
+                        &authenticated.nonce,
+                        &runtime.signing_key,
+                    )
+                }
+                ReplayDecision::InFlight => {
+                    metrics::REPLAY_CACHE_LOOKUPS
+                        .with_label_values(&[tool_id, "in_flight"])
+                        .inc();
+
+                    json_response(
+                        StatusCode::CONFLICT,
+                        json!({
+                            "error": "request_in_flight",
+                            "details": "request with the same nonce is still processing",
+                        }),
+                    )
+                }
                 ReplayDecision::Proceed(reservation) => {
+                    metrics::REPLAY_CACHE_LOOKUPS
+                        .with_label_values(&[tool_id, "miss"])
+                        .inc();
+
                     let (status, body, is_result) =
                         run(Some(authenticated.clone()), body_bytes).await;
                     let cached = CachedResponse {
@@ -435,6 +469,8 @@ mod tests {
         warp::hyper::body::to_bytes,
     };
 
+    const TEST_TOOL_ID: &str = "xyz.dummy.tool@1";
+
     fn allowed_leaders_file(leader_id: &str, kid: u64, key: &SigningKey) -> AllowedLeadersFileV1 {
         AllowedLeadersFileV1 {
             version: 1,
@@ -706,7 +742,10 @@ mod tests {
         let leader = SigningKey::from_bytes(&[7; 32]);
         let tool = SigningKey::from_bytes(&[9; 32]);
         let replay = ReplayCache::new(1_000);
+        // Unique tool ID so the global metrics are not shared with other tests.
+        let tool_id = "xyz.metrics.missing_headers@1";
         let response = handle_invoke(
+            tool_id,
             &signed_runtime(&leader, &tool),
             &replay,
             HeaderMap::new(),
@@ -716,6 +755,12 @@ mod tests {
         .await;
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
         assert!(response.headers().get(HEADER_TOOL_SIGNATURE).is_none());
+        assert_eq!(
+            metrics::SIGNATURE_FAILURES
+                .with_label_values(&[tool_id])
+                .get(),
+            1
+        );
     }
 
     #[tokio::test]
@@ -734,6 +779,7 @@ mod tests {
         for input_hash in [[1; 32], [2; 32]] {
             let callback_calls = Arc::clone(&calls);
             let response = handle_invoke(
+                TEST_TOOL_ID,
                 &runtime,
                 &replay,
                 request_headers(&leader, input_hash, nonce),
@@ -762,6 +808,7 @@ mod tests {
         let body = vec![1, 2, 3];
         let replay = ReplayCache::new(1_000);
         let response = handle_invoke(
+            TEST_TOOL_ID,
             &InvokeAuthRuntime::Unsigned,
             &replay,
             HeaderMap::new(),
@@ -802,6 +849,7 @@ mod tests {
         let result = vec![4, 5, 6];
         let replay = ReplayCache::new(1_000);
         let response = handle_invoke(
+            TEST_TOOL_ID,
             &signed_runtime(&leader, &tool),
             &replay,
             request_headers(&leader, input_hash, nonce),
@@ -837,10 +885,13 @@ mod tests {
         let runtime = signed_runtime(&leader, &tool);
         let replay = ReplayCache::new(1_000);
         let calls = Arc::new(AtomicUsize::new(0));
+        // Unique tool ID so the global metrics are not shared with other tests.
+        let tool_id = "xyz.metrics.exact_retry@1";
 
         for _ in 0..2 {
             let calls = Arc::clone(&calls);
             let response = handle_invoke(
+                tool_id,
                 &runtime,
                 &replay,
                 request_headers(&leader, [1; 32], [1; 32]),
@@ -857,6 +908,7 @@ mod tests {
 
         let changed_calls = Arc::clone(&calls);
         let response = handle_invoke(
+            tool_id,
             &runtime,
             &replay,
             request_headers(&leader, [2; 32], [1; 32]),
@@ -870,6 +922,15 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
         assert!(response.headers().get(HEADER_SIGNATURE_VERSION).is_some());
         assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let lookups = |result: &str| {
+            metrics::REPLAY_CACHE_LOOKUPS
+                .with_label_values(&[tool_id, result])
+                .get()
+        };
+        assert_eq!(lookups("hit"), 1);
+        assert_eq!(lookups("miss"), 2);
+        assert_eq!(lookups("in_flight"), 0);
     }
 
     #[tokio::test]
@@ -883,6 +944,7 @@ mod tests {
         for _ in 0..2 {
             let calls = Arc::clone(&calls);
             let response = handle_invoke(
+                TEST_TOOL_ID,
                 &runtime,
                 &replay,
                 request_headers(&leader, [1; 32], [5; 32]),
@@ -948,6 +1010,7 @@ mod tests {
         )
         .unwrap();
         let response_a = handle_invoke(
+            TEST_TOOL_ID,
             &runtime_a,
             &replay,
             headers_from_encoded(request_a),
@@ -991,6 +1054,7 @@ mod tests {
         )
         .unwrap();
         let response_b = handle_invoke(
+            TEST_TOOL_ID,
             &runtime_b,
             &replay,
             headers_from_encoded(request_b),
@@ -0,0 +1,350 @@
+//! Walrus offloading of large tool outputs and fetching of Walrus-referenced
+//! tool inputs.
+//!
+//! See the `storage` section of [`crate::ToolkitRuntimeConfig`].
+
+use {
+    crate::config::StorageRuntimeConfig,
+    nexus_sdk::move_bindings::primitives::{data::NexusData, tagged_output::TaggedOutput},
+    serde_json::Value,
+};
+
+/// Upload output port values larger than their configured threshold to Walrus
+/// and replace them with `walrus` storage references.
+pub(crate) async fn offload_outputs(
+    mut output: TaggedOutput,
+    tool_id: &str,
+    conf: &StorageRuntimeConfig,
+) -> anyhow::Result<TaggedOutput> {
+    let storage = conf.storage_for(tool_id);
+
+    for entry in &mut output.named_payload.contents {
+        let port = String::from_utf8_lossy(&entry.key).into_owned();
+        let Some(threshold) = conf.offload_threshold(tool_id, &port) else {
+            continue;
+        };
+
+        let data = &mut entry.value.data;
+
+        if !data.is_inline() || payload_size(data) <= threshold {
+            continue;
+        }
+
+        let walrus = if data.many.is_empty() {
+            NexusData::walrus_one(std::mem::take(&mut data.one))
+        } else {
+            NexusData::walrus_many(std::mem::take(&mut data.many))
+        };
+
+        *data = walrus.commit(&storage).await.map_err(|e| {
+            anyhow::anyhow!("Failed to offload output port '{port}' to Walrus: {e}")
+        })?;
+    }
+
+    Ok(output)
+}
+
+/// Replace top-level input fields of the form
+/// `{"$nexus_walrus": <blob id or array of blob ids>}` with the values fetched
+/// from the Walrus aggregator. Other objects are passed through untouched, see
+/// [`NexusData::WALRUS_INPUT_KEY`].
+///
+/// Fetched payloads that are valid JSON are decoded as such, anything else is
+/// passed to the tool as a string. Encrypted payloads are decrypted with the
+/// keys shared with `tool_id`. Fails once the fetched payloads exceed
+/// `max_bytes` in total. Returns `None` if the body contains no Walrus
+/// references so it can be deserialized untouched.
+pub(crate) async fn fetch_inputs(
+    body: &[u8],
+    tool_id: &str,
+    conf: &StorageRuntimeConfig,
+    max_bytes: u64,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    // Malformed bodies are reported by the regular input deserialization.
+    let Ok(Value::Object(mut fields)) = serde_json::from_slice::<Value>(body) else {
+        return Ok(None);
+    };
+
+    let storage = conf.storage_for(tool_id);
+    let mut fetched_any = false;
+    let mut fetched_bytes = 0u64;
+
+    for (port, value) in fields.iter_mut() {
+        let Some((reference, is_many)) = walrus_reference(value) else {
+            continue;
+        };
+
+        let fetched = reference
+            .fetch(&storage)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch input port '{port}' from Walrus: {e}"))?;
+
+        fetched_bytes += payload_size(&fetched);
+
+        if fetched_bytes > max_bytes {
+            anyhow::bail!("Walrus inputs exceed the limit of {max_bytes} bytes");
+        }
+
+        *value = if is_many {
+            Value::Array(
+                fetched
+                    .many
+                    .iter()
+                    .map(|bytes| payload_to_json(bytes))
+                    .collect(),
+            )
+        } else {
+            payload_to_json(&fetched.one)
+        };
+
+        fetched_any = true;
+    }
+
+    if !fetched_any {
+        return Ok(None);
+    }
+
+    Ok(Some(serde_json::to_vec(&Value::Object(fields))?))
+}
+
+fn payload_size(data: &NexusData) -> u64 {
+    let many = data.many.iter().map(Vec::len).sum::<usize>();
+
+    (data.one.len() + many) as u64
+}
+
+/// Parse a Walrus reference into [`NexusData`] holding the blob IDs, and
+/// whether it refers to many values.
+fn walrus_reference(value: &Value) -> Option<(NexusData, bool)> {
+    let Value::Object(reference) = value else {
+        return None;
+    };
+
+    if reference.len() != 1 {
+        return None;
+    }
+
+    match reference.get(NexusData::WALRUS_INPUT_KEY)? {
+        Value::String(blob_id) => Some((NexusData::walrus_one(blob_id.as_bytes()), false)),
+        Value::Array(blob_ids) => blob_ids
+            .iter()
+            .map(|blob_id| blob_id.as_str().map(str::as_bytes))
+            .collect::<Option<Vec<_>>>()
+            .map(|blob_ids| (NexusData::walrus_many(blob_ids), true)),
+        _ => None,
+    }
+}
+
+fn payload_to_json(bytes: &[u8]) -> Value {
+    serde_json::from_slice(bytes)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        mockito::Server,
+        nexus_sdk::{
+            move_bindings::{
+                primitives::data::{DataTypeHint, TypedNexusData},
+                sui_framework::vec_map::{Entry as VecMapEntry, VecMap},
+            },
+            walrus::{BlobObject, BlobStorage, NewlyCreated, StorageConf, StorageInfo},
+        },
+        serde_json::json,
+        std::collections::BTreeMap,
+    };
+
+    fn storage_conf(url: String, offload_threshold_bytes: Option<u64>) -> StorageRuntimeConfig {
+        StorageRuntimeConfig {
+            storage: StorageConf {
+                walrus_publisher_url: Some(url.clone()),
+                walrus_aggregator_url: Some(url),
+                walrus_save_for_epochs: Some(2),
+                ..Default::default()
+            },
+            offload_threshold_bytes,
+            port_thresholds: BTreeMap::from([(
+                "xyz.dummy.tool@1".to_string(),
+                BTreeMap::from([("small".to_string(), 0)]),
+            )]),
+        }
+    }
+
+    fn output(ports: &[(&str, &[u8])]) -> TaggedOutput {
+        TaggedOutput {
+            tag: b"Ok".to_vec(),
+            named_payload: VecMap {
+                contents: ports
+                    .iter()
+                    .map(|(port, value)| VecMapEntry {
+                        key: port.as_bytes().to_vec(),
+                        value: TypedNexusData {
+                            type_hint: DataTypeHint::String,
+                            data: NexusData::inline_one(value.to_vec()),
+                        },
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn outputs_above_threshold_are_offloaded() {
+        let mut server = Server::new_async().await;
+        let stored = StorageInfo {
+            newly_created: Some(NewlyCreated {
+                blob_object: BlobObject {
+                    blob_id: "blob_id".to_string(),
+                    id: "object_id".to_string(),
+                    storage: BlobStorage { end_epoch: 200 },
+                },
+            }),
+            already_certified: None,
+        };
+        let put = server
+            .mock("PUT", "/v1/blobs?epochs=2")
+            .expect(2)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&stored).unwrap())
+            .create_async()
+            .await;
+        let conf = storage_conf(server.url(), Some(8));
+
+        let offloaded = offload_outputs(
+            output(&[
+                ("document", b"a rather long document"),
+                ("small", b"tiny"),
+                ("title", b"short"),
+            ]),
+            "xyz.dummy.tool@1",
+            &conf,
+        )
+        .await
+        .unwrap();
+
+        let ports = offloaded
+            .named_payload
+            .contents
+            .iter()
+            .map(|entry| (entry.key.as_slice(), &entry.value))
+            .collect::<BTreeMap<_, _>>();
+
+        // Above the global threshold.
+        assert_eq!(
+            ports[b"document".as_slice()].data,
+            NexusData::walrus_one(b"blob_id".to_vec())
+        );
+        assert_eq!(
+            ports[b"document".as_slice()].type_hint,
+            DataTypeHint::String
+        );
+        // Above the per-port threshold.
+        assert_eq!(
+            ports[b"small".as_slice()].data,
+            NexusData::walrus_one(b"blob_id".to_vec())
+        );
+        // Below the global threshold.
+        assert_eq!(
+            ports[b"title".as_slice()].data,
+            NexusData::inline_one(b"short".to_vec())
+        );
+
+        put.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn walrus_input_references_are_fetched() {
+        let mut server = Server::new_async().await;
+        let get_json = server
+            .mock("GET", "/v1/blobs/json_blob")
+            .with_status(200)
+            .with_body(r#"{"nested":[1,2]}"#)
+            .create_async()
+            .await;
+        let get_text = server
+            .mock("GET", "/v1/blobs/text_blob")
+            .expect(2)
+            .with_status(200)
+            .with_body("plain text")
+            .create_async()
+            .await;
+        let conf = storage_conf(server.url(), None);
+
+        let body = json!({
+            "document": { "$nexus_walrus": "json_blob" },
+            "chunks": { "$nexus_walrus": ["text_blob", "text_blob"] },
+            // Tool inputs that happen to look like storage references.
+            "plain": { "storage": "walrus", "data": "kept" },
+            "extra": { "$nexus_walrus": "kept", "other": 1 },
+            "prompt": "hello",
+        });
+
+        let fetched = fetch_inputs(
+            &serde_json::to_vec(&body).unwrap(),
+            "xyz.dummy.tool@1",
+            &conf,
+            1024,
+        )
+        .await
+        .unwrap()
+        .expect("references were fetched");
+
+        assert_eq!(
+            serde_json::from_slice::<Value>(&fetched).unwrap(),
+            json!({
+                "document": { "nested": [1, 2] },
+                "chunks": ["plain text", "plain text"],
+                "plain": { "storage": "walrus", "data": "kept" },
+                "extra": { "$nexus_walrus": "kept", "other": 1 },
+                "prompt": "hello",
+            })
+        );
+
+        get_json.assert_async().await;
+        get_text.assert_async().await;
+
+        assert!(
+            fetch_inputs(br#"{"prompt":"hello"}"#, "xyz.dummy.tool@1", &conf, 1024)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(fetch_inputs(b"not json", "xyz.dummy.tool@1", &conf, 1024)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn walrus_inputs_are_capped() {
+        let mut server = Server::new_async().await;
+        let get = server
+            .mock("GET", "/v1/blobs/text_blob")
+            .expect(2)
+            .with_status(200)
+            .with_body("plain text")
+            .create_async()
+            .await;
+        let conf = storage_conf(server.url(), None);
+
+        let body = json!({
+            "chunks": { "$nexus_walrus": ["text_blob", "text_blob"] },
+        });
+
+        let err = fetch_inputs(
+            &serde_json::to_vec(&body).unwrap(),
+            "xyz.dummy.tool@1",
+            &conf,
+            15,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(err.to_string().contains("limit of 15 bytes"));
+
+        get.assert_async().await;
+    }
+}
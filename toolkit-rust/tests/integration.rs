@@ -74,6 +74,55 @@ impl NexusTool for DummyErrTool {
     }
 }
 
+static POOLED_TOOL_INSTANCES: std::sync::atomic::AtomicUsize =
+    std::sync::atomic::AtomicUsize::new(0);
+
+/// Tool with an expensive constructor, e.g. one that opens a connection pool.
+struct PooledTool {
+    started: std::sync::atomic::AtomicBool,
+}
+
+impl NexusTool for PooledTool {
+    type Input = Input;
+    type Output = Output;
+
+    async fn new() -> Self {
+        POOLED_TOOL_INSTANCES.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+        Self {
+            started: std::sync::atomic::AtomicBool::new(false),
+        }
+    }
+
+    fn fqn() -> ToolFqn {
+        fqn!("xyz.dummy.pooled@1")
+    }
+
+    async fn on_startup(&self) -> AnyResult<()> {
+        self.started
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    async fn health(&self) -> AnyResult<StatusCode> {
+        if self.started.load(std::sync::atomic::Ordering::SeqCst) {
+            Ok(StatusCode::OK)
+        } else {
+            Ok(StatusCode::SERVICE_UNAVAILABLE)
+        }
+    }
+
+    async fn invoke(&self, Self::Input { prompt }: Self::Input) -> Self::Output {
+        Output::Ok {
+            message: format!(
+                "Instances: {}, you said: {prompt}",
+                POOLED_TOOL_INSTANCES.load(std::sync::atomic::Ordering::SeqCst)
+            ),
+        }
+    }
+}
+
 // == Integration tests ==
 
 #[cfg(test)]
@@ -258,4 +307,35 @@ mod tests {
             "Scheme must be either 'http' or 'https'."
         );
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_tool_instance_is_shared_across_requests() {
+        tokio::spawn(async move { bootstrap!(([127, 0, 0, 1], 8048), PooledTool) });
+
+        // Give the webserver some time to start.
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        for _ in 0..3 {
+            let health = Client::new()
+                .get("http://localhost:8048/health")
+                .send()
+                .await
+                .unwrap();
+
+            assert_eq!(health.status(), 200);
+        }
+
+        for _ in 0..3 {
+            let invoke = Client::new()
+                .post("http://localhost:8048/invoke")
+                .json(&json!({ "prompt": "hi" }))
+                .send()
+                .await
+                .unwrap();
+
+            assert_eq!(invoke.status(), 200);
+
+            assert_tagged_output(invoke, b"Ok", b"message", b"Instances: 1, you said: hi").await;
+        }
+    }
 }